@@ -16,7 +16,7 @@ use ethers_core::{
 use inflector::Inflector;
 use proc_macro2::{Ident, Literal, TokenStream};
 use quote::quote;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 use syn::{Path, Visibility};
 
 /// Internal shared context for generating smart contract bindings.
@@ -33,6 +33,9 @@ pub(crate) struct Context {
     /// Was the ABI in human readable format?
     human_readable: bool,
 
+    /// The deployment bytecode, if the ABI was part of a compiled contract artifact
+    bytecode: Option<Literal>,
+
     /// The contract name as an identifier.
     contract_name: Ident,
 
@@ -43,9 +46,34 @@ pub(crate) struct Context {
     event_derives: Vec<Path>,
 }
 
+/// A type declared by the bindings, e.g. an event's struct, which may be shared with
+/// the bindings of other contracts declaring an identical type
+pub(crate) struct TypeDeclaration {
+    /// The names of the declared items, starting with the type's
+    pub(crate) names: Vec<Ident>,
+    pub(crate) tokens: TokenStream,
+}
+
+impl TypeDeclaration {
+    /// The name of the declared type
+    pub(crate) fn name(&self) -> String {
+        self.names[0].to_string()
+    }
+}
+
 impl Context {
     pub(crate) fn expand(args: Abigen) -> Result<TokenStream> {
         let cx = Self::from_abigen(args)?;
+        cx.expand_with_shared_types(&HashSet::new())
+    }
+
+    /// Expands the bindings, importing the types named in `shared_types` from the
+    /// sibling `shared_types` module instead of declaring them
+    pub(crate) fn expand_with_shared_types(
+        &self,
+        shared_types: &HashSet<String>,
+    ) -> Result<TokenStream> {
+        let cx = self;
         let name = &cx.contract_name;
         let name_mod = util::ident(&format!(
             "{}_mod",
@@ -58,10 +86,25 @@ impl Context {
         let imports = common::imports(&name.to_string());
 
         // 1. Declare Contract struct
-        let struct_decl = common::struct_declaration(&cx, &abi_name);
+        let struct_decl = common::struct_declaration(cx, &abi_name);
+        let (bytecode_decl, deploy) = common::deployment(cx, &abi_name);
 
-        // 2. Declare events structs & impl FromTokens for each event
-        let events_decl = cx.events_declaration()?;
+        // 2. Declare events structs & impl FromTokens for each event, along with the
+        // structs parsed from the human readable abi, unless they are shared
+        let (shared_decls, type_decls): (Vec<_>, Vec<_>) = cx
+            .type_declarations()?
+            .into_iter()
+            .partition(|decl| shared_types.contains(&decl.name()));
+        let shared_imports = if shared_decls.is_empty() {
+            quote! {}
+        } else {
+            let names = shared_decls.iter().flat_map(|decl| &decl.names);
+            quote! {
+                pub use super::super::shared_types::{#( #names ),*};
+            }
+        };
+        let type_decls = type_decls.iter().map(|decl| &decl.tokens);
+        let events_enum_decl = cx.events_enum_declaration();
 
         // 3. impl block for the event functions
         let contract_events = cx.event_methods()?;
@@ -69,9 +112,6 @@ impl Context {
         // 4. impl block for the contract methods
         let contract_methods = cx.methods()?;
 
-        // 5. Declare the structs parsed from the human readable abi
-        let abi_structs_decl = cx.abi_structs()?;
-
         Ok(quote! {
             // export all the created data types
             pub use #name_mod::*;
@@ -79,7 +119,9 @@ impl Context {
             #[allow(clippy::too_many_arguments)]
             mod #name_mod {
                 #imports
+                #shared_imports
                 #struct_decl
+                #bytecode_decl
 
                 impl<'a, M: ethers_providers::Middleware> #name<M> {
                     /// Creates a new contract instance with the specified `ethers`
@@ -90,24 +132,33 @@ impl Context {
                         Self(contract)
                     }
 
-                    // TODO: Implement deployment.
+                    #deploy
 
                     #contract_methods
 
                     #contract_events
                 }
 
-                #events_decl
+                #( #type_decls )*
 
-                #abi_structs_decl
+                #events_enum_decl
             }
         })
     }
 
+    /// Returns the declarations of the events' structs and of the structs parsed from
+    /// the human readable abi
+    pub(crate) fn type_declarations(&self) -> Result<Vec<TypeDeclaration>> {
+        let mut declarations = self.event_declarations()?;
+        declarations.extend(self.abi_structs()?);
+        Ok(declarations)
+    }
+
     /// Create a context from the code generation arguments.
-    fn from_abigen(args: Abigen) -> Result<Self> {
-        // get the actual ABI string
-        let abi_str = args.abi_source.get().context("failed to get ABI JSON")?;
+    pub(crate) fn from_abigen(args: Abigen) -> Result<Self> {
+        // get the actual ABI string, and the bytecode if it is a contract artifact
+        let source = args.abi_source.get().context("failed to get ABI JSON")?;
+        let (abi_str, bytecode) = parse_artifact(&source)?;
         let mut abi_parser = AbiParser::default();
         // parse it
         let (abi, human_readable): (Abi, _) = if let Ok(abi) = serde_json::from_str(&abi_str) {
@@ -144,6 +195,7 @@ impl Context {
         Ok(Context {
             abi,
             human_readable,
+            bytecode: bytecode.map(|bytecode| Literal::byte_string(&bytecode)),
             abi_str: Literal::string(&abi_str),
             abi_parser,
             contract_name,
@@ -152,3 +204,79 @@ impl Context {
         })
     }
 }
+
+/// Splits a compiled contract artifact, e.g. of hardhat, truffle or solc, into its ABI and
+/// its deployment bytecode. Other sources are returned as is, without bytecode.
+fn parse_artifact(source: &str) -> Result<(String, Option<Vec<u8>>)> {
+    let artifact = match serde_json::from_str(source) {
+        Ok(serde_json::Value::Object(artifact)) => artifact,
+        _ => return Ok((source.to_owned(), None)),
+    };
+    let abi = artifact
+        .get("abi")
+        .ok_or_else(|| anyhow!("contract artifact has no ABI"))?;
+
+    // solc nests the bytecode in an `object` field
+    let bytecode = match artifact.get("bytecode") {
+        Some(serde_json::Value::String(bytecode)) => Some(bytecode.as_str()),
+        Some(serde_json::Value::Object(bytecode)) => {
+            bytecode.get("object").and_then(serde_json::Value::as_str)
+        }
+        _ => None,
+    }
+    .map(|bytecode| bytecode.trim_start_matches("0x"))
+    .filter(|bytecode| !bytecode.is_empty())
+    .map(hex::decode)
+    .transpose()
+    .context("invalid bytecode, libraries must be linked before generating the bindings")?;
+
+    Ok((serde_json::to_string(abi)?, bytecode))
+}
+
+/// Expands the module declaring the types shared by the bindings of multiple contracts
+pub(crate) fn expand_shared_types(declarations: &[TypeDeclaration]) -> TokenStream {
+    let imports = common::imports("shared_types");
+    let declarations = declarations.iter().map(|decl| &decl.tokens);
+
+    quote! {
+        pub use shared_types_mod::*;
+
+        mod shared_types_mod {
+            #imports
+
+            #( #declarations )*
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_artifacts() {
+        let abi = r#"[{"inputs":[],"stateMutability":"nonpayable","type":"constructor"}]"#;
+        assert_eq!(parse_artifact(abi).unwrap(), (abi.to_owned(), None));
+
+        let artifact = format!(r#"{{"abi":{},"bytecode":"0x6080"}}"#, abi);
+        assert_eq!(
+            parse_artifact(&artifact).unwrap(),
+            (abi.to_owned(), Some(vec![0x60, 0x80]))
+        );
+
+        // solc's output
+        let artifact = format!(r#"{{"abi":{},"bytecode":{{"object":"6080"}}}}"#, abi);
+        assert_eq!(
+            parse_artifact(&artifact).unwrap(),
+            (abi.to_owned(), Some(vec![0x60, 0x80]))
+        );
+
+        // interfaces have no bytecode
+        let artifact = format!(r#"{{"abi":{},"bytecode":"0x"}}"#, abi);
+        assert_eq!(parse_artifact(&artifact).unwrap(), (abi.to_owned(), None));
+
+        // unlinked libraries are rejected
+        let artifact = format!(r#"{{"abi":{},"bytecode":"0x60__$lib$__"}}"#, abi);
+        assert!(parse_artifact(&artifact).is_err());
+    }
+}
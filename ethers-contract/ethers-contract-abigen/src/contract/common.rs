@@ -58,6 +58,12 @@ pub(crate) fn struct_declaration(cx: &Context, abi_name: &proc_macro2::Ident) ->
             fn deref(&self) -> &Self::Target { &self.0 }
         }
 
+        impl<M: ethers_providers::Middleware> From<ethers_contract::Contract<M>> for #name<M> {
+            fn from(contract: ethers_contract::Contract<M>) -> Self {
+                Self(contract)
+            }
+        }
+
         impl<M: ethers_providers::Middleware> std::fmt::Debug for #name<M> {
             fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
                 f.debug_tuple(stringify!(#name))
@@ -67,3 +73,44 @@ pub(crate) fn struct_declaration(cx: &Context, abi_name: &proc_macro2::Ident) ->
         }
     }
 }
+
+/// Generates the static bytecode constant and the `deploy` function of the contract, if its
+/// bytecode is known
+pub(crate) fn deployment(
+    cx: &Context,
+    abi_name: &proc_macro2::Ident,
+) -> (TokenStream, TokenStream) {
+    let bytecode = match &cx.bytecode {
+        Some(bytecode) => bytecode,
+        None => return (quote! {}, quote! {}),
+    };
+    let bytecode_name = util::safe_ident(&format!(
+        "{}_BYTECODE",
+        cx.contract_name.to_string().to_uppercase()
+    ));
+
+    let decl = quote! {
+        // Inline bytecode declaration
+        pub static #bytecode_name: ethers_contract::Lazy<ethers_core::types::Bytes> =
+            ethers_contract::Lazy::new(|| #bytecode.to_vec().into());
+    };
+    let doc = util::expand_doc(&format!(
+        "Constructs the deployment transaction of the contract with the provided constructor arguments (`()` if there are none). Once broadcast, `send` returns the deployed contract along with the deployment's receipt, and the contract can be converted into these bindings with `into`, e.g. `let (contract, receipt) = {0}::deploy(client, ())?.send().await?; let bindings: {0}<M> = contract.into();`",
+        cx.contract_name
+    ));
+    let deploy = quote! {
+        #doc
+        pub fn deploy<T: ethers_core::abi::Tokenize>(
+            client: ::std::sync::Arc<M>,
+            constructor_args: T,
+        ) -> Result<ethers_contract::builders::Deployer<M>, ethers_contract::ContractError<M>> {
+            let factory = ethers_contract::ContractFactory::new(
+                #abi_name.clone(),
+                #bytecode_name.clone(),
+                client,
+            );
+            factory.deploy(constructor_args)
+        }
+    };
+    (decl, deploy)
+}
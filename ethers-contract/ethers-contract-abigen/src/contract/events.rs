@@ -1,4 +1,4 @@
-use super::{types, util, Context};
+use super::{types, util, Context, TypeDeclaration};
 use anyhow::Result;
 use ethers_core::abi::{Event, EventExt, EventParam, Hash, ParamType, SolStruct};
 use inflector::Inflector;
@@ -9,26 +9,31 @@ use syn::Path;
 
 impl Context {
     /// Expands each event to a struct + its impl Detokenize block
-    pub fn events_declaration(&self) -> Result<TokenStream> {
+    pub(crate) fn event_declarations(&self) -> Result<Vec<TypeDeclaration>> {
         let sorted_events: BTreeMap<_, _> = self.abi.events.clone().into_iter().collect();
-        let data_types = sorted_events
+        sorted_events
             .values()
             .flatten()
-            .map(|event| self.expand_event(event))
-            .collect::<Result<Vec<_>>>()?;
+            .map(|event| {
+                let mut names = vec![expand_struct_name(event)];
+                if event.inputs.iter().any(|input| input.indexed) {
+                    names.push(expand_builder_name(event));
+                }
+                Ok(TypeDeclaration {
+                    names,
+                    tokens: self.expand_event(event)?,
+                })
+            })
+            .collect()
+    }
 
-        // only expand enums when multiple events are present
-        let events_enum_decl = if sorted_events.values().flatten().count() > 1 {
+    /// Expands the enum of all events, only when multiple events are present
+    pub(crate) fn events_enum_declaration(&self) -> TokenStream {
+        if self.abi.events.values().flatten().count() > 1 {
             self.expand_events_enum()
         } else {
             quote! {}
-        };
-
-        Ok(quote! {
-            #( #data_types )*
-
-            #events_enum_decl
-        })
+        }
     }
 
     /// Generate the event filter methods for the contract
@@ -60,13 +65,22 @@ impl Context {
             .collect::<Vec<_>>();
 
         let enum_name = self.expand_event_enum_name();
+        let derives = expand_derives(&self.event_derives);
 
         quote! {
-            #[derive(Debug, Clone, PartialEq, Eq)]
+            #[derive(Debug, Clone, PartialEq, Eq, #derives)]
             pub enum #enum_name {
                 #(#variants(#variants)),*
             }
 
+            #(
+                impl ::std::convert::From<#variants> for #enum_name {
+                    fn from(event: #variants) -> Self {
+                        #enum_name::#variants(event)
+                    }
+                }
+            )*
+
              impl ethers_core::abi::Tokenizable for #enum_name {
 
                  fn from_token(token: ethers_core::abi::Token) -> Result<Self, ethers_core::abi::InvalidOutputType> where
@@ -246,54 +260,102 @@ impl Context {
         let derives = expand_derives(&self.event_derives);
         let abi_signature = event.abi_signature();
         let event_abi_name = &event.name;
+        let topic_filters = self.expand_builder_topic_filters(event)?;
 
         Ok(quote! {
             #[derive(Clone, Debug, Default, Eq, PartialEq, ethers_contract::EthEvent, #derives)]
             #[ethevent( name = #event_abi_name, abi = #abi_signature )]
             pub #data_type_definition
+
+            #topic_filters
         })
     }
 
-    /// Expands a event parameter into an event builder filter method for the
-    /// specified topic index.
+    /// Expands a event parameter into event builder filter methods for the
+    /// specified topic index: one matching a single value and one matching any of
+    /// several values.
     fn expand_builder_topic_filter(
         &self,
         topic_index: usize,
+        param_index: usize,
         param: &EventParam,
-    ) -> Result<TokenStream> {
+    ) -> Result<(TokenStream, TokenStream)> {
         let doc = util::expand_doc(&format!(
             "Adds a filter for the `{}` event parameter.",
             param.name,
         ));
         let topic = util::ident(&format!("topic{}", topic_index));
-        let name = if param.name.is_empty() {
-            topic.clone()
-        } else {
-            util::safe_ident(&param.name.to_snake_case())
-        };
-        let ty = self.expand_input_type(&param)?;
+        let name = expand_builder_method_name(param_index, &param.name);
+        let any_doc = util::expand_doc(&format!(
+            "Adds a filter matching any of the values of the `{}` event parameter. Like an empty array of topics, an empty iterator matches any value.",
+            param.name,
+        ));
+        let any_name = util::ident(&format!("{}_any", name.to_string().trim_end_matches('_')));
+        // filters take the values of strings, bytes, arrays and tuples, which are hashed
+        // into their topics, instead of the hashes found in the event's struct
+        let ty = types::expand(&param.kind)?;
 
-        Ok(quote! {
+        let declaration = quote! {
             #doc
-            pub fn #name(mut self, topic: Topic<#ty>) -> Self {
-                self.0 = (self.0).#topic(topic);
-                self
+            fn #name(self, value: #ty) -> Self;
+
+            #any_doc
+            fn #any_name<I: IntoIterator<Item = #ty>>(self, values: I) -> Self;
+        };
+        let implementation = quote! {
+            fn #name(self, value: #ty) -> Self {
+                self.#topic(ethers_core::abi::encode_topic(ethers_core::abi::Tokenizable::into_token(value)))
             }
-        })
+
+            fn #any_name<I: IntoIterator<Item = #ty>>(self, values: I) -> Self {
+                let topics = values
+                    .into_iter()
+                    .map(|value| ethers_core::abi::encode_topic(ethers_core::abi::Tokenizable::into_token(value)))
+                    .collect::<Vec<_>>();
+                self.#topic(topics)
+            }
+        };
+        Ok((declaration, implementation))
     }
 
-    /// Expands an ABI event into filter methods for its indexed parameters.
+    /// Expands an ABI event into a trait with typed filter methods for its indexed
+    /// parameters, implemented for the event's builder.
     fn expand_builder_topic_filters(&self, event: &Event) -> Result<TokenStream> {
-        let topic_filters = event
+        // the first topic of non-anonymous events is the event's signature
+        let offset = if event.anonymous { 0 } else { 1 };
+        let (declarations, implementations): (Vec<_>, Vec<_>) = event
             .inputs
             .iter()
-            .filter(|input| input.indexed)
             .enumerate()
-            .map(|(topic_index, input)| self.expand_builder_topic_filter(topic_index, input))
-            .collect::<Result<Vec<_>>>()?;
+            .filter(|(_, input)| input.indexed)
+            .enumerate()
+            .map(|(topic_index, (param_index, input))| {
+                self.expand_builder_topic_filter(topic_index + offset, param_index, input)
+            })
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .unzip();
+        if declarations.is_empty() {
+            return Ok(quote! {});
+        }
+
+        let event_name = expand_struct_name(event);
+        let name = expand_builder_name(event);
+        let doc = util::expand_doc(&format!(
+            "Filters for the indexed parameters of the `{}` event, once the trait is in scope",
+            event.name
+        ));
 
         Ok(quote! {
-            #( #topic_filters )*
+            #doc
+            #[allow(clippy::wrong_self_convention)]
+            pub trait #name: Sized {
+                #( #declarations )*
+            }
+
+            impl<'a, M> #name for ethers_contract::builders::Event<'a, M, #event_name> {
+                #( #implementations )*
+            }
         })
     }
 }
@@ -337,10 +399,42 @@ fn expand_data_tuple(name: &Ident, params: &[(TokenStream, TokenStream, bool)])
     quote! { struct #name( #( #fields ),* ); }
 }
 
-/// Expands an ABI event into an identifier for its event data type.
-fn expand_builder_name(event: &Event) -> TokenStream {
-    let builder_name = util::ident(&format!("{}Builder", &event.name.to_pascal_case()));
-    quote! { #builder_name }
+/// Expands an ABI event into an identifier for its filter builder trait.
+fn expand_builder_name(event: &Event) -> Ident {
+    util::ident(&format!("{}FilterTopics", event.name.to_pascal_case()))
+}
+
+/// The methods of the event builder, which would take precedence over the filter
+/// methods of the same name
+const EVENT_BUILDER_METHODS: &[&str] = &[
+    "from_block",
+    "to_block",
+    "at_block_hash",
+    "topic0",
+    "topic1",
+    "topic2",
+    "topic3",
+    "stream",
+    "stream_with_meta",
+    "subscribe",
+    "subscribe_with_meta",
+    "query",
+    "query_with_meta",
+];
+
+/// Expands an event parameter into the name of its filter method, which is named like
+/// the parameter's field in the event's struct. Names of the event builder's own methods
+/// get `_` appended to them, like reserved keywords.
+fn expand_builder_method_name(index: usize, name: &str) -> Ident {
+    let name = match name {
+        "" => format!("p{}", index),
+        n => n.to_snake_case(),
+    };
+    if EVENT_BUILDER_METHODS.contains(&name.as_str()) {
+        util::ident(&format!("{}_", name))
+    } else {
+        util::safe_ident(&name)
+    }
 }
 
 fn expand_derives(derives: &[Path]) -> TokenStream {
@@ -399,6 +493,64 @@ mod tests {
         });
     }
 
+    #[test]
+    #[rustfmt::skip]
+    fn expand_transfer_topic_filters() {
+        let event = Event {
+            name: "Transfer".into(),
+            inputs: vec![
+                EventParam {
+                    name: "from".into(),
+                    kind: ParamType::Address,
+                    indexed: false,
+                },
+                EventParam {
+                    name: "note".into(),
+                    kind: ParamType::String,
+                    indexed: true,
+                },
+            ],
+            anonymous: false,
+        };
+        let cx = test_context();
+        assert_quote!(cx.expand_builder_topic_filters(&event).unwrap(), {
+            #[doc = "Filters for the indexed parameters of the `Transfer` event, once the trait is in scope"]
+            #[allow(clippy::wrong_self_convention)]
+            pub trait TransferFilterTopics: Sized {
+                #[doc = "Adds a filter for the `note` event parameter."]
+                fn note(self, value: String) -> Self;
+
+                #[doc = "Adds a filter matching any of the values of the `note` event parameter. Like an empty array of topics, an empty iterator matches any value."]
+                fn note_any<I: IntoIterator<Item = String>>(self, values: I) -> Self;
+            }
+
+            impl<'a, M> TransferFilterTopics for ethers_contract::builders::Event<'a, M, TransferFilter> {
+                fn note(self, value: String) -> Self {
+                    self.topic1(ethers_core::abi::encode_topic(ethers_core::abi::Tokenizable::into_token(value)))
+                }
+
+                fn note_any<I: IntoIterator<Item = String>>(self, values: I) -> Self {
+                    let topics = values
+                        .into_iter()
+                        .map(|value| ethers_core::abi::encode_topic(ethers_core::abi::Tokenizable::into_token(value)))
+                        .collect::<Vec<_>>();
+                    self.topic1(topics)
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn expand_builder_method_names() {
+        // unnamed parameters are named like their field in the event's struct
+        assert_eq!(expand_builder_method_name(2, ""), "p2");
+        assert_eq!(expand_builder_method_name(0, "tokenId"), "token_id");
+        // names of the event builder's methods and keywords do not collide
+        assert_eq!(expand_builder_method_name(0, "fromBlock"), "from_block_");
+        assert_eq!(expand_builder_method_name(0, "query"), "query_");
+        assert_eq!(expand_builder_method_name(0, "type"), "type_");
+    }
+
     #[test]
     fn expand_data_struct_value() {
         let event = Event {
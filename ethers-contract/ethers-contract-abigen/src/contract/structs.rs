@@ -6,7 +6,7 @@ use quote::quote;
 
 use ethers_core::abi::{struct_def::FieldType, ParamType};
 
-use crate::contract::{types, Context};
+use crate::contract::{types, Context, TypeDeclaration};
 use crate::util;
 
 impl Context {
@@ -15,7 +15,7 @@ impl Context {
     /// NOTE: This assumes that all structs that are potentially used as type for variable are
     /// in fact present in the `AbiParser`, this is sound because `AbiParser::parse` would have
     /// failed already
-    pub fn abi_structs(&self) -> Result<TokenStream> {
+    pub(crate) fn abi_structs(&self) -> Result<Vec<TypeDeclaration>> {
         let mut structs = Vec::with_capacity(self.abi_parser.structs.len());
         for (name, sol_struct) in &self.abi_parser.structs {
             let mut fields = Vec::with_capacity(sol_struct.fields().len());
@@ -96,14 +96,18 @@ impl Context {
             let derives = &self.event_derives;
             let derives = quote! {#(#derives),*};
 
-            structs.push(quote! {
+            let tokens = quote! {
                 #abi_signature_doc
                 #[derive(Clone, Debug, Default, Eq, PartialEq, ethers::contract::EthAbiType, #derives)]
                 pub struct #name {
                     #( #fields ),*
                 }
+            };
+            structs.push(TypeDeclaration {
+                names: vec![name],
+                tokens,
             });
         }
-        Ok(quote! {#( #structs )*})
+        Ok(structs)
     }
 }
@@ -15,11 +15,13 @@ mod test_macros;
 mod contract;
 use contract::Context;
 
+mod multi;
 mod rustfmt;
 mod source;
 mod util;
 
 pub use ethers_core::types::Address;
+pub use multi::{MultiAbigen, MultiBindings};
 pub use source::Source;
 pub use util::parse_address;
 
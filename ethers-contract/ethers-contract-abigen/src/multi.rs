@@ -0,0 +1,324 @@
+//! Generate bindings for multiple contracts at once, e.g. from a build script
+use super::{
+    contract::{expand_shared_types, TypeDeclaration},
+    Abigen, Context, ContractBindings,
+};
+
+use anyhow::{anyhow, Context as _, Result};
+use inflector::Inflector;
+use std::{
+    collections::{BTreeMap, HashSet},
+    env,
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Builder for generating the bindings of multiple contracts, with one module per
+/// contract.
+///
+/// # Example
+///
+/// Generating the bindings of every ABI in the `abi` directory from a `build.rs`:
+///
+/// ```no_run
+/// # use ethers_contract_abigen::MultiAbigen;
+/// # fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// MultiAbigen::from_json_files("./abi")?.build()?.write_to_out_dir()?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// The bindings can then be imported with:
+///
+/// ```ignore
+/// include!(concat!(env!("OUT_DIR"), "/mod.rs"));
+/// ```
+///
+/// With [`shared_types`](MultiAbigen::shared_types), the events and structs which several
+/// contracts declare identically are generated once in a `shared_types` module, so that
+/// they are the same type in each contract's bindings.
+#[derive(Default)]
+pub struct MultiAbigen {
+    abigens: Vec<Abigen>,
+
+    /// The files the bindings are generated from, used for cache invalidation
+    sources: Vec<PathBuf>,
+
+    /// Whether identical types are deduplicated into a shared module
+    shared_types: bool,
+}
+
+impl MultiAbigen {
+    /// Creates an empty builder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a builder with an `Abigen` for each `.json` ABI file in the directory,
+    /// named after the file (e.g. `ERC20Token.json` generates the `ERC20Token` contract).
+    pub fn from_json_files<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        let dir = dir
+            .as_ref()
+            .canonicalize()
+            .with_context(|| format!("could not read {}", dir.as_ref().display()))?;
+
+        let mut files = fs::read_dir(&dir)?
+            .map(|entry| Ok(entry?.path()))
+            .collect::<Result<Vec<_>>>()?;
+        files.retain(|path| path.extension().map_or(false, |ext| ext == "json"));
+        // keep the output deterministic
+        files.sort();
+
+        let mut this = Self::new();
+        for file in files {
+            let name = file
+                .file_stem()
+                .and_then(|name| name.to_str())
+                .ok_or_else(|| anyhow!("invalid file name {}", file.display()))?;
+            let abigen = Abigen::new(name, file.to_string_lossy())?;
+            this = this.push(abigen);
+            this.sources.push(file);
+        }
+        this.sources.push(dir);
+
+        Ok(this)
+    }
+
+    /// Adds the `Abigen` of another contract
+    pub fn push(mut self, abigen: Abigen) -> Self {
+        self.abigens.push(abigen);
+        self
+    }
+
+    /// Specify whether the types declared identically by several contracts are generated
+    /// once in a `shared_types` module, which the bindings of these contracts import.
+    pub fn shared_types(mut self, shared_types: bool) -> Self {
+        self.shared_types = shared_types;
+        self
+    }
+
+    /// Generates the bindings of all contracts
+    pub fn build(self) -> Result<MultiBindings> {
+        if !self.shared_types {
+            let bindings = self
+                .abigens
+                .into_iter()
+                .map(|abigen| {
+                    let module = abigen.contract_name.to_snake_case();
+                    Ok((module, abigen.generate()?))
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            return Ok(MultiBindings {
+                bindings,
+                shared_types: None,
+                sources: self.sources,
+            });
+        }
+
+        let rustfmt = self.abigens.iter().all(|abigen| abigen.rustfmt);
+        let contracts = self
+            .abigens
+            .into_iter()
+            .map(|abigen| {
+                let module = abigen.contract_name.to_snake_case();
+                let rustfmt = abigen.rustfmt;
+                Ok((module, rustfmt, Context::from_abigen(abigen)?))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let shared = shared_type_declarations(contracts.iter().map(|(_, _, cx)| cx))?;
+        let shared_names: HashSet<_> = shared.iter().map(TypeDeclaration::name).collect();
+
+        let bindings = contracts
+            .iter()
+            .map(|(module, rustfmt, cx)| {
+                let tokens = cx.expand_with_shared_types(&shared_names)?;
+                let bindings = ContractBindings {
+                    tokens,
+                    rustfmt: *rustfmt,
+                };
+                Ok((module.clone(), bindings))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let shared_types = if shared.is_empty() {
+            None
+        } else {
+            Some(ContractBindings {
+                tokens: expand_shared_types(&shared),
+                rustfmt,
+            })
+        };
+
+        Ok(MultiBindings {
+            bindings,
+            shared_types,
+            sources: self.sources,
+        })
+    }
+}
+
+/// Returns the types which are declared by several contracts, all identically. Types
+/// with conflicting declarations stay in each contract's bindings.
+fn shared_type_declarations<'a>(
+    contracts: impl Iterator<Item = &'a Context>,
+) -> Result<Vec<TypeDeclaration>> {
+    let mut declarations: BTreeMap<String, Vec<TypeDeclaration>> = BTreeMap::new();
+    for cx in contracts {
+        for decl in cx.type_declarations()? {
+            declarations.entry(decl.name()).or_default().push(decl);
+        }
+    }
+
+    Ok(declarations
+        .into_iter()
+        .filter_map(|(_, mut decls)| {
+            let tokens = decls[0].tokens.to_string();
+            let identical = decls.iter().all(|decl| decl.tokens.to_string() == tokens);
+            if decls.len() > 1 && identical {
+                Some(decls.swap_remove(0))
+            } else {
+                None
+            }
+        })
+        .collect())
+}
+
+/// The type-safe bindings of multiple contracts, generated by a `MultiAbigen`
+pub struct MultiBindings {
+    /// The module name and bindings of each contract
+    bindings: Vec<(String, ContractBindings)>,
+
+    /// The types shared by the contracts, if any
+    shared_types: Option<ContractBindings>,
+
+    /// The files the bindings were generated from
+    sources: Vec<PathBuf>,
+}
+
+impl MultiBindings {
+    /// Writes the bindings of each contract to its own file in `dir`, along with a
+    /// `mod.rs` which declares them, so that `dir` can be used as a module of the crate.
+    pub fn write_to_module<P: AsRef<Path>>(&self, dir: P) -> Result<()> {
+        let mod_rs = self.write_files(dir.as_ref(), |module| format!("pub mod {};", module))?;
+        fs::write(dir.as_ref().join("mod.rs"), mod_rs)?;
+        Ok(())
+    }
+
+    /// Writes the bindings to the `OUT_DIR` of the running build script, along with a
+    /// `mod.rs` which can be included via `include!(concat!(env!("OUT_DIR"), "/mod.rs"))`.
+    ///
+    /// This also instructs cargo to re-run the build script whenever the ABI files change.
+    pub fn write_to_out_dir(&self) -> Result<PathBuf> {
+        let out_dir =
+            PathBuf::from(env::var_os("OUT_DIR").ok_or_else(|| anyhow!("OUT_DIR is not set"))?);
+
+        // `mod` declarations in included files are resolved relative to the including
+        // file, so each module includes its bindings instead
+        let mod_rs = self.write_files(&out_dir, |module| {
+            format!(
+                "pub mod {0} {{ include!(concat!(env!(\"OUT_DIR\"), \"/{0}.rs\")); }}",
+                module
+            )
+        })?;
+        let path = out_dir.join("mod.rs");
+        fs::write(&path, mod_rs)?;
+
+        for source in &self.sources {
+            println!("cargo:rerun-if-changed={}", source.display());
+        }
+
+        Ok(path)
+    }
+
+    /// Writes the bindings to `<dir>/<module>.rs` and returns the module declarations
+    fn write_files(&self, dir: &Path, declaration: impl Fn(&str) -> String) -> Result<String> {
+        fs::create_dir_all(dir)?;
+
+        let mut mod_rs = String::from("// Auto-generated contract bindings\n");
+        let shared_types = self
+            .shared_types
+            .as_ref()
+            .map(|bindings| ("shared_types", bindings));
+        let contracts = self
+            .bindings
+            .iter()
+            .map(|(module, bindings)| (module.as_str(), bindings));
+        for (module, bindings) in shared_types.into_iter().chain(contracts) {
+            bindings.write_to_file(dir.join(format!("{}.rs", module)))?;
+            writeln!(mod_rs, "{}", declaration(module))?;
+        }
+        Ok(mod_rs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_module_per_contract() {
+        let root = env::temp_dir().join(format!("ethers-multi-abigen-{}", std::process::id()));
+        let abi_dir = root.join("abi");
+        fs::create_dir_all(&abi_dir).unwrap();
+        let abi = r#"[{"type":"function","name":"balanceOf","inputs":[{"name":"owner","type":"address"}],"outputs":[{"name":"","type":"uint256"}],"stateMutability":"view"}]"#;
+        fs::write(abi_dir.join("SimpleToken.json"), abi).unwrap();
+        fs::write(abi_dir.join("Vault.json"), abi).unwrap();
+        fs::write(abi_dir.join("README.md"), "not an abi").unwrap();
+
+        let out_dir = root.join("bindings");
+        let bindings = MultiAbigen::from_json_files(&abi_dir)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(bindings.sources.len(), 3);
+        bindings.write_to_module(&out_dir).unwrap();
+
+        let mod_rs = fs::read_to_string(out_dir.join("mod.rs")).unwrap();
+        assert!(mod_rs.contains("pub mod simple_token;\npub mod vault;\n"));
+        let token = fs::read_to_string(out_dir.join("simple_token.rs")).unwrap();
+        assert!(token.contains("SimpleToken"));
+        assert!(out_dir.join("vault.rs").exists());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn deduplicates_shared_types() {
+        let transfer = r#"{"type":"event","name":"Transfer","inputs":[{"name":"from","type":"address","indexed":true},{"name":"to","type":"address","indexed":true},{"name":"value","type":"uint256","indexed":false}],"anonymous":false}"#;
+        let approval = |ty: &str| {
+            format!(
+                r#"{{"type":"event","name":"Approval","inputs":[{{"name":"owner","type":"address","indexed":true}},{{"name":"value","type":"{}","indexed":false}}],"anonymous":false}}"#,
+                ty
+            )
+        };
+        let multi = MultiAbigen::new()
+            .push(Abigen::new("Token", format!("[{},{}]", transfer, approval("uint256"))).unwrap())
+            .push(Abigen::new("Vault", format!("[{},{}]", transfer, approval("uint128"))).unwrap())
+            .shared_types(true);
+        let bindings = multi.build().unwrap();
+
+        let mut shared = Vec::new();
+        bindings
+            .shared_types
+            .as_ref()
+            .unwrap()
+            .write(&mut shared)
+            .unwrap();
+        let shared = String::from_utf8(shared).unwrap();
+        assert!(shared.contains("TransferFilter"));
+        assert!(shared.contains("TransferFilterTopics"));
+        // conflicting declarations are not shared
+        assert!(!shared.contains("ApprovalFilter"));
+
+        for (_, contract) in &bindings.bindings {
+            let mut tokens = Vec::new();
+            contract.write(&mut tokens).unwrap();
+            let tokens = String::from_utf8(tokens).unwrap();
+            assert!(tokens.contains("shared_types"));
+            assert!(!tokens.contains("pub struct TransferFilter"));
+            assert!(tokens.contains("pub struct ApprovalFilter"));
+        }
+    }
+}
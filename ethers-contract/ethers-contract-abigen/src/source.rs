@@ -38,7 +38,7 @@ impl Source {
     /// from `etherscan.io`. They can also be provided in-line. This method parses
     /// ABI source URLs and accepts the following:
     ///
-    /// - raw ABI JSON
+    /// - raw ABI JSON, or a raw compiled contract artifact with its ABI and bytecode
     ///
     /// - `relative/path/to/Contract.json`: a relative path to an ABI JSON file.
     /// This relative path is rooted in the current working directory.
@@ -62,7 +62,8 @@ impl Source {
         S: AsRef<str>,
     {
         let source = source.as_ref();
-        if source.starts_with('[') {
+        // inline human readable or JSON ABIs, and inline compiled contract artifacts
+        if source.starts_with('[') || source.starts_with('{') {
             return Ok(Source::String(source.to_owned()));
         }
         let root = env::current_dir()?.canonicalize()?;
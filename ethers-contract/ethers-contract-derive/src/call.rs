@@ -0,0 +1,178 @@
+//! Implementation of the `EthCall` derive
+use super::{derive_tokenizeable_impl, find_parameter_type, param_type_quote};
+
+use ethers_core::{
+    abi::{AbiParser, ParamType},
+    utils::id,
+};
+use hex::FromHex;
+use proc_macro2::{Literal, TokenStream};
+use quote::quote;
+use syn::{
+    parse::Error, spanned::Spanned as _, AttrStyle, Data, DeriveInput, Field, Fields, Lit, Meta,
+    NestedMeta,
+};
+
+/// The `#[ethcall(...)]` attributes of the struct
+#[derive(Default)]
+struct CallAttributes {
+    name: Option<String>,
+    abi: Option<(String, proc_macro2::Span)>,
+    selector: Option<Vec<u8>>,
+}
+
+pub(crate) fn derive_eth_call_impl(input: DeriveInput) -> Result<TokenStream, Error> {
+    let name = &input.ident;
+    let attributes = parse_call_attributes(&input)?;
+
+    let (function_name, param_types) = match attributes.abi {
+        Some((abi, span)) => {
+            let abi = abi.trim();
+            let abi = if abi.starts_with("function ") {
+                abi.to_string()
+            } else {
+                format!("function {}", abi)
+            };
+            let function = AbiParser::default().parse_function(&abi).map_err(|err| {
+                Error::new(span, format!("Failed to parse the function ABI: {:?}", err))
+            })?;
+            // the fields are encoded in order as the function's params
+            let fields = struct_fields(&input)?.len();
+            if fields != function.inputs.len() {
+                return Err(Error::new(
+                    span,
+                    format!(
+                        "the function has {} params but the struct has {} fields",
+                        function.inputs.len(),
+                        fields
+                    ),
+                ));
+            }
+            let param_types = function
+                .inputs
+                .into_iter()
+                .map(|param| param.kind)
+                .collect();
+            (attributes.name.unwrap_or(function.name), param_types)
+        }
+        None => (
+            attributes.name.unwrap_or_else(|| input.ident.to_string()),
+            derive_param_types_from_fields(&input)?,
+        ),
+    };
+
+    let abi_signature = format!(
+        "{}({})",
+        function_name,
+        param_types
+            .iter()
+            .map(ParamType::to_string)
+            .collect::<Vec<_>>()
+            .join(",")
+    );
+    let selector = attributes
+        .selector
+        .unwrap_or_else(|| id(&abi_signature).to_vec());
+    if selector.len() != 4 {
+        return Err(Error::new(input.span(), "selector must be 4 bytes"));
+    }
+    let selector = selector.into_iter().map(Literal::u8_unsuffixed);
+    let param_types = param_types.iter().map(param_type_quote);
+
+    let tokenize_impl = derive_tokenizeable_impl(&input);
+
+    Ok(quote! {
+        #tokenize_impl
+
+        impl ethers_contract::EthCall for #name {
+            fn function_name() -> ::std::borrow::Cow<'static, str> {
+                #function_name.into()
+            }
+
+            fn abi_signature() -> ::std::borrow::Cow<'static, str> {
+                #abi_signature.into()
+            }
+
+            fn selector() -> ethers_core::types::Selector {
+                [#( #selector ),*]
+            }
+
+            fn param_types() -> ::std::vec::Vec<ethers_core::abi::ParamType> {
+                vec![#( #param_types ),*]
+            }
+        }
+    })
+}
+
+fn derive_param_types_from_fields(input: &DeriveInput) -> Result<Vec<ParamType>, Error> {
+    struct_fields(input)?
+        .iter()
+        .map(|field| find_parameter_type(&field.ty))
+        .collect()
+}
+
+fn struct_fields(input: &DeriveInput) -> Result<Vec<&Field>, Error> {
+    let fields: Vec<_> = match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => fields.named.iter().collect(),
+            Fields::Unnamed(ref fields) => fields.unnamed.iter().collect(),
+            Fields::Unit => Vec::new(),
+        },
+        Data::Enum(_) => {
+            return Err(Error::new(
+                input.span(),
+                "EthCall cannot be derived for enums",
+            ));
+        }
+        Data::Union(_) => {
+            return Err(Error::new(
+                input.span(),
+                "EthCall cannot be derived for unions",
+            ));
+        }
+    };
+    Ok(fields)
+}
+
+fn parse_call_attributes(input: &DeriveInput) -> Result<CallAttributes, Error> {
+    let mut result = CallAttributes::default();
+    for attr in input.attrs.iter() {
+        if let AttrStyle::Outer = attr.style {
+            if let Ok(Meta::List(meta)) = attr.parse_meta() {
+                if !meta.path.is_ident("ethcall") {
+                    continue;
+                }
+                for nested in meta.nested.iter() {
+                    let meta = match nested {
+                        NestedMeta::Meta(Meta::NameValue(meta)) => meta,
+                        _ => {
+                            return Err(Error::new(nested.span(), "unrecognized ethcall parameter"))
+                        }
+                    };
+                    let value = match meta.lit {
+                        Lit::Str(ref lit_str) => lit_str,
+                        _ => return Err(Error::new(meta.span(), "expected a string")),
+                    };
+
+                    if meta.path.is_ident("name") && result.name.is_none() {
+                        result.name = Some(value.value());
+                    } else if meta.path.is_ident("abi") && result.abi.is_none() {
+                        result.abi = Some((value.value(), value.span()));
+                    } else if meta.path.is_ident("selector") && result.selector.is_none() {
+                        let selector = Vec::from_hex(value.value().trim_start_matches("0x"))
+                            .map_err(|err| {
+                                Error::new(meta.span(), format!("Expected hex selector: {:?}", err))
+                            })?;
+                        result.selector = Some(selector);
+                    } else {
+                        return Err(Error::new(
+                            meta.span(),
+                            "unrecognized or duplicate ethcall parameter",
+                        ));
+                    }
+                }
+            }
+        }
+    }
+    Ok(result)
+}
@@ -18,6 +18,7 @@ use hex::FromHex;
 use spanned::Spanned;
 
 mod abigen;
+mod call;
 mod spanned;
 
 /// Proc macro to generate type-safe bindings to a contract. This macro accepts
@@ -239,6 +240,41 @@ pub fn derive_abi_event(input: TokenStream) -> TokenStream {
     })
 }
 
+/// Derives the `EthCall` and `Tokenizeable` trait for the labeled type, which
+/// represents the arguments of a function call.
+///
+/// Additional arguments can be specified using the `#[ethcall(...)]` attribute:
+///
+/// - `name = "..."`: Overrides the function's name, default is the name of the function
+///  in `abi` or else the struct's name.
+/// - `abi = "..."`: The solidity function definition (e.g. `transfer(address,uint256)`)
+///  the struct's fields correspond to. If omitted, the ABI is determined from the fields.
+/// - `selector = "..."`: The selector as hex string to override the function's selector.
+///
+/// # Example
+/// ```ignore
+/// # use ethers_core::types::{Address, U256};
+/// # use ethers_contract::EthCall;
+///
+/// #[derive(Debug, EthCall)]
+/// #[ethcall(abi = "transfer(address,uint256)")]
+/// struct TransferCall {
+///     to: Address,
+///     amount: U256,
+/// }
+///
+/// let calldata = TransferCall { to: Address::zero(), amount: 100.into() }.encode();
+/// let call = TransferCall::decode(calldata).unwrap();
+/// ```
+#[proc_macro_derive(EthCall, attributes(ethcall))]
+pub fn derive_abi_call(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match call::derive_eth_call_impl(input) {
+        Ok(tokens) => TokenStream::from(tokens),
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    }
+}
+
 struct EventField {
     topic_name: Option<String>,
     index: usize,
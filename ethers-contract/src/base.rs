@@ -2,12 +2,14 @@ use crate::Contract;
 
 use ethers_core::{
     abi::{
-        Abi, Detokenize, Error, Event, Function, FunctionExt, InvalidOutputType, RawLog, Tokenize,
+        Abi, Detokenize, Error, Event, EventExt, Function, FunctionExt, InvalidOutputType, RawLog,
+        Token, Tokenize,
     },
-    types::{Address, Bytes, Selector, H256},
+    types::{Address, Bytes, Log, Selector, TransactionReceipt, H256, I256},
 };
 use ethers_providers::Middleware;
 
+use serde_json::{Map, Value};
 use std::{collections::HashMap, fmt::Debug, hash::Hash, sync::Arc};
 use thiserror::Error;
 
@@ -101,6 +103,53 @@ impl BaseContract {
         decode_function_data(function, bytes, true)
     }
 
+    /// Decodes the provided calldata into a JSON object with the `name` and `signature`
+    /// of the called function, and its `args` keyed by parameter name. The function is
+    /// looked up by the calldata's selector.
+    pub fn decode_input_json<T: AsRef<[u8]>>(&self, bytes: T) -> Result<Value, AbiError> {
+        let bytes = bytes.as_ref();
+        if bytes.len() < 4 {
+            return Err(AbiError::WrongSelector);
+        }
+        let mut selector = [0; 4];
+        selector.copy_from_slice(&bytes[..4]);
+        let function = self.get_from_signature(selector)?;
+        let tokens = function.decode_input(&bytes[4..])?;
+        let args = function.inputs.iter().map(|param| param.name.as_str());
+
+        Ok(serde_json::json!({
+            "name": function.name,
+            "signature": function.abi_signature(),
+            "args": tokens_to_json_map(args, tokens),
+        }))
+    }
+
+    /// Decodes the provided log into a JSON object with the `name` and `signature` of
+    /// the emitted event, and its `args` keyed by parameter name. The event is looked up
+    /// by the log's first topic, so anonymous events cannot be decoded.
+    pub fn decode_log_json(&self, log: &Log) -> Result<Value, AbiError> {
+        let topic = log.topics.first().copied().unwrap_or_default();
+        let event = self
+            .abi
+            .events()
+            .find(|event| !event.anonymous && event.signature() == topic)
+            .ok_or_else(|| Error::InvalidName(format!("{:?}", topic)))?;
+        let params = event
+            .parse_log(RawLog {
+                topics: log.topics.clone(),
+                data: log.data.to_vec(),
+            })?
+            .params;
+        let args = params.iter().map(|param| param.name.as_str());
+        let tokens = params.iter().map(|param| param.value.clone());
+
+        Ok(serde_json::json!({
+            "name": event.name,
+            "signature": event.abi_signature(),
+            "args": tokens_to_json_map(args, tokens),
+        }))
+    }
+
     fn get_from_signature(&self, signature: Selector) -> Result<&Function, AbiError> {
         Ok(self
             .methods
@@ -147,6 +196,42 @@ pub(crate) fn decode_event<D: Detokenize>(
     Ok(D::from_tokens(tokens)?)
 }
 
+/// Decodes the logs of a transaction receipt which were emitted by `address` for the
+/// ABI's event `name`. Logs emitted by other contracts or for other events are skipped.
+pub fn decode_receipt_logs<D: Detokenize>(
+    abi: &Abi,
+    name: &str,
+    address: Address,
+    receipt: &TransactionReceipt,
+) -> Result<Vec<D>, AbiError> {
+    let event = abi.event(name)?;
+    decode_receipt_logs_with(
+        receipt,
+        address,
+        event.signature(),
+        event.anonymous,
+        |log| decode_event(event, log.topics.clone(), log.data.clone()),
+    )
+}
+
+/// Decodes the logs of a transaction receipt which were emitted by `address` for the
+/// event with the provided signature
+pub(crate) fn decode_receipt_logs_with<D, E>(
+    receipt: &TransactionReceipt,
+    address: Address,
+    signature: H256,
+    anonymous: bool,
+    decode: impl Fn(&Log) -> Result<D, E>,
+) -> Result<Vec<D>, E> {
+    receipt
+        .logs
+        .iter()
+        .filter(|log| log.address == address)
+        .filter(|log| anonymous || log.topics.first() == Some(&signature))
+        .map(decode)
+        .collect()
+}
+
 /// Helper for ABI encoding arguments for a specific function
 pub fn encode_function_data<T: Tokenize>(function: &Function, args: T) -> Result<Bytes, AbiError> {
     let tokens = args.into_tokens();
@@ -172,6 +257,44 @@ pub fn decode_function_data<D: Detokenize, T: AsRef<[u8]>>(
     Ok(D::from_tokens(tokens)?)
 }
 
+/// Converts an ABI token to a JSON value. Integers are converted to decimal strings since
+/// they may not fit in a JSON number, and addresses and bytes to `0x`-prefixed hex strings.
+pub fn token_to_json(token: &Token) -> Value {
+    match token {
+        Token::Address(address) => Value::String(format!("{:?}", address)),
+        Token::FixedBytes(bytes) | Token::Bytes(bytes) => {
+            Value::String(format!("0x{}", hex::encode(bytes)))
+        }
+        Token::Int(int) => Value::String(I256::from_raw(*int).to_string()),
+        Token::Uint(uint) => Value::String(uint.to_string()),
+        Token::Bool(b) => Value::Bool(*b),
+        Token::String(s) => Value::String(s.clone()),
+        Token::FixedArray(tokens) | Token::Array(tokens) | Token::Tuple(tokens) => {
+            Value::Array(tokens.iter().map(token_to_json).collect())
+        }
+    }
+}
+
+/// Pairs the decoded tokens with their parameter names, falling back to the parameter's
+/// position for unnamed parameters
+fn tokens_to_json_map<'a>(
+    names: impl Iterator<Item = &'a str>,
+    tokens: impl IntoIterator<Item = Token>,
+) -> Map<String, Value> {
+    names
+        .zip(tokens)
+        .enumerate()
+        .map(|(idx, (name, token))| {
+            let name = if name.is_empty() {
+                idx.to_string()
+            } else {
+                name.to_owned()
+            };
+            (name, token_to_json(&token))
+        })
+        .collect()
+}
+
 /// Utility function for creating a mapping between a unique signature and a
 /// name-index pair for accessing contract ABI items.
 fn create_mapping<T, S, F>(
@@ -197,7 +320,10 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ethers_core::{abi::parse_abi, types::U256};
+    use ethers_core::{
+        abi::parse_abi,
+        types::{Log, U256},
+    };
 
     #[test]
     fn can_parse_function_inputs() {
@@ -257,4 +383,108 @@ mod tests {
                 .unwrap()
         );
     }
+
+    #[test]
+    fn can_decode_receipt_logs() {
+        let abi = parse_abi(&[
+            "event Approval(address indexed owner, address indexed spender, uint256 value)",
+            "event Transfer(address indexed from, address indexed to, uint256 value)",
+        ])
+        .unwrap();
+        let token = Address::repeat_byte(1);
+        let owner = Address::repeat_byte(2);
+        let spender = Address::repeat_byte(3);
+
+        let log = |address: Address, event: &str, value: u64| Log {
+            address,
+            topics: vec![
+                abi.event(event).unwrap().signature(),
+                H256::from(owner),
+                H256::from(spender),
+            ],
+            data: ethers_core::abi::encode(&[ethers_core::abi::Token::Uint(value.into())]).into(),
+            block_hash: None,
+            block_number: None,
+            transaction_hash: None,
+            transaction_index: None,
+            log_index: None,
+            transaction_log_index: None,
+            log_type: None,
+            removed: None,
+        };
+        let receipt = TransactionReceipt {
+            logs: vec![
+                log(token, "Approval", 1),
+                // emitted by another contract
+                log(Address::zero(), "Approval", 2),
+                // another event
+                log(token, "Transfer", 3),
+                log(token, "Approval", 4),
+            ],
+            ..Default::default()
+        };
+
+        let approvals: Vec<(Address, Address, U256)> =
+            decode_receipt_logs(&abi, "Approval", token, &receipt).unwrap();
+        assert_eq!(
+            approvals,
+            vec![(owner, spender, 1.into()), (owner, spender, 4.into())]
+        );
+    }
+
+    #[test]
+    fn can_decode_to_json() {
+        let abi = BaseContract::from(
+            parse_abi(&[
+                "event Transfer(address indexed from, address indexed to, int256 value)",
+                "function transfer(address to, uint256) external returns (bool)",
+            ])
+            .unwrap(),
+        );
+        let to = Address::repeat_byte(2);
+
+        let calldata = abi.encode("transfer", (to, U256::from(1000))).unwrap();
+        assert_eq!(
+            abi.decode_input_json(&calldata).unwrap(),
+            serde_json::json!({
+                "name": "transfer",
+                "signature": "transfer(address,uint256)",
+                "args": { "to": format!("{:?}", to), "1": "1000" },
+            })
+        );
+        assert!(abi.decode_input_json(&calldata.as_ref()[..3]).is_err());
+
+        let log = Log {
+            address: Address::zero(),
+            topics: vec![
+                abi.abi().event("Transfer").unwrap().signature(),
+                H256::from(Address::repeat_byte(1)),
+                H256::from(to),
+            ],
+            data: ethers_core::abi::encode(&[ethers_core::abi::Token::Int(
+                I256::from(-5i32).into_raw(),
+            )])
+            .into(),
+            block_hash: None,
+            block_number: None,
+            transaction_hash: None,
+            transaction_index: None,
+            log_index: None,
+            transaction_log_index: None,
+            log_type: None,
+            removed: None,
+        };
+        assert_eq!(
+            abi.decode_log_json(&log).unwrap(),
+            serde_json::json!({
+                "name": "Transfer",
+                "signature": "Transfer(address,address,int256)",
+                "args": {
+                    "from": format!("{:?}", Address::repeat_byte(1)),
+                    "to": format!("{:?}", to),
+                    "value": "-5",
+                },
+            })
+        );
+    }
 }
@@ -1,11 +1,14 @@
 use super::base::{decode_function_data, AbiError};
 use ethers_core::{
-    abi::{Detokenize, Function, InvalidOutputType},
-    types::{Address, BlockId, Bytes, TransactionRequest, U256},
+    abi::{self, Detokenize, Function, InvalidOutputType, ParamType, Token, Tokenizable},
+    types::{
+        Address, BlockId, Bytes, Eip1559TransactionRequest, Selector, TransactionRequest, TxHash,
+        TypedTransaction, U256,
+    },
 };
 use ethers_providers::{Middleware, PendingTransaction, ProviderError};
 
-use std::{fmt::Debug, marker::PhantomData, sync::Arc};
+use std::{borrow::Cow, fmt, fmt::Debug, marker::PhantomData, sync::Arc};
 
 use thiserror::Error as ThisError;
 
@@ -41,14 +44,200 @@ pub enum ContractError<M: Middleware> {
     /// receipt
     #[error("Contract was not deployed")]
     ContractNotDeployed,
+
+    /// Thrown if the contract deployment transaction was mined but reverted
+    #[error("Contract deployment reverted in transaction {0:?}")]
+    DeploymentReverted(TxHash),
+
+    /// Thrown when a call reverted, containing the data it reverted with
+    #[error("Contract call reverted: {}", ContractRevert::decode(.0.as_ref()))]
+    Revert(Bytes),
+
+    /// Thrown when a call sending ETH is batched with a Multicall contract which cannot
+    /// forward it, i.e. any version before `Multicall3`
+    #[error("Only Multicall3 can send ETH with the batched calls")]
+    MulticallValueNotSupported,
+}
+
+/// The reason a contract call reverted, decoded from its revert data
+#[derive(Clone, Debug, PartialEq)]
+pub enum ContractRevert {
+    /// `Error(string)`, thrown by `revert("reason")` and failing `require`s
+    Error(String),
+    /// `Panic(uint256)`, thrown by failing assertions, arithmetic overflows, out of
+    /// bounds accesses and other internal errors, along with the panic code
+    Panic(U256),
+    /// Any other revert data, e.g. a custom error or an empty `revert()`
+    Custom(Bytes),
+}
+
+impl ContractRevert {
+    /// Decodes the builtin Solidity errors from revert data, falling back to
+    /// `ContractRevert::Custom` for any other data
+    pub fn decode(data: &[u8]) -> Self {
+        match decode_builtin_error(data) {
+            Some(Token::String(reason)) => ContractRevert::Error(reason),
+            Some(Token::Uint(code)) => ContractRevert::Panic(code),
+            _ => ContractRevert::Custom(data.to_vec().into()),
+        }
+    }
+
+    /// Returns the description of the panic code, as listed in the Solidity docs
+    fn panic_description(code: U256) -> Option<&'static str> {
+        if code > U256::from(u8::MAX) {
+            return None;
+        }
+        let description = match code.low_u64() {
+            0x00 => "generic compiler panic",
+            0x01 => "assertion failed",
+            0x11 => "arithmetic underflow or overflow",
+            0x12 => "division or modulo by zero",
+            0x21 => "invalid enum value",
+            0x22 => "invalid storage byte array encoding",
+            0x31 => "pop on an empty array",
+            0x32 => "array index out of bounds",
+            0x41 => "out of memory",
+            0x51 => "call to an uninitialized function",
+            _ => return None,
+        };
+        Some(description)
+    }
+}
+
+impl fmt::Display for ContractRevert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContractRevert::Error(reason) => write!(f, "{}", reason),
+            ContractRevert::Panic(code) => match Self::panic_description(*code) {
+                Some(description) => write!(f, "panic {:#x} ({})", code, description),
+                None => write!(f, "panic {:#x}", code),
+            },
+            ContractRevert::Custom(data) if data.as_ref().is_empty() => {
+                write!(f, "no revert data")
+            }
+            ContractRevert::Custom(data) => write!(f, "0x{}", hex::encode(data)),
+        }
+    }
+}
+
+/// The selector of Solidity's `Error(string)`, used by `revert("reason")` and `require`
+const ERROR_SELECTOR: Selector = [0x08, 0xc3, 0x79, 0xa0];
+
+/// The selector of Solidity's `Panic(uint256)`, used by failing assertions and arithmetic
+const PANIC_SELECTOR: Selector = [0x4e, 0x48, 0x7b, 0x71];
+
+/// Decodes the builtin Solidity error of revert data: the reason of `Error(string)` or
+/// the code of `Panic(uint256)`
+fn decode_builtin_error(data: &[u8]) -> Option<Token> {
+    if data.len() < 4 {
+        return None;
+    }
+    let param = if data[..4] == ERROR_SELECTOR {
+        ParamType::String
+    } else if data[..4] == PANIC_SELECTOR {
+        ParamType::Uint(256)
+    } else {
+        return None;
+    };
+    abi::decode(&[param], &data[4..]).ok()?.pop()
+}
+
+impl<M: Middleware> ContractError<M> {
+    /// Converts the error of a middleware call, extracting the revert data from the
+    /// node's error response if the call reverted
+    pub(crate) fn from_middleware_error(client: &M, err: M::Error) -> Self {
+        match client
+            .as_error_response(&err)
+            .and_then(|res| res.as_revert_data())
+        {
+            Some(data) => ContractError::Revert(data),
+            None => ContractError::MiddlewareError(err),
+        }
+    }
+
+    /// Returns the data the call reverted with, if it reverted
+    pub fn as_revert(&self) -> Option<&Bytes> {
+        match self {
+            ContractError::Revert(data) => Some(data),
+            _ => None,
+        }
+    }
+
+    /// Returns the decoded reason the call reverted with, if it reverted
+    pub fn revert_reason(&self) -> Option<ContractRevert> {
+        self.as_revert()
+            .map(|data| ContractRevert::decode(data.as_ref()))
+    }
+
+    /// Decodes the builtin Solidity error the call reverted with: the reason of
+    /// `Error(string)` as a `String`, or the code of `Panic(uint256)` as a `U256`.
+    ///
+    /// ```no_run
+    /// # use ethers::{contract::ContractError, providers::{Http, Provider}};
+    /// # fn foo(err: ContractError<Provider<Http>>) {
+    /// if let Some(reason) = err.decode_revert::<String>() {
+    ///     println!("reverted with {}", reason);
+    /// }
+    /// # }
+    /// ```
+    pub fn decode_revert<T: Detokenize>(&self) -> Option<T> {
+        let token = decode_builtin_error(self.as_revert()?.as_ref())?;
+        T::from_tokens(vec![token]).ok()
+    }
+
+    /// Decodes the custom error the call reverted with, e.g. `InsufficientBalance` for
+    /// `error InsufficientBalance(uint256 available, uint256 required)`. Custom errors are
+    /// encoded like calldata, so they are represented by `EthCall` types.
+    pub fn decode_custom_error<E: EthCall>(&self) -> Option<E> {
+        E::decode(self.as_revert()?).ok()
+    }
+}
+
+/// A trait for types which represent the arguments of a function call, which can
+/// be encoded into and decoded from calldata
+pub trait EthCall: Tokenizable + Sized {
+    /// The name of the function this type represents
+    fn function_name() -> Cow<'static, str>;
+
+    /// Retrieves the ABI signature of the function, e.g. `transfer(address,uint256)`
+    fn abi_signature() -> Cow<'static, str>;
+
+    /// Retrieves the function's selector, the first 4 bytes of the Keccak-256 hash of
+    /// its ABI signature
+    fn selector() -> Selector;
+
+    /// Returns the types of the function's arguments
+    fn param_types() -> Vec<ParamType>;
+
+    /// ABI encodes the arguments, prefixed with the function's selector
+    fn encode(self) -> Bytes {
+        let tokens = match self.into_token() {
+            Token::Tuple(tokens) => tokens,
+            token => vec![token],
+        };
+        let mut calldata = Self::selector().to_vec();
+        calldata.extend(abi::encode(&tokens));
+        calldata.into()
+    }
+
+    /// Decodes the arguments from calldata, which must start with the function's selector
+    fn decode<T: AsRef<[u8]>>(calldata: T) -> Result<Self, AbiError> {
+        let calldata = calldata.as_ref();
+        if calldata.len() < 4 || calldata[..4] != Self::selector() {
+            return Err(AbiError::WrongSelector);
+        }
+        let tokens = abi::decode(&Self::param_types(), &calldata[4..])?;
+        Ok(Self::from_token(Token::Tuple(tokens))?)
+    }
 }
 
 #[derive(Debug, Clone)]
 #[must_use = "contract calls do nothing unless you `send` or `call` them"]
 /// Helper for managing a transaction before submitting it to a node
 pub struct ContractCall<M, D> {
-    /// The raw transaction object
-    pub tx: TransactionRequest,
+    /// The raw transaction object, a legacy transaction unless converted with
+    /// [`eip1559`](ContractCall::eip1559)
+    pub tx: TypedTransaction,
     /// The ABI of the function being called
     pub function: Function,
     /// Optional block number to be used when calculating the transaction's gas and nonce
@@ -60,25 +249,42 @@ pub struct ContractCall<M, D> {
 impl<M, D: Detokenize> ContractCall<M, D> {
     /// Sets the `from` field in the transaction to the provided value
     pub fn from<T: Into<Address>>(mut self, from: T) -> Self {
-        self.tx.from = Some(from.into());
+        self.tx.set_from(from.into());
         self
     }
 
     /// Sets the `gas` field in the transaction to the provided value
     pub fn gas<T: Into<U256>>(mut self, gas: T) -> Self {
-        self.tx.gas = Some(gas.into());
+        self.tx.set_gas(gas);
         self
     }
 
-    /// Sets the `gas_price` field in the transaction to the provided value
+    /// Sets the `gas_price` field in the transaction to the provided value. For EIP-1559
+    /// transactions, both the max fee and the max priority fee per gas are set to it.
     pub fn gas_price<T: Into<U256>>(mut self, gas_price: T) -> Self {
-        self.tx.gas_price = Some(gas_price.into());
+        self.tx.set_gas_price(gas_price);
         self
     }
 
     /// Sets the `value` field in the transaction to the provided value
     pub fn value<T: Into<U256>>(mut self, value: T) -> Self {
-        self.tx.value = Some(value.into());
+        self.tx.set_value(value);
+        self
+    }
+
+    /// Sends the call as an [EIP-1559](https://eips.ethereum.org/EIPS/eip-1559)
+    /// transaction, whose fees are filled in by the middleware. A gas price set beforehand
+    /// becomes its max fee per gas.
+    pub fn eip1559(mut self) -> Self {
+        self.tx = <Eip1559TransactionRequest as From<_>>::from(&self.tx).into();
+        self
+    }
+
+    /// Sends the call as a legacy transaction, e.g. on chains which do not support
+    /// EIP-1559. The fees of EIP-1559 transactions are dropped, so that the gas price
+    /// gets filled in by the middleware unless set afterwards.
+    pub fn legacy(mut self) -> Self {
+        self.tx = <TransactionRequest as From<_>>::from(&self.tx).into();
         self
     }
 
@@ -96,7 +302,7 @@ where
 {
     /// Returns the underlying transaction's ABI encoded data
     pub fn calldata(&self) -> Option<Bytes> {
-        self.tx.data.clone()
+        self.tx.data().cloned()
     }
 
     /// Returns the estimated gas cost for the underlying transaction to be executed
@@ -104,7 +310,7 @@ where
         self.client
             .estimate_gas(&self.tx)
             .await
-            .map_err(ContractError::MiddlewareError)
+            .map_err(|err| ContractError::from_middleware_error(&*self.client, err))
     }
 
     /// Queries the blockchain via an `eth_call` for the provided transaction.
@@ -121,7 +327,7 @@ where
             .client
             .call(&self.tx, self.block)
             .await
-            .map_err(ContractError::MiddlewareError)?;
+            .map_err(|err| ContractError::from_middleware_error(&*self.client, err))?;
 
         // decode output
         let data = decode_function_data(&self.function, &bytes, false)?;
@@ -134,6 +340,6 @@ where
         self.client
             .send_transaction(self.tx.clone(), self.block)
             .await
-            .map_err(ContractError::MiddlewareError)
+            .map_err(|err| ContractError::from_middleware_error(&*self.client, err))
     }
 }
@@ -1,13 +1,13 @@
 use crate::{
-    base::{encode_function_data, AbiError, BaseContract},
+    base::{decode_receipt_logs_with, encode_function_data, AbiError, BaseContract},
     call::ContractCall,
     event::{EthEvent, Event},
     EthLogDecode,
 };
 
 use ethers_core::{
-    abi::{Abi, Detokenize, Error, EventExt, Function, Tokenize},
-    types::{Address, Filter, NameOrAddress, Selector, TransactionRequest},
+    abi::{Abi, Detokenize, Error, EventExt, Function, RawLog, Tokenize},
+    types::{Address, Filter, NameOrAddress, Selector, TransactionReceipt, TransactionRequest},
 };
 use ethers_providers::Middleware;
 
@@ -184,6 +184,23 @@ impl<M: Middleware> Contract<M> {
         Ok(self.event_with_filter(Filter::new().event(&event.abi_signature())))
     }
 
+    /// Decodes the logs of a transaction receipt which were emitted by this contract
+    /// for the event `D`. Logs emitted by other contracts or for other events are skipped.
+    pub fn decode_logs<D: EthEvent>(&self, receipt: &TransactionReceipt) -> Result<Vec<D>, Error> {
+        decode_receipt_logs_with(
+            receipt,
+            self.address,
+            D::signature(),
+            D::is_anonymous(),
+            |log| {
+                <D as EthEvent>::decode_log(&RawLog {
+                    topics: log.topics.clone(),
+                    data: log.data.to_vec(),
+                })
+            },
+        )
+    }
+
     /// Returns a transaction builder for the provided function name. If there are
     /// multiple functions with the same name due to overloading, consider using
     /// the `method_hash` method instead, since this will use the first match.
@@ -228,7 +245,7 @@ impl<M: Middleware> Contract<M> {
         };
 
         Ok(ContractCall {
-            tx,
+            tx: tx.into(),
             client: Arc::clone(&self.client), // cheap clone behind the Arc
             block: None,
             function: function.to_owned(),
@@ -0,0 +1,145 @@
+use crate::{BaseContract, Contract, ContractError};
+
+use ethers_core::types::{Address, NameOrAddress};
+use ethers_providers::Middleware;
+
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// A contract which is addressed by its ENS name. The name is resolved when the
+/// contract is first used, and optionally re-resolved once a TTL has elapsed, so that
+/// long-running services follow the contract when the name is pointed to a new deployment.
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers::{
+///     abi::Abi,
+///     contract::EnsContract,
+///     providers::{Http, Provider},
+///     types::Address,
+/// };
+/// use std::{convert::TryFrom, time::Duration};
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// # let abi: Abi = serde_json::from_str("")?;
+/// let client = Provider::<Http>::try_from("http://localhost:8545")?;
+///
+/// // re-resolve the name every hour
+/// let registry = EnsContract::new("registry.example.eth", abi, client)
+///     .ttl(Duration::from_secs(3600));
+///
+/// let owner = registry
+///     .contract()
+///     .await?
+///     .method::<_, Address>("owner", ())?
+///     .call()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct EnsContract<M> {
+    target: NameOrAddress,
+    base_contract: BaseContract,
+    client: Arc<M>,
+    ttl: Option<Duration>,
+    /// The last resolved address along with when it was resolved
+    resolved: Mutex<Option<(Address, Instant)>>,
+}
+
+impl<M: Middleware> EnsContract<M> {
+    /// Creates a contract addressed by the provided ENS name or address. Names are
+    /// resolved once, unless a [`ttl`](EnsContract::ttl) is set.
+    pub fn new(
+        target: impl Into<NameOrAddress>,
+        abi: impl Into<BaseContract>,
+        client: impl Into<Arc<M>>,
+    ) -> Self {
+        Self {
+            target: target.into(),
+            base_contract: abi.into(),
+            client: client.into(),
+            ttl: None,
+            resolved: Mutex::new(None),
+        }
+    }
+
+    /// Sets the duration after which the ENS name is resolved again
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Returns the ENS name or address of the contract
+    pub fn target(&self) -> &NameOrAddress {
+        &self.target
+    }
+
+    /// Returns the address of the contract, resolving its ENS name if it was not resolved
+    /// yet or if the TTL has elapsed since it was last resolved
+    pub async fn address(&self) -> Result<Address, ContractError<M>> {
+        let name = match &self.target {
+            NameOrAddress::Address(address) => return Ok(*address),
+            NameOrAddress::Name(name) => name,
+        };
+
+        let resolved = *self.resolved.lock().unwrap();
+        if let Some((address, resolved_at)) = resolved {
+            let expired = self.ttl.map_or(false, |ttl| resolved_at.elapsed() >= ttl);
+            if !expired {
+                return Ok(address);
+            }
+        }
+
+        let address = self
+            .client
+            .resolve_name(name)
+            .await
+            .map_err(|err| ContractError::from_middleware_error(&*self.client, err))?;
+        *self.resolved.lock().unwrap() = Some((address, Instant::now()));
+        Ok(address)
+    }
+
+    /// Returns the contract at the address its ENS name currently resolves to
+    pub async fn contract(&self) -> Result<Contract<M>, ContractError<M>> {
+        let address = self.address().await?;
+        Ok(Contract::new(
+            address,
+            self.base_contract.clone(),
+            Arc::clone(&self.client),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers_core::{
+        abi::{self, Abi, Token},
+        types::Bytes,
+    };
+    use ethers_providers::Provider;
+
+    #[tokio::test]
+    async fn resolves_name_once() {
+        let (provider, mock) = Provider::mocked();
+        let resolver = Address::repeat_byte(0x11);
+        let address = Address::repeat_byte(0x22);
+        let encode = |address: Address| Bytes::from(abi::encode(&[Token::Address(address)]));
+        mock.push_response("eth_call", encode(resolver)).unwrap();
+        mock.push_response("eth_call", encode(address)).unwrap();
+
+        let abi: Abi = serde_json::from_str("[]").unwrap();
+        let contract = EnsContract::new("registry.eth", abi, provider);
+        assert_eq!(contract.contract().await.unwrap().address(), address);
+        // the address is cached, resolving again would run out of mocked responses
+        assert_eq!(contract.address().await.unwrap(), address);
+
+        // once the ttl elapsed, the name is resolved again
+        let contract = contract.ttl(Duration::from_secs(0));
+        assert!(contract.address().await.is_err());
+    }
+}
@@ -138,6 +138,26 @@ where
             Box::new(move |log| self.parse_log(log)),
         ))
     }
+
+    /// Returns a stream for the event along with the metadata of each log, i.e. the block
+    /// and transaction in which it was emitted
+    pub async fn stream_with_meta(
+        &'a self,
+    ) -> Result<
+        EventStream<'a, FilterWatcher<'a, M::Provider, Log>, (D, LogMeta), ContractError<M>>,
+        ContractError<M>,
+    > {
+        let filter = self
+            .provider
+            .watch(&self.filter)
+            .await
+            .map_err(ContractError::MiddlewareError)?;
+        Ok(EventStream::new(
+            filter.id,
+            filter,
+            Box::new(move |log| self.parse_log_with_meta(log)),
+        ))
+    }
 }
 
 impl<'a, M, D> Event<'a, M, D>
@@ -165,6 +185,25 @@ where
             Box::new(move |log| self.parse_log(log)),
         ))
     }
+
+    /// Returns a subscription for the event along with the metadata of each log
+    pub async fn subscribe_with_meta(
+        &'a self,
+    ) -> Result<
+        EventStream<'a, SubscriptionStream<'a, M::Provider, Log>, (D, LogMeta), ContractError<M>>,
+        ContractError<M>,
+    > {
+        let filter = self
+            .provider
+            .subscribe_logs(&self.filter)
+            .await
+            .map_err(ContractError::MiddlewareError)?;
+        Ok(EventStream::new(
+            filter.id,
+            filter,
+            Box::new(move |log| self.parse_log_with_meta(log)),
+        ))
+    }
 }
 
 impl<M, D> Event<'_, M, D>
@@ -197,15 +236,22 @@ where
             .map_err(ContractError::MiddlewareError)?;
         let events = logs
             .into_iter()
-            .map(|log| {
-                let meta = LogMeta::from(&log);
-                let event = self.parse_log(log)?;
-                Ok((event, meta))
-            })
+            .map(|log| self.parse_log_with_meta(log))
             .collect::<Result<_, ContractError<M>>>()?;
         Ok(events)
     }
 
+    /// Decodes a log along with its metadata, erroring on pending logs which were not
+    /// mined in a block yet
+    fn parse_log_with_meta(&self, log: Log) -> Result<(D, LogMeta), ContractError<M>> {
+        if log.block_number.is_none() || log.transaction_hash.is_none() {
+            return Err(ethers_core::abi::Error::InvalidData.into());
+        }
+        let meta = LogMeta::from(&log);
+        let event = self.parse_log(log)?;
+        Ok((event, meta))
+    }
+
     fn parse_log(&self, log: Log) -> Result<D, ContractError<M>> {
         D::decode_log(&RawLog {
             topics: log.topics,
@@ -233,3 +279,68 @@ impl From<&Log> for LogMeta {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers_core::types::Address;
+    use ethers_providers::Provider;
+
+    #[derive(Debug, PartialEq)]
+    struct Topics(Vec<H256>);
+
+    impl EthLogDecode for Topics {
+        fn decode_log(log: &RawLog) -> Result<Self, ethers_core::abi::Error> {
+            Ok(Topics(log.topics.clone()))
+        }
+    }
+
+    fn log(block_number: Option<U64>, transaction_hash: Option<TxHash>) -> Log {
+        Log {
+            address: Address::zero(),
+            topics: vec![H256::repeat_byte(1)],
+            data: Default::default(),
+            block_hash: None,
+            block_number,
+            transaction_hash,
+            transaction_index: None,
+            log_index: None,
+            transaction_log_index: None,
+            log_type: Some("pending".to_string()),
+            removed: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn meta_of_pending_logs_errors() {
+        let (provider, mock) = Provider::mocked();
+        let event = Event::<_, Topics> {
+            filter: Filter::new(),
+            provider: &provider,
+            datatype: PhantomData,
+        };
+
+        mock.push_response::<Vec<Log>, _>("eth_getLogs", vec![log(None, None)])
+            .unwrap();
+        let err = event.query_with_meta().await.unwrap_err();
+        assert!(matches!(
+            err,
+            ContractError::DecodingError(ethers_core::abi::Error::InvalidData)
+        ));
+
+        let hash = H256::repeat_byte(2);
+        mock.push_response::<Vec<Log>, _>("eth_getLogs", vec![log(Some(3.into()), Some(hash))])
+            .unwrap();
+        let events = event.query_with_meta().await.unwrap();
+        assert_eq!(
+            events,
+            vec![(
+                Topics(vec![H256::repeat_byte(1)]),
+                LogMeta {
+                    block_number: 3.into(),
+                    transaction_hash: hash,
+                }
+            )]
+        );
+    }
+}
@@ -2,7 +2,7 @@ use crate::{Contract, ContractError};
 
 use ethers_core::{
     abi::{Abi, Tokenize},
-    types::{BlockNumber, Bytes, TransactionRequest},
+    types::{BlockNumber, Bytes, TransactionReceipt, TransactionRequest},
 };
 use ethers_providers::Middleware;
 
@@ -33,25 +33,47 @@ impl<M: Middleware> Deployer<M> {
 
     /// Broadcasts the contract deployment transaction and after waiting for it to
     /// be sufficiently confirmed (default: 1), it returns a [`Contract`](crate::Contract)
-    /// struct at the deployed contract's address.
-    pub async fn send(self) -> Result<Contract<M>, ContractError<M>> {
-        let pending_tx = self
-            .client
-            .send_transaction(self.tx, Some(self.block.into()))
+    /// struct at the deployed contract's address along with the deployment's receipt.
+    ///
+    /// Returns [`ContractError::DeploymentReverted`] if the deployment transaction
+    /// reverted, and [`ContractError::ContractNotDeployed`] if there is no code at the
+    /// contract's address.
+    pub async fn send(self) -> Result<(Contract<M>, TransactionReceipt), ContractError<M>> {
+        let Deployer {
+            tx,
+            abi,
+            client,
+            confs,
+            block,
+        } = self;
+        let pending_tx = client
+            .send_transaction(tx, Some(block.into()))
             .await
-            .map_err(ContractError::MiddlewareError)?;
+            .map_err(|err| ContractError::from_middleware_error(&*client, err))?;
 
         // TODO: Should this be calculated "optimistically" by address/nonce?
         let receipt = pending_tx
-            .confirmations(self.confs)
+            .confirmations(confs)
             .await
-            .map_err(|_| ContractError::ContractNotDeployed)?;
+            .map_err(ContractError::ProviderError)?;
+        if receipt.status == Some(0u64.into()) {
+            return Err(ContractError::DeploymentReverted(receipt.transaction_hash));
+        }
         let address = receipt
             .contract_address
             .ok_or(ContractError::ContractNotDeployed)?;
 
-        let contract = Contract::new(address, self.abi.clone(), self.client);
-        Ok(contract)
+        // the constructor may also return empty code, which leaves no contract behind
+        let code = client
+            .get_code(address, None)
+            .await
+            .map_err(|err| ContractError::from_middleware_error(&*client, err))?;
+        if code.as_ref().is_empty() {
+            return Err(ContractError::ContractNotDeployed);
+        }
+
+        let contract = Contract::new(address, abi, client);
+        Ok((contract, receipt))
     }
 
     /// Returns a reference to the deployer's ABI
@@ -71,7 +93,7 @@ impl<M: Middleware> Deployer<M> {
 /// (ABI), usually generated from the Solidity compiler.
 ///
 /// Once the factory's deployment transaction is mined with sufficient confirmations,
-/// the [`Contract`](crate::Contract) object is returned.
+/// the [`Contract`](crate::Contract) object is returned along with the deployment receipt.
 ///
 /// # Example
 ///
@@ -101,12 +123,12 @@ impl<M: Middleware> Deployer<M> {
 ///
 /// // The deployer created by the `deploy` call exposes a builder which gets consumed
 /// // by the async `send` call
-/// let contract = factory
+/// let (contract, receipt) = factory
 ///     .deploy("initial value".to_string())?
 ///     .confirmations(0usize)
 ///     .send()
 ///     .await?;
-/// println!("{}", contract.address());
+/// println!("{} deployed at block {:?}", contract.address(), receipt.block_number);
 /// # Ok(())
 /// # }
 pub struct ContractFactory<M> {
@@ -0,0 +1,568 @@
+use crate::EthLogDecode;
+
+use async_trait::async_trait;
+use ethers_core::{
+    abi::RawLog,
+    types::{Filter, Log, H256, U256, U64},
+};
+use ethers_providers::{interval, Middleware, StreamExt};
+
+use std::{
+    cmp,
+    collections::VecDeque,
+    error::Error,
+    marker::PhantomData,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use thiserror::Error as ThisError;
+
+/// The last block processed by an [`EventIndexer`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cursor {
+    /// The number of the block
+    pub block_number: U64,
+    /// The hash of the block, used to detect when it is reorganized out of the chain
+    pub block_hash: Option<H256>,
+}
+
+/// A decoded event along with the position of its log in the chain
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexedEvent<D> {
+    /// The decoded event
+    pub event: D,
+    /// The number of the block including the log
+    pub block_number: U64,
+    /// The hash of the block including the log
+    pub block_hash: H256,
+    /// The hash of the transaction which emitted the log
+    pub transaction_hash: H256,
+    /// The index of the log in the block
+    pub log_index: U256,
+}
+
+/// The destination of the events indexed by an [`EventIndexer`], which also persists the
+/// indexer's progress
+#[async_trait]
+pub trait EventSink<D: Send>: Send + Sync {
+    /// The error of the sink
+    type Error: Error + Send + Sync;
+
+    /// Returns the cursor last passed to [`handle`](EventSink::handle), so that indexing
+    /// resumes after the last processed block
+    async fn load_cursor(&self) -> Result<Option<Cursor>, Self::Error>;
+
+    /// Handles the events of a range of blocks, in the order they were emitted. The events
+    /// and the new cursor should be persisted atomically, so that no event is lost or
+    /// processed twice if the indexer is interrupted.
+    async fn handle(&self, events: Vec<IndexedEvent<D>>, cursor: Cursor)
+        -> Result<(), Self::Error>;
+
+    /// Discards the events of the blocks starting at `from_block`, which were
+    /// reorganized out of the chain and will be indexed again
+    async fn revert(&self, from_block: U64) -> Result<(), Self::Error>;
+}
+
+/// An error thrown by an [`EventIndexer`]
+#[derive(ThisError, Debug)]
+pub enum IndexerError<M: Middleware, E: Error> {
+    /// Thrown when a middleware call fails
+    #[error("{0}")]
+    MiddlewareError(M::Error),
+
+    /// Thrown when a log cannot be decoded
+    #[error(transparent)]
+    DecodingError(#[from] ethers_core::abi::Error),
+
+    /// Thrown when the sink fails
+    #[error("{0}")]
+    SinkError(E),
+
+    /// Thrown when all the tracked blocks, spanning the whole reorg depth, were replaced,
+    /// so that the blocks before them may have been replaced as well
+    #[error("reorg deeper than the reorg depth, at block {0} or before")]
+    ReorgTooDeep(U64),
+}
+
+/// Indexes the events matching a filter: past events are backfilled in pages, after which
+/// new blocks are polled. The decoded events are pushed to an [`EventSink`] along with a
+/// cursor, so that indexing resumes where it stopped. Only blocks with enough confirmations
+/// are indexed. The hashes of the latest indexed blocks are checked against the chain, and
+/// the blocks from the first one reorganized out of the chain are reverted and indexed
+/// again.
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers::{
+///     contract::{Contract, EthEvent, EventIndexer, EventSink},
+///     providers::{Http, Provider},
+/// };
+/// use std::sync::Arc;
+///
+/// # async fn foo<D: EthEvent + Send + Sync, S: EventSink<D>>(
+/// #     contract: Contract<Provider<Http>>,
+/// #     client: Arc<Provider<Http>>,
+/// #     sink: S,
+/// # ) -> Result<(), Box<dyn std::error::Error>>
+/// # where S::Error: 'static {
+/// let filter = contract.event::<D>().filter;
+/// EventIndexer::new(client, filter, sink)
+///     .start_block(12_000_000u64)
+///     .confirmations(6)
+///     .run()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct EventIndexer<M, D, S> {
+    client: Arc<M>,
+    filter: Filter,
+    sink: S,
+    start_block: U64,
+    confirmations: u64,
+    page_size: u64,
+    reorg_depth: u64,
+    interval: Duration,
+    window: Mutex<VecDeque<(U64, H256)>>,
+    datatype: PhantomData<D>,
+}
+
+impl<M, D, S> EventIndexer<M, D, S>
+where
+    M: Middleware,
+    D: EthLogDecode + Send,
+    S: EventSink<D>,
+{
+    /// Creates an indexer of the events matching `filter`, starting at the genesis block
+    pub fn new(client: Arc<M>, filter: Filter, sink: S) -> Self {
+        Self {
+            client,
+            filter,
+            sink,
+            start_block: U64::zero(),
+            confirmations: 0,
+            page_size: 10_000,
+            reorg_depth: 12,
+            interval: Duration::from_secs(7),
+            window: Mutex::new(VecDeque::new()),
+            datatype: PhantomData,
+        }
+    }
+
+    /// Sets the block to start indexing at, when the sink has no cursor yet
+    pub fn start_block<T: Into<U64>>(mut self, block: T) -> Self {
+        self.start_block = block.into();
+        self
+    }
+
+    /// Sets the number of confirmations a block needs before it is indexed (default: 0)
+    pub fn confirmations(mut self, confirmations: u64) -> Self {
+        self.confirmations = confirmations;
+        self
+    }
+
+    /// Sets the maximum number of blocks queried at once (default: 10000)
+    pub fn page_size(mut self, page_size: u64) -> Self {
+        self.page_size = cmp::max(page_size, 1);
+        self
+    }
+
+    /// Sets the number of latest blocks checked for reorgs, i.e. the deepest reorg which
+    /// is reverted (default: 12)
+    pub fn reorg_depth(mut self, reorg_depth: u64) -> Self {
+        self.reorg_depth = cmp::max(reorg_depth, 1);
+        self
+    }
+
+    /// Sets the interval at which new blocks are polled (default: 7 seconds)
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Returns a reference to the indexer's sink
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    /// Indexes the events up to the latest confirmed block, and returns the cursor
+    pub async fn sync(&self) -> Result<Option<Cursor>, IndexerError<M, S::Error>> {
+        let mut cursor = self
+            .sink
+            .load_cursor()
+            .await
+            .map_err(IndexerError::SinkError)?;
+
+        loop {
+            if let Some(current) = cursor {
+                if let Some(from_block) = self.find_reorg(current).await? {
+                    cursor = self.revert(from_block).await?;
+                }
+            }
+
+            let head = self
+                .client
+                .get_block_number()
+                .await
+                .map_err(IndexerError::MiddlewareError)?;
+            let safe_head = head.saturating_sub(self.confirmations.into());
+            let from_block = cursor.map_or(self.start_block, |cursor| cursor.block_number + 1);
+            if from_block > safe_head {
+                return Ok(cursor);
+            }
+            let to_block = cmp::min(from_block + self.page_size - 1, safe_head);
+
+            // the hashes of the blocks which may still be reorged are fetched before the
+            // logs, pinning the range to the chain they belong to
+            let window_start = cmp::min(
+                cmp::max(
+                    from_block,
+                    (safe_head + 1).saturating_sub(self.reorg_depth.into()),
+                ),
+                to_block,
+            );
+            let mut hashes = Vec::new();
+            for number in window_start.as_u64()..=to_block.as_u64() {
+                if let Some(hash) = self.block_hash(number.into()).await? {
+                    hashes.push((U64::from(number), hash));
+                }
+            }
+            let to_block_hash = hashes
+                .last()
+                .filter(|(number, _)| *number == to_block)
+                .map(|(_, hash)| *hash);
+
+            let filter = self
+                .filter
+                .clone()
+                .from_block(from_block)
+                .to_block(to_block);
+            let logs = self
+                .client
+                .get_logs(&filter)
+                .await
+                .map_err(IndexerError::MiddlewareError)?;
+
+            // a reorg while the logs were queried shows in their block hashes or in the
+            // hash of the last block, in which case the page is queried again
+            let pinned = logs
+                .iter()
+                .all(|log| match (log.block_number, log.block_hash) {
+                    (Some(block_number), Some(block_hash)) => hashes
+                        .iter()
+                        .all(|(number, hash)| *number != block_number || *hash == block_hash),
+                    _ => true,
+                });
+            if !pinned || self.block_hash(to_block).await? != to_block_hash {
+                continue;
+            }
+            let events = logs
+                .into_iter()
+                .map(decode_log)
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let next = Cursor {
+                block_number: to_block,
+                block_hash: to_block_hash,
+            };
+            self.sink
+                .handle(events, next)
+                .await
+                .map_err(IndexerError::SinkError)?;
+            cursor = Some(next);
+
+            let mut window = self.window.lock().unwrap();
+            window.extend(hashes);
+            while window.len() as u64 > self.reorg_depth {
+                window.pop_front();
+            }
+        }
+    }
+
+    /// Backfills the past events and then keeps indexing the new blocks, until an
+    /// error occurs
+    pub async fn run(&self) -> Result<(), IndexerError<M, S::Error>> {
+        let mut interval = interval(self.interval);
+        loop {
+            self.sync().await?;
+            interval.next().await;
+        }
+    }
+
+    /// Checks the hashes of the latest indexed blocks against the chain, returning the
+    /// first block which was reorganized out of it, if any
+    async fn find_reorg(&self, cursor: Cursor) -> Result<Option<U64>, IndexerError<M, S::Error>> {
+        let window: Vec<_> = self.window.lock().unwrap().iter().copied().collect();
+
+        // without the hashes of the latest blocks, e.g. after a restart, only the block of
+        // the cursor can be checked and the whole reorg depth is reverted
+        if window.is_empty() {
+            let canonical = match cursor.block_hash {
+                Some(hash) => self.block_hash(cursor.block_number).await? == Some(hash),
+                None => true,
+            };
+            return Ok(if canonical {
+                None
+            } else {
+                Some(cursor.block_number.saturating_sub(self.reorg_depth.into()))
+            });
+        }
+
+        for (i, (number, hash)) in window.iter().enumerate() {
+            if self.block_hash(*number).await? != Some(*hash) {
+                if i > 0 {
+                    return Ok(Some(*number));
+                }
+                // until the window spans the whole reorg depth, e.g. shortly after a
+                // (re)start, the blocks before it may have been reorged as well
+                if (window.len() as u64) < self.reorg_depth {
+                    return Ok(Some(number.saturating_sub(self.reorg_depth.into())));
+                }
+                return Err(IndexerError::ReorgTooDeep(*number));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Reverts the blocks starting at `from_block` after a reorg, returning the cursor to
+    /// resume from
+    async fn revert(&self, from_block: U64) -> Result<Option<Cursor>, IndexerError<M, S::Error>> {
+        let from_block = cmp::max(from_block, self.start_block);
+        self.sink
+            .revert(from_block)
+            .await
+            .map_err(IndexerError::SinkError)?;
+
+        let block_number = from_block.saturating_sub(1.into());
+        let tracked = {
+            let mut window = self.window.lock().unwrap();
+            window.retain(|(number, _)| *number < from_block);
+            window.back().copied()
+        };
+
+        if from_block <= self.start_block {
+            return Ok(None);
+        }
+        let block_hash = match tracked {
+            Some((number, hash)) if number == block_number => Some(hash),
+            _ => self.block_hash(block_number).await?,
+        };
+        Ok(Some(Cursor {
+            block_number,
+            block_hash,
+        }))
+    }
+
+    async fn block_hash(
+        &self,
+        block_number: U64,
+    ) -> Result<Option<H256>, IndexerError<M, S::Error>> {
+        let block = self
+            .client
+            .get_block(block_number)
+            .await
+            .map_err(IndexerError::MiddlewareError)?;
+        Ok(block.and_then(|block| block.hash))
+    }
+}
+
+fn decode_log<D: EthLogDecode>(log: Log) -> Result<IndexedEvent<D>, ethers_core::abi::Error> {
+    let event = D::decode_log(&RawLog {
+        topics: log.topics,
+        data: log.data.to_vec(),
+    })?;
+    Ok(IndexedEvent {
+        event,
+        block_number: log.block_number.unwrap_or_default(),
+        block_hash: log.block_hash.unwrap_or_default(),
+        transaction_hash: log.transaction_hash.unwrap_or_default(),
+        log_index: log.log_index.unwrap_or_default(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers_core::types::{Address, Block, TxHash};
+    use ethers_providers::{MockError, MockProvider, Provider};
+
+    #[derive(Debug, PartialEq)]
+    struct Topics(Vec<H256>);
+
+    impl EthLogDecode for Topics {
+        fn decode_log(log: &RawLog) -> Result<Self, ethers_core::abi::Error> {
+            Ok(Topics(log.topics.clone()))
+        }
+    }
+
+    #[derive(Default)]
+    struct MemorySink {
+        events: Mutex<Vec<IndexedEvent<Topics>>>,
+        cursor: Mutex<Option<Cursor>>,
+    }
+
+    #[async_trait]
+    impl EventSink<Topics> for MemorySink {
+        type Error = MockError;
+
+        async fn load_cursor(&self) -> Result<Option<Cursor>, MockError> {
+            Ok(*self.cursor.lock().unwrap())
+        }
+
+        async fn handle(
+            &self,
+            events: Vec<IndexedEvent<Topics>>,
+            cursor: Cursor,
+        ) -> Result<(), MockError> {
+            self.events.lock().unwrap().extend(events);
+            *self.cursor.lock().unwrap() = Some(cursor);
+            Ok(())
+        }
+
+        async fn revert(&self, from_block: U64) -> Result<(), MockError> {
+            self.events
+                .lock()
+                .unwrap()
+                .retain(|event| event.block_number < from_block);
+            Ok(())
+        }
+    }
+
+    fn push_block_numbers(mock: &MockProvider, numbers: &[u64]) {
+        for number in numbers {
+            mock.push_response("eth_blockNumber", U64::from(*number))
+                .unwrap();
+        }
+    }
+
+    /// Pushes the blocks returned for the requested numbers, by the byte of their hash
+    fn push_blocks(mock: &MockProvider, hashes: &[u8]) {
+        for hash in hashes {
+            let block = Block::<TxHash> {
+                hash: Some(H256::repeat_byte(*hash)),
+                ..Default::default()
+            };
+            mock.push_response("eth_getBlockByNumber", block).unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn indexes_confirmed_blocks() {
+        let (provider, mock) = Provider::mocked();
+        let topic = H256::repeat_byte(1);
+        let log = Log {
+            address: Address::zero(),
+            topics: vec![topic],
+            data: Default::default(),
+            block_hash: Some(H256::repeat_byte(2)),
+            block_number: Some(8.into()),
+            transaction_hash: Some(H256::repeat_byte(3)),
+            transaction_index: Some(0.into()),
+            log_index: Some(0.into()),
+            transaction_log_index: None,
+            log_type: None,
+            removed: None,
+        };
+
+        // the hashes of the blocks which may still be reorged are fetched before the
+        // logs and checked on the next iteration
+        push_block_numbers(&mock, &[12, 12]);
+        push_blocks(&mock, &[9, 4, 4, 9, 4]);
+        mock.push_response::<Vec<Log>, _>("eth_getLogs", vec![log])
+            .unwrap();
+
+        let indexer = EventIndexer::new(Arc::new(provider), Filter::new(), MemorySink::default())
+            .start_block(5u64)
+            .confirmations(2)
+            .reorg_depth(2);
+        let cursor = indexer.sync().await.unwrap().unwrap();
+
+        assert_eq!(cursor.block_number, 10.into());
+        assert_eq!(cursor.block_hash, Some(H256::repeat_byte(4)));
+        let events = indexer.sink().events.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event, Topics(vec![topic]));
+        assert_eq!(events[0].block_number, 8.into());
+    }
+
+    #[tokio::test]
+    async fn reverts_reorged_blocks() {
+        let (provider, mock) = Provider::mocked();
+        let sink = MemorySink::default();
+        *sink.cursor.lock().unwrap() = Some(Cursor {
+            block_number: 10.into(),
+            block_hash: Some(H256::repeat_byte(4)),
+        });
+
+        // the block of the cursor has a different hash, so the indexer reverts the
+        // blocks from 7 and resumes after block 6, which is the head of the new chain
+        push_blocks(&mock, &[5, 6]);
+        push_block_numbers(&mock, &[6]);
+
+        let indexer = EventIndexer::new(Arc::new(provider), Filter::new(), sink)
+            .start_block(5u64)
+            .reorg_depth(3);
+        let cursor = indexer.sync().await.unwrap().unwrap();
+
+        assert_eq!(cursor.block_number, 6.into());
+        assert_eq!(cursor.block_hash, Some(H256::repeat_byte(6)));
+    }
+
+    #[tokio::test]
+    async fn reverts_from_the_first_reorged_block() {
+        let (provider, mock) = Provider::mocked();
+        let indexer = EventIndexer::new(Arc::new(provider), Filter::new(), MemorySink::default())
+            .start_block(5u64)
+            .reorg_depth(2);
+
+        // blocks 5 to 7 are indexed, and the hashes of blocks 6 and 7 are tracked
+        push_block_numbers(&mock, &[7, 7]);
+        push_blocks(&mock, &[6, 7, 7, 6, 7]);
+        mock.push_response::<Vec<Log>, _>("eth_getLogs", vec![])
+            .unwrap();
+        indexer.sync().await.unwrap();
+
+        // block 7 was replaced while block 6 was not, so only block 7 is indexed again
+        push_block_numbers(&mock, &[7, 7]);
+        push_blocks(&mock, &[6, 0x77, 0x77, 0x77, 6, 0x77]);
+        mock.push_response::<Vec<Log>, _>("eth_getLogs", vec![])
+            .unwrap();
+        let cursor = indexer.sync().await.unwrap().unwrap();
+        assert_eq!(cursor.block_number, 7.into());
+        assert_eq!(cursor.block_hash, Some(H256::repeat_byte(0x77)));
+
+        // when all the tracked blocks were replaced, the reorg is too deep to be reverted
+        push_blocks(&mock, &[0x66]);
+        match indexer.sync().await.unwrap_err() {
+            IndexerError::ReorgTooDeep(block_number) => assert_eq!(block_number, 6.into()),
+            err => panic!("unexpected error {:?}", err),
+        }
+    }
+    #[tokio::test]
+    async fn reverts_the_reorg_depth_before_a_partial_window() {
+        let (provider, mock) = Provider::mocked();
+        let indexer = EventIndexer::new(Arc::new(provider), Filter::new(), MemorySink::default())
+            .start_block(5u64)
+            .confirmations(0)
+            .reorg_depth(3);
+
+        // only block 5 is indexed, so only its hash is tracked
+        push_block_numbers(&mock, &[5, 5]);
+        push_blocks(&mock, &[5, 5, 5]);
+        mock.push_response::<Vec<Log>, _>("eth_getLogs", vec![])
+            .unwrap();
+        indexer.sync().await.unwrap();
+
+        // block 5 was replaced, which does not rule out a reorg of the blocks before it,
+        // so the whole reorg depth is reverted and block 5 is indexed again
+        push_block_numbers(&mock, &[5, 5]);
+        push_blocks(&mock, &[0x55, 0x55, 0x55, 0x55]);
+        mock.push_response::<Vec<Log>, _>("eth_getLogs", vec![])
+            .unwrap();
+        let cursor = indexer.sync().await.unwrap().unwrap();
+        assert_eq!(cursor.block_number, 5.into());
+        assert_eq!(cursor.block_hash, Some(H256::repeat_byte(0x55)));
+    }
+}
@@ -17,24 +17,37 @@ mod contract;
 pub use contract::Contract;
 
 mod base;
-pub use base::{decode_function_data, encode_function_data, AbiError, BaseContract};
+pub use base::{
+    decode_function_data, decode_receipt_logs, encode_function_data, token_to_json, AbiError,
+    BaseContract,
+};
 
 mod call;
-pub use call::ContractError;
+pub use call::{ContractError, ContractRevert, EthCall};
 
 mod factory;
 pub use factory::ContractFactory;
 
+mod ens;
+pub use ens::EnsContract;
+
+mod registrar;
+pub use registrar::{EthRegistrarController, ReverseRegistrar, ETH_REGISTRAR_CONTROLLER};
+
 mod event;
-pub use event::EthEvent;
+pub use event::{EthEvent, LogMeta};
+
+mod indexer;
+pub use indexer::{Cursor, EventIndexer, EventSink, IndexedEvent, IndexerError};
 
 mod log;
 pub use log::{decode_logs, EthLogDecode};
 
 mod stream;
+pub use stream::LogStreamExt;
 
 mod multicall;
-pub use multicall::Multicall;
+pub use multicall::{Multicall, MulticallVersion};
 
 /// This module exposes low lever builder structures which are only consumed by the
 /// type-safe ABI bindings generators.
@@ -46,11 +59,11 @@ pub mod builders {
 
 #[cfg(feature = "abigen")]
 #[cfg_attr(docsrs, doc(cfg(feature = "abigen")))]
-pub use ethers_contract_abigen::Abigen;
+pub use ethers_contract_abigen::{Abigen, MultiAbigen};
 
 #[cfg(feature = "abigen")]
 #[cfg_attr(docsrs, doc(cfg(feature = "abigen")))]
-pub use ethers_contract_derive::{abigen, EthAbiType, EthEvent};
+pub use ethers_contract_derive::{abigen, EthAbiType, EthCall, EthEvent};
 
 // Hide the Lazy re-export, it's just for convenience
 #[doc(hidden)]
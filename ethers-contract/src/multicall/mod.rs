@@ -1,6 +1,6 @@
 use ethers_core::{
-    abi::{Detokenize, Function, Token},
-    types::{Address, BlockNumber, Bytes, NameOrAddress, TxHash, U256},
+    abi::{Detokenize, Function, Token, Tokenizable},
+    types::{Address, BlockNumber, Bytes, NameOrAddress, TxHash, H160, U256},
 };
 use ethers_providers::Middleware;
 
@@ -42,6 +42,40 @@ pub static ADDRESS_BOOK: Lazy<HashMap<U256, Address>> = Lazy::new(|| {
     m
 });
 
+/// The address of the [`Multicall2` smart contract](https://etherscan.io/address/0x5ba1e12693dc8f9c48aad8770482f4739beed696#code),
+/// which is the same on the Ethereum mainnet and on the Ropsten, Rinkeby, Goerli and Kovan testnets
+pub const MULTICALL2_ADDRESS: Address = H160([
+    0x5b, 0xa1, 0xe1, 0x26, 0x93, 0xdc, 0x8f, 0x9c, 0x48, 0xaa, 0xd8, 0x77, 0x04, 0x82, 0xf4, 0x73,
+    0x9b, 0xee, 0xd6, 0x96,
+]);
+
+/// The address of the [`Multicall3` smart contract](https://etherscan.io/address/0xca11bde05977b3631167028862be2a173976ca11#code),
+/// which is the same on every chain it is deployed to
+pub const MULTICALL3_ADDRESS: Address = H160([
+    0xca, 0x11, 0xbd, 0xe0, 0x59, 0x77, 0xb3, 0x63, 0x11, 0x67, 0x02, 0x88, 0x62, 0xbe, 0x2a, 0x17,
+    0x39, 0x76, 0xca, 0x11,
+]);
+
+/// The version of the Multicall smart contract, which determines how the calls are aggregated
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MulticallVersion {
+    /// Uses `aggregate`, where the whole batch reverts if any call fails. The calls cannot
+    /// send ETH.
+    Multicall,
+    /// Uses `tryAggregate`, which returns whether each call succeeded. The calls cannot send
+    /// ETH.
+    Multicall2,
+    /// Uses `aggregate3` (or `aggregate3Value` if any call sends ETH), where failures are
+    /// allowed per call and each call can send ETH
+    Multicall3,
+}
+
+impl Default for MulticallVersion {
+    fn default() -> Self {
+        MulticallVersion::Multicall
+    }
+}
+
 /// A Multicall is an abstraction for sending batched calls/transactions to the Ethereum blockchain.
 /// It stores an instance of the [`Multicall` smart contract](https://etherscan.io/address/0xeefba1e63905ef1d7acba5a8513c70307c1ce441#code)
 /// and the user provided list of transactions to be made.
@@ -55,6 +89,11 @@ pub static ADDRESS_BOOK: Lazy<HashMap<U256, Address>> = Lazy::new(|| {
 /// Additionally, the `block` number can be provided for the call by using the [`block`] method.
 /// Build on the `Multicall` instance by adding calls using the [`add_call`] method.
 ///
+/// By default the batch is made with the original Multicall contract, which reverts if any
+/// of the calls fails. With [`new_with_version`], the `Multicall2` and `Multicall3`
+/// contracts can be used instead, so that the calls added with [`add_call_allow_failure`]
+/// may fail without reverting the batch. Their results are then inspected with [`call_raw`].
+///
 /// # Example
 ///
 /// ```no_run
@@ -127,11 +166,15 @@ pub static ADDRESS_BOOK: Lazy<HashMap<U256, Address>> = Lazy::new(|| {
 /// [`new`]: method@crate::Multicall::new
 /// [`block`]: method@crate::Multicall::block
 /// [`add_call`]: methond@crate::Multicall::add_call
+/// [`new_with_version`]: method@crate::Multicall::new_with_version
+/// [`add_call_allow_failure`]: method@crate::Multicall::add_call_allow_failure
+/// [`call_raw`]: method@crate::Multicall::call_raw
 #[derive(Clone)]
 pub struct Multicall<M> {
     calls: Vec<Call>,
     block: Option<BlockNumber>,
     contract: MulticallContract<M>,
+    version: MulticallVersion,
 }
 
 #[derive(Clone)]
@@ -140,6 +183,8 @@ pub struct Multicall<M> {
 pub struct Call {
     target: Address,
     data: Bytes,
+    value: U256,
+    allow_failure: bool,
     function: Function,
 }
 
@@ -154,15 +199,34 @@ impl<M: Middleware> Multicall<M> {
     pub async fn new<C: Into<Arc<M>>>(
         client: C,
         address: Option<Address>,
+    ) -> Result<Self, ContractError<M>> {
+        Self::new_with_version(client, address, MulticallVersion::Multicall).await
+    }
+
+    /// Creates a new Multicall instance which aggregates the calls with the provided
+    /// `version` of the Multicall contract. If no `address` is provided, `Multicall2` and
+    /// `Multicall3` use their canonical deployments at [`MULTICALL2_ADDRESS`] and
+    /// [`MULTICALL3_ADDRESS`].
+    ///
+    /// # Panics
+    /// If a `None` address is provided for the original Multicall contract, and the provided
+    /// client also does not belong to one of the supported network IDs (mainnet, kovan,
+    /// rinkeby and goerli)
+    pub async fn new_with_version<C: Into<Arc<M>>>(
+        client: C,
+        address: Option<Address>,
+        version: MulticallVersion,
     ) -> Result<Self, ContractError<M>> {
         let client = client.into();
 
         // Fetch chain id and the corresponding address of Multicall contract
         // preference is given to Multicall contract's address if provided
         // otherwise check the address book for the client's chain ID
-        let address: Address = match address {
-            Some(addr) => addr,
-            None => {
+        let address: Address = match (address, version) {
+            (Some(addr), _) => addr,
+            (None, MulticallVersion::Multicall2) => MULTICALL2_ADDRESS,
+            (None, MulticallVersion::Multicall3) => MULTICALL3_ADDRESS,
+            (None, MulticallVersion::Multicall) => {
                 let chain_id = client
                     .get_chainid()
                     .await
@@ -183,6 +247,7 @@ impl<M: Middleware> Multicall<M> {
             calls: vec![],
             block: None,
             contract,
+            version,
         })
     }
 
@@ -193,21 +258,37 @@ impl<M: Middleware> Multicall<M> {
     }
 
     /// Appends a `call` to the list of calls for the Multicall instance
+    pub fn add_call<D: Detokenize>(&mut self, call: ContractCall<M, D>) -> &mut Self {
+        self.push_call(call, false)
+    }
+
+    /// Appends a `call` which is allowed to fail without reverting the whole batch. Its
+    /// failure is returned by [`call_raw`], while [`call`] returns an error.
     ///
-    /// # Panics
+    /// Only the `Multicall2` and `Multicall3` contracts support failing calls, with the
+    /// original Multicall contract this behaves like [`add_call`].
     ///
-    /// If more than the maximum number of supported calls are added. The maximum
-    /// limits is constrained due to tokenization/detokenization support for tuples
-    pub fn add_call<D: Detokenize>(&mut self, call: ContractCall<M, D>) -> &mut Self {
-        if self.calls.len() >= 16 {
-            panic!("Cannot support more than {} calls", 16);
-        }
+    /// [`call_raw`]: method@crate::Multicall::call_raw
+    /// [`call`]: method@crate::Multicall::call
+    /// [`add_call`]: method@crate::Multicall::add_call
+    pub fn add_call_allow_failure<D: Detokenize>(&mut self, call: ContractCall<M, D>) -> &mut Self {
+        self.push_call(call, true)
+    }
 
-        match (call.tx.to, call.tx.data) {
+    fn push_call<D: Detokenize>(
+        &mut self,
+        call: ContractCall<M, D>,
+        allow_failure: bool,
+    ) -> &mut Self {
+        match (call.tx.to(), call.tx.data()) {
             (Some(NameOrAddress::Address(target)), Some(data)) => {
                 let call = Call {
-                    target,
-                    data,
+                    target: *target,
+                    data: data.clone(),
+                    // only the `Multicall3` contract forwards the value of the calls, the
+                    // other versions refuse to aggregate calls sending ETH
+                    value: call.tx.value().copied().unwrap_or_default(),
+                    allow_failure,
                     function: call.function,
                 };
                 self.calls.push(call);
@@ -219,11 +300,6 @@ impl<M: Middleware> Multicall<M> {
 
     /// Appends a `call` to the list of calls for the Multicall instance for querying
     /// the ETH balance of an address
-    ///
-    /// # Panics
-    ///
-    /// If more than the maximum number of supported calls are added. The maximum
-    /// limits is constrained due to tokenization/detokenization support for tuples
     pub fn eth_balance_of(&mut self, addr: Address) -> &mut Self {
         let call = self.contract.get_eth_balance(addr);
         self.add_call(call)
@@ -292,38 +368,122 @@ impl<M: Middleware> Multicall<M> {
     /// # }
     /// ```
     ///
-    /// Note: this method _does not_ send a transaction from your account
+    /// Note: this method _does not_ send a transaction from your account. Since the results
+    /// are detokenized into a tuple, at most 16 calls are supported, see [`call_array`] for
+    /// larger batches.
     ///
     /// [`ContractError<M>`]: crate::ContractError<M>
+    /// [`call_array`]: method@crate::Multicall::call_array
     pub async fn call<D: Detokenize>(&self) -> Result<D, ContractError<M>> {
-        let contract_call = self.as_contract_call();
+        let tokens = self
+            .call_raw()
+            .await?
+            .into_iter()
+            .map(|result| result.map_err(ContractError::Revert))
+            .collect::<Result<Vec<Token>, ContractError<M>>>()?;
+
+        // Form tokens that represent tuples
+        let tokens = vec![Token::Tuple(tokens)];
+
+        // Detokenize from the tokens into the provided tuple D
+        let data = D::from_tokens(tokens)?;
+
+        Ok(data)
+    }
+
+    /// Queries the Ethereum blockchain via an `eth_call` like [`call`], but detokenizes the
+    /// results of calls which all return the same type into a `Vec`. Unlike [`call`], this
+    /// supports any number of calls, e.g. to query the balances of many accounts at once.
+    ///
+    /// ```no_run
+    /// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// # use ethers::prelude::*;
+    /// # use std::convert::TryFrom;
+    /// #
+    /// # let client = Provider::<Http>::try_from("http://localhost:8545")?;
+    /// # let accounts: Vec<Address> = vec![];
+    /// #
+    /// let mut multicall = Multicall::new(client, None).await?;
+    /// for account in accounts {
+    ///     multicall.eth_balance_of(account);
+    /// }
+    /// let balances: Vec<U256> = multicall.call_array().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`call`]: method@crate::Multicall::call
+    pub async fn call_array<D: Tokenizable>(&self) -> Result<Vec<D>, ContractError<M>> {
+        self.call_raw()
+            .await?
+            .into_iter()
+            .map(|result| -> Result<D, ContractError<M>> {
+                let token = result.map_err(ContractError::Revert)?;
+                Ok(D::from_token(token)?)
+            })
+            .collect()
+    }
+
+    /// Queries the Ethereum blockchain via an `eth_call` like [`call`], but returns the
+    /// decoded tokens of each call separately, or the data it reverted with if it was
+    /// added with [`add_call_allow_failure`] and failed.
+    ///
+    /// ```no_run
+    /// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// # use ethers::{contract::MulticallVersion, prelude::*};
+    /// # use std::convert::TryFrom;
+    /// #
+    /// # let client = Provider::<Http>::try_from("http://localhost:8545")?;
+    /// #
+    /// let multicall = Multicall::new_with_version(client, None, MulticallVersion::Multicall3).await?;
+    /// for result in multicall.call_raw().await? {
+    ///     match result {
+    ///         Ok(token) => println!("returned {:?}", token),
+    ///         Err(revert_data) => println!("reverted with {:?}", revert_data),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`call`]: method@crate::Multicall::call
+    /// [`add_call_allow_failure`]: method@crate::Multicall::add_call_allow_failure
+    pub async fn call_raw(&self) -> Result<Vec<Result<Token, Bytes>>, ContractError<M>> {
+        self.check_values()?;
 
         // Fetch response from the Multicall contract
-        let (_block_number, return_data) = contract_call.call().await?;
+        let return_data = match self.version {
+            MulticallVersion::Multicall => {
+                let (_block_number, return_data) = self.as_aggregate().call().await?;
+                return_data.into_iter().map(|data| (true, data)).collect()
+            }
+            MulticallVersion::Multicall2 => self.as_try_aggregate(false).call().await?,
+            MulticallVersion::Multicall3 => self.as_aggregate_3().call().await?,
+        };
 
         // Decode return data into ABI tokens
-        let tokens = self
-            .calls
+        self.calls
             .iter()
-            .zip(&return_data)
-            .map(|(call, bytes)| {
+            .zip(return_data)
+            .map(|(call, (success, bytes))| -> Result<_, ContractError<M>> {
+                if !success {
+                    // `tryAggregate` is queried without requiring success, so failures
+                    // of calls which are not allowed to fail are caught here
+                    if !call.allow_failure {
+                        return Err(ContractError::Revert(bytes.into()));
+                    }
+                    return Ok(Err(bytes.into()));
+                }
+
                 let mut tokens: Vec<Token> = call.function.decode_output(&bytes)?;
 
-                Ok(match tokens.len() {
+                Ok(Ok(match tokens.len() {
                     0 => Token::Tuple(vec![]),
                     1 => tokens.remove(0),
                     _ => Token::Tuple(tokens),
-                })
+                }))
             })
-            .collect::<Result<Vec<Token>, ContractError<M>>>()?;
-
-        // Form tokens that represent tuples
-        let tokens = vec![Token::Tuple(tokens)];
-
-        // Detokenize from the tokens into the provided tuple D
-        let data = D::from_tokens(tokens)?;
-
-        Ok(data)
+            .collect()
     }
 
     /// Signs and broadcasts a batch of transactions by using the Multicall contract as proxy.
@@ -342,17 +502,35 @@ impl<M: Middleware> Multicall<M> {
     /// Note: this method sends a transaction from your account, and will return an error
     /// if you do not have sufficient funds to pay for gas
     pub async fn send(&self) -> Result<TxHash, ContractError<M>> {
-        let contract_call = self.as_contract_call();
+        self.check_values()?;
 
         // Broadcast transaction and return the transaction hash
         // TODO: Can we make this return a PendingTransaction directly instead?
         // Seems hard due to `returns a value referencing data owned by the current function`
-        let tx_hash = *contract_call.send().await?;
+        let tx_hash = match self.version {
+            MulticallVersion::Multicall => *self.as_aggregate().send().await?,
+            MulticallVersion::Multicall2 => {
+                // `tryAggregate` can only allow failures for the whole batch
+                let require_success = self.calls.iter().all(|call| !call.allow_failure);
+                *self.as_try_aggregate(require_success).send().await?
+            }
+            MulticallVersion::Multicall3 => *self.as_aggregate_3().send().await?,
+        };
 
         Ok(tx_hash)
     }
 
-    fn as_contract_call(&self) -> ContractCall<M, (U256, Vec<Vec<u8>>)> {
+    /// Returns an error if a call sends ETH but the Multicall contract cannot forward it
+    fn check_values(&self) -> Result<(), ContractError<M>> {
+        if self.version != MulticallVersion::Multicall3
+            && self.calls.iter().any(|call| !call.value.is_zero())
+        {
+            return Err(ContractError::MulticallValueNotSupported);
+        }
+        Ok(())
+    }
+
+    fn as_aggregate(&self) -> ContractCall<M, (U256, Vec<Vec<u8>>)> {
         // Map the Multicall struct into appropriate types for `aggregate` function
         let calls: Vec<(Address, Vec<u8>)> = self
             .calls
@@ -362,6 +540,53 @@ impl<M: Middleware> Multicall<M> {
 
         // Construct the ContractCall for `aggregate` function to broadcast the transaction
         let contract_call = self.contract.aggregate(calls);
+        self.at_block(contract_call)
+    }
+
+    fn as_try_aggregate(&self, require_success: bool) -> ContractCall<M, Vec<(bool, Vec<u8>)>> {
+        let calls: Vec<(Address, Vec<u8>)> = self
+            .calls
+            .iter()
+            .map(|call| (call.target, call.data.to_vec()))
+            .collect();
+
+        let contract_call = self.contract.try_aggregate(require_success, calls);
+        self.at_block(contract_call)
+    }
+
+    fn as_aggregate_3(&self) -> ContractCall<M, Vec<(bool, Vec<u8>)>> {
+        let total_value = self
+            .calls
+            .iter()
+            .fold(U256::zero(), |total, call| total + call.value);
+
+        // `aggregate3Value` is only needed if any of the calls sends ETH
+        let contract_call = if total_value.is_zero() {
+            let calls: Vec<(Address, bool, Vec<u8>)> = self
+                .calls
+                .iter()
+                .map(|call| (call.target, call.allow_failure, call.data.to_vec()))
+                .collect();
+            self.contract.aggregate_3(calls)
+        } else {
+            let calls: Vec<(Address, bool, U256, Vec<u8>)> = self
+                .calls
+                .iter()
+                .map(|call| {
+                    (
+                        call.target,
+                        call.allow_failure,
+                        call.value,
+                        call.data.to_vec(),
+                    )
+                })
+                .collect();
+            self.contract.aggregate_3_value(calls).value(total_value)
+        };
+        self.at_block(contract_call)
+    }
+
+    fn at_block<D: Detokenize>(&self, contract_call: ContractCall<M, D>) -> ContractCall<M, D> {
         if let Some(block) = self.block {
             contract_call.block(block)
         } else {
@@ -369,3 +594,61 @@ impl<M: Middleware> Multicall<M> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers_core::abi;
+    use ethers_providers::Provider;
+
+    #[tokio::test]
+    async fn calls_array_of_any_length() {
+        let (provider, mock) = Provider::mocked();
+        let mut multicall = Multicall::new_with_version(
+            provider,
+            Some(MULTICALL3_ADDRESS),
+            MulticallVersion::Multicall3,
+        )
+        .await
+        .unwrap();
+
+        // more calls than can be detokenized into a tuple
+        let results = (0..20u64)
+            .map(|i| {
+                multicall.eth_balance_of(Address::from_low_u64_be(i));
+                Token::Tuple(vec![
+                    Token::Bool(true),
+                    Token::Bytes(abi::encode(&[Token::Uint(i.into())])),
+                ])
+            })
+            .collect();
+        mock.push(Bytes::from(abi::encode(&[Token::Array(results)])))
+            .unwrap();
+
+        let balances: Vec<U256> = multicall.call_array().await.unwrap();
+        assert_eq!(balances, (0..20u64).map(U256::from).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn rejects_values_before_multicall3() {
+        let (provider, _) = Provider::mocked();
+        let mut multicall = Multicall::new_with_version(
+            provider,
+            Some(MULTICALL2_ADDRESS),
+            MulticallVersion::Multicall2,
+        )
+        .await
+        .unwrap();
+
+        let call = multicall.contract.get_basefee().value(1);
+        multicall.add_call(call);
+        assert!(matches!(
+            multicall.call_raw().await.unwrap_err(),
+            ContractError::MulticallValueNotSupported
+        ));
+        assert!(matches!(
+            multicall.send().await.unwrap_err(),
+            ContractError::MulticallValueNotSupported
+        ));
+    }
+}
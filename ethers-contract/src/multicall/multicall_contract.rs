@@ -1,8 +1,10 @@
 pub use multicallcontract_mod::*;
+#[allow(clippy::too_many_arguments)]
 mod multicallcontract_mod {
     #![allow(dead_code)]
     #![allow(unused_imports)]
     use crate::{
+        self as ethers_contract,
         builders::{ContractCall, Event},
         Contract, Lazy,
     };
@@ -10,86 +12,195 @@ mod multicallcontract_mod {
         abi::{Abi, Detokenize, InvalidOutputType, Token, Tokenizable},
         types::*,
     };
-    use ethers_providers::{JsonRpcClient, Middleware};
+    use ethers_providers::Middleware;
     #[doc = "MulticallContract was auto-generated with ethers-rs Abigen. More information at: https://github.com/gakonst/ethers-rs"]
     use std::sync::Arc;
-    pub static MULTICALLCONTRACT_ABI: Lazy<Abi> = Lazy::new(|| {
-        serde_json :: from_str ( "[{\"inputs\":[{\"components\":[{\"internalType\":\"address\",\"name\":\"target\",\"type\":\"address\"},{\"internalType\":\"bytes\",\"name\":\"callData\",\"type\":\"bytes\"}],\"internalType\":\"struct MulticallContract.Call[]\",\"name\":\"calls\",\"type\":\"tuple[]\"}],\"name\":\"aggregate\",\"outputs\":[{\"internalType\":\"uint256\",\"name\":\"blockNumber\",\"type\":\"uint256\"},{\"internalType\":\"bytes[]\",\"name\":\"returnData\",\"type\":\"bytes[]\"}],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"uint256\",\"name\":\"blockNumber\",\"type\":\"uint256\"}],\"name\":\"getBlockHash\",\"outputs\":[{\"internalType\":\"bytes32\",\"name\":\"blockHash\",\"type\":\"bytes32\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"getCurrentBlockCoinbase\",\"outputs\":[{\"internalType\":\"address\",\"name\":\"coinbase\",\"type\":\"address\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"getCurrentBlockDifficulty\",\"outputs\":[{\"internalType\":\"uint256\",\"name\":\"difficulty\",\"type\":\"uint256\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"getCurrentBlockGasLimit\",\"outputs\":[{\"internalType\":\"uint256\",\"name\":\"gaslimit\",\"type\":\"uint256\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"getCurrentBlockTimestamp\",\"outputs\":[{\"internalType\":\"uint256\",\"name\":\"timestamp\",\"type\":\"uint256\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"address\",\"name\":\"addr\",\"type\":\"address\"}],\"name\":\"getEthBalance\",\"outputs\":[{\"internalType\":\"uint256\",\"name\":\"balance\",\"type\":\"uint256\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"getLastBlockHash\",\"outputs\":[{\"internalType\":\"bytes32\",\"name\":\"blockHash\",\"type\":\"bytes32\"}],\"stateMutability\":\"view\",\"type\":\"function\"}]" ) . expect ( "invalid abi" )
-    });
+    pub static MULTICALLCONTRACT_ABI: ethers_contract::Lazy<ethers_core::abi::Abi> =
+        ethers_contract::Lazy::new(|| {
+            serde_json :: from_str ("[{\"inputs\":[{\"components\":[{\"internalType\":\"address\",\"name\":\"target\",\"type\":\"address\"},{\"internalType\":\"bytes\",\"name\":\"callData\",\"type\":\"bytes\"}],\"internalType\":\"struct Multicall3.Call[]\",\"name\":\"calls\",\"type\":\"tuple[]\"}],\"name\":\"aggregate\",\"outputs\":[{\"internalType\":\"uint256\",\"name\":\"blockNumber\",\"type\":\"uint256\"},{\"internalType\":\"bytes[]\",\"name\":\"returnData\",\"type\":\"bytes[]\"}],\"stateMutability\":\"payable\",\"type\":\"function\"},{\"inputs\":[{\"components\":[{\"internalType\":\"address\",\"name\":\"target\",\"type\":\"address\"},{\"internalType\":\"bool\",\"name\":\"allowFailure\",\"type\":\"bool\"},{\"internalType\":\"bytes\",\"name\":\"callData\",\"type\":\"bytes\"}],\"internalType\":\"struct Multicall3.Call3[]\",\"name\":\"calls\",\"type\":\"tuple[]\"}],\"name\":\"aggregate3\",\"outputs\":[{\"components\":[{\"internalType\":\"bool\",\"name\":\"success\",\"type\":\"bool\"},{\"internalType\":\"bytes\",\"name\":\"returnData\",\"type\":\"bytes\"}],\"internalType\":\"struct Multicall3.Result[]\",\"name\":\"returnData\",\"type\":\"tuple[]\"}],\"stateMutability\":\"payable\",\"type\":\"function\"},{\"inputs\":[{\"components\":[{\"internalType\":\"address\",\"name\":\"target\",\"type\":\"address\"},{\"internalType\":\"bool\",\"name\":\"allowFailure\",\"type\":\"bool\"},{\"internalType\":\"uint256\",\"name\":\"value\",\"type\":\"uint256\"},{\"internalType\":\"bytes\",\"name\":\"callData\",\"type\":\"bytes\"}],\"internalType\":\"struct Multicall3.Call3Value[]\",\"name\":\"calls\",\"type\":\"tuple[]\"}],\"name\":\"aggregate3Value\",\"outputs\":[{\"components\":[{\"internalType\":\"bool\",\"name\":\"success\",\"type\":\"bool\"},{\"internalType\":\"bytes\",\"name\":\"returnData\",\"type\":\"bytes\"}],\"internalType\":\"struct Multicall3.Result[]\",\"name\":\"returnData\",\"type\":\"tuple[]\"}],\"stateMutability\":\"payable\",\"type\":\"function\"},{\"inputs\":[{\"components\":[{\"internalType\":\"address\",\"name\":\"target\",\"type\":\"address\"},{\"internalType\":\"bytes\",\"name\":\"callData\",\"type\":\"bytes\"}],\"internalType\":\"struct Multicall3.Call[]\",\"name\":\"calls\",\"type\":\"tuple[]\"}],\"name\":\"blockAndAggregate\",\"outputs\":[{\"internalType\":\"uint256\",\"name\":\"blockNumber\",\"type\":\"uint256\"},{\"internalType\":\"bytes32\",\"name\":\"blockHash\",\"type\":\"bytes32\"},{\"components\":[{\"internalType\":\"bool\",\"name\":\"success\",\"type\":\"bool\"},{\"internalType\":\"bytes\",\"name\":\"returnData\",\"type\":\"bytes\"}],\"internalType\":\"struct Multicall3.Result[]\",\"name\":\"returnData\",\"type\":\"tuple[]\"}],\"stateMutability\":\"payable\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"getBasefee\",\"outputs\":[{\"internalType\":\"uint256\",\"name\":\"basefee\",\"type\":\"uint256\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"uint256\",\"name\":\"blockNumber\",\"type\":\"uint256\"}],\"name\":\"getBlockHash\",\"outputs\":[{\"internalType\":\"bytes32\",\"name\":\"blockHash\",\"type\":\"bytes32\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"getBlockNumber\",\"outputs\":[{\"internalType\":\"uint256\",\"name\":\"blockNumber\",\"type\":\"uint256\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"getChainId\",\"outputs\":[{\"internalType\":\"uint256\",\"name\":\"chainid\",\"type\":\"uint256\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"getCurrentBlockCoinbase\",\"outputs\":[{\"internalType\":\"address\",\"name\":\"coinbase\",\"type\":\"address\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"getCurrentBlockDifficulty\",\"outputs\":[{\"internalType\":\"uint256\",\"name\":\"difficulty\",\"type\":\"uint256\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"getCurrentBlockGasLimit\",\"outputs\":[{\"internalType\":\"uint256\",\"name\":\"gaslimit\",\"type\":\"uint256\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"getCurrentBlockTimestamp\",\"outputs\":[{\"internalType\":\"uint256\",\"name\":\"timestamp\",\"type\":\"uint256\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"address\",\"name\":\"addr\",\"type\":\"address\"}],\"name\":\"getEthBalance\",\"outputs\":[{\"internalType\":\"uint256\",\"name\":\"balance\",\"type\":\"uint256\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"getLastBlockHash\",\"outputs\":[{\"internalType\":\"bytes32\",\"name\":\"blockHash\",\"type\":\"bytes32\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"bool\",\"name\":\"requireSuccess\",\"type\":\"bool\"},{\"components\":[{\"internalType\":\"address\",\"name\":\"target\",\"type\":\"address\"},{\"internalType\":\"bytes\",\"name\":\"callData\",\"type\":\"bytes\"}],\"internalType\":\"struct Multicall3.Call[]\",\"name\":\"calls\",\"type\":\"tuple[]\"}],\"name\":\"tryAggregate\",\"outputs\":[{\"components\":[{\"internalType\":\"bool\",\"name\":\"success\",\"type\":\"bool\"},{\"internalType\":\"bytes\",\"name\":\"returnData\",\"type\":\"bytes\"}],\"internalType\":\"struct Multicall3.Result[]\",\"name\":\"returnData\",\"type\":\"tuple[]\"}],\"stateMutability\":\"payable\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"bool\",\"name\":\"requireSuccess\",\"type\":\"bool\"},{\"components\":[{\"internalType\":\"address\",\"name\":\"target\",\"type\":\"address\"},{\"internalType\":\"bytes\",\"name\":\"callData\",\"type\":\"bytes\"}],\"internalType\":\"struct Multicall3.Call[]\",\"name\":\"calls\",\"type\":\"tuple[]\"}],\"name\":\"tryBlockAndAggregate\",\"outputs\":[{\"internalType\":\"uint256\",\"name\":\"blockNumber\",\"type\":\"uint256\"},{\"internalType\":\"bytes32\",\"name\":\"blockHash\",\"type\":\"bytes32\"},{\"components\":[{\"internalType\":\"bool\",\"name\":\"success\",\"type\":\"bool\"},{\"internalType\":\"bytes\",\"name\":\"returnData\",\"type\":\"bytes\"}],\"internalType\":\"struct Multicall3.Result[]\",\"name\":\"returnData\",\"type\":\"tuple[]\"}],\"stateMutability\":\"payable\",\"type\":\"function\"}]\n") . expect ("invalid abi")
+        });
     #[derive(Clone)]
-    pub struct MulticallContract<M>(Contract<M>);
+    pub struct MulticallContract<M>(ethers_contract::Contract<M>);
     impl<M> std::ops::Deref for MulticallContract<M> {
-        type Target = Contract<M>;
+        type Target = ethers_contract::Contract<M>;
         fn deref(&self) -> &Self::Target {
             &self.0
         }
     }
-    impl<M: Middleware> std::fmt::Debug for MulticallContract<M> {
+    impl<M: ethers_providers::Middleware> From<ethers_contract::Contract<M>> for MulticallContract<M> {
+        fn from(contract: ethers_contract::Contract<M>) -> Self {
+            Self(contract)
+        }
+    }
+    impl<M: ethers_providers::Middleware> std::fmt::Debug for MulticallContract<M> {
         fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
             f.debug_tuple(stringify!(MulticallContract))
                 .field(&self.address())
                 .finish()
         }
     }
-    impl<'a, M: Middleware> MulticallContract<M> {
+    impl<'a, M: ethers_providers::Middleware> MulticallContract<M> {
         #[doc = r" Creates a new contract instance with the specified `ethers`"]
         #[doc = r" client at the given `Address`. The contract derefs to a `ethers::Contract`"]
         #[doc = r" object"]
-        pub fn new<T: Into<Address>, C: Into<Arc<M>>>(address: T, client: C) -> Self {
-            let contract =
-                Contract::new(address.into(), MULTICALLCONTRACT_ABI.clone(), client.into());
+        pub fn new<T: Into<ethers_core::types::Address>>(
+            address: T,
+            client: ::std::sync::Arc<M>,
+        ) -> Self {
+            let contract = ethers_contract::Contract::new(
+                address.into(),
+                MULTICALLCONTRACT_ABI.clone(),
+                client,
+            );
             Self(contract)
         }
         #[doc = "Calls the contract's `aggregate` (0x252dba42) function"]
         pub fn aggregate(
             &self,
-            calls: Vec<(Address, Vec<u8>)>,
-        ) -> ContractCall<M, (U256, Vec<Vec<u8>>)> {
+            calls: Vec<(ethers_core::types::Address, Vec<u8>)>,
+        ) -> ethers_contract::builders::ContractCall<M, (ethers_core::types::U256, Vec<Vec<u8>>)>
+        {
             self.0
                 .method_hash([37, 45, 186, 66], calls)
                 .expect("method not found (this should never happen)")
         }
-        #[doc = "Calls the contract's `getCurrentBlockDifficulty` (0x72425d9d) function"]
-        pub fn get_current_block_difficulty(&self) -> ContractCall<M, U256> {
+        #[doc = "Calls the contract's `aggregate3` (0x82ad56cb) function"]
+        pub fn aggregate_3(
+            &self,
+            calls: Vec<(ethers_core::types::Address, bool, Vec<u8>)>,
+        ) -> ethers_contract::builders::ContractCall<M, Vec<(bool, Vec<u8>)>> {
             self.0
-                .method_hash([114, 66, 93, 157], ())
+                .method_hash([130, 173, 86, 203], calls)
                 .expect("method not found (this should never happen)")
         }
-        #[doc = "Calls the contract's `getCurrentBlockGasLimit` (0x86d516e8) function"]
-        pub fn get_current_block_gas_limit(&self) -> ContractCall<M, U256> {
+        #[doc = "Calls the contract's `aggregate3Value` (0x174dea71) function"]
+        pub fn aggregate_3_value(
+            &self,
+            calls: Vec<(
+                ethers_core::types::Address,
+                bool,
+                ethers_core::types::U256,
+                Vec<u8>,
+            )>,
+        ) -> ethers_contract::builders::ContractCall<M, Vec<(bool, Vec<u8>)>> {
             self.0
-                .method_hash([134, 213, 22, 232], ())
+                .method_hash([23, 77, 234, 113], calls)
                 .expect("method not found (this should never happen)")
         }
-        #[doc = "Calls the contract's `getCurrentBlockTimestamp` (0x0f28c97d) function"]
-        pub fn get_current_block_timestamp(&self) -> ContractCall<M, U256> {
+        #[doc = "Calls the contract's `blockAndAggregate` (0xc3077fa9) function"]
+        pub fn block_and_aggregate(
+            &self,
+            calls: Vec<(ethers_core::types::Address, Vec<u8>)>,
+        ) -> ethers_contract::builders::ContractCall<
+            M,
+            (ethers_core::types::U256, [u8; 32], Vec<(bool, Vec<u8>)>),
+        > {
             self.0
-                .method_hash([15, 40, 201, 125], ())
+                .method_hash([195, 7, 127, 169], calls)
                 .expect("method not found (this should never happen)")
         }
-        #[doc = "Calls the contract's `getCurrentBlockCoinbase` (0xa8b0574e) function"]
-        pub fn get_current_block_coinbase(&self) -> ContractCall<M, Address> {
+        #[doc = "Calls the contract's `getBasefee` (0x3e64a696) function"]
+        pub fn get_basefee(
+            &self,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::U256> {
             self.0
-                .method_hash([168, 176, 87, 78], ())
+                .method_hash([62, 100, 166, 150], ())
                 .expect("method not found (this should never happen)")
         }
         #[doc = "Calls the contract's `getBlockHash` (0xee82ac5e) function"]
-        pub fn get_block_hash(&self, block_number: U256) -> ContractCall<M, [u8; 32]> {
+        pub fn get_block_hash(
+            &self,
+            block_number: ethers_core::types::U256,
+        ) -> ethers_contract::builders::ContractCall<M, [u8; 32]> {
             self.0
                 .method_hash([238, 130, 172, 94], block_number)
                 .expect("method not found (this should never happen)")
         }
+        #[doc = "Calls the contract's `getBlockNumber` (0x42cbb15c) function"]
+        pub fn get_block_number(
+            &self,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::U256> {
+            self.0
+                .method_hash([66, 203, 177, 92], ())
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `getChainId` (0x3408e470) function"]
+        pub fn get_chain_id(
+            &self,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::U256> {
+            self.0
+                .method_hash([52, 8, 228, 112], ())
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `getCurrentBlockCoinbase` (0xa8b0574e) function"]
+        pub fn get_current_block_coinbase(
+            &self,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::Address> {
+            self.0
+                .method_hash([168, 176, 87, 78], ())
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `getCurrentBlockDifficulty` (0x72425d9d) function"]
+        pub fn get_current_block_difficulty(
+            &self,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::U256> {
+            self.0
+                .method_hash([114, 66, 93, 157], ())
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `getCurrentBlockGasLimit` (0x86d516e8) function"]
+        pub fn get_current_block_gas_limit(
+            &self,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::U256> {
+            self.0
+                .method_hash([134, 213, 22, 232], ())
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `getCurrentBlockTimestamp` (0x0f28c97d) function"]
+        pub fn get_current_block_timestamp(
+            &self,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::U256> {
+            self.0
+                .method_hash([15, 40, 201, 125], ())
+                .expect("method not found (this should never happen)")
+        }
         #[doc = "Calls the contract's `getEthBalance` (0x4d2301cc) function"]
-        pub fn get_eth_balance(&self, addr: Address) -> ContractCall<M, U256> {
+        pub fn get_eth_balance(
+            &self,
+            addr: ethers_core::types::Address,
+        ) -> ethers_contract::builders::ContractCall<M, ethers_core::types::U256> {
             self.0
                 .method_hash([77, 35, 1, 204], addr)
                 .expect("method not found (this should never happen)")
         }
         #[doc = "Calls the contract's `getLastBlockHash` (0x27e86d6e) function"]
-        pub fn get_last_block_hash(&self) -> ContractCall<M, [u8; 32]> {
+        pub fn get_last_block_hash(&self) -> ethers_contract::builders::ContractCall<M, [u8; 32]> {
             self.0
                 .method_hash([39, 232, 109, 110], ())
                 .expect("method not found (this should never happen)")
         }
+        #[doc = "Calls the contract's `tryAggregate` (0xbce38bd7) function"]
+        pub fn try_aggregate(
+            &self,
+            require_success: bool,
+            calls: Vec<(ethers_core::types::Address, Vec<u8>)>,
+        ) -> ethers_contract::builders::ContractCall<M, Vec<(bool, Vec<u8>)>> {
+            self.0
+                .method_hash([188, 227, 139, 215], (require_success, calls))
+                .expect("method not found (this should never happen)")
+        }
+        #[doc = "Calls the contract's `tryBlockAndAggregate` (0x399542e9) function"]
+        pub fn try_block_and_aggregate(
+            &self,
+            require_success: bool,
+            calls: Vec<(ethers_core::types::Address, Vec<u8>)>,
+        ) -> ethers_contract::builders::ContractCall<
+            M,
+            (ethers_core::types::U256, [u8; 32], Vec<(bool, Vec<u8>)>),
+        > {
+            self.0
+                .method_hash([57, 149, 66, 233], (require_success, calls))
+                .expect("method not found (this should never happen)")
+        }
     }
 }
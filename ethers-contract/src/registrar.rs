@@ -0,0 +1,312 @@
+use crate::{builders::ContractCall, Contract, ContractError};
+
+use ethers_core::{
+    abi::{parse_abi, Detokenize, Tokenize},
+    types::{Address, H160, H256, U256},
+    utils::keccak256,
+};
+use ethers_providers::{namehash, Middleware};
+
+use std::sync::Arc;
+
+/// The address of the `.eth` registrar controller on mainnet,
+/// 0x283Af0B28c62C092C9727F1Ee09c02CA627EB7F5
+pub const ETH_REGISTRAR_CONTROLLER: Address = H160([
+    40, 58, 240, 178, 140, 98, 192, 146, 201, 114, 127, 30, 224, 156, 2, 202, 98, 126, 183, 245,
+]);
+
+const CONTROLLER_ABI: &[&str] = &[
+    "function available(string name) view returns (bool)",
+    "function rentPrice(string name, uint256 duration) view returns (uint256)",
+    "function minCommitmentAge() view returns (uint256)",
+    "function makeCommitment(string name, address owner, bytes32 secret) pure returns (bytes32)",
+    "function commit(bytes32 commitment)",
+    "function register(string name, address owner, uint256 duration, bytes32 secret) payable",
+    "function renew(string name, uint256 duration) payable",
+];
+
+/// The controller registering and renewing `.eth` names.
+///
+/// Names are registered in two steps so that pending registrations cannot be front-run:
+/// a hash of the name and a secret is first committed, and the name is registered with
+/// the secret once the commitment is at least
+/// [`min_commitment_age`](EthRegistrarController::min_commitment_age) seconds old.
+/// Registrations and renewals must pay the [`rent_price`](EthRegistrarController::rent_price)
+/// of the name as their value, any excess being refunded.
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers::{
+///     contract::{EthRegistrarController, ETH_REGISTRAR_CONTROLLER},
+///     providers::{Http, Middleware, Provider},
+///     types::{H256, U256},
+/// };
+/// use std::convert::TryFrom;
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Provider::<Http>::try_from("http://localhost:8545")?;
+/// let owner = client.get_accounts().await?[0];
+/// let controller = EthRegistrarController::new(ETH_REGISTRAR_CONTROLLER, client);
+///
+/// let name = "my-name";
+/// let duration = U256::from(365 * 24 * 60 * 60);
+/// if controller.available(name).call().await? {
+///     let secret = H256::random();
+///     let commitment = controller.commitment(name, owner, secret);
+///     controller.commit(commitment).from(owner).send().await?.await?;
+///
+///     // wait for `min_commitment_age` seconds, then reveal the commitment
+///     let price = controller.rent_price(name, duration).call().await?;
+///     controller
+///         .register(name, owner, duration, secret)
+///         .from(owner)
+///         .value(price)
+///         .send()
+///         .await?
+///         .await?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct EthRegistrarController<M> {
+    contract: Contract<M>,
+}
+
+impl<M: Middleware> EthRegistrarController<M> {
+    /// Creates a new instance of the controller deployed at `address`
+    pub fn new(address: Address, client: impl Into<Arc<M>>) -> Self {
+        let abi = parse_abi(CONTROLLER_ABI).expect("could not parse ABI");
+        Self {
+            contract: Contract::new(address, abi, client),
+        }
+    }
+
+    /// Returns the address of the controller
+    pub fn address(&self) -> Address {
+        self.contract.address()
+    }
+
+    /// Returns the commitment to the registration of the name by the owner, without a
+    /// resolver. This is the hash returned by
+    /// [`make_commitment`](EthRegistrarController::make_commitment), computed locally.
+    pub fn commitment(&self, name: &str, owner: Address, secret: H256) -> H256 {
+        let label = keccak256(name.as_bytes());
+        keccak256([&label[..], owner.as_bytes(), secret.as_bytes()].concat()).into()
+    }
+
+    /// Returns whether the name, without its `.eth` suffix, can be registered
+    pub fn available(&self, name: &str) -> ContractCall<M, bool> {
+        self.method("available", name.to_owned())
+    }
+
+    /// Returns the price in wei of renting the name for `duration` seconds
+    pub fn rent_price(&self, name: &str, duration: U256) -> ContractCall<M, U256> {
+        self.method("rentPrice", (name.to_owned(), duration))
+    }
+
+    /// Returns the number of seconds after which commitments can be revealed
+    pub fn min_commitment_age(&self) -> ContractCall<M, U256> {
+        self.method("minCommitmentAge", ())
+    }
+
+    /// Returns the commitment to the registration of the name by the owner, as computed
+    /// by the controller
+    pub fn make_commitment(
+        &self,
+        name: &str,
+        owner: Address,
+        secret: H256,
+    ) -> ContractCall<M, H256> {
+        self.method("makeCommitment", (name.to_owned(), owner, secret))
+    }
+
+    /// Commits to a registration, which is the first step of the registration
+    pub fn commit(&self, commitment: H256) -> ContractCall<M, ()> {
+        self.method("commit", commitment)
+    }
+
+    /// Registers the name for `duration` seconds by revealing the secret of a commitment.
+    /// The value of the call must be at least the rent price of the name.
+    pub fn register(
+        &self,
+        name: &str,
+        owner: Address,
+        duration: U256,
+        secret: H256,
+    ) -> ContractCall<M, ()> {
+        self.method("register", (name.to_owned(), owner, duration, secret))
+    }
+
+    /// Extends the registration of the name by `duration` seconds. The value of the call
+    /// must be at least the rent price of the name.
+    pub fn renew(&self, name: &str, duration: U256) -> ContractCall<M, ()> {
+        self.method("renew", (name.to_owned(), duration))
+    }
+
+    fn method<T: Tokenize, D: Detokenize>(&self, name: &str, args: T) -> ContractCall<M, D> {
+        self.contract
+            .method(name, args)
+            .expect("method not found in the controller's ABI")
+    }
+}
+
+const REGISTRY_ABI: &[&str] = &["function owner(bytes32 node) view returns (address)"];
+
+const REVERSE_REGISTRAR_ABI: &[&str] = &[
+    "function claim(address owner) returns (bytes32)",
+    "function setName(string name) returns (bytes32)",
+];
+
+/// The registrar of the `addr.reverse` names, which map addresses back to their primary
+/// ENS name. The reverse record of an address is set by the address itself, so calls must
+/// be sent from it, e.g. through a [`SignerMiddleware`].
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers::{
+///     contract::ReverseRegistrar,
+///     prelude::*,
+///     providers::ENS_ADDRESS,
+/// };
+/// use std::convert::TryFrom;
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let wallet: LocalWallet = "380eb0f3d505f087e438eca80bc4df9a7faa24f868e69fc0440261a0fc0567dc"
+///     .parse()?;
+/// let provider = Provider::<Http>::try_from("http://localhost:8545")?;
+/// let client = SignerMiddleware::new(provider, wallet);
+///
+/// let registrar = ReverseRegistrar::from_registry(ENS_ADDRESS, client).await?;
+/// registrar.set_name("my-name.eth").send().await?.await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`SignerMiddleware`]: https://docs.rs/ethers-middleware/latest/ethers_middleware/signer/struct.SignerMiddleware.html
+#[derive(Debug)]
+pub struct ReverseRegistrar<M> {
+    contract: Contract<M>,
+}
+
+impl<M: Middleware> ReverseRegistrar<M> {
+    /// Creates a new instance of the reverse registrar deployed at `address`
+    pub fn new(address: Address, client: impl Into<Arc<M>>) -> Self {
+        let abi = parse_abi(REVERSE_REGISTRAR_ABI).expect("could not parse ABI");
+        Self {
+            contract: Contract::new(address, abi, client),
+        }
+    }
+
+    /// Creates a new instance of the reverse registrar which currently owns `addr.reverse`
+    /// in the ENS registry at `registry`
+    pub async fn from_registry(
+        registry: Address,
+        client: impl Into<Arc<M>>,
+    ) -> Result<Self, ContractError<M>> {
+        let client = client.into();
+        let abi = parse_abi(REGISTRY_ABI).expect("could not parse ABI");
+        let address = Contract::new(registry, abi, Arc::clone(&client))
+            .method::<_, Address>("owner", namehash("addr.reverse"))?
+            .call()
+            .await?;
+        Ok(Self::new(address, client))
+    }
+
+    /// Returns the address of the reverse registrar
+    pub fn address(&self) -> Address {
+        self.contract.address()
+    }
+
+    /// Sets the name of the sender's reverse record, after claiming it if needed. The name
+    /// should resolve back to the sender for clients to trust it.
+    pub fn set_name(&self, name: &str) -> ContractCall<M, H256> {
+        self.contract
+            .method("setName", name.to_owned())
+            .expect("method not found in the reverse registrar's ABI")
+    }
+
+    /// Transfers the ownership of the sender's reverse record to `owner`, e.g. so that
+    /// a contract's record can be managed by an externally owned account
+    pub fn claim(&self, owner: Address) -> ContractCall<M, H256> {
+        self.contract
+            .method("claim", owner)
+            .expect("method not found in the reverse registrar's ABI")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers_core::{
+        abi::{self, Token},
+        types::Bytes,
+        utils::id,
+    };
+    use ethers_providers::Provider;
+
+    #[test]
+    fn computes_commitments() {
+        let (provider, _) = Provider::mocked();
+        let controller = EthRegistrarController::new(ETH_REGISTRAR_CONTROLLER, provider);
+        let owner = Address::repeat_byte(1);
+        let secret = H256::repeat_byte(2);
+        let packed = [&keccak256(b"vitalik")[..], &[1; 20][..], &[2; 32][..]].concat();
+        assert_eq!(
+            controller.commitment("vitalik", owner, secret),
+            H256::from(keccak256(packed))
+        );
+    }
+
+    #[tokio::test]
+    async fn encodes_registrations() {
+        let (provider, mock) = Provider::mocked();
+        let controller = EthRegistrarController::new(ETH_REGISTRAR_CONTROLLER, provider);
+        let owner = Address::repeat_byte(1);
+        let duration = U256::from(31_536_000);
+        let secret = H256::repeat_byte(2);
+
+        mock.push(Bytes::from(abi::encode(&[Token::Bool(true)])))
+            .unwrap();
+        assert!(controller.available("vitalik").call().await.unwrap());
+
+        let calldata = controller
+            .register("vitalik", owner, duration, secret)
+            .calldata()
+            .unwrap();
+        let expected = [
+            &id("register(string,address,uint256,bytes32)")[..],
+            &abi::encode(&[
+                Token::String("vitalik".to_owned()),
+                Token::Address(owner),
+                Token::Uint(duration),
+                Token::FixedBytes(secret.as_bytes().to_vec()),
+            ]),
+        ]
+        .concat();
+        assert_eq!(calldata.as_ref(), &expected[..]);
+    }
+
+    #[tokio::test]
+    async fn sets_reverse_records() {
+        let (provider, mock) = Provider::mocked();
+        let registrar = Address::repeat_byte(1);
+        mock.push(Bytes::from(abi::encode(&[Token::Address(registrar)])))
+            .unwrap();
+
+        let reverse = ReverseRegistrar::from_registry(ethers_providers::ENS_ADDRESS, provider)
+            .await
+            .unwrap();
+        assert_eq!(reverse.address(), registrar);
+
+        let calldata = reverse.set_name("alice.eth").calldata().unwrap();
+        let expected = [
+            &id("setName(string)")[..],
+            &abi::encode(&[Token::String("alice.eth".to_owned())]),
+        ]
+        .concat();
+        assert_eq!(calldata.as_ref(), &expected[..]);
+    }
+}
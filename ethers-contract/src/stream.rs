@@ -1,5 +1,10 @@
-use ethers_core::types::{Log, U256};
-use futures_util::stream::{Stream, StreamExt};
+use crate::{EthLogDecode, LogMeta};
+
+use ethers_core::{
+    abi::{Error, RawLog},
+    types::{Log, U256},
+};
+use futures_util::stream::{Map, Stream, StreamExt};
 use pin_project::pin_project;
 use std::pin::Pin;
 use std::task::{Context, Poll};
@@ -35,7 +40,76 @@ where
         let mut this = self.project();
         match futures_util::ready!(this.stream.poll_next_unpin(ctx)) {
             Some(item) => Poll::Ready(Some((this.parse)(item))),
-            None => Poll::Pending,
+            // the underlying stream has ended, so there are no more events to decode
+            None => Poll::Ready(None),
         }
     }
 }
+
+/// Adaptors decoding the logs of a stream, e.g. a
+/// [`FilterWatcher`](ethers_providers::FilterWatcher) or a
+/// [`SubscriptionStream`](ethers_providers::SubscriptionStream) of logs, into events.
+/// Logs which cannot be decoded are yielded as errors, without ending the stream.
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers::{
+///     contract::{EthEvent, LogStreamExt},
+///     providers::{Http, Middleware, Provider, StreamExt},
+///     types::{Address, Filter, U256},
+/// };
+/// use std::convert::TryFrom;
+///
+/// #[derive(Debug, Clone, EthEvent)]
+/// struct Transfer {
+///     #[ethevent(indexed)]
+///     from: Address,
+///     #[ethevent(indexed)]
+///     to: Address,
+///     value: U256,
+/// }
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Provider::<Http>::try_from("http://localhost:8545")?;
+/// let filter = Filter::new().event("Transfer(address,address,uint256)");
+///
+/// let mut transfers = provider.watch(&filter).await?.map_decode::<Transfer>();
+/// while let Some(transfer) = transfers.next().await {
+///     println!("{:?}", transfer?);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub trait LogStreamExt: Stream<Item = Log> + Sized {
+    /// Decodes each log of the stream into a `D` event
+    fn map_decode<D: EthLogDecode>(self) -> Map<Self, fn(Log) -> Result<D, Error>> {
+        self.map(decode_log as fn(Log) -> Result<D, Error>)
+    }
+
+    /// Decodes each log of the stream into a `D` event, along with the metadata of the
+    /// log. Logs which are not mined yet, e.g. from subscriptions to pending logs, have no
+    /// metadata and are returned as errors.
+    fn map_decode_with_meta<D: EthLogDecode>(
+        self,
+    ) -> Map<Self, fn(Log) -> Result<(D, LogMeta), Error>> {
+        self.map(decode_log_with_meta as fn(Log) -> Result<(D, LogMeta), Error>)
+    }
+}
+
+impl<S: Stream<Item = Log>> LogStreamExt for S {}
+
+fn decode_log<D: EthLogDecode>(log: Log) -> Result<D, Error> {
+    D::decode_log(&RawLog {
+        topics: log.topics,
+        data: log.data.to_vec(),
+    })
+}
+
+fn decode_log_with_meta<D: EthLogDecode>(log: Log) -> Result<(D, LogMeta), Error> {
+    if log.block_number.is_none() || log.transaction_hash.is_none() {
+        return Err(Error::InvalidData);
+    }
+    let meta = LogMeta::from(&log);
+    Ok((decode_log(log)?, meta))
+}
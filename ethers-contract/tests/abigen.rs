@@ -43,6 +43,154 @@ fn can_gen_structs_readable() {
     );
 }
 
+#[test]
+fn can_gen_deploy_from_artifact() {
+    abigen!(
+        Greeter,
+        r#"{
+        "abi": [{"inputs":[{"internalType":"string","name":"greeting","type":"string"}],"stateMutability":"nonpayable","type":"constructor"}],
+        "bytecode": "0x6080"
+    }"#
+    );
+    let (provider, _) = ethers_providers::Provider::mocked();
+    let deployer = Greeter::deploy(std::sync::Arc::new(provider), "hello".to_owned()).unwrap();
+
+    // the constructor arguments are appended to the bytecode
+    let data = deployer.tx.data.unwrap();
+    assert_eq!(&data.as_ref()[..2], &GREETER_BYTECODE.as_ref()[..]);
+    assert_eq!(
+        &data.as_ref()[2..],
+        &ethers_core::abi::encode(&["hello".to_owned().into_token()])[..]
+    );
+    assert!(deployer.tx.to.is_none());
+}
+
+#[test]
+fn can_filter_indexed_params() {
+    abigen!(
+        SimpleContract,
+        r#"[
+        event Transfer(address indexed from, address indexed to, uint256 indexed amount, string note)
+    ]"#
+    );
+    let (provider, _) = ethers_providers::Provider::mocked();
+    let contract = SimpleContract::new(
+        ethers_core::types::Address::zero(),
+        std::sync::Arc::new(provider),
+    );
+
+    let to: ethers_core::types::Address =
+        "eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee".parse().unwrap();
+    let event = contract.transfer_filter().to(to).amount(1000.into());
+
+    // the first topic is the event's signature
+    assert_eq!(
+        event.filter.topics[0],
+        Some(TransferFilter::signature().into())
+    );
+    assert_eq!(event.filter.topics[1], None);
+    assert_eq!(event.filter.topics[2], Some(to.into()));
+    assert_eq!(
+        event.filter.topics[3],
+        Some(ethers_core::types::U256::from(1000).into())
+    );
+
+    // arrays of values match any of them
+    let from = ethers_core::types::Address::repeat_byte(1);
+    let event = contract
+        .transfer_filter()
+        .from_any(vec![from, to])
+        .amount_any(vec![ethers_core::types::U256::one(), 2.into()]);
+    assert_eq!(
+        event.filter.topics[1],
+        Some(ethers_core::types::ValueOrArray::Array(vec![
+            from.into(),
+            to.into()
+        ]))
+    );
+    assert_eq!(
+        event.filter.topics[3],
+        Some(ethers_core::types::ValueOrArray::Array(vec![
+            ethers_core::types::H256::from_low_u64_be(1),
+            ethers_core::types::H256::from_low_u64_be(2)
+        ]))
+    );
+
+    // nodes match any value at a position with an empty array of topics
+    let event = contract.transfer_filter().from_any(vec![]);
+    assert_eq!(
+        event.filter.topics[1],
+        Some(ethers_core::types::ValueOrArray::Array(vec![]))
+    );
+}
+
+#[test]
+fn can_filter_indexed_strings() {
+    abigen!(
+        SimpleContract,
+        r#"[
+        event ValueChanged(string indexed oldValue, string newValue)
+    ]"#
+    );
+    let (provider, _) = ethers_providers::Provider::mocked();
+    let contract = SimpleContract::new(
+        ethers_core::types::Address::zero(),
+        std::sync::Arc::new(provider),
+    );
+
+    // indexed strings are matched by the hash of their contents
+    let event = contract
+        .value_changed_filter()
+        .old_value("hello".to_owned());
+    assert_eq!(
+        event.filter.topics[1],
+        Some(ethers_core::types::H256::from(ethers_core::utils::keccak256("hello")).into())
+    );
+}
+
+#[test]
+fn can_decode_events_enum() {
+    use ethers_contract::EthLogDecode;
+    use ethers_core::{
+        abi::{self, RawLog, Token},
+        types::{Address, H256},
+    };
+
+    abigen!(
+        SimpleContract,
+        r#"[
+        event ValueChanged(address indexed author, string oldValue, string newValue)
+        event Transfer(address indexed from, address indexed to, uint256 value)
+    ]"#,
+        event_derives(serde::Deserialize, serde::Serialize)
+    );
+
+    let from: Address = "eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee".parse().unwrap();
+    let to: Address = "ffffffffffffffffffffffffffffffffffffffff".parse().unwrap();
+    let log = RawLog {
+        topics: vec![
+            TransferFilter::signature(),
+            H256::from(from),
+            H256::from(to),
+        ],
+        data: abi::encode(&[Token::Uint(100.into())]),
+    };
+
+    let transfer = TransferFilter {
+        from,
+        to,
+        value: 100.into(),
+    };
+    match SimpleContractEvents::decode_log(&log).unwrap() {
+        SimpleContractEvents::TransferFilter(event) => assert_eq!(event, transfer),
+        event => panic!("unexpected event {:?}", event),
+    }
+    assert_eq!(
+        SimpleContractEvents::from(transfer.clone()),
+        SimpleContractEvents::TransferFilter(transfer)
+    );
+}
+
 // NOTE(mattsse): There is currently a limitation with the `ethabi` crate's `Reader`
 //  that doesn't support arrays of tuples; https://github.com/gakonst/ethabi/pull/1 should fix this
 // See also https://github.com/rust-ethereum/ethabi/issues/178 and
@@ -1,5 +1,7 @@
 use ethers::core::types::{H160, H256, I256, U128, U256};
-use ethers_contract::{abigen, EthAbiType, EthEvent};
+use ethers_contract::{
+    abigen, AbiError, ContractError, ContractRevert, EthAbiType, EthCall, EthEvent,
+};
 use ethers_core::abi::Tokenizable;
 use ethers_core::types::Address;
 
@@ -236,3 +238,184 @@ fn can_generate_ethevent_from_json() {
         CreatedFilter::signature()
     );
 }
+
+#[test]
+fn can_derive_eth_call() {
+    #[derive(Debug, Clone, PartialEq, EthCall)]
+    #[ethcall(abi = "transfer(address,uint256)")]
+    struct TransferCall {
+        to: Address,
+        amount: U256,
+    }
+
+    assert_eq!("transfer", TransferCall::function_name());
+    assert_eq!("transfer(address,uint256)", TransferCall::abi_signature());
+    assert_eq!([0xa9, 0x05, 0x9c, 0xbb], TransferCall::selector());
+
+    let call = TransferCall {
+        to: "eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee".parse().unwrap(),
+        amount: 100.into(),
+    };
+    let calldata = call.clone().encode();
+    assert_eq!(calldata.as_ref().len(), 4 + 2 * 32);
+    assert_eq!(&calldata.as_ref()[..4], &[0xa9, 0x05, 0x9c, 0xbb]);
+    assert_eq!(TransferCall::decode(&calldata).unwrap(), call);
+
+    // the selector must match
+    assert!(matches!(
+        TransferCall::decode(&calldata.as_ref()[1..]),
+        Err(AbiError::WrongSelector)
+    ));
+}
+
+#[test]
+fn can_derive_eth_call_from_fields() {
+    #[derive(Debug, Clone, PartialEq, EthCall)]
+    #[ethcall(name = "approve")]
+    struct ApproveCall(Address, U256);
+
+    assert_eq!("approve(address,uint256)", ApproveCall::abi_signature());
+    assert_eq!([0x09, 0x5e, 0xa7, 0xb3], ApproveCall::selector());
+
+    let call = ApproveCall(Address::zero(), U256::MAX);
+    assert_eq!(ApproveCall::decode(call.clone().encode()).unwrap(), call);
+}
+
+#[test]
+fn can_decode_revert_data() {
+    type Error = ContractError<ethers::providers::Provider<ethers::providers::MockProvider>>;
+
+    #[derive(Debug, Clone, PartialEq, EthCall)]
+    #[ethcall(abi = "InsufficientBalance(uint256,uint256)")]
+    struct InsufficientBalance {
+        available: U256,
+        required: U256,
+    }
+
+    let mut data = vec![0x08, 0xc3, 0x79, 0xa0];
+    data.extend(ethers_core::abi::encode(&["not enough balance"
+        .to_owned()
+        .into_token()]));
+    let err = Error::Revert(data.into());
+    assert_eq!(err.decode_revert::<String>().unwrap(), "not enough balance");
+    assert!(err.decode_custom_error::<InsufficientBalance>().is_none());
+
+    let mut data = vec![0x4e, 0x48, 0x7b, 0x71];
+    data.extend(ethers_core::abi::encode(&[U256::from(0x11).into_token()]));
+    let err = Error::Revert(data.into());
+    assert_eq!(err.decode_revert::<U256>().unwrap(), U256::from(0x11));
+
+    let custom = InsufficientBalance {
+        available: 1.into(),
+        required: 2.into(),
+    };
+    let err = Error::Revert(custom.clone().encode());
+    assert_eq!(
+        err.decode_custom_error::<InsufficientBalance>().unwrap(),
+        custom
+    );
+    assert!(err.decode_revert::<String>().is_none());
+
+    assert!(Error::ContractNotDeployed.as_revert().is_none());
+}
+
+#[test]
+fn can_decode_revert_reason() {
+    type Error = ContractError<ethers::providers::Provider<ethers::providers::MockProvider>>;
+
+    let mut data = vec![0x08, 0xc3, 0x79, 0xa0];
+    data.extend(ethers_core::abi::encode(&["not enough balance"
+        .to_owned()
+        .into_token()]));
+    let err = Error::Revert(data.into());
+    assert_eq!(
+        err.revert_reason().unwrap(),
+        ContractRevert::Error("not enough balance".to_owned())
+    );
+    assert_eq!(
+        err.to_string(),
+        "Contract call reverted: not enough balance"
+    );
+
+    let mut data = vec![0x4e, 0x48, 0x7b, 0x71];
+    data.extend(ethers_core::abi::encode(&[U256::from(0x11).into_token()]));
+    let err = Error::Revert(data.into());
+    assert_eq!(
+        err.revert_reason().unwrap(),
+        ContractRevert::Panic(0x11.into())
+    );
+    assert_eq!(
+        err.to_string(),
+        "Contract call reverted: panic 0x11 (arithmetic underflow or overflow)"
+    );
+
+    // truncated `Error(string)` data is not a valid reason
+    let err = Error::Revert(vec![0x08, 0xc3, 0x79, 0xa0, 0x01].into());
+    assert!(matches!(
+        err.revert_reason().unwrap(),
+        ContractRevert::Custom(_)
+    ));
+    assert_eq!(
+        Error::Revert(Default::default()).to_string(),
+        "Contract call reverted: no revert data"
+    );
+    assert!(Error::ContractNotDeployed.revert_reason().is_none());
+}
+
+#[tokio::test]
+async fn can_decode_log_streams() {
+    use ethers_contract::LogStreamExt;
+    use ethers_core::types::Log;
+    use futures_util::stream::{self, StreamExt};
+
+    #[derive(Debug, PartialEq, EthEvent)]
+    struct Transfer {
+        #[ethevent(indexed)]
+        from: Address,
+        #[ethevent(indexed)]
+        to: Address,
+        value: U256,
+    }
+
+    let log = |data: &str| -> Log {
+        serde_json::from_value(serde_json::json!({
+            "address": Address::zero(),
+            "topics": [
+                Transfer::signature(),
+                H256::from(Address::repeat_byte(1)),
+                H256::from(Address::repeat_byte(2)),
+            ],
+            "data": data,
+        }))
+        .unwrap()
+    };
+    let value = format!("0x{:064x}", 5);
+
+    // the malformed log does not end the stream
+    let transfers = stream::iter(vec![log("0x"), log(&value)])
+        .map_decode::<Transfer>()
+        .collect::<Vec<_>>()
+        .await;
+    assert!(transfers[0].is_err());
+    assert_eq!(
+        transfers[1].as_ref().unwrap(),
+        &Transfer {
+            from: Address::repeat_byte(1),
+            to: Address::repeat_byte(2),
+            value: 5.into(),
+        }
+    );
+
+    // the pending log has no metadata, which does not end the stream either
+    let mut mined = log(&value);
+    mined.block_number = Some(7.into());
+    mined.transaction_hash = Some(H256::repeat_byte(3));
+    let transfers = stream::iter(vec![log(&value), mined])
+        .map_decode_with_meta::<Transfer>()
+        .collect::<Vec<_>>()
+        .await;
+    assert!(transfers[0].is_err());
+    let (_, meta) = transfers[1].as_ref().unwrap();
+    assert_eq!(meta.block_number, 7.into());
+    assert_eq!(meta.transaction_hash, H256::repeat_byte(3));
+}
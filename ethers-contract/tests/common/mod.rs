@@ -52,4 +52,5 @@ pub async fn deploy<M: Middleware>(client: Arc<M>, abi: Abi, bytecode: Bytes) ->
         .send()
         .await
         .unwrap()
+        .0
 }
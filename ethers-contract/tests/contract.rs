@@ -36,7 +36,7 @@ mod eth_tests {
         // (practically it's not expected that you'll need to deploy multiple instances of
         // the _same_ deployer, so it's fine to clone here from a dev UX vs perf tradeoff)
         let deployer = factory.deploy("initial value".to_string()).unwrap();
-        let contract = deployer.clone().send().await.unwrap();
+        let (contract, _) = deployer.clone().send().await.unwrap();
 
         let get_value = contract.method::<_, String>("getValue", ()).unwrap();
         let last_sender = contract.method::<_, Address>("lastSender", ()).unwrap();
@@ -64,7 +64,7 @@ mod eth_tests {
 
         // we can also call contract methods at other addresses with the `at` call
         // (useful when interacting with multiple ERC20s for example)
-        let contract2_addr = deployer.send().await.unwrap().address();
+        let contract2_addr = deployer.send().await.unwrap().0.address();
         let contract2 = contract.at(contract2_addr);
         let init_value: String = contract2
             .method::<_, String>("getValue", ())
@@ -132,6 +132,45 @@ mod eth_tests {
         assert_eq!(logs.len(), 1);
     }
 
+    #[tokio::test]
+    async fn decode_receipt_logs() {
+        let (abi, bytecode) = compile_contract("SimpleStorage", "SimpleStorage.sol");
+        let ganache = Ganache::new().spawn();
+        let client = connect(&ganache, 0);
+        let contract = deploy(client.clone(), abi, bytecode).await;
+
+        let receipt = contract
+            .method::<_, H256>("setValue", "hi".to_owned())
+            .unwrap()
+            .send()
+            .await
+            .unwrap()
+            .await
+            .unwrap();
+
+        let logs: Vec<ValueChanged> = contract.decode_logs(&receipt).unwrap();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].old_value, "initial value");
+        assert_eq!(logs[0].new_value, "hi");
+
+        // the same logs can be decoded with just the ABI
+        let logs: Vec<(Address, Address, String, String)> = ethers::contract::decode_receipt_logs(
+            contract.abi(),
+            "ValueChanged",
+            contract.address(),
+            &receipt,
+        )
+        .unwrap();
+        assert_eq!(logs[0].3, "hi");
+
+        // logs emitted by other contracts are skipped
+        let other = contract.at(Address::zero());
+        assert!(other
+            .decode_logs::<ValueChanged>(&receipt)
+            .unwrap()
+            .is_empty());
+    }
+
     #[tokio::test]
     async fn call_past_state() {
         let (abi, bytecode) = compile_contract("SimpleStorage", "SimpleStorage.sol");
@@ -285,6 +324,63 @@ mod eth_tests {
         }
     }
 
+    #[test]
+    fn converts_call_transaction_type() {
+        use ethers::types::TypedTransaction;
+
+        let (provider, _) = Provider::mocked();
+        let abi = ethers::abi::parse_abi(&["function setValue(string)"]).unwrap();
+        let contract = ethers_contract::Contract::new(Address::zero(), abi, provider);
+        let call = contract
+            .method::<_, ()>("setValue", "hi".to_owned())
+            .unwrap()
+            .value(1)
+            .gas_price(2);
+        assert!(matches!(call.tx, TypedTransaction::Legacy(_)));
+
+        let call = call.eip1559();
+        let calldata = call.calldata().unwrap();
+        match &call.tx {
+            TypedTransaction::Eip1559(tx) => {
+                assert_eq!(tx.value, Some(1.into()));
+                assert_eq!(tx.max_fee_per_gas, Some(2.into()));
+            }
+            tx => panic!("expected an EIP-1559 transaction, got {:?}", tx),
+        }
+
+        let call = call.legacy().gas(3);
+        match &call.tx {
+            TypedTransaction::Legacy(tx) => {
+                assert_eq!(tx.value, Some(1.into()));
+                assert_eq!(tx.gas, Some(3.into()));
+                assert_eq!(tx.gas_price, None);
+            }
+            tx => panic!("expected a legacy transaction, got {:?}", tx),
+        }
+        assert_eq!(call.calldata().unwrap(), calldata);
+    }
+
+    #[tokio::test]
+    async fn watch_events_with_meta() {
+        let (abi, bytecode) = compile_contract("SimpleStorage", "SimpleStorage.sol");
+        let ganache = Ganache::new().spawn();
+        let client = connect(&ganache, 0);
+        let contract = deploy(client, abi, bytecode).await;
+
+        let event = contract.event::<ValueChanged>();
+        let mut stream = event.stream_with_meta().await.unwrap();
+
+        let call = contract
+            .method::<_, H256>("setValue", "hi".to_owned())
+            .unwrap();
+        let receipt = call.send().await.unwrap().await.unwrap();
+
+        let (log, meta) = stream.next().await.unwrap().unwrap();
+        assert_eq!(log.new_value, "hi");
+        assert_eq!(meta.block_number, receipt.block_number.unwrap());
+        assert_eq!(meta.transaction_hash, receipt.transaction_hash);
+    }
+
     #[tokio::test]
     async fn signer_on_node() {
         let (abi, bytecode) = compile_contract("SimpleStorage", "SimpleStorage.sol");
@@ -321,6 +417,26 @@ mod eth_tests {
         assert_eq!(value, "hi");
     }
 
+    #[tokio::test]
+    async fn deploy_with_receipt() {
+        let (abi, bytecode) = compile_contract("SimpleStorage", "SimpleStorage.sol");
+        let ganache = Ganache::new().spawn();
+        let client = connect(&ganache, 0);
+
+        let factory = ContractFactory::new(abi, bytecode, client.clone());
+        let (contract, receipt) = factory
+            .deploy("initial value".to_string())
+            .unwrap()
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(receipt.contract_address, Some(contract.address()));
+        assert_eq!(receipt.status, Some(1u64.into()));
+        let code = client.get_code(contract.address(), None).await.unwrap();
+        assert!(!code.as_ref().is_empty());
+    }
+
     #[tokio::test]
     async fn multicall_aggregate() {
         // get ABI and bytecode for the Multcall contract
@@ -354,16 +470,16 @@ mod eth_tests {
         let not_so_simple_factory =
             ContractFactory::new(not_so_simple_abi, not_so_simple_bytecode, client3.clone());
 
-        let multicall_contract = multicall_factory.deploy(()).unwrap().send().await.unwrap();
+        let (multicall_contract, _) = multicall_factory.deploy(()).unwrap().send().await.unwrap();
         let addr = multicall_contract.address();
 
-        let simple_contract = simple_factory
+        let (simple_contract, _) = simple_factory
             .deploy("the first one".to_string())
             .unwrap()
             .send()
             .await
             .unwrap();
-        let not_so_simple_contract = not_so_simple_factory
+        let (not_so_simple_contract, _) = not_so_simple_factory
             .deploy("the second one".to_string())
             .unwrap()
             .send()
@@ -469,6 +585,69 @@ mod eth_tests {
         assert_eq!(balances.1, U256::from(100000000000000000000u128));
         assert_eq!(balances.2, U256::from(100000000000000000000u128));
     }
+
+    #[tokio::test]
+    async fn multicall3_allows_failures() {
+        use ethers::{
+            abi::Token,
+            contract::{Contract, ContractError, MulticallVersion},
+        };
+
+        let (multicall_abi, multicall_bytecode) = compile_contract("Multicall3", "Multicall3.sol");
+        let (not_so_simple_abi, _) =
+            compile_contract("NotSoSimpleStorage", "NotSoSimpleStorage.sol");
+        let (abi, bytecode) = compile_contract("SimpleStorage", "SimpleStorage.sol");
+
+        let ganache = Ganache::new().spawn();
+        let client = connect(&ganache, 0);
+
+        let (multicall_contract, _) =
+            ContractFactory::new(multicall_abi, multicall_bytecode, client.clone())
+                .deploy(())
+                .unwrap()
+                .send()
+                .await
+                .unwrap();
+        let simple_contract = deploy(client.clone(), abi, bytecode).await;
+
+        // SimpleStorage has no `getValues` function nor a fallback, so calling it reverts
+        let broken_contract =
+            Contract::new(simple_contract.address(), not_so_simple_abi, client.clone());
+        let value = simple_contract.method::<_, String>("getValue", ()).unwrap();
+        let values = broken_contract
+            .method::<_, (String, Address)>("getValues", ())
+            .unwrap();
+
+        let mut multicall = Multicall::new_with_version(
+            client.clone(),
+            Some(multicall_contract.address()),
+            MulticallVersion::Multicall3,
+        )
+        .await
+        .unwrap();
+        multicall.add_call(value).add_call_allow_failure(values);
+
+        let results = multicall.call_raw().await.unwrap();
+        assert_eq!(
+            results[0].clone().unwrap(),
+            Token::String("initial value".to_owned())
+        );
+        assert!(results[1].is_err());
+
+        // the typed results cannot represent the failure
+        let err = multicall
+            .call::<(String, (String, Address))>()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ContractError::Revert(_)));
+
+        // calls which are not allowed to fail revert the whole batch
+        let values = broken_contract
+            .method::<_, (String, Address)>("getValues", ())
+            .unwrap();
+        multicall.clear_calls().add_call(values);
+        assert!(multicall.call_raw().await.is_err());
+    }
 }
 
 #[cfg(feature = "celo")]
@@ -501,7 +680,7 @@ mod celo_tests {
 
         let factory = ContractFactory::new(abi, bytecode, client);
         let deployer = factory.deploy("initial value".to_string()).unwrap();
-        let contract = deployer.block(BlockNumber::Pending).send().await.unwrap();
+        let (contract, _) = deployer.block(BlockNumber::Pending).send().await.unwrap();
 
         let value: String = contract
             .method("getValue", ())
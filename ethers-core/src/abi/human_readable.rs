@@ -28,6 +28,10 @@ impl AbiParser {
     ///         event ValueChanged(address indexed author, string oldValue, string newValue)
     ///     ]"#).unwrap();
     /// ```
+    ///
+    /// Tuples can be declared inline, either as `(uint256,address)` or in the ethers.js
+    /// format `tuple(uint256 amount, address to) order`, and lines starting with `//`
+    /// are ignored as comments.
     pub fn parse_str(&mut self, s: &str) -> Result<Abi> {
         self.parse(
             &s.trim()
@@ -62,7 +66,7 @@ impl AbiParser {
             .iter()
             .map(|s| escape_quotes(s))
             .map(str::trim)
-            .filter(|s| !s.is_empty())
+            .filter(|s| !s.is_empty() && !s.starts_with("//"))
             .partition(|s| s.starts_with("struct"));
 
         for sol in structs {
@@ -90,6 +94,10 @@ impl AbiParser {
                     .push(event);
             } else if line.starts_with("constructor") {
                 abi.constructor = Some(self.parse_constructor(line)?);
+            } else if is_special_function(line, "receive") {
+                abi.receive = true;
+            } else if is_special_function(line, "fallback") {
+                abi.fallback = true;
             } else {
                 bail!("Illegal abi `{}`", line)
             }
@@ -198,8 +206,8 @@ impl AbiParser {
                     let inputs = if event.is_empty() {
                         Vec::new()
                     } else {
-                        event
-                            .split(',')
+                        split_params(event)
+                            .into_iter()
                             .map(|e| self.parse_event_arg(e))
                             .collect::<Result<Vec<_>, _>>()?
                     };
@@ -221,6 +229,22 @@ impl AbiParser {
 
     /// Parse a single event param
     fn parse_event_arg(&self, input: &str) -> Result<EventParam> {
+        if let Some((kind, rest)) = self.parse_tuple(input)? {
+            let mut indexed = false;
+            let mut name = "";
+            for word in rest.split(is_whitespace).filter(|s| !s.is_empty()) {
+                match word {
+                    "indexed" => indexed = true,
+                    _ => name = word,
+                }
+            }
+            return Ok(EventParam {
+                name: name.to_string(),
+                indexed,
+                kind,
+            });
+        }
+
         let mut iter = input.trim().rsplitn(3, is_whitespace);
         let mut indexed = false;
         let mut name = iter
@@ -309,12 +333,69 @@ impl AbiParser {
     }
 
     fn parse_params(&self, s: &str) -> Result<Vec<Param>> {
-        s.split(',')
-            .filter(|s| !s.is_empty())
+        split_params(s)
+            .into_iter()
+            .filter(|s| !s.trim().is_empty())
             .map(|s| self.parse_param(s))
             .collect::<Result<Vec<_>, _>>()
     }
 
+    /// Parses the type of an inline tuple param, e.g. `(uint256,address)[]` or
+    /// `tuple(uint256 amount, address to)`, returning it along with the rest of the param,
+    /// i.e. its modifiers and name. Returns `None` if the param is not a tuple.
+    fn parse_tuple<'a>(&self, param: &'a str) -> Result<Option<(ParamType, &'a str)>> {
+        let param = param.trim();
+        let inner = match param
+            .strip_prefix("tuple(")
+            .or_else(|| param.strip_prefix('('))
+        {
+            Some(inner) => inner,
+            None => return Ok(None),
+        };
+
+        // find the matching closing parenthesis
+        let mut depth = 1;
+        let end = inner
+            .char_indices()
+            .find_map(|(i, c)| {
+                match c {
+                    '(' => depth += 1,
+                    ')' => depth -= 1,
+                    _ => {}
+                }
+                if depth == 0 {
+                    Some(i)
+                } else {
+                    None
+                }
+            })
+            .ok_or_else(|| format_err!("Expected closing `)` in `{}`", param))?;
+        let components = self
+            .parse_params(&inner[..end])?
+            .into_iter()
+            .map(|param| param.kind)
+            .collect();
+        let mut kind = ParamType::Tuple(components);
+
+        // the tuple may be an array of tuples
+        let mut rest = &inner[end + 1..];
+        while let Some(suffix) = rest.strip_prefix('[') {
+            let close = suffix
+                .find(']')
+                .ok_or_else(|| format_err!("Expected closing `]` in `{}`", param))?;
+            kind = match &suffix[..close] {
+                "" => ParamType::Array(Box::new(kind)),
+                size => ParamType::FixedArray(
+                    Box::new(kind),
+                    size.parse()
+                        .map_err(|_| format_err!("Invalid array size in `{}`", param))?,
+                ),
+            };
+            rest = &suffix[close + 1..];
+        }
+        Ok(Some((kind, rest)))
+    }
+
     fn parse_type(&self, type_str: &str) -> Result<ParamType> {
         if let Ok(kind) = Reader::read(type_str) {
             Ok(kind)
@@ -366,6 +447,18 @@ impl AbiParser {
     }
 
     fn parse_param(&self, param: &str) -> Result<Param> {
+        if let Some((kind, rest)) = self.parse_tuple(param)? {
+            let name = rest
+                .split(is_whitespace)
+                .filter(|s| !s.is_empty() && *s != "memory" && *s != "calldata")
+                .last()
+                .unwrap_or_default();
+            return Ok(Param {
+                name: name.to_string(),
+                kind,
+            });
+        }
+
         let mut iter = param.trim().rsplitn(3, is_whitespace);
 
         let mut name = iter
@@ -444,6 +537,33 @@ pub(crate) fn parse_identifier(input: &mut &str) -> Result<String> {
     Ok(name)
 }
 
+/// Splits the params of a declaration on the commas which are not within a tuple
+fn split_params(s: &str) -> Vec<&str> {
+    let mut params = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                params.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    params.push(&s[start..]);
+    params
+}
+
+/// Returns whether the line declares the `receive` or `fallback` function, e.g.
+/// `receive() external payable`
+fn is_special_function(line: &str, name: &str) -> bool {
+    line.strip_prefix(name)
+        .map_or(false, |rest| rest.trim_start().starts_with('('))
+}
+
 fn detect_state_mutability(s: &str) -> StateMutability {
     if s.contains("pure") {
         StateMutability::Pure
@@ -682,6 +802,66 @@ mod tests {
         });
     }
 
+    #[test]
+    fn can_parse_inline_tuples() {
+        let parsed = AbiParser::default()
+            .parse_function(
+                "function fill((address,uint256)[] orders, tuple(uint8 v, bytes32 r) memory sig) returns (bool, (uint256,address))",
+            )
+            .unwrap();
+        assert_eq!(parsed.inputs[0].name, "orders");
+        assert_eq!(
+            parsed.inputs[0].kind,
+            ParamType::Array(Box::new(ParamType::Tuple(vec![
+                ParamType::Address,
+                ParamType::Uint(256)
+            ])))
+        );
+        assert_eq!(parsed.inputs[1].name, "sig");
+        assert_eq!(
+            parsed.inputs[1].kind,
+            ParamType::Tuple(vec![ParamType::Uint(8), ParamType::FixedBytes(32)])
+        );
+        assert_eq!(
+            parsed.outputs[1].kind,
+            ParamType::Tuple(vec![ParamType::Uint(256), ParamType::Address])
+        );
+
+        let event = AbiParser::default()
+            .parse_event("event Filled(tuple(address maker, uint amount)[2] indexed fills, uint x)")
+            .unwrap();
+        assert_eq!(
+            event.inputs[0],
+            EventParam {
+                name: "fills".to_string(),
+                kind: ParamType::FixedArray(
+                    Box::new(ParamType::Tuple(vec![
+                        ParamType::Address,
+                        ParamType::Uint(256)
+                    ])),
+                    2
+                ),
+                indexed: true,
+            }
+        );
+        assert_eq!(event.inputs[1].kind, ParamType::Uint(256));
+    }
+
+    #[test]
+    fn can_parse_special_functions_and_comments() {
+        let abi = parse(&[
+            "// an ERC20 token",
+            "function transfer(address to, uint amount) returns (bool)",
+            "receive() external payable",
+            "fallback()",
+        ])
+        .unwrap();
+        assert!(abi.receive);
+        assert!(abi.fallback);
+        assert_eq!(abi.functions.len(), 1);
+        assert!(parse(&["receiver()"]).is_err());
+    }
+
     #[test]
     fn can_read_backslashes() {
         parse(&[
@@ -1,6 +1,9 @@
 //! This module implements extensions to the [`ethabi`](https://docs.rs/ethabi) API.
 // Adapted from [Gnosis' ethcontract](https://github.com/gnosis/ethcontract-rs/blob/master/common/src/abiext.rs)
-use crate::{types::Selector, utils::id};
+use crate::{
+    types::{Selector, H256},
+    utils::{id, keccak256},
+};
 
 pub use ethabi::Contract as Abi;
 pub use ethabi::*;
@@ -65,10 +68,74 @@ impl EventExt for Event {
     }
 }
 
+/// Encodes a token as the topic of an indexed event parameter. Value types are ABI
+/// encoded as-is, while strings, bytes, arrays and tuples are replaced by the hash of
+/// their [in-place encoding](https://docs.soliditylang.org/en/latest/abi-spec.html#encoding-of-indexed-event-parameters).
+pub fn encode_topic(token: Token) -> H256 {
+    match token {
+        Token::String(_)
+        | Token::Bytes(_)
+        | Token::Array(_)
+        | Token::FixedArray(_)
+        | Token::Tuple(_) => {
+            let mut encoded = Vec::new();
+            encode_in_place(&token, false, &mut encoded);
+            keccak256(encoded).into()
+        }
+        token => H256::from_slice(&encode(&[token])),
+    }
+}
+
+/// Encodes a token without length prefixes, padding strings and bytes to 32 bytes only
+/// when they are nested in arrays or tuples
+fn encode_in_place(token: &Token, nested: bool, out: &mut Vec<u8>) {
+    let bytes = match token {
+        Token::String(s) => s.as_bytes(),
+        Token::Bytes(bytes) => bytes.as_slice(),
+        Token::Array(tokens) | Token::FixedArray(tokens) | Token::Tuple(tokens) => {
+            for token in tokens {
+                encode_in_place(token, true, out);
+            }
+            return;
+        }
+        token => {
+            out.extend(encode(&[token.clone()]));
+            return;
+        }
+    };
+    out.extend_from_slice(bytes);
+    if nested && bytes.len() % 32 != 0 {
+        out.resize(out.len() + 32 - bytes.len() % 32, 0);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn encode_topics() {
+        assert_eq!(
+            encode_topic(Token::Uint(1.into())),
+            H256::from_low_u64_be(1)
+        );
+        assert_eq!(
+            encode_topic(Token::String("hello".to_owned())),
+            H256::from(keccak256("hello"))
+        );
+
+        let mut nested = b"hi".to_vec();
+        nested.resize(32, 0);
+        nested.extend(H256::from_low_u64_be(2).as_bytes());
+        assert_eq!(
+            encode_topic(Token::Array(vec![
+                Token::String("hi".to_owned()),
+                Token::Uint(2.into())
+            ])),
+            H256::from(keccak256(nested))
+        );
+    }
+
     #[test]
     fn format_function_signature() {
         for (f, expected) in &[
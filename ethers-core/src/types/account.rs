@@ -0,0 +1,44 @@
+use crate::types::{H256, U256};
+use serde::{Deserialize, Serialize};
+
+/// The state of an account, as returned by Geth's `eth_getAccount`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Account {
+    /// The number of transactions sent by the account
+    pub nonce: U256,
+    /// The balance of the account
+    pub balance: U256,
+    /// The hash of the account's code
+    pub code_hash: H256,
+    /// The root of the account's storage trie, `None` if the node does not support
+    /// `eth_getAccount` and the account was queried with the portable endpoints
+    #[serde(rename = "root", default, skip_serializing_if = "Option::is_none")]
+    pub storage_root: Option<H256>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserializes_geth_account() {
+        let account: Account = serde_json::from_str(
+            r#"{
+                "balance": "0x6c6b935b8bbd400000",
+                "codeHash": "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+                "nonce": "0x1",
+                "root": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(account.nonce, 1.into());
+        assert_eq!(
+            account.code_hash,
+            "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+                .parse()
+                .unwrap()
+        );
+        assert!(account.storage_root.is_some());
+    }
+}
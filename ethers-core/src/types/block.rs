@@ -71,6 +71,13 @@ pub struct Block<TX> {
     /// Nonce
     #[cfg(not(feature = "celo"))]
     pub nonce: Option<U64>,
+    /// Base fee per unit of gas, present since the London hard fork (EIP-1559)
+    #[serde(
+        rename = "baseFeePerGas",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub base_fee_per_gas: Option<U256>,
 
     #[cfg(feature = "celo")]
     #[cfg_attr(docsrs, doc(cfg(feature = "celo")))]
@@ -108,10 +115,14 @@ pub struct EpochSnarkData {
 #[derive(Copy, Clone, Debug, PartialEq)]
 /// A Block Hash or Block Number
 pub enum BlockId {
-    // TODO: May want to expand this to include the requireCanonical field
-    // https://github.com/ethereum/EIPs/blob/master/EIPS/eip-1898.md
-    /// A block hash
+    /// A block hash. State queries at a hash which is not part of the canonical chain
+    /// (e.g. after a reorg) are still answered by nodes which kept the block's state.
     Hash(H256),
+    /// A block hash which must be part of the canonical chain, serialized with the
+    /// [EIP-1898](https://github.com/ethereum/EIPs/blob/master/EIPS/eip-1898.md)
+    /// `requireCanonical` flag. State queries fail if the block was reorged out, which
+    /// keeps a series of reads at the same block consistent.
+    CanonicalHash(H256),
     /// A block number
     Number(BlockNumber),
 }
@@ -140,6 +151,16 @@ impl From<H256> for BlockId {
     }
 }
 
+impl BlockId {
+    /// Returns the block hash, if the block is identified by its hash
+    pub fn as_hash(&self) -> Option<H256> {
+        match *self {
+            BlockId::Hash(hash) | BlockId::CanonicalHash(hash) => Some(hash),
+            BlockId::Number(_) => None,
+        }
+    }
+}
+
 impl Serialize for BlockId {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -151,6 +172,12 @@ impl Serialize for BlockId {
                 s.serialize_field("blockHash", &format!("{:?}", x))?;
                 s.end()
             }
+            BlockId::CanonicalHash(ref x) => {
+                let mut s = serializer.serialize_struct("BlockIdEip1898", 2)?;
+                s.serialize_field("blockHash", &format!("{:?}", x))?;
+                s.serialize_field("requireCanonical", &true)?;
+                s.end()
+            }
             BlockId::Number(ref num) => num.serialize(serializer),
         }
     }
@@ -195,6 +222,23 @@ mod tests {
     use super::*;
     use crate::types::{Transaction, TxHash};
 
+    #[test]
+    fn serialize_block_id() {
+        let hash = H256::repeat_byte(1);
+        assert_eq!(
+            serde_json::to_value(BlockId::Hash(hash)).unwrap(),
+            serde_json::json!({ "blockHash": format!("{:?}", hash) })
+        );
+        assert_eq!(
+            serde_json::to_value(BlockId::CanonicalHash(hash)).unwrap(),
+            serde_json::json!({ "blockHash": format!("{:?}", hash), "requireCanonical": true })
+        );
+        assert_eq!(
+            serde_json::to_value(BlockId::from(16u64)).unwrap(),
+            serde_json::json!("0x10")
+        );
+    }
+
     #[test]
     fn deserialize_blk_no_txs() {
         let block = r#"{"number":"0x3","hash":"0xda53da08ef6a3cbde84c33e51c04f68c3853b6a3731f10baa2324968eee63972","parentHash":"0x689c70c080ca22bc0e681694fa803c1aba16a69c8b6368fed5311d279eb9de90","mixHash":"0x0000000000000000000000000000000000000000000000000000000000000000","nonce":"0x0000000000000000","sha3Uncles":"0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347","logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000","transactionsRoot":"0x7270c1c4440180f2bd5215809ee3d545df042b67329499e1ab97eb759d31610d","stateRoot":"0x29f32984517a7d25607da485b23cefabfd443751422ca7e603395e1de9bc8a4b","receiptsRoot":"0x056b23fbba480696b65fe5a59b8f2148a1299103c4f57df839233af2cf4ca2d2","miner":"0x0000000000000000000000000000000000000000","difficulty":"0x0","totalDifficulty":"0x0","extraData":"0x","size":"0x3e8","gasLimit":"0x6691b7","gasUsed":"0x5208","timestamp":"0x5ecedbb9","transactions":["0xc3c5f700243de37ae986082fd2af88d2a7c2752a0c0f7b9d6ac47c729d45e067"],"uncles":[]}"#;
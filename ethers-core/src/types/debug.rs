@@ -0,0 +1,66 @@
+use crate::types::{Block, Bytes, Transaction, H256};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A block rejected by the node, as returned by Geth's `debug_getBadBlocks`
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct BadBlock {
+    /// The hash of the block
+    pub hash: H256,
+    /// The decoded block
+    pub block: Block<Transaction>,
+    /// The RLP encoding of the block
+    pub rlp: Bytes,
+}
+
+/// A page of the storage of an account, as returned by Geth's `debug_storageRangeAt`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageRange {
+    /// The storage slots of the page, keyed by the hash of their key
+    pub storage: BTreeMap<H256, StorageEntry>,
+    /// The hashed key of the first slot of the next page, `None` if this is the last
+    /// page
+    pub next_key: Option<H256>,
+}
+
+/// A storage slot of a [`StorageRange`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct StorageEntry {
+    /// The key of the slot, `None` if the node does not know its preimage
+    pub key: Option<H256>,
+    /// The value of the slot
+    pub value: H256,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserializes_storage_range() {
+        let range: StorageRange = serde_json::from_str(
+            r#"{
+                "storage": {
+                    "0x290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e563": {
+                        "key": "0x0000000000000000000000000000000000000000000000000000000000000000",
+                        "value": "0x0000000000000000000000000000000000000000000000000000000000000001"
+                    },
+                    "0xb10e2d527612073b26eecdfd717e6a320cf44b4afac2b0732d9fcbe2b7fa0cf6": {
+                        "key": null,
+                        "value": "0x0000000000000000000000000000000000000000000000000000000000000002"
+                    }
+                },
+                "nextKey": null
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(range.storage.len(), 2);
+        assert_eq!(range.next_key, None);
+
+        let entries = range.storage.values().collect::<Vec<_>>();
+        assert_eq!(entries[0].key, Some(H256::zero()));
+        assert_eq!(entries[0].value, H256::from_low_u64_be(1));
+        assert_eq!(entries[1].key, None);
+    }
+}
@@ -0,0 +1,162 @@
+use crate::types::U256;
+use serde::{Deserialize, Serialize};
+
+/// The priority fee suggested when recent blocks paid no priority fees (1 gwei)
+const DEFAULT_PRIORITY_FEE: u64 = 1_000_000_000;
+
+/// The fee history of a range of blocks, as returned by `eth_feeHistory`
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeHistory {
+    /// The number of the oldest block of the range
+    pub oldest_block: U256,
+    /// The base fee per gas of each block, followed by the base fee of the next block
+    pub base_fee_per_gas: Vec<U256>,
+    /// The ratio of gas used to the gas limit of each block
+    pub gas_used_ratio: Vec<f64>,
+    /// The priority fees paid at the requested percentiles, for each block
+    #[serde(default)]
+    pub reward: Vec<Vec<U256>>,
+}
+
+impl FeeHistory {
+    /// Returns the number of the newest block of the range
+    pub fn newest_block(&self) -> Option<U256> {
+        let blocks = self.gas_used_ratio.len() as u64;
+        if blocks == 0 {
+            return None;
+        }
+        Some(self.oldest_block + blocks - 1)
+    }
+
+    /// Returns the base fee per gas of the block after the range, which the node
+    /// derives from the gas used by the newest block
+    pub fn next_base_fee(&self) -> Option<U256> {
+        match self.base_fee_per_gas.len() {
+            // the next base fee is only included after the base fees of the range
+            len if len > self.gas_used_ratio.len() => self.base_fee_per_gas.last().copied(),
+            _ => None,
+        }
+    }
+}
+
+/// How quickly a transaction should be included, used to suggest its fees
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Priority {
+    /// Cheap, for transactions which can wait
+    Slow,
+    /// The usual priority
+    Standard,
+    /// Above the usual priority
+    Fast,
+    /// For transactions which must be included as soon as possible
+    Urgent,
+}
+
+impl Priority {
+    /// The percentile of the priority fees paid in recent blocks which is suggested
+    pub fn reward_percentile(&self) -> f64 {
+        match self {
+            Priority::Slow => 10.0,
+            Priority::Standard => 25.0,
+            Priority::Fast => 50.0,
+            Priority::Urgent => 75.0,
+        }
+    }
+
+    /// The number of consecutive full blocks the suggested max fee survives. The base fee
+    /// rises by at most 12.5% per block, so the transaction stays includable for at least
+    /// these many blocks.
+    pub fn headroom_blocks(&self) -> u32 {
+        match self {
+            Priority::Slow => 2,
+            Priority::Standard => 4,
+            Priority::Fast => 6,
+            Priority::Urgent => 8,
+        }
+    }
+}
+
+/// Suggested transaction fees
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeeSuggestion {
+    /// A gas price, for chains which do not support EIP-1559
+    Legacy { gas_price: U256 },
+    /// A max fee and max priority fee per gas, for chains supporting EIP-1559
+    Eip1559 {
+        max_fee_per_gas: U256,
+        max_priority_fee_per_gas: U256,
+    },
+}
+
+impl FeeSuggestion {
+    /// Suggests EIP-1559 fees from the fee history of recent blocks, requested with the
+    /// priority's [`reward_percentile`](Priority::reward_percentile). The priority fee is
+    /// the median of the rewards at that percentile, and the max fee adds it to the next
+    /// block's base fee increased by the priority's headroom.
+    pub fn from_fee_history(history: &FeeHistory, priority: Priority) -> Self {
+        let mut rewards = history
+            .reward
+            .iter()
+            .filter_map(|rewards| rewards.first().copied())
+            .filter(|reward| !reward.is_zero())
+            .collect::<Vec<_>>();
+        rewards.sort();
+        let max_priority_fee_per_gas = match rewards.len() {
+            0 => U256::from(DEFAULT_PRIORITY_FEE),
+            len => rewards[len / 2],
+        };
+
+        let base_fee = history.base_fee_per_gas.last().copied().unwrap_or_default();
+        let blocks = priority.headroom_blocks();
+        let max_base_fee =
+            base_fee * U256::from(9).pow(blocks.into()) / U256::from(8).pow(blocks.into());
+
+        FeeSuggestion::Eip1559 {
+            max_fee_per_gas: max_base_fee + max_priority_fee_per_gas,
+            max_priority_fee_per_gas,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suggests_fees_from_history() {
+        let history: FeeHistory = serde_json::from_str(
+            r#"{
+                "oldestBlock": "0xc6e1d0",
+                "baseFeePerGas": ["0x3b9aca00", "0x3b9aca00", "0x3b9aca00", "0x4190ab00"],
+                "gasUsedRatio": [0.5, 0.0, 1.0],
+                "reward": [["0x77359400"], ["0x0"], ["0x3b9aca00"]]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(history.oldest_block, 13_033_936.into());
+        assert_eq!(history.newest_block(), Some(13_033_938.into()));
+        assert_eq!(history.next_base_fee(), Some(1_100_000_000.into()));
+        assert_eq!(FeeHistory::default().newest_block(), None);
+        assert_eq!(FeeHistory::default().next_base_fee(), None);
+
+        let suggestion = FeeSuggestion::from_fee_history(&history, Priority::Slow);
+        // the empty block is ignored, and the base fee of 1.1 gwei grows by 12.5% twice
+        assert_eq!(
+            suggestion,
+            FeeSuggestion::Eip1559 {
+                max_fee_per_gas: (1_392_187_500u64 + 2_000_000_000).into(),
+                max_priority_fee_per_gas: 2_000_000_000u64.into(),
+            }
+        );
+
+        let suggestion = FeeSuggestion::from_fee_history(&FeeHistory::default(), Priority::Fast);
+        assert_eq!(
+            suggestion,
+            FeeSuggestion::Eip1559 {
+                max_fee_per_gas: DEFAULT_PRIORITY_FEE.into(),
+                max_priority_fee_per_gas: DEFAULT_PRIORITY_FEE.into(),
+            }
+        );
+    }
+}
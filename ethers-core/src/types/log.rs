@@ -121,9 +121,7 @@ pub struct Filter {
     pub block_option: FilterBlockOption,
 
     /// Address
-    // TODO: The spec says that this can also be an array, do we really want to
-    // monitor for the same event for multiple contracts?
-    address: Option<Address>,
+    address: Option<ValueOrArray<Address>>,
 
     /// Topics
     // TODO: We could improve the low level API here by using ethabi's RawTopicFilter
@@ -205,11 +203,17 @@ impl Filter {
         self
     }
 
-    pub fn address<T: Into<Address>>(mut self, address: T) -> Self {
+    /// Sets the address (or addresses) of the contracts emitting the logs
+    pub fn address<T: Into<ValueOrArray<Address>>>(mut self, address: T) -> Self {
         self.address = Some(address.into());
         self
     }
 
+    /// Returns the address (or addresses) of the contracts emitting the logs, if set
+    pub fn get_address(&self) -> Option<&ValueOrArray<Address>> {
+        self.address.as_ref()
+    }
+
     /// given the event in string form, it hashes it and adds it to the topics to monitor
     pub fn event(self, event_name: &str) -> Self {
         let hash = H256::from(keccak256(event_name.as_bytes()));
@@ -244,6 +248,24 @@ impl Filter {
         self.limit = Some(limit);
         self
     }
+
+    /// Returns whether the log's address and topics match the filter. The block range
+    /// and limit are not checked.
+    pub fn matches(&self, log: &Log) -> bool {
+        if let Some(ref address) = self.address {
+            if !address.contains(&log.address) {
+                return false;
+            }
+        }
+
+        self.topics
+            .iter()
+            .enumerate()
+            .all(|(i, topic)| match topic {
+                Some(topic) => log.topics.get(i).map_or(false, |t| topic.contains(t)),
+                None => true,
+            })
+    }
 }
 
 /// Union type for representing a single value or a vector of values inside a filter
@@ -255,8 +277,36 @@ pub enum ValueOrArray<T> {
     Array(Vec<T>),
 }
 
+impl<T: PartialEq> ValueOrArray<T> {
+    /// Returns whether the value is, or the array contains, `value`
+    pub fn contains(&self, value: &T) -> bool {
+        match self {
+            ValueOrArray::Value(inner) => inner == value,
+            ValueOrArray::Array(inner) => inner.contains(value),
+        }
+    }
+}
+
 // TODO: Implement more common types - or adjust this to work with all Tokenizable items
 
+impl From<Address> for ValueOrArray<Address> {
+    fn from(src: Address) -> Self {
+        ValueOrArray::Value(src)
+    }
+}
+
+impl From<Vec<Address>> for ValueOrArray<Address> {
+    fn from(src: Vec<Address>) -> Self {
+        ValueOrArray::Array(src)
+    }
+}
+
+impl From<Vec<H256>> for ValueOrArray<H256> {
+    fn from(src: Vec<H256>) -> Self {
+        ValueOrArray::Array(src)
+    }
+}
+
 impl From<H256> for ValueOrArray<H256> {
     fn from(src: H256) -> Self {
         ValueOrArray::Value(src)
@@ -377,4 +427,38 @@ mod tests {
             json!({ "address" : addr, "topics": [t0, t1_padded, t2, t3_padded]})
         );
     }
+
+    #[test]
+    fn filter_matches_logs() {
+        let log = |address: Address, topics: Vec<H256>| Log {
+            address,
+            topics,
+            data: Default::default(),
+            block_hash: None,
+            block_number: None,
+            transaction_hash: None,
+            transaction_index: None,
+            log_index: None,
+            transaction_log_index: None,
+            log_type: None,
+            removed: None,
+        };
+        let (a, b) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let (t0, t1) = (H256::repeat_byte(3), H256::repeat_byte(4));
+
+        let filter = Filter::new().address(vec![a, b]).topic0(t0);
+        assert!(filter.matches(&log(a, vec![t0])));
+        assert!(filter.matches(&log(b, vec![t0, t1])));
+        assert!(!filter.matches(&log(Address::zero(), vec![t0])));
+        assert!(!filter.matches(&log(a, vec![t1])));
+        assert!(!filter.matches(&log(a, vec![])));
+
+        let filter = Filter::new().topic1(vec![t0, t1]);
+        assert!(filter.matches(&log(a, vec![t0, t1])));
+        assert!(!filter.matches(&log(a, vec![t0])));
+        assert_eq!(
+            serialize(&Filter::new().address(vec![a, b])),
+            json!({ "address": [a, b], "topics": [] })
+        );
+    }
 }
@@ -5,10 +5,15 @@ pub type Selector = [u8; 4];
 /// A transaction Hash
 pub use ethabi::ethereum_types::H256 as TxHash;
 
-pub use ethabi::ethereum_types::{Address, Bloom, H160, H256, U128, U256, U64};
+pub use ethabi::ethereum_types::{Address, Bloom, H160, H256, H64, U128, U256, U64};
 
 mod transaction;
-pub use transaction::{Transaction, TransactionReceipt, TransactionRequest};
+pub use transaction::{
+    AccessList, AccessListItem, AccessListWithGasUsed, Eip1559TransactionRequest,
+    Eip2930TransactionRequest, PrivateTransaction, PrivateTransactionPreferences,
+    SignedTransaction, Transaction, TransactionReceipt, TransactionRequest, TypedTransaction,
+    EIP1559_TX_TYPE, EIP2930_TX_TYPE,
+};
 
 mod address_or_bytes;
 pub use address_or_bytes::AddressOrBytes;
@@ -22,14 +27,32 @@ pub use i256::I256;
 mod bytes;
 pub use self::bytes::Bytes;
 
+mod account;
+pub use account::Account;
+
+mod proof;
+pub use proof::{EIP1186ProofResponse, StorageProof};
+
+mod state_override;
+pub use state_override::{AccountOverride, BlockOverrides, StateOverride};
+
+mod simulate;
+pub use simulate::{
+    SimulateError, SimulatePayload, SimulatedBlock, SimulatedBlockCalls, SimulatedCallResult,
+    SIMULATED_TRANSFER_ADDRESS,
+};
+
 mod block;
 pub use block::{Block, BlockId, BlockNumber};
 
 #[cfg(feature = "celo")]
 pub use block::Randomness;
 
+mod fee;
+pub use fee::{FeeHistory, FeeSuggestion, Priority};
+
 mod log;
-pub use log::{Filter, Log, ValueOrArray};
+pub use log::{Filter, FilterBlockOption, Log, ValueOrArray};
 
 mod ens;
 pub use ens::NameOrAddress;
@@ -40,5 +63,17 @@ pub use signature::*;
 mod txpool;
 pub use txpool::*;
 
+mod syncing;
+pub use syncing::{SyncProgress, SyncingStatus};
+
+mod work;
+pub use work::Work;
+
+mod debug;
+pub use debug::{BadBlock, StorageEntry, StorageRange};
+
 mod trace;
 pub use trace::*;
+
+mod user_operation;
+pub use user_operation::*;
@@ -0,0 +1,193 @@
+use crate::{
+    types::{Address, Bytes, H256, U256, U64},
+    utils::{verify_proof_value, ProofError, EMPTY_ROOT_HASH, KECCAK_EMPTY},
+};
+use rlp::RlpStream;
+use serde::{Deserialize, Serialize};
+
+/// The Merkle proof of a storage slot, as returned by `eth_getProof`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct StorageProof {
+    /// The storage slot
+    pub key: U256,
+    /// The value of the slot
+    pub value: U256,
+    /// The nodes of the storage trie on the path to the slot, from the root
+    pub proof: Vec<Bytes>,
+}
+
+/// An account and some of its storage with their Merkle proofs, as returned by
+/// `eth_getProof` ([EIP-1186](https://eips.ethereum.org/EIPS/eip-1186))
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EIP1186ProofResponse {
+    /// The address of the account
+    pub address: Address,
+    /// The balance of the account
+    pub balance: U256,
+    /// The hash of the account's code
+    pub code_hash: H256,
+    /// The nonce of the account
+    pub nonce: U64,
+    /// The root of the account's storage trie
+    pub storage_hash: H256,
+    /// The nodes of the state trie on the path to the account, from the root
+    pub account_proof: Vec<Bytes>,
+    /// The proofs of the requested storage slots
+    pub storage_proof: Vec<StorageProof>,
+}
+
+impl EIP1186ProofResponse {
+    /// Returns whether the account does not exist, in which case the proof shows its
+    /// absence from the state trie. Nodes report the code and storage hashes of missing
+    /// accounts either as zero or as the hashes of empty code and storage.
+    pub fn is_empty(&self) -> bool {
+        self.nonce.is_zero()
+            && self.balance.is_zero()
+            && (self.code_hash.is_zero() || self.code_hash == KECCAK_EMPTY)
+            && (self.storage_hash.is_zero() || self.storage_hash == EMPTY_ROOT_HASH)
+    }
+
+    /// Returns the RLP encoding of the account, as stored in the state trie
+    pub fn account_rlp(&self) -> Bytes {
+        let mut rlp = RlpStream::new_list(4);
+        rlp.append(&self.nonce);
+        rlp.append(&self.balance);
+        rlp.append(&self.storage_hash);
+        rlp.append(&self.code_hash);
+        rlp.out().freeze().into()
+    }
+
+    /// Verifies the account proof against the state root of the block the proof was
+    /// requested at
+    pub fn verify_account(&self, state_root: H256) -> Result<(), ProofError> {
+        let account = if self.is_empty() {
+            None
+        } else {
+            Some(self.account_rlp())
+        };
+        verify_proof_value(
+            state_root,
+            self.address.as_bytes(),
+            account.as_ref().map(AsRef::as_ref),
+            &self.account_proof,
+        )
+    }
+
+    /// Verifies the proofs of the storage slots against the storage root of the account
+    pub fn verify_storage(&self) -> Result<(), ProofError> {
+        let storage_root = if self.storage_hash.is_zero() {
+            EMPTY_ROOT_HASH
+        } else {
+            self.storage_hash
+        };
+        self.storage_proof
+            .iter()
+            .try_for_each(|slot| slot.verify(storage_root))
+    }
+
+    /// Verifies the account and storage proofs against the state root of the block the
+    /// proof was requested at
+    pub fn verify(&self, state_root: H256) -> Result<(), ProofError> {
+        self.verify_account(state_root)?;
+        self.verify_storage()
+    }
+}
+
+impl StorageProof {
+    /// Verifies the proof against the storage root of the account. Empty slots are
+    /// proven by their absence from the storage trie.
+    pub fn verify(&self, storage_root: H256) -> Result<(), ProofError> {
+        let mut key = [0; 32];
+        self.key.to_big_endian(&mut key);
+        let value = if self.value.is_zero() {
+            None
+        } else {
+            Some(rlp::encode(&self.value))
+        };
+        verify_proof_value(
+            storage_root,
+            &key,
+            value.as_ref().map(AsRef::as_ref),
+            &self.proof,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::trie::tests::two_leaf_trie;
+
+    fn account(address: Address, balance: u64) -> EIP1186ProofResponse {
+        EIP1186ProofResponse {
+            address,
+            balance: balance.into(),
+            code_hash: KECCAK_EMPTY,
+            nonce: 1.into(),
+            storage_hash: EMPTY_ROOT_HASH,
+            account_proof: Vec::new(),
+            storage_proof: vec![StorageProof {
+                key: 1.into(),
+                value: U256::zero(),
+                proof: Vec::new(),
+            }],
+        }
+    }
+
+    #[test]
+    fn verifies_account_proofs() {
+        let a = account(Address::from_low_u64_be(1), 100);
+        let b = account(Address::from_low_u64_be(2), 200);
+        let (state_root, proof_a, proof_b) = two_leaf_trie(
+            (a.address.as_bytes(), a.account_rlp().as_ref()),
+            (b.address.as_bytes(), b.account_rlp().as_ref()),
+        );
+
+        let a = EIP1186ProofResponse {
+            account_proof: proof_a.into_iter().map(Into::into).collect(),
+            ..a
+        };
+        a.verify(state_root).unwrap();
+
+        let b = EIP1186ProofResponse {
+            account_proof: proof_b.into_iter().map(Into::into).collect(),
+            ..b
+        };
+        b.verify(state_root).unwrap();
+
+        // a tampered balance does not match the proof
+        let tampered = EIP1186ProofResponse {
+            balance: 1000.into(),
+            ..b
+        };
+        assert_eq!(tampered.verify(state_root), Err(ProofError::ValueMismatch));
+    }
+
+    #[test]
+    fn deserializes_proofs() {
+        let proof: EIP1186ProofResponse = serde_json::from_str(
+            r#"{
+                "address": "0x7f0d15c7faae65896648c8273b6d7e43f58fa842",
+                "accountProof": ["0xf90211a0", "0xf90211a1"],
+                "balance": "0x0",
+                "codeHash": "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+                "nonce": "0x0",
+                "storageHash": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+                "storageProof": [
+                    {
+                        "key": "0x0",
+                        "proof": [],
+                        "value": "0x0"
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert!(proof.is_empty());
+        assert_eq!(proof.account_proof.len(), 2);
+        assert_eq!(proof.storage_proof[0].key, U256::zero());
+        // the storage trie of a missing account is empty
+        proof.verify_storage().unwrap();
+    }
+}
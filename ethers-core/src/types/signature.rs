@@ -1,6 +1,6 @@
 // Code adapted from: https://github.com/tomusdrw/rust-web3/blob/master/src/api/accounts.rs
 use crate::{
-    types::{Address, H256},
+    types::{Address, H256, U256},
     utils::hash_message,
 };
 
@@ -17,6 +17,12 @@ use k256::ecdsa::{
 };
 use k256::EncodedPoint as K256PublicKey;
 
+/// The order of the secp256k1 curve
+const SECP256K1_N: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe,
+    0xba, 0xae, 0xdc, 0xe6, 0xaf, 0x48, 0xa0, 0x3b, 0xbf, 0xd2, 0x5e, 0x8c, 0xd0, 0x36, 0x41, 0x41,
+];
+
 /// An error involving a signature.
 #[derive(Debug, Error)]
 pub enum SignatureError {
@@ -137,6 +143,48 @@ impl Signature {
     pub fn to_vec(&self) -> Vec<u8> {
         self.into()
     }
+
+    /// Returns the y-parity of the signature, i.e. its recovery id, whether its `v` is in
+    /// 'Electrum' notation, an EIP-155 `v` or already the y-parity (typed transactions)
+    pub fn y_parity(&self) -> Result<u8, SignatureError> {
+        Ok(self.recovery_id()?.into())
+    }
+
+    /// Returns the chain id encoded in the `v` of signatures with EIP-155 replay
+    /// protection, if any
+    pub fn chain_id(&self) -> Option<u64> {
+        if self.v >= 35 {
+            Some((self.v - 35) / 2)
+        } else {
+            None
+        }
+    }
+
+    /// Returns whether the `s` of the signature is in the lower half of the curve order.
+    /// For each signature, the one with the complementary `s` is equally valid, but only
+    /// the low one is accepted by Ethereum since EIP-2.
+    pub fn is_low_s(&self) -> bool {
+        U256::from_big_endian(self.s.as_bytes()) <= U256::from_big_endian(&SECP256K1_N) / 2
+    }
+
+    /// Normalizes a malleable signature with a high `s` into its equivalent one with a low
+    /// `s`, flipping its y-parity. Returns whether the signature was changed.
+    pub fn normalize_s(&mut self) -> bool {
+        if self.is_low_s() {
+            return false;
+        }
+
+        let s = U256::from_big_endian(&SECP256K1_N) - U256::from_big_endian(self.s.as_bytes());
+        s.to_big_endian(self.s.as_bytes_mut());
+        self.v = match self.v {
+            0 | 1 => self.v ^ 1,
+            27 | 28 => 55 - self.v,
+            v if v >= 35 && (v - 35) % 2 == 0 => v + 1,
+            v if v >= 35 => v - 1,
+            v => v,
+        };
+        true
+    }
 }
 
 fn normalize_recovery_id(v: u64) -> u8 {
@@ -262,6 +310,56 @@ mod tests {
         );
     }
 
+    #[test]
+    fn normalizes_high_s() {
+        let signature = Signature::from_str(
+            "b91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a0291c"
+        ).unwrap();
+        assert!(signature.is_low_s());
+
+        // the malleated signature has the complementary `s` and the opposite y-parity
+        let mut malleated = Signature {
+            s: "9ff818b327d1fc847ffe79bdd03d25e83e3a5df66962ceb160751b8bd754a118"
+                .parse()
+                .unwrap(),
+            v: 27,
+            ..signature.clone()
+        };
+        assert!(!malleated.is_low_s());
+        assert!(malleated.normalize_s());
+        assert_eq!(malleated, signature);
+        assert!(!malleated.normalize_s());
+
+        // the y-parity of EIP-155 signatures keeps their chain id
+        let mut malleated = Signature { v: 37, ..malleated };
+        malleated.s = "9ff818b327d1fc847ffe79bdd03d25e83e3a5df66962ceb160751b8bd754a118"
+            .parse()
+            .unwrap();
+        assert!(malleated.normalize_s());
+        assert_eq!(malleated.v, 38);
+        assert_eq!(malleated.chain_id(), Some(1));
+    }
+
+    #[test]
+    fn eip155_v() {
+        let mut signature = Signature {
+            r: H256::repeat_byte(1),
+            s: H256::repeat_byte(2),
+            v: 28,
+        };
+        assert_eq!(signature.y_parity().unwrap(), 1);
+        assert_eq!(signature.chain_id(), None);
+
+        signature.v = 1;
+        assert_eq!(signature.y_parity().unwrap(), 1);
+        assert_eq!(signature.chain_id(), None);
+
+        // v = chain_id * 2 + 35 + y_parity
+        signature.v = 2 * 137 + 35;
+        assert_eq!(signature.y_parity().unwrap(), 0);
+        assert_eq!(signature.chain_id(), Some(137));
+    }
+
     #[test]
     fn signature_from_str() {
         let s1 = Signature::from_str(
@@ -0,0 +1,212 @@
+use crate::types::{
+    Address, Block, BlockOverrides, Bytes, Log, StateOverride, TransactionRequest, TxHash, H160,
+    U64,
+};
+use serde::{Deserialize, Serialize};
+
+/// The address which the ether transfers traced by `eth_simulateV1` are reported as
+/// logs from, when [`SimulatePayload::trace_transfers`] is enabled
+pub const SIMULATED_TRANSFER_ADDRESS: Address = H160([0xee; 20]);
+
+/// A sequence of blocks of calls to simulate with `eth_simulateV1`. Each block is
+/// executed on top of the state left by the previous one.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatePayload {
+    /// The blocks to simulate, in order
+    pub block_state_calls: Vec<SimulatedBlockCalls>,
+    /// Whether to report the ether transfers of the calls as ERC-20 `Transfer` logs
+    /// emitted by [`SIMULATED_TRANSFER_ADDRESS`]
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub trace_transfers: bool,
+    /// Whether to validate the calls like actual transactions (nonces, balances and
+    /// base fee) instead of executing them like `eth_call`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub validation: bool,
+}
+
+impl SimulatePayload {
+    /// Appends a block of calls to simulate
+    pub fn block(mut self, block: SimulatedBlockCalls) -> Self {
+        self.block_state_calls.push(block);
+        self
+    }
+
+    /// Sets the `trace_transfers` field to the provided value
+    pub fn trace_transfers(mut self, trace_transfers: bool) -> Self {
+        self.trace_transfers = trace_transfers;
+        self
+    }
+
+    /// Sets the `validation` field to the provided value
+    pub fn validation(mut self, validation: bool) -> Self {
+        self.validation = validation;
+        self
+    }
+}
+
+/// The calls of a simulated block, along with the overrides to apply before executing
+/// them
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedBlockCalls {
+    /// The fields of the block context to override
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_overrides: Option<BlockOverrides>,
+    /// The accounts to override before executing the calls
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<StateOverride>,
+    /// The calls to execute, in order
+    #[serde(default)]
+    pub calls: Vec<TransactionRequest>,
+}
+
+impl SimulatedBlockCalls {
+    /// Appends a call to the block
+    pub fn call<T: Into<TransactionRequest>>(mut self, call: T) -> Self {
+        self.calls.push(call.into());
+        self
+    }
+
+    /// Sets the `block_overrides` field to the provided value
+    pub fn block_overrides(mut self, block_overrides: BlockOverrides) -> Self {
+        self.block_overrides = Some(block_overrides);
+        self
+    }
+
+    /// Sets the `state_overrides` field to the provided value
+    pub fn state_overrides(mut self, state_overrides: StateOverride) -> Self {
+        self.state_overrides = Some(state_overrides);
+        self
+    }
+}
+
+/// A block produced by `eth_simulateV1`, along with the results of its calls
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SimulatedBlock {
+    /// The simulated block, whose transactions are given by their hashes
+    #[serde(flatten)]
+    pub block: Block<TxHash>,
+    /// The results of the block's calls, in order
+    pub calls: Vec<SimulatedCallResult>,
+}
+
+/// The result of a call simulated by `eth_simulateV1`
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedCallResult {
+    /// The data returned by the call, or its revert data if it failed
+    pub return_data: Bytes,
+    /// The logs emitted by the call, including the traced ether transfers
+    #[serde(default)]
+    pub logs: Vec<Log>,
+    /// The gas used by the call
+    pub gas_used: U64,
+    /// 1 if the call succeeded, 0 if it failed
+    pub status: U64,
+    /// The reason the call failed, if it did
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<SimulateError>,
+}
+
+impl SimulatedCallResult {
+    /// Returns whether the call succeeded
+    pub fn is_success(&self) -> bool {
+        self.status == U64::one()
+    }
+
+    /// Returns the traced ether transfers emitted by the call, when
+    /// [`SimulatePayload::trace_transfers`] is enabled
+    pub fn transfers(&self) -> impl Iterator<Item = &Log> {
+        self.logs
+            .iter()
+            .filter(|log| log.address == SIMULATED_TRANSFER_ADDRESS)
+    }
+}
+
+/// The reason a call simulated by `eth_simulateV1` failed
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SimulateError {
+    /// The error code
+    pub code: i64,
+    /// The error message
+    pub message: String,
+    /// The revert data of the call, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{AccountOverride, U256};
+
+    #[test]
+    fn serde_simulate_payload() {
+        let mut state = StateOverride::new();
+        state.insert(Address::zero(), AccountOverride::default().balance(1));
+        let payload = SimulatePayload::default()
+            .block(
+                SimulatedBlockCalls::default()
+                    .state_overrides(state)
+                    .call(TransactionRequest::new().to(Address::zero()).value(1)),
+            )
+            .trace_transfers(true);
+        assert_eq!(
+            serde_json::to_value(&payload).unwrap(),
+            serde_json::json!({
+                "blockStateCalls": [{
+                    "stateOverrides": {
+                        "0x0000000000000000000000000000000000000000": { "balance": "0x1" }
+                    },
+                    "calls": [{
+                        "to": "0x0000000000000000000000000000000000000000",
+                        "value": "0x1"
+                    }]
+                }],
+                "traceTransfers": true
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_simulated_block() {
+        let block: SimulatedBlock = serde_json::from_value(serde_json::json!({
+            "number": "0x10",
+            "hash": "0x0101010101010101010101010101010101010101010101010101010101010101",
+            "logsBloom": null,
+            "totalDifficulty": null,
+            "size": "0x2a0",
+            "mixHash": null,
+            "nonce": null,
+            "transactions": [],
+            "calls": [{
+                "returnData": "0x",
+                "logs": [{
+                    "address": "0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee",
+                    "topics": [],
+                    "data": "0x0000000000000000000000000000000000000000000000000000000000000001"
+                }],
+                "gasUsed": "0x5208",
+                "status": "0x1"
+            }, {
+                "returnData": "0x",
+                "logs": [],
+                "gasUsed": "0x0",
+                "status": "0x0",
+                "error": { "code": -32015, "message": "execution reverted" }
+            }]
+        }))
+        .unwrap();
+        assert_eq!(block.block.number, Some(16.into()));
+        assert!(block.calls[0].is_success());
+        assert_eq!(block.calls[0].transfers().count(), 1);
+        assert_eq!(block.calls[0].gas_used, 21_000.into());
+        assert!(!block.calls[1].is_success());
+        assert_eq!(block.calls[1].error.as_ref().unwrap().code, -32015);
+        assert_eq!(
+            U256::from(1),
+            U256::from_big_endian(block.calls[0].logs[0].data.as_ref())
+        );
+    }
+}
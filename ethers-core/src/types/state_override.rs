@@ -0,0 +1,169 @@
+use crate::types::{Address, Bytes, H256, U256, U64};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A set of account overrides applied on top of the state a call is simulated
+/// against, as accepted by Geth's `eth_call` and `debug_traceCall`
+pub type StateOverride = BTreeMap<Address, AccountOverride>;
+
+/// The fields of an account to override while simulating a call. Fields left empty
+/// keep their current value.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountOverride {
+    /// The balance of the account
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<U256>,
+    /// The nonce of the account
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<U64>,
+    /// The code of the account
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    /// Replaces the whole storage of the account
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<BTreeMap<H256, H256>>,
+    /// Replaces the given storage slots of the account, keeping the other ones
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_diff: Option<BTreeMap<H256, H256>>,
+}
+
+impl AccountOverride {
+    /// Sets the `balance` field to the provided value
+    pub fn balance<T: Into<U256>>(mut self, balance: T) -> Self {
+        self.balance = Some(balance.into());
+        self
+    }
+
+    /// Sets the `nonce` field to the provided value
+    pub fn nonce<T: Into<U64>>(mut self, nonce: T) -> Self {
+        self.nonce = Some(nonce.into());
+        self
+    }
+
+    /// Sets the `code` field to the provided value
+    pub fn code<T: Into<Bytes>>(mut self, code: T) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+
+    /// Replaces the whole storage of the account with the provided slots
+    pub fn state(mut self, state: BTreeMap<H256, H256>) -> Self {
+        self.state = Some(state);
+        self
+    }
+
+    /// Overrides a single storage slot of the account, keeping the other ones
+    pub fn state_diff(mut self, slot: H256, value: H256) -> Self {
+        self.state_diff
+            .get_or_insert_with(BTreeMap::new)
+            .insert(slot, value);
+        self
+    }
+}
+
+/// The fields of the block context to override while simulating a call, as accepted
+/// by Geth's `eth_call` and `debug_traceCall`. Fields left empty keep the value of
+/// the block the call is executed at.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockOverrides {
+    /// The block number
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub number: Option<U64>,
+    /// The block difficulty
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub difficulty: Option<U256>,
+    /// The block timestamp
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time: Option<U64>,
+    /// The block gas limit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<U64>,
+    /// The address receiving the block's fees
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coinbase: Option<Address>,
+    /// The block's randomness (`PREVRANDAO`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub random: Option<H256>,
+    /// The block base fee
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_fee: Option<U256>,
+}
+
+impl BlockOverrides {
+    /// Sets the `number` field to the provided value
+    pub fn number<T: Into<U64>>(mut self, number: T) -> Self {
+        self.number = Some(number.into());
+        self
+    }
+
+    /// Sets the `difficulty` field to the provided value
+    pub fn difficulty<T: Into<U256>>(mut self, difficulty: T) -> Self {
+        self.difficulty = Some(difficulty.into());
+        self
+    }
+
+    /// Sets the `time` field to the provided value
+    pub fn time<T: Into<U64>>(mut self, time: T) -> Self {
+        self.time = Some(time.into());
+        self
+    }
+
+    /// Sets the `gas_limit` field to the provided value
+    pub fn gas_limit<T: Into<U64>>(mut self, gas_limit: T) -> Self {
+        self.gas_limit = Some(gas_limit.into());
+        self
+    }
+
+    /// Sets the `coinbase` field to the provided value
+    pub fn coinbase<T: Into<Address>>(mut self, coinbase: T) -> Self {
+        self.coinbase = Some(coinbase.into());
+        self
+    }
+
+    /// Sets the `random` field to the provided value
+    pub fn random<T: Into<H256>>(mut self, random: T) -> Self {
+        self.random = Some(random.into());
+        self
+    }
+
+    /// Sets the `base_fee` field to the provided value
+    pub fn base_fee<T: Into<U256>>(mut self, base_fee: T) -> Self {
+        self.base_fee = Some(base_fee.into());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_state_override() {
+        let mut overrides = StateOverride::new();
+        overrides.insert(
+            Address::from_low_u64_be(1),
+            AccountOverride::default()
+                .balance(16)
+                .nonce(1u64)
+                .state_diff(H256::zero(), H256::from_low_u64_be(2)),
+        );
+        assert_eq!(
+            serde_json::to_string(&overrides).unwrap(),
+            r#"{"0x0000000000000000000000000000000000000001":{"balance":"0x10","nonce":"0x1","stateDiff":{"0x0000000000000000000000000000000000000000000000000000000000000000":"0x0000000000000000000000000000000000000000000000000000000000000002"}}}"#
+        );
+    }
+
+    #[test]
+    fn serde_block_overrides() {
+        let overrides = BlockOverrides::default()
+            .time(1_700_000_000u64)
+            .base_fee(7)
+            .coinbase(Address::zero());
+        assert_eq!(
+            serde_json::to_string(&overrides).unwrap(),
+            r#"{"time":"0x6553f100","coinbase":"0x0000000000000000000000000000000000000000","baseFee":"0x7"}"#
+        );
+    }
+}
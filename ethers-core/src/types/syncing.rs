@@ -0,0 +1,141 @@
+use crate::types::U64;
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+/// The sync status of a node, as returned by `eth_syncing`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncingStatus {
+    /// The node is not syncing
+    IsFalse,
+    /// The node is syncing
+    IsSyncing(Box<SyncProgress>),
+}
+
+impl SyncingStatus {
+    /// Returns true if the node is still syncing
+    pub fn is_syncing(&self) -> bool {
+        matches!(self, SyncingStatus::IsSyncing(_))
+    }
+}
+
+impl Serialize for SyncingStatus {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            SyncingStatus::IsFalse => serializer.serialize_bool(false),
+            SyncingStatus::IsSyncing(progress) => progress.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for SyncingStatus {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Status {
+            Bool(bool),
+            Progress(Box<SyncProgress>),
+        }
+
+        match Status::deserialize(deserializer)? {
+            Status::Bool(false) => Ok(SyncingStatus::IsFalse),
+            Status::Bool(true) => Err(D::Error::custom("expected `false` or a sync progress")),
+            Status::Progress(progress) => Ok(SyncingStatus::IsSyncing(progress)),
+        }
+    }
+}
+
+/// The progress of a syncing node. The fields after the block numbers are only
+/// returned by Geth.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncProgress {
+    /// The block at which the sync started
+    pub starting_block: U64,
+    /// The latest synced block
+    pub current_block: U64,
+    /// The highest block known to the node
+    pub highest_block: U64,
+
+    /// The number of state entries downloaded by the fast sync
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pulled_states: Option<U64>,
+    /// The number of state entries known to be left to download by the fast sync
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub known_states: Option<U64>,
+    /// The number of accounts downloaded by the snap sync
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub synced_accounts: Option<U64>,
+    /// The number of bytes of accounts downloaded by the snap sync
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub synced_account_bytes: Option<U64>,
+    /// The number of bytecodes downloaded by the snap sync
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub synced_bytecodes: Option<U64>,
+    /// The number of bytes of bytecodes downloaded by the snap sync
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub synced_bytecode_bytes: Option<U64>,
+    /// The number of storage slots downloaded by the snap sync
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub synced_storage: Option<U64>,
+    /// The number of bytes of storage slots downloaded by the snap sync
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub synced_storage_bytes: Option<U64>,
+    /// The number of state trie nodes healed by the snap sync
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub healed_trienodes: Option<U64>,
+    /// The number of bytes of state trie nodes healed by the snap sync
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub healed_trienode_bytes: Option<U64>,
+    /// The number of bytecodes healed by the snap sync
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub healed_bytecodes: Option<U64>,
+    /// The number of bytes of bytecodes healed by the snap sync
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub healed_bytecode_bytes: Option<U64>,
+    /// The number of state trie nodes pending healing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub healing_trienodes: Option<U64>,
+    /// The number of bytecodes pending healing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub healing_bytecode: Option<U64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_syncing_status() {
+        let status: SyncingStatus = serde_json::from_str("false").unwrap();
+        assert_eq!(status, SyncingStatus::IsFalse);
+        assert!(!status.is_syncing());
+        assert_eq!(serde_json::to_string(&status).unwrap(), "false");
+        assert!(serde_json::from_str::<SyncingStatus>("true").is_err());
+
+        let status: SyncingStatus = serde_json::from_str(
+            r#"{
+                "startingBlock": "0x0",
+                "currentBlock": "0x10",
+                "highestBlock": "0x20",
+                "syncedAccounts": "0x5",
+                "healingTrienodes": "0x0"
+            }"#,
+        )
+        .unwrap();
+        match status {
+            SyncingStatus::IsSyncing(ref progress) => {
+                assert_eq!(progress.current_block, 16.into());
+                assert_eq!(progress.highest_block, 32.into());
+                assert_eq!(progress.synced_accounts, Some(5.into()));
+                assert_eq!(progress.known_states, None);
+            }
+            _ => panic!("expected the node to be syncing"),
+        }
+        assert!(status.is_syncing());
+    }
+}
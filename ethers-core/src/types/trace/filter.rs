@@ -223,6 +223,23 @@ pub struct Create {
     pub gas: U256,
     /// Initialization code
     pub init: Bytes,
+    /// The opcode which created the contract, if the node reports it
+    #[serde(
+        rename = "creationMethod",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub creation_method: Option<CreationMethod>,
+}
+
+/// The opcode of a contract creation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CreationMethod {
+    /// Create
+    Create,
+    /// Create2
+    Create2,
 }
 
 /// Suicide
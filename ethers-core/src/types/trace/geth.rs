@@ -0,0 +1,772 @@
+//! Types for Geth's `debug_traceTransaction` options and tracer results, along with
+//! conversions of the `callTracer` frames from and to the Parity-style
+//! [`TransactionTrace`](crate::types::TransactionTrace)
+use super::{
+    Action, ActionType, Call, CallResult, CallType, Create, CreateResult, CreationMethod, Res,
+    Suicide, TransactionTrace,
+};
+use crate::types::{Address, BlockOverrides, Bytes, StateOverride, H256, U256};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Options for the Geth `debug_trace*` methods. Leaving the tracer empty uses the
+/// default struct logger, which returns a [`DefaultFrame`].
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GethDebugTracingOptions {
+    /// Disables the storage capture of the struct logger
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disable_storage: Option<bool>,
+    /// Disables the stack capture of the struct logger
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disable_stack: Option<bool>,
+    /// Enables the memory capture of the struct logger
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enable_memory: Option<bool>,
+    /// Enables the return data capture of the struct logger
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enable_return_data: Option<bool>,
+    /// The tracer to run instead of the struct logger
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tracer: Option<GethDebugTracerType>,
+    /// The configuration of the built-in tracer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tracer_config: Option<GethDebugTracerConfig>,
+    /// The maximum duration of the tracing, e.g. "5s" (defaults to 5 seconds)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<String>,
+}
+
+impl GethDebugTracingOptions {
+    /// Sets the tracer to one of Geth's built-in tracers
+    pub fn tracer(mut self, tracer: GethDebugBuiltInTracerType) -> Self {
+        self.tracer = Some(GethDebugTracerType::BuiltInTracer(tracer));
+        self
+    }
+
+    /// Sets the tracer to a custom JavaScript tracer
+    pub fn js_tracer<T: Into<String>>(mut self, code: T) -> Self {
+        self.tracer = Some(GethDebugTracerType::JsTracer(code.into()));
+        self
+    }
+
+    /// Sets the configuration of the built-in tracer
+    pub fn tracer_config<T: Into<GethDebugTracerConfig>>(mut self, config: T) -> Self {
+        self.tracer_config = Some(config.into());
+        self
+    }
+
+    /// Sets the maximum duration of the tracing
+    pub fn timeout<T: Into<String>>(mut self, timeout: T) -> Self {
+        self.timeout = Some(timeout.into());
+        self
+    }
+}
+
+/// Options for Geth's `debug_traceCall`, which may simulate the call against a
+/// modified state
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GethDebugTracingCallOptions {
+    /// The tracing options
+    #[serde(flatten)]
+    pub tracing_options: GethDebugTracingOptions,
+    /// The account overrides applied before executing the call
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<StateOverride>,
+    /// The block context overrides applied before executing the call
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_overrides: Option<BlockOverrides>,
+}
+
+impl From<GethDebugTracingOptions> for GethDebugTracingCallOptions {
+    fn from(tracing_options: GethDebugTracingOptions) -> Self {
+        Self {
+            tracing_options,
+            state_overrides: None,
+            block_overrides: None,
+        }
+    }
+}
+
+impl GethDebugTracingCallOptions {
+    /// Sets the account overrides applied before executing the call
+    pub fn state_overrides(mut self, state_overrides: StateOverride) -> Self {
+        self.state_overrides = Some(state_overrides);
+        self
+    }
+
+    /// Sets the block context overrides applied before executing the call
+    pub fn block_overrides(mut self, block_overrides: BlockOverrides) -> Self {
+        self.block_overrides = Some(block_overrides);
+        self
+    }
+}
+
+/// The tracer run by the Geth `debug_trace*` methods
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum GethDebugTracerType {
+    /// One of Geth's built-in tracers
+    BuiltInTracer(GethDebugBuiltInTracerType),
+    /// The source code of a custom JavaScript tracer
+    JsTracer(String),
+}
+
+/// Geth's built-in tracers with a typed result
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum GethDebugBuiltInTracerType {
+    /// Returns the call frames of the transaction as a [`CallFrame`]
+    #[serde(rename = "callTracer")]
+    CallTracer,
+    /// Returns the accounts touched by the transaction as a [`PreStateFrame`]
+    #[serde(rename = "prestateTracer")]
+    PreStateTracer,
+}
+
+/// The configuration of a built-in tracer
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum GethDebugTracerConfig {
+    /// Configuration of the `callTracer`
+    CallTracer(CallConfig),
+    /// Configuration of the `prestateTracer`
+    PreStateTracer(PreStateConfig),
+}
+
+/// Configuration of the `callTracer`
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct CallConfig {
+    /// Only traces the top-level call, without its sub-calls
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub only_top_call: Option<bool>,
+    /// Includes the logs emitted by the calls in [`CallFrame::logs`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub with_log: Option<bool>,
+}
+
+impl From<CallConfig> for GethDebugTracerConfig {
+    fn from(src: CallConfig) -> Self {
+        GethDebugTracerConfig::CallTracer(src)
+    }
+}
+
+/// Configuration of the `prestateTracer`
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct PreStateConfig {
+    /// Returns the state before and after the transaction instead of only the former
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diff_mode: Option<bool>,
+}
+
+impl From<PreStateConfig> for GethDebugTracerConfig {
+    fn from(src: PreStateConfig) -> Self {
+        GethDebugTracerConfig::PreStateTracer(src)
+    }
+}
+
+/// The result of the Geth `debug_trace*` methods, which depends on the selected tracer
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum GethTrace {
+    /// The result of the default struct logger
+    Default(DefaultFrame),
+    /// The result of the `callTracer`
+    CallTracer(CallFrame),
+    /// The result of the `prestateTracer`
+    PreStateTracer(PreStateFrame),
+    /// The result of a custom JavaScript tracer
+    Unknown(serde_json::Value),
+}
+
+/// The result of the default struct logger
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DefaultFrame {
+    /// Whether the transaction failed
+    pub failed: bool,
+    /// The gas used by the transaction
+    pub gas: u64,
+    /// The hex encoded return value of the transaction, without the 0x prefix
+    pub return_value: String,
+    /// The executed opcodes
+    pub struct_logs: Vec<StructLog>,
+}
+
+/// An opcode executed by the transaction, as logged by the default struct logger
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLog {
+    /// The program counter
+    pub pc: u64,
+    /// The name of the opcode
+    pub op: String,
+    /// The gas left before executing the opcode
+    pub gas: u64,
+    /// The gas cost of the opcode
+    pub gas_cost: u64,
+    /// The call depth
+    pub depth: u64,
+    /// The error of the opcode, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// The stack before executing the opcode (unless disabled)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stack: Option<Vec<U256>>,
+    /// The return data of the last call (if enabled)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub return_data: Option<Bytes>,
+    /// The memory as hex encoded 32 byte words (if enabled)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<Vec<String>>,
+    /// The storage slots accessed so far by the current contract (unless disabled)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<BTreeMap<H256, H256>>,
+    /// The gas refund counter
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refund: Option<u64>,
+}
+
+/// The result of the `prestateTracer`
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum PreStateFrame {
+    /// The state of the touched accounts before the transaction
+    Default(BTreeMap<Address, AccountState>),
+    /// The state of the modified accounts before and after the transaction, in diff mode
+    Diff(DiffMode),
+}
+
+/// The result of the `prestateTracer` in diff mode
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct DiffMode {
+    /// The state of the modified accounts before the transaction
+    pub pre: BTreeMap<Address, AccountState>,
+    /// The state of the modified accounts after the transaction
+    pub post: BTreeMap<Address, AccountState>,
+}
+
+/// The state of an account, as returned by the `prestateTracer`
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct AccountState {
+    /// The balance of the account
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<U256>,
+    /// The code of the account
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    /// The nonce of the account
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<u64>,
+    /// The storage slots of the account
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<BTreeMap<H256, H256>>,
+}
+
+/// The type of a call frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum CallFrameType {
+    Call,
+    StaticCall,
+    DelegateCall,
+    CallCode,
+    Create,
+    Create2,
+    SelfDestruct,
+}
+
+/// A nested call frame, as returned by Geth's `callTracer`
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct CallFrame {
+    /// The type of the call
+    #[serde(rename = "type")]
+    pub typ: CallFrameType,
+    /// Sender
+    pub from: Address,
+    /// Recipient, or the created contract's address
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<Address>,
+    /// Transferred value
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<U256>,
+    /// Gas provided to the call
+    #[serde(default)]
+    pub gas: U256,
+    /// Gas used by the call
+    #[serde(rename = "gasUsed", default)]
+    pub gas_used: U256,
+    /// Input data, or the initialization code for creations
+    #[serde(default)]
+    pub input: Bytes,
+    /// Output data, or the deployed code for creations
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<Bytes>,
+    /// Error, if the call failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// The sub-calls made by this call
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub calls: Vec<CallFrame>,
+    /// The logs emitted by this call (if enabled with [`CallConfig::with_log`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logs: Option<Vec<CallLogFrame>>,
+}
+
+/// A log emitted by a call frame, as returned by the `callTracer` with `withLog`
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct CallLogFrame {
+    /// The address of the contract which emitted the log
+    pub address: Address,
+    /// The topics of the log
+    #[serde(default)]
+    pub topics: Vec<H256>,
+    /// The data of the log
+    #[serde(default)]
+    pub data: Bytes,
+}
+
+impl CallFrame {
+    /// Flattens the call frame and its sub-calls (depth-first) into Parity-style traces
+    pub fn to_transaction_traces(&self) -> Vec<TransactionTrace> {
+        let mut traces = Vec::new();
+        self.flatten(Vec::new(), &mut traces);
+        traces
+    }
+
+    /// Rebuilds a call frame from depth-first ordered Parity-style traces, as returned
+    /// by `trace_replayTransaction`. Returns `None` if the traces are empty, out of order,
+    /// or contain a block reward.
+    pub fn from_transaction_traces(traces: &[TransactionTrace]) -> Option<Self> {
+        let (root, rest) = traces.split_first()?;
+        if !root.trace_address.is_empty() {
+            return None;
+        }
+
+        let mut frame = Self::from_transaction_trace(root)?;
+        for trace in rest {
+            let (last, path) = trace.trace_address.split_last()?;
+            let mut parent = &mut frame;
+            for idx in path {
+                parent = parent.calls.get_mut(*idx)?;
+            }
+            if parent.calls.len() != *last {
+                return None;
+            }
+            parent.calls.push(Self::from_transaction_trace(trace)?);
+        }
+
+        Some(frame)
+    }
+
+    fn flatten(&self, trace_address: Vec<usize>, traces: &mut Vec<TransactionTrace>) {
+        let (action, action_type, result) = match self.typ {
+            CallFrameType::Create | CallFrameType::Create2 => (
+                Action::Create(Create {
+                    from: self.from,
+                    value: self.value.unwrap_or_default(),
+                    gas: self.gas,
+                    init: self.input.clone(),
+                    creation_method: Some(if self.typ == CallFrameType::Create2 {
+                        CreationMethod::Create2
+                    } else {
+                        CreationMethod::Create
+                    }),
+                }),
+                ActionType::Create,
+                Res::Create(CreateResult {
+                    gas_used: self.gas_used,
+                    code: self.output.clone().unwrap_or_default(),
+                    address: self.to.unwrap_or_default(),
+                }),
+            ),
+            CallFrameType::SelfDestruct => (
+                Action::Suicide(Suicide {
+                    address: self.from,
+                    refund_address: self.to.unwrap_or_default(),
+                    balance: self.value.unwrap_or_default(),
+                }),
+                ActionType::Suicide,
+                Res::None,
+            ),
+            typ => (
+                Action::Call(Call {
+                    from: self.from,
+                    to: self.to.unwrap_or_default(),
+                    value: self.value.unwrap_or_default(),
+                    gas: self.gas,
+                    input: self.input.clone(),
+                    call_type: match typ {
+                        CallFrameType::StaticCall => CallType::StaticCall,
+                        CallFrameType::DelegateCall => CallType::DelegateCall,
+                        CallFrameType::CallCode => CallType::CallCode,
+                        _ => CallType::Call,
+                    },
+                }),
+                ActionType::Call,
+                Res::Call(CallResult {
+                    gas_used: self.gas_used,
+                    output: self.output.clone().unwrap_or_default(),
+                }),
+            ),
+        };
+
+        // failed calls and self-destructs have no result
+        let result = match (&self.error, result) {
+            (None, Res::None) | (Some(_), _) => None,
+            (None, result) => Some(result),
+        };
+
+        traces.push(TransactionTrace {
+            trace_address: trace_address.clone(),
+            subtraces: self.calls.len(),
+            action,
+            action_type,
+            result,
+            error: self.error.clone(),
+        });
+
+        for (i, call) in self.calls.iter().enumerate() {
+            let mut address = trace_address.clone();
+            address.push(i);
+            call.flatten(address, traces);
+        }
+    }
+
+    fn from_transaction_trace(trace: &TransactionTrace) -> Option<Self> {
+        let (typ, from, to, value, gas, input) = match &trace.action {
+            Action::Call(call) => {
+                let typ = match call.call_type {
+                    CallType::StaticCall => CallFrameType::StaticCall,
+                    CallType::DelegateCall => CallFrameType::DelegateCall,
+                    CallType::CallCode => CallFrameType::CallCode,
+                    CallType::Call | CallType::None => CallFrameType::Call,
+                };
+                (
+                    typ,
+                    call.from,
+                    Some(call.to),
+                    call.value,
+                    call.gas,
+                    call.input.clone(),
+                )
+            }
+            Action::Create(create) => {
+                let to = match &trace.result {
+                    Some(Res::Create(res)) => Some(res.address),
+                    _ => None,
+                };
+                let typ = match create.creation_method {
+                    Some(CreationMethod::Create2) => CallFrameType::Create2,
+                    _ => CallFrameType::Create,
+                };
+                (
+                    typ,
+                    create.from,
+                    to,
+                    create.value,
+                    create.gas,
+                    create.init.clone(),
+                )
+            }
+            Action::Suicide(suicide) => (
+                CallFrameType::SelfDestruct,
+                suicide.address,
+                Some(suicide.refund_address),
+                suicide.balance,
+                U256::zero(),
+                Bytes::default(),
+            ),
+            Action::Reward(_) => return None,
+        };
+
+        let (gas_used, output) = match &trace.result {
+            Some(Res::Call(res)) => (res.gas_used, Some(res.output.clone())),
+            Some(Res::Create(res)) => (res.gas_used, Some(res.code.clone())),
+            _ => (U256::zero(), None),
+        };
+
+        Some(Self {
+            typ,
+            from,
+            to,
+            value: Some(value),
+            gas,
+            gas_used,
+            input,
+            output,
+            error: trace.error.clone(),
+            calls: Vec::with_capacity(trace.subtraces),
+            logs: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_CALL_FRAME: &str = r#"{
+        "type": "CALL",
+        "from": "0x25e8d4ff5f1ccf5e1b9e6fbde1b0d1e34f4e6ae9",
+        "to": "0x7a250d5630b4cf539739df2c5dacb4c659f2488d",
+        "value": "0x0",
+        "gas": "0x2b9c8",
+        "gasUsed": "0x1c3f4",
+        "input": "0x38ed1739",
+        "output": "0x",
+        "calls": [
+            {
+                "type": "STATICCALL",
+                "from": "0x7a250d5630b4cf539739df2c5dacb4c659f2488d",
+                "to": "0xb4e16d0168e52d35cacd2c6185b44281ec28c9dc",
+                "gas": "0x29a2b",
+                "gasUsed": "0x9c8",
+                "input": "0x0902f1ac",
+                "output": "0x01"
+            },
+            {
+                "type": "CALL",
+                "from": "0x7a250d5630b4cf539739df2c5dacb4c659f2488d",
+                "to": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+                "value": "0x0",
+                "gas": "0x28b8c",
+                "gasUsed": "0x2e6b",
+                "input": "0x23b872dd",
+                "error": "execution reverted",
+                "calls": [
+                    {
+                        "type": "DELEGATECALL",
+                        "from": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+                        "to": "0xa2327a938febf5fec13bacfb16ae10ecbc4cbdcf",
+                        "gas": "0x27a4e",
+                        "gasUsed": "0x1d26",
+                        "input": "0x23b872dd",
+                        "output": "0x"
+                    }
+                ]
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn serde_tracing_options() {
+        let opts = GethDebugTracingOptions::default()
+            .tracer(GethDebugBuiltInTracerType::CallTracer)
+            .tracer_config(CallConfig {
+                only_top_call: Some(true),
+                with_log: None,
+            });
+        assert_eq!(
+            serde_json::to_string(&opts).unwrap(),
+            r#"{"tracer":"callTracer","tracerConfig":{"onlyTopCall":true}}"#
+        );
+
+        let opts = GethDebugTracingOptions::default().js_tracer("{}");
+        assert_eq!(serde_json::to_string(&opts).unwrap(), r#"{"tracer":"{}"}"#);
+    }
+
+    #[test]
+    fn serde_tracing_call_options() {
+        let mut overrides = StateOverride::new();
+        overrides.insert(
+            Address::zero(),
+            crate::types::AccountOverride::default().balance(1),
+        );
+        let opts = GethDebugTracingCallOptions::from(
+            GethDebugTracingOptions::default().tracer(GethDebugBuiltInTracerType::PreStateTracer),
+        )
+        .state_overrides(overrides)
+        .block_overrides(BlockOverrides::default().number(10u64));
+        assert_eq!(
+            serde_json::to_string(&opts).unwrap(),
+            r#"{"tracer":"prestateTracer","stateOverrides":{"0x0000000000000000000000000000000000000000":{"balance":"0x1"}},"blockOverrides":{"number":"0xa"}}"#
+        );
+    }
+
+    #[test]
+    fn deserializes_geth_traces() {
+        let trace: GethTrace = serde_json::from_str(
+            r#"{
+                "failed": false,
+                "gas": 21000,
+                "returnValue": "",
+                "structLogs": [{
+                    "pc": 0,
+                    "op": "PUSH1",
+                    "gas": 78902,
+                    "gasCost": 3,
+                    "depth": 1,
+                    "stack": ["0x80"]
+                }]
+            }"#,
+        )
+        .unwrap();
+        match trace {
+            GethTrace::Default(frame) => {
+                assert_eq!(frame.gas, 21000);
+                assert_eq!(frame.struct_logs[0].op, "PUSH1");
+                assert_eq!(frame.struct_logs[0].stack, Some(vec![0x80.into()]));
+            }
+            _ => panic!("expected a struct logger trace"),
+        }
+
+        let trace: GethTrace = serde_json::from_str(EXAMPLE_CALL_FRAME).unwrap();
+        assert!(matches!(trace, GethTrace::CallTracer(_)));
+
+        let trace: GethTrace = serde_json::from_str(
+            r#"{
+                "0x0000000000000000000000000000000000000001": {
+                    "balance": "0x10",
+                    "nonce": 1,
+                    "storage": {
+                        "0x0000000000000000000000000000000000000000000000000000000000000001": "0x0000000000000000000000000000000000000000000000000000000000000002"
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+        match trace {
+            GethTrace::PreStateTracer(PreStateFrame::Default(accounts)) => {
+                let account = &accounts[&Address::from_low_u64_be(1)];
+                assert_eq!(account.balance, Some(16.into()));
+                assert_eq!(account.nonce, Some(1));
+                assert_eq!(account.code, None);
+            }
+            _ => panic!("expected a prestate trace"),
+        }
+
+        let trace: GethTrace = serde_json::from_str(r#"{"pre": {}, "post": {}}"#).unwrap();
+        assert!(matches!(
+            trace,
+            GethTrace::PreStateTracer(PreStateFrame::Diff(_))
+        ));
+    }
+
+    #[test]
+    fn deserializes_call_frame_logs() {
+        let frame: CallFrame = serde_json::from_str(
+            r#"{
+                "type": "CALL",
+                "from": "0x25e8d4ff5f1ccf5e1b9e6fbde1b0d1e34f4e6ae9",
+                "to": "0xdac17f958d2ee523a2206206994597c13d831ec7",
+                "value": "0x0",
+                "gas": "0x1c3f4",
+                "gasUsed": "0xa2b1",
+                "input": "0xa9059cbb",
+                "output": "0x",
+                "logs": [
+                    {
+                        "address": "0xdac17f958d2ee523a2206206994597c13d831ec7",
+                        "topics": [
+                            "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+                            "0x00000000000000000000000025e8d4ff5f1ccf5e1b9e6fbde1b0d1e34f4e6ae9",
+                            "0x0000000000000000000000007a250d5630b4cf539739df2c5dacb4c659f2488d"
+                        ],
+                        "data": "0x00000000000000000000000000000000000000000000000000000000000003e8"
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let logs = frame.logs.as_ref().unwrap();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(
+            logs[0].address,
+            "0xdac17f958d2ee523a2206206994597c13d831ec7"
+                .parse::<Address>()
+                .unwrap()
+        );
+        assert_eq!(logs[0].topics.len(), 3);
+        assert_eq!(logs[0].data.as_ref()[31], 0xe8);
+
+        let serialized = serde_json::to_value(&frame).unwrap();
+        assert_eq!(
+            serde_json::from_value::<CallFrame>(serialized).unwrap(),
+            frame
+        );
+
+        // frames traced without logs omit them
+        let frame: CallFrame = serde_json::from_str(EXAMPLE_CALL_FRAME).unwrap();
+        assert_eq!(frame.logs, None);
+        assert!(!serde_json::to_string(&frame).unwrap().contains("logs"));
+    }
+
+    #[test]
+    fn flattens_call_frame() {
+        let frame: CallFrame = serde_json::from_str(EXAMPLE_CALL_FRAME).unwrap();
+        let traces = frame.to_transaction_traces();
+
+        let addresses = traces
+            .iter()
+            .map(|t| t.trace_address.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(addresses, vec![vec![], vec![0], vec![1], vec![1, 0]]);
+        assert_eq!(traces[0].subtraces, 2);
+        assert_eq!(traces[2].result, None);
+        assert_eq!(traces[2].error.as_deref(), Some("execution reverted"));
+        match &traces[3].action {
+            Action::Call(call) => assert_eq!(call.call_type, CallType::DelegateCall),
+            _ => panic!("expected a call"),
+        }
+    }
+
+    #[test]
+    fn roundtrips_call_frame() {
+        let frame: CallFrame = serde_json::from_str(EXAMPLE_CALL_FRAME).unwrap();
+        let traces = frame.to_transaction_traces();
+        let rebuilt = CallFrame::from_transaction_traces(&traces).unwrap();
+
+        assert_eq!(rebuilt.to_transaction_traces(), traces);
+        assert_eq!(rebuilt.calls.len(), 2);
+        assert_eq!(rebuilt.calls[1].calls[0].typ, CallFrameType::DelegateCall);
+
+        // out of order traces cannot be rebuilt
+        assert!(CallFrame::from_transaction_traces(&traces[1..]).is_none());
+    }
+
+    #[test]
+    fn roundtrips_create2_frame() {
+        let frame: CallFrame = serde_json::from_str(
+            r#"{
+                "type": "CALL",
+                "from": "0x25e8d4ff5f1ccf5e1b9e6fbde1b0d1e34f4e6ae9",
+                "to": "0x5c69bee701ef814a2b6a3edd4b1652cb9cc5aa6f",
+                "gas": "0x2b9c8",
+                "gasUsed": "0x1c3f4",
+                "input": "0xc9c65396",
+                "output": "0x",
+                "calls": [
+                    {
+                        "type": "CREATE2",
+                        "from": "0x5c69bee701ef814a2b6a3edd4b1652cb9cc5aa6f",
+                        "to": "0xb4e16d0168e52d35cacd2c6185b44281ec28c9dc",
+                        "value": "0x0",
+                        "gas": "0x1a3f0",
+                        "gasUsed": "0x1437e",
+                        "input": "0x6080",
+                        "output": "0x6080"
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+        let traces = frame.to_transaction_traces();
+        match &traces[1].action {
+            Action::Create(create) => {
+                assert_eq!(create.creation_method, Some(CreationMethod::Create2))
+            }
+            _ => panic!("expected a creation"),
+        }
+
+        // the creation method survives the Parity-style traces' serialization
+        let traces: Vec<TransactionTrace> =
+            serde_json::from_value(serde_json::to_value(&traces).unwrap()).unwrap();
+        let rebuilt = CallFrame::from_transaction_traces(&traces).unwrap();
+        assert_eq!(rebuilt.calls[0].typ, CallFrameType::Create2);
+        assert_eq!(rebuilt.to_transaction_traces(), traces);
+    }
+}
@@ -8,6 +8,12 @@ use std::collections::BTreeMap;
 mod filter;
 pub use filter::*;
 
+mod geth;
+pub use geth::*;
+
+mod tree;
+pub use tree::{CallNode, CallTree};
+
 #[derive(Debug, Clone, Serialize)]
 /// Description of the type of trace to make
 pub enum TraceType {
@@ -67,6 +73,31 @@ pub enum Diff<T> {
     Changed(ChangedType<T>),
 }
 
+impl<T> Diff<T> {
+    /// Returns whether the value was left unchanged
+    pub fn is_same(&self) -> bool {
+        matches!(self, Diff::Same)
+    }
+
+    /// Returns the value before the change, `None` if it was unchanged or newly set
+    pub fn previous(&self) -> Option<&T> {
+        match self {
+            Diff::Died(value) => Some(value),
+            Diff::Changed(ChangedType { from, .. }) => Some(from),
+            _ => None,
+        }
+    }
+
+    /// Returns the value after the change, `None` if it was unchanged or removed
+    pub fn current(&self) -> Option<&T> {
+        match self {
+            Diff::Born(value) => Some(value),
+            Diff::Changed(ChangedType { to, .. }) => Some(to),
+            _ => None,
+        }
+    }
+}
+
 /// Serde-friendly `AccountDiff` shadow.
 #[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
 pub struct AccountDiff {
@@ -80,10 +111,47 @@ pub struct AccountDiff {
     pub storage: BTreeMap<H256, Diff<H256>>,
 }
 
+impl AccountDiff {
+    /// Returns whether the account was created, e.g. by a contract deployment or by
+    /// receiving ether for the first time
+    pub fn is_created(&self) -> bool {
+        matches!(self.balance, Diff::Born(_))
+    }
+
+    /// Returns the storage slots which were changed
+    pub fn changed_storage(&self) -> impl Iterator<Item = (&H256, &Diff<H256>)> {
+        self.storage.iter().filter(|(_, diff)| !diff.is_same())
+    }
+}
+
 /// Serde-friendly `StateDiff` shadow.
 #[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
 pub struct StateDiff(pub BTreeMap<H160, AccountDiff>);
 
+impl StateDiff {
+    /// Returns the balance before and after the changes of each account whose balance
+    /// changed. The balance of created or removed accounts is zero before or after.
+    pub fn balance_changes(&self) -> BTreeMap<H160, (U256, U256)> {
+        self.0
+            .iter()
+            .filter(|(_, diff)| !diff.balance.is_same())
+            .map(|(address, diff)| {
+                let before = diff.balance.previous().copied().unwrap_or_default();
+                let after = diff.balance.current().copied().unwrap_or_default();
+                (*address, (before, after))
+            })
+            .collect()
+    }
+
+    /// Returns the changed storage slots of each account
+    pub fn storage_changes(&self) -> impl Iterator<Item = (&H160, &H256, &Diff<H256>)> {
+        self.0.iter().flat_map(|(address, diff)| {
+            diff.changed_storage()
+                .map(move |(slot, change)| (address, slot, change))
+        })
+    }
+}
+
 // ------------------ Trace -------------
 /// Trace
 #[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
@@ -115,6 +183,23 @@ pub struct VMTrace {
     pub ops: Vec<VMOperation>,
 }
 
+impl VMTrace {
+    /// Returns the storage writes of the trace, including those of its sub-calls, in
+    /// execution order
+    pub fn storage_writes(&self) -> Vec<&StorageDiff> {
+        let mut writes = Vec::new();
+        for op in &self.ops {
+            if let Some(store) = op.ex.as_ref().and_then(|ex| ex.store.as_ref()) {
+                writes.push(store);
+            }
+            if let Some(sub) = &op.sub {
+                writes.extend(sub.storage_writes());
+            }
+        }
+        writes
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
 #[allow(clippy::upper_case_acronyms)]
 /// A record of the execution of a single VM operation.
@@ -199,4 +284,58 @@ mod tests {
     fn test_deserialize_blocktraces() {
         let _traces: Vec<BlockTrace> = serde_json::from_str(EXAMPLE_TRACES).unwrap();
     }
+
+    #[test]
+    fn test_state_diff_changes() {
+        let trace: BlockTrace = serde_json::from_str(EXAMPLE_TRACE).unwrap();
+        let state_diff = trace.state_diff.unwrap();
+
+        let changes = state_diff.balance_changes();
+        assert_eq!(changes.len(), 3);
+        let sender = "01f0eb5c4b0a9d8285b67195f5f10ce22971a102"
+            .parse::<H160>()
+            .unwrap();
+        assert_eq!(
+            changes[&sender],
+            (0x7361af5818297800u64.into(), 0x734a36bb22448000u64.into())
+        );
+        assert_eq!(state_diff.0[&sender].nonce.current(), Some(&0x1d7.into()));
+        assert!(!state_diff.0[&sender].is_created());
+        assert_eq!(state_diff.storage_changes().count(), 0);
+    }
+
+    #[test]
+    fn test_vm_trace_storage_writes() {
+        let store = |key: u64| VMOperation {
+            ex: Some(VMExecutedOperation {
+                store: Some(StorageDiff {
+                    key: key.into(),
+                    val: 1.into(),
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let trace = VMTrace {
+            code: Default::default(),
+            ops: vec![
+                store(1),
+                VMOperation {
+                    sub: Some(VMTrace {
+                        code: Default::default(),
+                        ops: vec![store(2)],
+                    }),
+                    ..Default::default()
+                },
+                store(3),
+            ],
+        };
+
+        let keys = trace
+            .storage_writes()
+            .into_iter()
+            .map(|write| write.key.as_u64())
+            .collect::<Vec<_>>();
+        assert_eq!(keys, vec![1, 2, 3]);
+    }
 }
@@ -0,0 +1,206 @@
+//! Nested call trees built from the flat traces of the Trace Filtering API
+use super::{Res, Trace};
+use crate::types::{H256, U256};
+use std::collections::HashMap;
+
+/// A call in a [`CallTree`], along with the links to its parent and sub-calls
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallNode {
+    /// The trace of the call
+    pub trace: Trace,
+    /// The index of the parent call in the tree, `None` for the root call
+    pub parent: Option<usize>,
+    /// The indices of the sub-calls in the tree, in execution order
+    pub children: Vec<usize>,
+}
+
+impl CallNode {
+    /// Returns the gas used by the call, including its sub-calls. Failed calls
+    /// report no result, in which case this is zero.
+    pub fn gas_used(&self) -> U256 {
+        match &self.trace.result {
+            Some(Res::Call(res)) => res.gas_used,
+            Some(Res::Create(res)) => res.gas_used,
+            _ => U256::zero(),
+        }
+    }
+
+    /// Returns whether the call reverted or otherwise failed
+    pub fn is_reverted(&self) -> bool {
+        self.trace.error.is_some()
+    }
+
+    /// Returns the depth of the call, zero for the root call
+    pub fn depth(&self) -> usize {
+        self.trace.trace_address.len()
+    }
+}
+
+/// The calls of a transaction arranged as a tree, as opposed to the flat traces (addressed
+/// by their `traceAddress`) returned by `trace_transaction` and `trace_block`.
+///
+/// The nodes are stored in the order of the traces, so the root call is the first node
+/// and sub-calls always come after their parent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallTree {
+    nodes: Vec<CallNode>,
+}
+
+impl CallTree {
+    /// Builds the call trees of the traces of a transaction or a block, one per transaction
+    /// (and per block reward). Returns `None` if a trace comes before its parent, or if its
+    /// parent is missing.
+    pub fn from_traces(traces: Vec<Trace>) -> Option<Vec<CallTree>> {
+        let mut trees: Vec<CallTree> = Vec::new();
+        // maps the trace addresses of the current tree to the indices of their nodes
+        let mut indices: HashMap<Vec<usize>, usize> = HashMap::new();
+
+        for trace in traces {
+            let parent = match trace.trace_address.split_last() {
+                None => {
+                    indices.clear();
+                    trees.push(CallTree { nodes: Vec::new() });
+                    None
+                }
+                Some((_, path)) => Some(*indices.get(path)?),
+            };
+
+            let tree = trees.last_mut()?;
+            let idx = tree.nodes.len();
+            indices.insert(trace.trace_address.clone(), idx);
+            if let Some(parent) = parent {
+                tree.nodes[parent].children.push(idx);
+            }
+            tree.nodes.push(CallNode {
+                trace,
+                parent,
+                children: Vec::new(),
+            });
+        }
+
+        Some(trees)
+    }
+
+    /// Returns the root call of the tree
+    pub fn root(&self) -> &CallNode {
+        &self.nodes[0]
+    }
+
+    /// Returns the call at the given index
+    pub fn node(&self, idx: usize) -> Option<&CallNode> {
+        self.nodes.get(idx)
+    }
+
+    /// Returns all calls of the tree, in depth-first order
+    pub fn nodes(&self) -> &[CallNode] {
+        &self.nodes
+    }
+
+    /// Returns the hash of the transaction, `None` for block rewards
+    pub fn transaction_hash(&self) -> Option<H256> {
+        self.root().trace.transaction_hash
+    }
+
+    /// Returns the sub-calls of the call at the given index
+    pub fn children(&self, idx: usize) -> impl Iterator<Item = &CallNode> + '_ {
+        self.nodes[idx]
+            .children
+            .iter()
+            .map(move |child| &self.nodes[*child])
+    }
+
+    /// Returns the parent of the call at the given index
+    pub fn parent(&self, idx: usize) -> Option<&CallNode> {
+        self.nodes[idx].parent.map(|parent| &self.nodes[parent])
+    }
+
+    /// Returns the gas used by the call at the given index, excluding the gas
+    /// used by its sub-calls
+    pub fn self_gas_used(&self, idx: usize) -> U256 {
+        let children = self.children(idx).fold(U256::zero(), |acc, child| {
+            acc.saturating_add(child.gas_used())
+        });
+        self.nodes[idx].gas_used().saturating_sub(children)
+    }
+
+    /// Returns the indices of the reverted calls, which includes the callers
+    /// which reverted because a sub-call did
+    pub fn reverted(&self) -> Vec<usize> {
+        (0..self.nodes.len())
+            .filter(|idx| self.nodes[*idx].is_reverted())
+            .collect()
+    }
+
+    /// Returns the indices of the reverted calls none of whose sub-calls reverted,
+    /// i.e. the calls where the reverts originated
+    pub fn revert_origins(&self) -> Vec<usize> {
+        self.reverted()
+            .into_iter()
+            .filter(|idx| !self.children(*idx).any(CallNode::is_reverted))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Action, ActionType, Call, CallResult};
+
+    fn trace(trace_address: Vec<usize>, gas_used: Option<u64>, subtraces: usize) -> Trace {
+        Trace {
+            action: Action::Call(Call::default()),
+            result: gas_used.map(|gas_used| {
+                Res::Call(CallResult {
+                    gas_used: gas_used.into(),
+                    output: Default::default(),
+                })
+            }),
+            trace_address,
+            subtraces,
+            transaction_position: Some(0),
+            transaction_hash: Some(H256::repeat_byte(1)),
+            block_number: 1,
+            block_hash: H256::zero(),
+            action_type: ActionType::Call,
+            error: match gas_used {
+                Some(_) => None,
+                None => Some("Reverted".to_owned()),
+            },
+        }
+    }
+
+    #[test]
+    fn builds_call_tree() {
+        let traces = vec![
+            trace(vec![], Some(1000), 2),
+            trace(vec![0], Some(300), 1),
+            trace(vec![0, 0], Some(100), 0),
+            trace(vec![1], None, 1),
+            trace(vec![1, 0], None, 0),
+            // a second transaction
+            trace(vec![], Some(500), 0),
+        ];
+        let trees = CallTree::from_traces(traces).unwrap();
+        assert_eq!(trees.len(), 2);
+
+        let tree = &trees[0];
+        assert_eq!(tree.nodes().len(), 5);
+        assert_eq!(tree.root().children, vec![1, 3]);
+        assert_eq!(tree.parent(2).unwrap().trace.trace_address, vec![0]);
+        assert_eq!(tree.node(4).unwrap().depth(), 2);
+        assert_eq!(tree.self_gas_used(0), 700.into());
+        assert_eq!(tree.self_gas_used(1), 200.into());
+        assert_eq!(tree.reverted(), vec![3, 4]);
+        assert_eq!(tree.revert_origins(), vec![4]);
+
+        assert_eq!(trees[1].nodes().len(), 1);
+        assert!(trees[1].reverted().is_empty());
+    }
+
+    #[test]
+    fn rejects_orphan_traces() {
+        let traces = vec![trace(vec![], Some(1000), 1), trace(vec![1, 0], Some(10), 0)];
+        assert!(CallTree::from_traces(traces).is_none());
+        assert!(CallTree::from_traces(vec![trace(vec![0], Some(10), 0)]).is_none());
+    }
+}
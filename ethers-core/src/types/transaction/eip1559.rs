@@ -0,0 +1,255 @@
+use super::{eip2930::AccessList, rlp_opt, rlp_signature, typed_envelope, TransactionRequest};
+use crate::{
+    types::{Address, Bytes, NameOrAddress, Signature, H256, U256, U64},
+    utils::keccak256,
+};
+
+use rlp::RlpStream;
+use serde::{Deserialize, Serialize};
+
+/// The type of EIP-1559 transactions, which prefixes their encoding
+pub const EIP1559_TX_TYPE: u8 = 0x02;
+
+// Number of tx fields before signing
+const UNSIGNED_TX_FIELDS: usize = 9;
+
+// Unsigned fields + signature [y_parity r s]
+const SIGNED_TX_FIELDS: usize = UNSIGNED_TX_FIELDS + 3;
+
+/// Parameters for sending an [EIP-1559](https://eips.ethereum.org/EIPS/eip-1559)
+/// transaction, which pays a base fee burnt by the network and a priority fee to the
+/// miner instead of a gas price
+#[derive(Clone, Default, Serialize, Deserialize, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Eip1559TransactionRequest {
+    /// Sender address
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<Address>,
+
+    /// Recipient address (None for contract creation)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<NameOrAddress>,
+
+    /// Supplied gas (None for sensible default)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas: Option<U256>,
+
+    /// Transfered value (None for no transfer)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<U256>,
+
+    /// The compiled code of a contract OR the first 4 bytes of the hash of the
+    /// invoked method signature and encoded parameters. For details see Ethereum Contract ABI
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Bytes>,
+
+    /// Transaction nonce (None for next available nonce)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<U256>,
+
+    /// The addresses and storage keys the transaction plans to access
+    #[serde(default)]
+    pub access_list: AccessList,
+
+    /// The max fee per gas paid to the miner on top of the base fee (None for
+    /// sensible default)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_priority_fee_per_gas: Option<U256>,
+
+    /// The max total fee per gas, including the base fee (None for sensible default)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_gas: Option<U256>,
+
+    /// The chain the transaction is valid on, which is part of the signed payload
+    /// (None for the signer's or the node's chain)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<U64>,
+}
+
+impl Eip1559TransactionRequest {
+    /// Creates an empty transaction request with all fields left empty
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Convenience function for sending a new payment transaction to the receiver.
+    pub fn pay<T: Into<NameOrAddress>, V: Into<U256>>(to: T, value: V) -> Self {
+        Eip1559TransactionRequest {
+            to: Some(to.into()),
+            value: Some(value.into()),
+            ..Default::default()
+        }
+    }
+
+    // Builder pattern helpers
+
+    /// Sets the `from` field in the transaction to the provided value
+    pub fn from<T: Into<Address>>(mut self, from: T) -> Self {
+        self.from = Some(from.into());
+        self
+    }
+
+    /// Sets the `to` field in the transaction to the provided value
+    pub fn to<T: Into<NameOrAddress>>(mut self, to: T) -> Self {
+        self.to = Some(to.into());
+        self
+    }
+
+    /// Sets the `gas` field in the transaction to the provided value
+    pub fn gas<T: Into<U256>>(mut self, gas: T) -> Self {
+        self.gas = Some(gas.into());
+        self
+    }
+
+    /// Sets the `value` field in the transaction to the provided value
+    pub fn value<T: Into<U256>>(mut self, value: T) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    /// Sets the `data` field in the transaction to the provided value
+    pub fn data<T: Into<Bytes>>(mut self, data: T) -> Self {
+        self.data = Some(data.into());
+        self
+    }
+
+    /// Sets the `nonce` field in the transaction to the provided value
+    pub fn nonce<T: Into<U256>>(mut self, nonce: T) -> Self {
+        self.nonce = Some(nonce.into());
+        self
+    }
+
+    /// Sets the `access_list` field in the transaction to the provided value
+    pub fn access_list<T: Into<AccessList>>(mut self, access_list: T) -> Self {
+        self.access_list = access_list.into();
+        self
+    }
+
+    /// Sets the `max_priority_fee_per_gas` field in the transaction to the provided value
+    pub fn max_priority_fee_per_gas<T: Into<U256>>(mut self, max_priority_fee_per_gas: T) -> Self {
+        self.max_priority_fee_per_gas = Some(max_priority_fee_per_gas.into());
+        self
+    }
+
+    /// Sets the `max_fee_per_gas` field in the transaction to the provided value
+    pub fn max_fee_per_gas<T: Into<U256>>(mut self, max_fee_per_gas: T) -> Self {
+        self.max_fee_per_gas = Some(max_fee_per_gas.into());
+        self
+    }
+
+    /// Sets the `chain_id` field in the transaction to the provided value
+    pub fn chain_id<T: Into<U64>>(mut self, chain_id: T) -> Self {
+        self.chain_id = Some(chain_id.into());
+        self
+    }
+
+    /// Hashes the transaction's data, which is what gets signed
+    pub fn sighash(&self) -> H256 {
+        keccak256(self.rlp().as_ref()).into()
+    }
+
+    /// Gets the unsigned transaction's encoding: the transaction type followed by the
+    /// RLP encoded fields
+    pub fn rlp(&self) -> Bytes {
+        let mut rlp = RlpStream::new();
+        rlp.begin_list(UNSIGNED_TX_FIELDS);
+        self.rlp_base(&mut rlp);
+        typed_envelope(EIP1559_TX_TYPE, rlp)
+    }
+
+    /// Produces the encoding of the transaction with the provided signature, ready to
+    /// be broadcast with `eth_sendRawTransaction`. The `v` of the signature may either be
+    /// the y-parity of the signature or in 'Electrum' or EIP-155 notation.
+    pub fn rlp_signed(&self, signature: &Signature) -> Bytes {
+        let mut rlp = RlpStream::new();
+        rlp.begin_list(SIGNED_TX_FIELDS);
+        self.rlp_base(&mut rlp);
+        rlp_signature(&mut rlp, signature);
+        typed_envelope(EIP1559_TX_TYPE, rlp)
+    }
+
+    fn rlp_base(&self, rlp: &mut RlpStream) {
+        rlp.append(&self.chain_id.unwrap_or_default());
+        rlp_opt(rlp, self.nonce);
+        rlp_opt(rlp, self.max_priority_fee_per_gas);
+        rlp_opt(rlp, self.max_fee_per_gas);
+        rlp_opt(rlp, self.gas);
+        rlp_opt(rlp, self.to.as_ref());
+        rlp_opt(rlp, self.value);
+        rlp_opt(rlp, self.data.as_ref().map(|d| d.as_ref()));
+        rlp.append(&self.access_list);
+    }
+}
+
+impl From<&Eip1559TransactionRequest> for TransactionRequest {
+    /// Converts the request to a legacy one without fees, e.g. to estimate its gas
+    fn from(tx: &Eip1559TransactionRequest) -> Self {
+        TransactionRequest {
+            from: tx.from,
+            to: tx.to.clone(),
+            gas: tx.gas,
+            value: tx.value,
+            data: tx.data.clone(),
+            nonce: tx.nonce,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::AccessListItem;
+
+    #[test]
+    fn encodes_unsigned_transactions() {
+        let tx = Eip1559TransactionRequest::new()
+            .chain_id(1u64)
+            .nonce(0)
+            .max_priority_fee_per_gas(1)
+            .max_fee_per_gas(2)
+            .gas(21_000)
+            .to(Address::zero())
+            .value(0);
+
+        // type || rlp([chain_id, nonce, max_priority_fee, max_fee, gas, to, value, data, []])
+        let mut expected = vec![0x02, 0xdf, 0x01, 0x80, 0x01, 0x02, 0x82, 0x52, 0x08, 0x94];
+        expected.extend_from_slice(&[0; 20]);
+        expected.extend_from_slice(&[0x80, 0x80, 0xc0]);
+        assert_eq!(tx.rlp().as_ref(), expected.as_slice());
+        assert_eq!(tx.sighash(), H256::from(keccak256(&expected)));
+
+        // the access list is part of the payload
+        let with_access_list = tx.clone().access_list(vec![AccessListItem::default()]);
+        assert_ne!(with_access_list.sighash(), tx.sighash());
+    }
+
+    #[test]
+    fn encodes_signed_transactions() {
+        let tx = Eip1559TransactionRequest::new().chain_id(1u64);
+        let signature = Signature {
+            r: H256::from_low_u64_be(1),
+            s: H256::from_low_u64_be(2),
+            v: 38,
+        };
+        // the EIP-155 `v` is normalized to the y-parity
+        let encoded = tx.rlp_signed(&signature);
+        assert_eq!(
+            encoded.as_ref(),
+            &[0x02, 0xcc, 0x01, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0xc0, 0x01, 0x01, 0x02]
+        );
+        let parity = Signature { v: 1, ..signature };
+        assert_eq!(tx.rlp_signed(&parity), encoded);
+    }
+
+    #[test]
+    fn serde_eip1559_request() {
+        let tx = Eip1559TransactionRequest::new()
+            .max_fee_per_gas(2)
+            .max_priority_fee_per_gas(1);
+        assert_eq!(
+            serde_json::to_string(&tx).unwrap(),
+            r#"{"accessList":[],"maxPriorityFeePerGas":"0x1","maxFeePerGas":"0x2"}"#
+        );
+    }
+}
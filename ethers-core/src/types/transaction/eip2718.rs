@@ -0,0 +1,467 @@
+use super::{
+    eip1559::Eip1559TransactionRequest,
+    eip2930::{AccessList, Eip2930TransactionRequest},
+    TransactionRequest,
+};
+use crate::{
+    types::{Address, Bytes, NameOrAddress, Signature, H256, U256, U64},
+    utils::keccak256,
+};
+
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
+
+/// A transaction request of any of the supported types, as defined in
+/// [EIP-2718](https://eips.ethereum.org/EIPS/eip-2718). This is what gets passed through
+/// the middleware stack, so that each middleware handles every transaction type.
+///
+/// Its `type` field is (de)serialized as a quantity, e.g. `"0x2"`, and requests without
+/// it are legacy ones.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum TypedTransaction {
+    /// A legacy transaction, which pays a gas price
+    Legacy(TransactionRequest),
+    /// An [EIP-2930](https://eips.ethereum.org/EIPS/eip-2930) transaction, which pays a
+    /// gas price and has an access list
+    Eip2930(Eip2930TransactionRequest),
+    /// An [EIP-1559](https://eips.ethereum.org/EIPS/eip-1559) transaction, which pays a
+    /// max fee and a max priority fee per gas
+    Eip1559(Eip1559TransactionRequest),
+}
+
+impl Serialize for TypedTransaction {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct Tagged<'a, T> {
+            #[serde(rename = "type")]
+            tx_type: U64,
+            #[serde(flatten)]
+            tx: &'a T,
+        }
+
+        match self {
+            TypedTransaction::Legacy(tx) => Tagged {
+                tx_type: 0.into(),
+                tx,
+            }
+            .serialize(serializer),
+            TypedTransaction::Eip2930(tx) => Tagged {
+                tx_type: 1.into(),
+                tx,
+            }
+            .serialize(serializer),
+            TypedTransaction::Eip1559(tx) => Tagged {
+                tx_type: 2.into(),
+                tx,
+            }
+            .serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for TypedTransaction {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut fields = Map::deserialize(deserializer)?;
+        let tx_type = match fields.remove("type") {
+            Some(Value::Null) | None => U64::zero(),
+            Some(tx_type) => serde_json::from_value(tx_type).map_err(D::Error::custom)?,
+        };
+
+        let fields = Value::Object(fields);
+        match tx_type.as_u64() {
+            0 => serde_json::from_value(fields).map(TypedTransaction::Legacy),
+            1 => serde_json::from_value(fields).map(TypedTransaction::Eip2930),
+            2 => serde_json::from_value(fields).map(TypedTransaction::Eip1559),
+            _ => {
+                return Err(D::Error::custom(format!(
+                    "unsupported transaction type {}",
+                    tx_type
+                )))
+            }
+        }
+        .map_err(D::Error::custom)
+    }
+}
+
+impl TypedTransaction {
+    /// Returns the sender of the transaction
+    pub fn from(&self) -> Option<&Address> {
+        match self {
+            TypedTransaction::Legacy(tx) => tx.from.as_ref(),
+            TypedTransaction::Eip2930(tx) => tx.tx.from.as_ref(),
+            TypedTransaction::Eip1559(tx) => tx.from.as_ref(),
+        }
+    }
+
+    /// Sets the sender of the transaction
+    pub fn set_from(&mut self, from: Address) {
+        match self {
+            TypedTransaction::Legacy(tx) => tx.from = Some(from),
+            TypedTransaction::Eip2930(tx) => tx.tx.from = Some(from),
+            TypedTransaction::Eip1559(tx) => tx.from = Some(from),
+        }
+    }
+
+    /// Returns the recipient of the transaction (None for contract creation)
+    pub fn to(&self) -> Option<&NameOrAddress> {
+        match self {
+            TypedTransaction::Legacy(tx) => tx.to.as_ref(),
+            TypedTransaction::Eip2930(tx) => tx.tx.to.as_ref(),
+            TypedTransaction::Eip1559(tx) => tx.to.as_ref(),
+        }
+    }
+
+    /// Sets the recipient of the transaction
+    pub fn set_to<T: Into<NameOrAddress>>(&mut self, to: T) {
+        let to = Some(to.into());
+        match self {
+            TypedTransaction::Legacy(tx) => tx.to = to,
+            TypedTransaction::Eip2930(tx) => tx.tx.to = to,
+            TypedTransaction::Eip1559(tx) => tx.to = to,
+        }
+    }
+
+    /// Returns the nonce of the transaction
+    pub fn nonce(&self) -> Option<&U256> {
+        match self {
+            TypedTransaction::Legacy(tx) => tx.nonce.as_ref(),
+            TypedTransaction::Eip2930(tx) => tx.tx.nonce.as_ref(),
+            TypedTransaction::Eip1559(tx) => tx.nonce.as_ref(),
+        }
+    }
+
+    /// Sets the nonce of the transaction
+    pub fn set_nonce<T: Into<U256>>(&mut self, nonce: T) {
+        let nonce = Some(nonce.into());
+        match self {
+            TypedTransaction::Legacy(tx) => tx.nonce = nonce,
+            TypedTransaction::Eip2930(tx) => tx.tx.nonce = nonce,
+            TypedTransaction::Eip1559(tx) => tx.nonce = nonce,
+        }
+    }
+
+    /// Returns the gas supplied to the transaction
+    pub fn gas(&self) -> Option<&U256> {
+        match self {
+            TypedTransaction::Legacy(tx) => tx.gas.as_ref(),
+            TypedTransaction::Eip2930(tx) => tx.tx.gas.as_ref(),
+            TypedTransaction::Eip1559(tx) => tx.gas.as_ref(),
+        }
+    }
+
+    /// Sets the gas supplied to the transaction
+    pub fn set_gas<T: Into<U256>>(&mut self, gas: T) {
+        let gas = Some(gas.into());
+        match self {
+            TypedTransaction::Legacy(tx) => tx.gas = gas,
+            TypedTransaction::Eip2930(tx) => tx.tx.gas = gas,
+            TypedTransaction::Eip1559(tx) => tx.gas = gas,
+        }
+    }
+
+    /// Returns the maximum price paid per unit of gas: the gas price of legacy and
+    /// EIP-2930 transactions, or the max fee per gas of EIP-1559 transactions
+    pub fn gas_price(&self) -> Option<U256> {
+        match self {
+            TypedTransaction::Legacy(tx) => tx.gas_price,
+            TypedTransaction::Eip2930(tx) => tx.tx.gas_price,
+            TypedTransaction::Eip1559(tx) => tx.max_fee_per_gas,
+        }
+    }
+
+    /// Sets the maximum price paid per unit of gas. This is the max fee per gas of EIP-1559
+    /// transactions, whose max priority fee per gas is lowered to it if it was higher.
+    pub fn set_gas_price<T: Into<U256>>(&mut self, gas_price: T) {
+        let gas_price = gas_price.into();
+        match self {
+            TypedTransaction::Legacy(tx) => tx.gas_price = Some(gas_price),
+            TypedTransaction::Eip2930(tx) => tx.tx.gas_price = Some(gas_price),
+            TypedTransaction::Eip1559(tx) => {
+                tx.max_fee_per_gas = Some(gas_price);
+                tx.max_priority_fee_per_gas = tx
+                    .max_priority_fee_per_gas
+                    .map(|max_priority_fee| max_priority_fee.min(gas_price));
+            }
+        }
+    }
+
+    /// Returns the value transferred by the transaction
+    pub fn value(&self) -> Option<&U256> {
+        match self {
+            TypedTransaction::Legacy(tx) => tx.value.as_ref(),
+            TypedTransaction::Eip2930(tx) => tx.tx.value.as_ref(),
+            TypedTransaction::Eip1559(tx) => tx.value.as_ref(),
+        }
+    }
+
+    /// Sets the value transferred by the transaction
+    pub fn set_value<T: Into<U256>>(&mut self, value: T) {
+        let value = Some(value.into());
+        match self {
+            TypedTransaction::Legacy(tx) => tx.value = value,
+            TypedTransaction::Eip2930(tx) => tx.tx.value = value,
+            TypedTransaction::Eip1559(tx) => tx.value = value,
+        }
+    }
+
+    /// Returns the data of the transaction
+    pub fn data(&self) -> Option<&Bytes> {
+        match self {
+            TypedTransaction::Legacy(tx) => tx.data.as_ref(),
+            TypedTransaction::Eip2930(tx) => tx.tx.data.as_ref(),
+            TypedTransaction::Eip1559(tx) => tx.data.as_ref(),
+        }
+    }
+
+    /// Sets the data of the transaction
+    pub fn set_data<T: Into<Bytes>>(&mut self, data: T) {
+        let data = Some(data.into());
+        match self {
+            TypedTransaction::Legacy(tx) => tx.data = data,
+            TypedTransaction::Eip2930(tx) => tx.tx.data = data,
+            TypedTransaction::Eip1559(tx) => tx.data = data,
+        }
+    }
+
+    /// Returns the access list of the transaction (None for legacy transactions)
+    pub fn access_list(&self) -> Option<&AccessList> {
+        match self {
+            TypedTransaction::Legacy(_) => None,
+            TypedTransaction::Eip2930(tx) => Some(&tx.access_list),
+            TypedTransaction::Eip1559(tx) => Some(&tx.access_list),
+        }
+    }
+
+    /// Returns the chain id of the transaction (None for legacy transactions, whose
+    /// chain id is applied to the `v` of their signature by the signer instead)
+    pub fn chain_id(&self) -> Option<U64> {
+        match self {
+            TypedTransaction::Legacy(_) => None,
+            TypedTransaction::Eip2930(tx) => tx.chain_id,
+            TypedTransaction::Eip1559(tx) => tx.chain_id,
+        }
+    }
+
+    /// Sets the chain id of typed transactions. Legacy transactions are left as is.
+    pub fn set_chain_id<T: Into<U64>>(&mut self, chain_id: T) {
+        let chain_id = Some(chain_id.into());
+        match self {
+            TypedTransaction::Legacy(_) => {}
+            TypedTransaction::Eip2930(tx) => tx.chain_id = chain_id,
+            TypedTransaction::Eip1559(tx) => tx.chain_id = chain_id,
+        }
+    }
+
+    /// Gets the unsigned transaction's encoding. The chain id is only used by legacy
+    /// transactions, for [EIP-155](https://eips.ethereum.org/EIPS/eip-155) replay
+    /// protection, since typed transactions contain their own.
+    pub fn rlp<T: Into<U64>>(&self, chain_id: Option<T>) -> Bytes {
+        match self {
+            TypedTransaction::Legacy(tx) => tx.rlp(chain_id),
+            TypedTransaction::Eip2930(tx) => tx.rlp(),
+            TypedTransaction::Eip1559(tx) => tx.rlp(),
+        }
+    }
+
+    /// Hashes the transaction's data, which is what gets signed. The chain id is only
+    /// used by legacy transactions, see [`rlp`](Self::rlp).
+    pub fn sighash<T: Into<U64>>(&self, chain_id: Option<T>) -> H256 {
+        keccak256(self.rlp(chain_id).as_ref()).into()
+    }
+
+    /// Produces the encoding of the transaction with the provided signature, ready to be
+    /// broadcast with `eth_sendRawTransaction`
+    pub fn rlp_signed(&self, signature: &Signature) -> Bytes {
+        match self {
+            TypedTransaction::Legacy(tx) => tx.rlp_signed(signature),
+            TypedTransaction::Eip2930(tx) => tx.rlp_signed(signature),
+            TypedTransaction::Eip1559(tx) => tx.rlp_signed(signature),
+        }
+    }
+}
+
+impl From<TransactionRequest> for TypedTransaction {
+    fn from(src: TransactionRequest) -> Self {
+        TypedTransaction::Legacy(src)
+    }
+}
+
+impl From<Eip2930TransactionRequest> for TypedTransaction {
+    fn from(src: Eip2930TransactionRequest) -> Self {
+        TypedTransaction::Eip2930(src)
+    }
+}
+
+impl From<Eip1559TransactionRequest> for TypedTransaction {
+    fn from(src: Eip1559TransactionRequest) -> Self {
+        TypedTransaction::Eip1559(src)
+    }
+}
+
+impl From<&TypedTransaction> for TransactionRequest {
+    /// Converts the request to a legacy one without access list or fees other than the
+    /// gas price, e.g. to estimate its gas
+    fn from(tx: &TypedTransaction) -> Self {
+        match tx {
+            TypedTransaction::Legacy(tx) => tx.clone(),
+            TypedTransaction::Eip2930(tx) => tx.tx.clone(),
+            TypedTransaction::Eip1559(tx) => tx.into(),
+        }
+    }
+}
+
+impl From<&TypedTransaction> for Eip1559TransactionRequest {
+    /// Converts the request to an EIP-1559 one, keeping its access list. The gas price
+    /// of legacy and EIP-2930 requests becomes their max fee, whereas their max priority
+    /// fee is left to be filled in.
+    fn from(tx: &TypedTransaction) -> Self {
+        match tx {
+            TypedTransaction::Eip1559(tx) => tx.clone(),
+            tx => Eip1559TransactionRequest {
+                from: tx.from().copied(),
+                to: tx.to().cloned(),
+                gas: tx.gas().copied(),
+                value: tx.value().copied(),
+                data: tx.data().cloned(),
+                nonce: tx.nonce().copied(),
+                access_list: tx.access_list().cloned().unwrap_or_default(),
+                max_priority_fee_per_gas: None,
+                max_fee_per_gas: tx.gas_price(),
+                chain_id: tx.chain_id(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{AccessListItem, EIP1559_TX_TYPE, EIP2930_TX_TYPE};
+
+    #[test]
+    fn accesses_fields() {
+        let mut txs: Vec<TypedTransaction> = vec![
+            TransactionRequest::new().into(),
+            Eip2930TransactionRequest::new(TransactionRequest::new(), AccessList::default()).into(),
+            Eip1559TransactionRequest::new().into(),
+        ];
+        for tx in txs.iter_mut() {
+            tx.set_from(Address::repeat_byte(1));
+            tx.set_to(Address::repeat_byte(2));
+            tx.set_nonce(3);
+            tx.set_gas(4);
+            tx.set_gas_price(5);
+            tx.set_value(6);
+            tx.set_data(vec![7u8]);
+            tx.set_chain_id(8u64);
+
+            assert_eq!(tx.from(), Some(&Address::repeat_byte(1)));
+            assert_eq!(tx.to(), Some(&Address::repeat_byte(2).into()));
+            assert_eq!(tx.nonce(), Some(&3.into()));
+            assert_eq!(tx.gas(), Some(&4.into()));
+            assert_eq!(tx.gas_price(), Some(5.into()));
+            assert_eq!(tx.value(), Some(&6.into()));
+            assert_eq!(tx.data(), Some(&vec![7u8].into()));
+        }
+
+        assert_eq!(txs[0].chain_id(), None);
+        assert_eq!(txs[0].access_list(), None);
+        assert_eq!(txs[1].chain_id(), Some(8.into()));
+        assert_eq!(txs[2].access_list(), Some(&AccessList::default()));
+        match &txs[2] {
+            TypedTransaction::Eip1559(tx) => assert_eq!(tx.max_priority_fee_per_gas, None),
+            _ => unreachable!(),
+        }
+
+        // the max priority fee is capped by the max fee
+        let mut tx: TypedTransaction = Eip1559TransactionRequest::new()
+            .max_priority_fee_per_gas(7)
+            .into();
+        tx.set_gas_price(9);
+        tx.set_gas_price(5);
+        match tx {
+            TypedTransaction::Eip1559(tx) => {
+                assert_eq!(tx.max_fee_per_gas, Some(5.into()));
+                assert_eq!(tx.max_priority_fee_per_gas, Some(5.into()));
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn converts_to_eip1559() {
+        let access_list = AccessList(vec![AccessListItem {
+            address: Address::repeat_byte(3),
+            storage_keys: vec![H256::repeat_byte(4)],
+        }]);
+        let tx: TypedTransaction = Eip2930TransactionRequest::new(
+            TransactionRequest::pay(Address::repeat_byte(1), 2).gas_price(5),
+            access_list.clone(),
+        )
+        .into();
+
+        let converted = <Eip1559TransactionRequest as From<_>>::from(&tx);
+        assert_eq!(converted.to, Some(Address::repeat_byte(1).into()));
+        assert_eq!(converted.value, Some(2.into()));
+        assert_eq!(converted.access_list, access_list);
+        assert_eq!(converted.max_fee_per_gas, Some(5.into()));
+        assert_eq!(converted.max_priority_fee_per_gas, None);
+
+        // converting back to a legacy request drops the access list
+        let legacy = <TransactionRequest as From<_>>::from(&TypedTransaction::Eip1559(converted));
+        assert_eq!(legacy.to, Some(Address::repeat_byte(1).into()));
+        assert_eq!(legacy.gas_price, None);
+    }
+
+    #[test]
+    fn encodes_typed_transactions() {
+        let legacy = TransactionRequest::new().nonce(1).gas_price(2).gas(21_000);
+        let tx: TypedTransaction = legacy.clone().into();
+        assert_eq!(tx.sighash(Some(1u64)), legacy.sighash(Some(1u64)));
+
+        let eip2930 =
+            Eip2930TransactionRequest::new(legacy, vec![AccessListItem::default()]).chain_id(1u64);
+        let tx: TypedTransaction = eip2930.clone().into();
+        // typed transactions contain their chain id
+        assert_eq!(tx.sighash(Some(5u64)), eip2930.sighash());
+        assert_eq!(tx.rlp(None::<u64>).as_ref()[0], EIP2930_TX_TYPE);
+
+        let eip1559 = Eip1559TransactionRequest::new().chain_id(1u64);
+        let tx: TypedTransaction = eip1559.clone().into();
+        assert_eq!(tx.sighash(None::<u64>), eip1559.sighash());
+        let signature = Signature {
+            r: H256::from_low_u64_be(1),
+            s: H256::from_low_u64_be(2),
+            v: 1,
+        };
+        assert_eq!(tx.rlp_signed(&signature), eip1559.rlp_signed(&signature));
+        assert_eq!(tx.rlp_signed(&signature).as_ref()[0], EIP1559_TX_TYPE);
+    }
+
+    #[test]
+    fn serde_typed_transactions() {
+        let tx: TypedTransaction = TransactionRequest::new().nonce(1).into();
+        let json = serde_json::to_string(&tx).unwrap();
+        assert_eq!(json, r#"{"type":"0x0","nonce":"0x1"}"#);
+        assert_eq!(serde_json::from_str::<TypedTransaction>(&json).unwrap(), tx);
+        // requests without a type are legacy ones
+        let json = r#"{"nonce":"0x1"}"#;
+        assert_eq!(serde_json::from_str::<TypedTransaction>(json).unwrap(), tx);
+
+        let tx: TypedTransaction = Eip1559TransactionRequest::new().max_fee_per_gas(1).into();
+        let json = serde_json::to_string(&tx).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"0x2","accessList":[],"maxFeePerGas":"0x1"}"#
+        );
+        assert_eq!(serde_json::from_str::<TypedTransaction>(&json).unwrap(), tx);
+
+        let tx: TypedTransaction =
+            Eip2930TransactionRequest::new(TransactionRequest::new(), AccessList::default()).into();
+        let json = serde_json::to_string(&tx).unwrap();
+        assert_eq!(json, r#"{"type":"0x1","accessList":[]}"#);
+        assert_eq!(serde_json::from_str::<TypedTransaction>(&json).unwrap(), tx);
+
+        assert!(serde_json::from_str::<TypedTransaction>(r#"{"type":"0x3"}"#).is_err());
+    }
+}
@@ -0,0 +1,218 @@
+use super::{rlp_opt, rlp_signature, typed_envelope, TransactionRequest};
+use crate::{
+    types::{Address, Bytes, Signature, H256, U256, U64},
+    utils::keccak256,
+};
+
+use rlp::{Encodable, RlpStream};
+use serde::{Deserialize, Serialize};
+
+/// The type of EIP-2930 transactions, which prefixes their encoding
+pub const EIP2930_TX_TYPE: u8 = 0x01;
+
+// Number of tx fields before signing
+const UNSIGNED_TX_FIELDS: usize = 8;
+
+// Unsigned fields + signature [y_parity r s]
+const SIGNED_TX_FIELDS: usize = UNSIGNED_TX_FIELDS + 3;
+
+/// The addresses and storage keys a transaction plans to access, as defined in
+/// [EIP-2930](https://eips.ethereum.org/EIPS/eip-2930). Accessing them is cheaper
+/// than accessing addresses and storage keys which are not in the list.
+#[derive(Clone, Default, Serialize, Deserialize, PartialEq, Eq, Debug)]
+pub struct AccessList(pub Vec<AccessListItem>);
+
+impl From<Vec<AccessListItem>> for AccessList {
+    fn from(src: Vec<AccessListItem>) -> Self {
+        AccessList(src)
+    }
+}
+
+impl Encodable for AccessList {
+    fn rlp_append(&self, s: &mut RlpStream) {
+        s.append_list::<AccessListItem, _>(&self.0);
+    }
+}
+
+/// An address and the storage keys of it a transaction plans to access
+#[derive(Clone, Default, Serialize, Deserialize, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListItem {
+    /// The accessed address
+    pub address: Address,
+    /// The accessed storage keys of the address
+    pub storage_keys: Vec<H256>,
+}
+
+impl Encodable for AccessListItem {
+    fn rlp_append(&self, s: &mut RlpStream) {
+        s.begin_list(2);
+        s.append(&self.address);
+        s.append_list::<H256, _>(&self.storage_keys);
+    }
+}
+
+/// An access list generated by the node for a transaction, as returned by
+/// `eth_createAccessList`
+#[derive(Clone, Default, Serialize, Deserialize, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListWithGasUsed {
+    /// The addresses and storage keys accessed by the transaction
+    pub access_list: AccessList,
+    /// The gas used by the transaction when sent with the access list
+    pub gas_used: U256,
+}
+
+/// Parameters for sending an [EIP-2930](https://eips.ethereum.org/EIPS/eip-2930)
+/// transaction: a legacy transaction with an access list, which makes accessing the
+/// listed addresses and storage keys cheaper
+#[derive(Clone, Default, Serialize, Deserialize, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Eip2930TransactionRequest {
+    /// The fields shared with legacy transactions
+    #[serde(flatten)]
+    pub tx: TransactionRequest,
+
+    /// The addresses and storage keys the transaction plans to access
+    pub access_list: AccessList,
+
+    /// The chain the transaction is valid on, which is part of the signed payload
+    /// (None for the signer's or the node's chain)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<U64>,
+}
+
+impl Eip2930TransactionRequest {
+    /// Creates the request from a legacy transaction and an access list
+    pub fn new<T: Into<AccessList>>(tx: TransactionRequest, access_list: T) -> Self {
+        Self {
+            tx,
+            access_list: access_list.into(),
+            chain_id: None,
+        }
+    }
+
+    /// Sets the `chain_id` field in the transaction to the provided value
+    pub fn chain_id<T: Into<U64>>(mut self, chain_id: T) -> Self {
+        self.chain_id = Some(chain_id.into());
+        self
+    }
+
+    /// Hashes the transaction's data, which is what gets signed
+    pub fn sighash(&self) -> H256 {
+        keccak256(self.rlp().as_ref()).into()
+    }
+
+    /// Gets the unsigned transaction's encoding: the transaction type followed by the
+    /// RLP encoded fields
+    pub fn rlp(&self) -> Bytes {
+        let mut rlp = RlpStream::new();
+        rlp.begin_list(UNSIGNED_TX_FIELDS);
+        self.rlp_base(&mut rlp);
+        typed_envelope(EIP2930_TX_TYPE, rlp)
+    }
+
+    /// Produces the encoding of the transaction with the provided signature, ready to
+    /// be broadcast with `eth_sendRawTransaction`. The `v` of the signature may either be
+    /// the y-parity of the signature or in 'Electrum' or EIP-155 notation.
+    pub fn rlp_signed(&self, signature: &Signature) -> Bytes {
+        let mut rlp = RlpStream::new();
+        rlp.begin_list(SIGNED_TX_FIELDS);
+        self.rlp_base(&mut rlp);
+        rlp_signature(&mut rlp, signature);
+        typed_envelope(EIP2930_TX_TYPE, rlp)
+    }
+
+    fn rlp_base(&self, rlp: &mut RlpStream) {
+        rlp.append(&self.chain_id.unwrap_or_default());
+        rlp_opt(rlp, self.tx.nonce);
+        rlp_opt(rlp, self.tx.gas_price);
+        rlp_opt(rlp, self.tx.gas);
+        rlp_opt(rlp, self.tx.to.as_ref());
+        rlp_opt(rlp, self.tx.value);
+        rlp_opt(rlp, self.tx.data.as_ref().map(|d| d.as_ref()));
+        rlp.append(&self.access_list);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_access_list() {
+        let list: AccessList = serde_json::from_str(
+            r#"[{
+                "address": "0x0000000000000000000000000000000000000001",
+                "storageKeys": ["0x0000000000000000000000000000000000000000000000000000000000000002"]
+            }]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            list,
+            AccessList(vec![AccessListItem {
+                address: Address::from_low_u64_be(1),
+                storage_keys: vec![H256::from_low_u64_be(2)],
+            }])
+        );
+    }
+
+    #[test]
+    fn encodes_access_list() {
+        assert_eq!(rlp::encode(&AccessList::default()).to_vec(), vec![0xc0]);
+
+        let list = AccessList(vec![AccessListItem {
+            address: Address::zero(),
+            storage_keys: vec![],
+        }]);
+        // [[address, []]]
+        let mut expected = vec![0xd7, 0xd6, 0x94];
+        expected.extend_from_slice(&[0; 20]);
+        expected.push(0xc0);
+        assert_eq!(rlp::encode(&list).to_vec(), expected);
+    }
+
+    #[test]
+    fn encodes_eip2930_transactions() {
+        let tx = TransactionRequest::new()
+            .nonce(0)
+            .gas_price(1)
+            .gas(21_000)
+            .to(Address::zero())
+            .value(0);
+        let tx = Eip2930TransactionRequest::new(tx, AccessList::default()).chain_id(1u64);
+
+        // type || rlp([chain_id, nonce, gas_price, gas, to, value, data, []])
+        let mut expected = vec![0x01, 0xde, 0x01, 0x80, 0x01, 0x82, 0x52, 0x08, 0x94];
+        expected.extend_from_slice(&[0; 20]);
+        expected.extend_from_slice(&[0x80, 0x80, 0xc0]);
+        assert_eq!(tx.rlp().as_ref(), expected.as_slice());
+        assert_eq!(tx.sighash(), H256::from(keccak256(&expected)));
+
+        let signature = Signature {
+            r: H256::from_low_u64_be(1),
+            s: H256::from_low_u64_be(2),
+            v: 28,
+        };
+        let mut expected = vec![0x01, 0xe1];
+        expected.extend_from_slice(&tx.rlp().as_ref()[2..]);
+        expected.extend_from_slice(&[0x01, 0x01, 0x02]);
+        assert_eq!(tx.rlp_signed(&signature).as_ref(), expected.as_slice());
+    }
+
+    #[test]
+    fn serde_eip2930_request() {
+        let tx = Eip2930TransactionRequest::new(
+            TransactionRequest::new().gas_price(1),
+            vec![AccessListItem::default()],
+        );
+        assert_eq!(
+            serde_json::to_string(&tx).unwrap(),
+            r#"{"gasPrice":"0x1","accessList":[{"address":"0x0000000000000000000000000000000000000000","storageKeys":[]}]}"#
+        );
+
+        let res: AccessListWithGasUsed =
+            serde_json::from_str(r#"{"accessList":[],"gasUsed":"0x5208"}"#).unwrap();
+        assert_eq!(res.gas_used, 21_000.into());
+    }
+}
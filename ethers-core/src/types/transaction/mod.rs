@@ -7,6 +7,20 @@ use crate::{
 use rlp::RlpStream;
 use serde::{Deserialize, Serialize};
 
+mod eip1559;
+pub use eip1559::{Eip1559TransactionRequest, EIP1559_TX_TYPE};
+
+mod eip2718;
+pub use eip2718::TypedTransaction;
+
+mod eip2930;
+pub use eip2930::{
+    AccessList, AccessListItem, AccessListWithGasUsed, Eip2930TransactionRequest, EIP2930_TX_TYPE,
+};
+
+mod private;
+pub use private::{PrivateTransaction, PrivateTransactionPreferences};
+
 // Number of tx fields before signing
 #[cfg(not(feature = "celo"))]
 const UNSIGNED_TX_FIELDS: usize = 6;
@@ -233,6 +247,31 @@ fn rlp_opt<T: rlp::Encodable>(rlp: &mut RlpStream, opt: Option<T>) {
     }
 }
 
+/// Appends the signature of a typed transaction, whose `v` is the y-parity of the
+/// signature
+fn rlp_signature(rlp: &mut RlpStream, signature: &Signature) {
+    rlp.append(&y_parity(signature.v));
+    rlp.append(&U256::from_big_endian(signature.r.as_bytes()));
+    rlp.append(&U256::from_big_endian(signature.s.as_bytes()));
+}
+
+/// Wraps the RLP encoded fields of a typed transaction in its envelope, as defined in
+/// [EIP-2718](https://eips.ethereum.org/EIPS/eip-2718)
+fn typed_envelope(tx_type: u8, rlp: RlpStream) -> Bytes {
+    let mut encoded = vec![tx_type];
+    encoded.extend_from_slice(rlp.out().as_ref());
+    encoded.into()
+}
+
+/// Normalizes a `v` in 'Electrum' or EIP-155 notation to the y-parity of the signature
+fn y_parity(v: u64) -> u64 {
+    match v {
+        27 | 28 => v - 27,
+        v if v >= 35 => (v - 35) % 2,
+        v => v,
+    }
+}
+
 /// Details of a signed transaction
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct Transaction {
@@ -286,6 +325,41 @@ pub struct Transaction {
     /// ECDSA signature s
     pub s: U256,
 
+    /// The type of the transaction, None for legacy transactions
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub transaction_type: Option<U64>,
+
+    /// The chain the transaction is valid on (only set for typed transactions)
+    #[serde(rename = "chainId", default, skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<U64>,
+
+    /// The addresses and storage keys the transaction accesses (only set for typed
+    /// transactions)
+    #[serde(
+        rename = "accessList",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub access_list: Option<AccessList>,
+
+    /// The max fee per gas paid to the miner on top of the base fee (only set for
+    /// EIP-1559 transactions)
+    #[serde(
+        rename = "maxPriorityFeePerGas",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_priority_fee_per_gas: Option<U256>,
+
+    /// The max total fee per gas, including the base fee (only set for EIP-1559
+    /// transactions)
+    #[serde(
+        rename = "maxFeePerGas",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub max_fee_per_gas: Option<U256>,
+
     /////////////////  Celo-specific transaction fields /////////////////
     /// The currency fees are paid in (None for native currency)
     #[cfg(feature = "celo")]
@@ -324,7 +398,15 @@ impl Transaction {
         keccak256(&self.rlp().as_ref()).into()
     }
 
+    /// Returns the encoding of the signed transaction, which for typed transactions is
+    /// the transaction type followed by the RLP encoded fields
     pub fn rlp(&self) -> Bytes {
+        match self.transaction_type.map(|tx_type| tx_type.as_u64()) {
+            Some(tx_type) if tx_type == EIP2930_TX_TYPE as u64 => return self.rlp_eip2930(),
+            Some(tx_type) if tx_type == EIP1559_TX_TYPE as u64 => return self.rlp_eip1559(),
+            _ => {}
+        }
+
         let mut rlp = RlpStream::new();
         rlp.begin_list(SIGNED_TX_FIELDS);
         rlp.append(&self.nonce);
@@ -343,6 +425,51 @@ impl Transaction {
 
         rlp.out().freeze().into()
     }
+
+    fn rlp_eip1559(&self) -> Bytes {
+        let mut rlp = RlpStream::new();
+        rlp.begin_list(12);
+        rlp.append(&self.chain_id.unwrap_or_default());
+        rlp.append(&self.nonce);
+        rlp.append(&self.max_priority_fee_per_gas.unwrap_or_default());
+        rlp.append(&self.max_fee_per_gas.unwrap_or_default());
+        rlp.append(&self.gas);
+        rlp_opt(&mut rlp, self.to);
+        rlp.append(&self.value);
+        rlp.append(&self.input.as_ref());
+        rlp.append(&self.access_list.clone().unwrap_or_default());
+        rlp.append(&y_parity(self.v.as_u64()));
+        rlp.append(&self.r);
+        rlp.append(&self.s);
+        typed_envelope(EIP1559_TX_TYPE, rlp)
+    }
+
+    fn rlp_eip2930(&self) -> Bytes {
+        let mut rlp = RlpStream::new();
+        rlp.begin_list(11);
+        rlp.append(&self.chain_id.unwrap_or_default());
+        rlp.append(&self.nonce);
+        rlp.append(&self.gas_price);
+        rlp.append(&self.gas);
+        rlp_opt(&mut rlp, self.to);
+        rlp.append(&self.value);
+        rlp.append(&self.input.as_ref());
+        rlp.append(&self.access_list.clone().unwrap_or_default());
+        rlp.append(&y_parity(self.v.as_u64()));
+        rlp.append(&self.r);
+        rlp.append(&self.s);
+        typed_envelope(EIP2930_TX_TYPE, rlp)
+    }
+}
+
+/// A transaction signed by the node, as returned by `eth_signTransaction`
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignedTransaction {
+    /// The signed encoding of the transaction, ready to be broadcast with
+    /// `eth_sendRawTransaction`
+    pub raw: Bytes,
+    /// The signed transaction
+    pub tx: Transaction,
 }
 
 /// "Receipt" of an executed transaction: details of its execution.
@@ -380,6 +507,19 @@ pub struct TransactionReceipt {
     /// Logs bloom
     #[serde(rename = "logsBloom")]
     pub logs_bloom: Bloom,
+    /// The type of the transaction, None for legacy transactions or nodes which
+    /// predate [EIP-2718](https://eips.ethereum.org/EIPS/eip-2718)
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub transaction_type: Option<U64>,
+    /// The price paid per unit of gas: the gas price of legacy transactions, or the
+    /// base fee plus the priority fee of EIP-1559 transactions. Only returned by nodes
+    /// supporting EIP-1559.
+    #[serde(
+        rename = "effectiveGasPrice",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub effective_gas_price: Option<U256>,
 }
 
 #[cfg(test)]
@@ -447,4 +587,145 @@ mod tests {
         )
         .unwrap();
     }
+
+    #[test]
+    fn decode_eip1559_transaction_response() {
+        let tx: Transaction = serde_json::from_str(
+            r#"{
+            "blockHash":"0x1d59ff54b1eb26b013ce3cb5fc9dab3705b415a67127a003c3e61eb445bb8df2",
+            "blockNumber":"0x5daf3b",
+            "from":"0xa7d9ddbe1f17865597fbd27ec712455208b6b76d",
+            "gas":"0x5208",
+            "gasPrice":"0x3b9aca0e",
+            "maxFeePerGas":"0x77359400",
+            "maxPriorityFeePerGas":"0xe",
+            "hash":"0x88df016429689c079f3b2f6ad39fa052532c56795b733da78a91ebe6a713944b",
+            "input":"0x",
+            "nonce":"0x15",
+            "to":"0xf02c1c8e6114b1dbe8937a39260b5b0a374432bb",
+            "transactionIndex":"0x41",
+            "value":"0xf3dbb76162000",
+            "type":"0x2",
+            "accessList":[],
+            "chainId":"0x1",
+            "v":"0x1",
+            "r":"0x1b5e176d927f8e9ab405058b2d2457392da3e20f328b16ddabcebc33eaac5fea",
+            "s":"0x4ba69724e8f69de52f0125ad8b3c5c2cef33019bac3249e2c0a2192766d1721c"
+    }"#,
+        )
+        .unwrap();
+        assert_eq!(tx.transaction_type, Some(2.into()));
+        assert_eq!(tx.max_fee_per_gas, Some(2_000_000_000u64.into()));
+        assert_eq!(tx.max_priority_fee_per_gas, Some(14.into()));
+        assert_eq!(tx.access_list, Some(AccessList::default()));
+        // typed transactions are encoded in their envelope
+        assert_eq!(tx.rlp().as_ref()[0], EIP1559_TX_TYPE);
+
+        // the signature of a request matches the one of the response
+        let request = Eip1559TransactionRequest::new()
+            .chain_id(1u64)
+            .nonce(0x15)
+            .max_priority_fee_per_gas(14)
+            .max_fee_per_gas(2_000_000_000u64)
+            .gas(0x5208)
+            .to(tx.to.unwrap())
+            .value(tx.value);
+        let to_h256 = |value: U256| {
+            let mut bytes = [0; 32];
+            value.to_big_endian(&mut bytes);
+            H256::from(bytes)
+        };
+        let signature = Signature {
+            r: to_h256(tx.r),
+            s: to_h256(tx.s),
+            v: 1,
+        };
+        assert_eq!(request.rlp_signed(&signature), tx.rlp());
+    }
+
+    #[test]
+    fn decode_eip2930_transaction_response() {
+        let tx: Transaction = serde_json::from_str(
+            r#"{
+            "blockHash":"0x1d59ff54b1eb26b013ce3cb5fc9dab3705b415a67127a003c3e61eb445bb8df2",
+            "blockNumber":"0x5daf3b",
+            "from":"0xa7d9ddbe1f17865597fbd27ec712455208b6b76d",
+            "gas":"0x5208",
+            "gasPrice":"0x3b9aca00",
+            "hash":"0x88df016429689c079f3b2f6ad39fa052532c56795b733da78a91ebe6a713944b",
+            "input":"0x",
+            "nonce":"0x15",
+            "to":"0xf02c1c8e6114b1dbe8937a39260b5b0a374432bb",
+            "transactionIndex":"0x41",
+            "value":"0x0",
+            "type":"0x1",
+            "accessList":[{
+                "address":"0xf02c1c8e6114b1dbe8937a39260b5b0a374432bb",
+                "storageKeys":["0x0000000000000000000000000000000000000000000000000000000000000001"]
+            }],
+            "chainId":"0x1",
+            "v":"0x0",
+            "r":"0x1b5e176d927f8e9ab405058b2d2457392da3e20f328b16ddabcebc33eaac5fea",
+            "s":"0x4ba69724e8f69de52f0125ad8b3c5c2cef33019bac3249e2c0a2192766d1721c"
+    }"#,
+        )
+        .unwrap();
+        assert_eq!(tx.rlp().as_ref()[0], EIP2930_TX_TYPE);
+
+        let request = TransactionRequest::new()
+            .nonce(0x15)
+            .gas_price(1_000_000_000u64)
+            .gas(0x5208)
+            .to(tx.to.unwrap())
+            .value(0);
+        let request =
+            Eip2930TransactionRequest::new(request, tx.access_list.clone().unwrap()).chain_id(1u64);
+        let to_h256 = |value: U256| {
+            let mut bytes = [0; 32];
+            value.to_big_endian(&mut bytes);
+            H256::from(bytes)
+        };
+        let signature = Signature {
+            r: to_h256(tx.r),
+            s: to_h256(tx.s),
+            v: 0,
+        };
+        assert_eq!(request.rlp_signed(&signature), tx.rlp());
+    }
+
+    #[test]
+    fn decode_eip1559_receipt() {
+        let receipt: TransactionReceipt = serde_json::from_str(
+            r#"{
+            "transactionHash":"0x88df016429689c079f3b2f6ad39fa052532c56795b733da78a91ebe6a713944b",
+            "transactionIndex":"0x41",
+            "blockHash":"0x1d59ff54b1eb26b013ce3cb5fc9dab3705b415a67127a003c3e61eb445bb8df2",
+            "blockNumber":"0x5daf3b",
+            "cumulativeGasUsed":"0x5208",
+            "gasUsed":"0x5208",
+            "effectiveGasPrice":"0x3b9aca0e",
+            "contractAddress":null,
+            "logs":[],
+            "logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+            "status":"0x1",
+            "type":"0x2"
+    }"#,
+        )
+        .unwrap();
+        assert_eq!(receipt.transaction_type, Some(2.into()));
+        assert_eq!(receipt.effective_gas_price, Some(1_000_000_014.into()));
+
+        // nodes predating EIP-1559 do not return these fields
+        let legacy = TransactionReceipt {
+            transaction_type: None,
+            effective_gas_price: None,
+            ..receipt
+        };
+        let json = serde_json::to_string(&legacy).unwrap();
+        assert!(!json.contains("effectiveGasPrice"));
+        assert_eq!(
+            serde_json::from_str::<TransactionReceipt>(&json).unwrap(),
+            legacy
+        );
+    }
 }
@@ -0,0 +1,68 @@
+use crate::types::{Bytes, U64};
+use serde::{Deserialize, Serialize};
+
+/// A signed transaction submitted privately to block builders with
+/// `eth_sendPrivateTransaction` (e.g. via Flashbots Protect), which keeps it out of the
+/// public mempool where it could be frontrun
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrivateTransaction {
+    /// The signed encoding of the transaction
+    pub tx: Bytes,
+    /// The highest block the transaction may be included in, after which the builders
+    /// stop trying to include it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_block_number: Option<U64>,
+    /// The submission preferences
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preferences: Option<PrivateTransactionPreferences>,
+}
+
+/// The submission preferences of a [`PrivateTransaction`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrivateTransactionPreferences {
+    /// Whether the transaction is shared with all the builders, for faster inclusion
+    pub fast: bool,
+}
+
+impl PrivateTransaction {
+    /// Creates a private submission of the signed transaction
+    pub fn new<T: Into<Bytes>>(tx: T) -> Self {
+        Self {
+            tx: tx.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the `max_block_number` field to the provided value
+    pub fn max_block_number<T: Into<U64>>(mut self, max_block_number: T) -> Self {
+        self.max_block_number = Some(max_block_number.into());
+        self
+    }
+
+    /// Sets whether the transaction is shared with all the builders
+    pub fn fast(mut self, fast: bool) -> Self {
+        self.preferences = Some(PrivateTransactionPreferences { fast });
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_private_tx() {
+        let tx = PrivateTransaction::new(vec![1u8, 2])
+            .max_block_number(100u64)
+            .fast(true);
+        assert_eq!(
+            serde_json::to_string(&tx).unwrap(),
+            r#"{"tx":"0x0102","maxBlockNumber":"0x64","preferences":{"fast":true}}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&PrivateTransaction::new(vec![1u8])).unwrap(),
+            r#"{"tx":"0x01"}"#
+        );
+    }
+}
@@ -4,7 +4,11 @@ use serde::{
     de::{self, Deserializer, Visitor},
     Deserialize, Serialize,
 };
-use std::{collections::BTreeMap, fmt, str::FromStr};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fmt,
+    str::FromStr,
+};
 
 /// Transaction summary as found in the Txpool Inspection property.
 #[derive(Debug, Clone, PartialEq, Serialize)]
@@ -112,6 +116,32 @@ pub struct TxpoolContent {
     pub queued: BTreeMap<Address, BTreeMap<String, TxpoolTransaction>>,
 }
 
+impl TxpoolContent {
+    /// Returns the pending transactions in the order a miner would include them: by
+    /// descending gas price, while keeping the transactions of each sender in nonce order
+    pub fn pending_by_price(&self) -> Vec<TxpoolTransaction> {
+        let mut senders = self
+            .pending
+            .values()
+            .map(|txs| {
+                let mut txs = txs.values().collect::<Vec<_>>();
+                txs.sort_by_key(|tx| tx.nonce.unwrap_or_default());
+                VecDeque::from(txs)
+            })
+            .collect::<Vec<_>>();
+
+        let mut ordered = Vec::new();
+        while let Some(txs) = senders
+            .iter_mut()
+            .filter(|txs| !txs.is_empty())
+            .max_by_key(|txs| txs[0].gas_price.unwrap_or_default())
+        {
+            ordered.extend(txs.pop_front().cloned());
+        }
+        ordered
+    }
+}
+
 /// Transaction Pool Inspect
 ///
 /// The inspect inspection property can be queried to list a textual summary
@@ -266,6 +296,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn orders_pending_by_price() {
+        let tx = |from: u8, nonce: u64, gas_price: u64| {
+            TxpoolTransaction::new()
+                .from(Address::repeat_byte(from))
+                .nonce(nonce)
+                .gas_price(gas_price)
+        };
+        let mut content = TxpoolContent::default();
+        content.pending.insert(
+            Address::repeat_byte(1),
+            vec![
+                ("1".to_owned(), tx(1, 1, 10)),
+                ("0".to_owned(), tx(1, 0, 1)),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        content.pending.insert(
+            Address::repeat_byte(2),
+            vec![("5".to_owned(), tx(2, 5, 5))].into_iter().collect(),
+        );
+
+        // the high priced transaction of the first sender waits for its predecessor
+        assert_eq!(
+            content.pending_by_price(),
+            vec![tx(2, 5, 5), tx(1, 0, 1), tx(1, 1, 10)]
+        );
+    }
+
     #[test]
     fn serde_txpool_inspect() {
         let txpool_inspect_json = r#"
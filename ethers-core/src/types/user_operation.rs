@@ -0,0 +1,225 @@
+//! [ERC-4337](https://eips.ethereum.org/EIPS/eip-4337) account abstraction types
+use crate::{
+    abi::{encode, Token},
+    types::{Address, Bytes, Log, TransactionReceipt, H256, U256, U64},
+    utils::keccak256,
+};
+
+use serde::{Deserialize, Serialize};
+
+/// A user operation, submitted to a bundler on behalf of a smart contract account
+#[derive(Clone, Default, Serialize, Deserialize, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperation {
+    /// The account making the operation
+    pub sender: Address,
+    /// Anti-replay parameter, also used as the salt for first-time account creation
+    pub nonce: U256,
+    /// The factory address followed by its calldata, only set if the account is not yet deployed
+    pub init_code: Bytes,
+    /// The data to pass to the sender during the main execution call
+    pub call_data: Bytes,
+    /// The amount of gas to allocate to the main execution call
+    pub call_gas_limit: U256,
+    /// The amount of gas to allocate to the verification step
+    pub verification_gas_limit: U256,
+    /// The amount of gas paid to the bundler for pre-verification execution and calldata
+    pub pre_verification_gas: U256,
+    /// Maximum fee per gas (similar to EIP-1559 `max_fee_per_gas`)
+    pub max_fee_per_gas: U256,
+    /// Maximum priority fee per gas (similar to EIP-1559 `max_priority_fee_per_gas`)
+    pub max_priority_fee_per_gas: U256,
+    /// The paymaster address followed by its data, empty if the account pays for itself
+    pub paymaster_and_data: Bytes,
+    /// The data passed into the account along with the nonce during the verification step
+    pub signature: Bytes,
+}
+
+impl UserOperation {
+    /// Creates an empty user operation for the provided account
+    pub fn new<T: Into<Address>>(sender: T) -> Self {
+        Self {
+            sender: sender.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the `nonce` field
+    pub fn nonce<T: Into<U256>>(mut self, nonce: T) -> Self {
+        self.nonce = nonce.into();
+        self
+    }
+
+    /// Sets the `init_code` field
+    pub fn init_code<T: Into<Bytes>>(mut self, init_code: T) -> Self {
+        self.init_code = init_code.into();
+        self
+    }
+
+    /// Sets the `call_data` field
+    pub fn call_data<T: Into<Bytes>>(mut self, call_data: T) -> Self {
+        self.call_data = call_data.into();
+        self
+    }
+
+    /// Sets the `call_gas_limit` field
+    pub fn call_gas_limit<T: Into<U256>>(mut self, gas: T) -> Self {
+        self.call_gas_limit = gas.into();
+        self
+    }
+
+    /// Sets the `verification_gas_limit` field
+    pub fn verification_gas_limit<T: Into<U256>>(mut self, gas: T) -> Self {
+        self.verification_gas_limit = gas.into();
+        self
+    }
+
+    /// Sets the `pre_verification_gas` field
+    pub fn pre_verification_gas<T: Into<U256>>(mut self, gas: T) -> Self {
+        self.pre_verification_gas = gas.into();
+        self
+    }
+
+    /// Sets the `max_fee_per_gas` field
+    pub fn max_fee_per_gas<T: Into<U256>>(mut self, fee: T) -> Self {
+        self.max_fee_per_gas = fee.into();
+        self
+    }
+
+    /// Sets the `max_priority_fee_per_gas` field
+    pub fn max_priority_fee_per_gas<T: Into<U256>>(mut self, fee: T) -> Self {
+        self.max_priority_fee_per_gas = fee.into();
+        self
+    }
+
+    /// Sets the `paymaster_and_data` field
+    pub fn paymaster_and_data<T: Into<Bytes>>(mut self, data: T) -> Self {
+        self.paymaster_and_data = data.into();
+        self
+    }
+
+    /// Sets the `signature` field
+    pub fn signature<T: Into<Bytes>>(mut self, signature: T) -> Self {
+        self.signature = signature.into();
+        self
+    }
+
+    /// ABI-encodes the operation without its signature, hashing the dynamic fields, as
+    /// done by the EntryPoint contract's `pack` function
+    pub fn pack(&self) -> Bytes {
+        let hash = |data: &Bytes| Token::FixedBytes(keccak256(data.as_ref()).to_vec());
+        encode(&[
+            Token::Address(self.sender),
+            Token::Uint(self.nonce),
+            hash(&self.init_code),
+            hash(&self.call_data),
+            Token::Uint(self.call_gas_limit),
+            Token::Uint(self.verification_gas_limit),
+            Token::Uint(self.pre_verification_gas),
+            Token::Uint(self.max_fee_per_gas),
+            Token::Uint(self.max_priority_fee_per_gas),
+            hash(&self.paymaster_and_data),
+        ])
+        .into()
+    }
+
+    /// Returns the hash of the operation which must be signed by the account, binding it
+    /// to the provided EntryPoint and chain id
+    pub fn hash<T: Into<U256>>(&self, entry_point: Address, chain_id: T) -> H256 {
+        let encoded = encode(&[
+            Token::FixedBytes(keccak256(self.pack().as_ref()).to_vec()),
+            Token::Address(entry_point),
+            Token::Uint(chain_id.into()),
+        ]);
+        keccak256(encoded).into()
+    }
+}
+
+/// Gas estimates for a user operation, as returned by `eth_estimateUserOperationGas`
+#[derive(Clone, Default, Serialize, Deserialize, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperationGasEstimation {
+    pub pre_verification_gas: U256,
+    pub verification_gas_limit: U256,
+    pub call_gas_limit: U256,
+}
+
+/// A user operation along with its inclusion details, as returned by
+/// `eth_getUserOperationByHash`
+#[derive(Clone, Default, Serialize, Deserialize, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperationByHash {
+    pub user_operation: UserOperation,
+    pub entry_point: Address,
+    pub transaction_hash: H256,
+    pub block_hash: H256,
+    pub block_number: U64,
+}
+
+/// The receipt of an included user operation, as returned by `eth_getUserOperationReceipt`
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperationReceipt {
+    pub user_op_hash: H256,
+    pub entry_point: Address,
+    pub sender: Address,
+    pub nonce: U256,
+    /// The paymaster which paid for the operation, if any
+    pub paymaster: Option<Address>,
+    pub actual_gas_cost: U256,
+    pub actual_gas_used: U256,
+    /// Whether the execution call succeeded
+    pub success: bool,
+    /// The revert reason, if the execution call reverted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// The logs emitted by this specific operation
+    pub logs: Vec<Log>,
+    /// The receipt of the bundle transaction which included the operation
+    pub receipt: TransactionReceipt,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hashes_user_operation() {
+        let op = UserOperation::new(
+            "1111111111111111111111111111111111111111"
+                .parse::<Address>()
+                .unwrap(),
+        )
+        .nonce(1)
+        .call_data(vec![0x12, 0x34])
+        .call_gas_limit(100_000)
+        .verification_gas_limit(200_000)
+        .pre_verification_gas(50_000)
+        .max_fee_per_gas(10_000_000_000u64)
+        .max_priority_fee_per_gas(1_000_000_000);
+        let entry_point = "5FF137D4b0FDCD49DcA30c7CF57E578a026d2789"
+            .parse::<Address>()
+            .unwrap();
+
+        assert_eq!(op.pack().as_ref().len(), 10 * 32);
+        let expected: H256 = "221b9b8c787a25bb4554db9c7809d0b15e508b05730c272d0f1ce45cd66fb8cc"
+            .parse()
+            .unwrap();
+        assert_eq!(op.hash(entry_point, 1), expected);
+
+        // the signature is not part of the hash
+        let signed = op.signature(vec![1; 65]);
+        assert_eq!(signed.hash(entry_point, 1), expected);
+    }
+
+    #[test]
+    fn serde_user_operation() {
+        let op = UserOperation::new(Address::zero()).nonce(2);
+        let json = serde_json::to_value(&op).unwrap();
+        assert_eq!(json["nonce"], "0x2");
+        assert_eq!(json["initCode"], "0x");
+        assert_eq!(json["maxPriorityFeePerGas"], "0x0");
+        let decoded: UserOperation = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, op);
+    }
+}
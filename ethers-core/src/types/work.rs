@@ -0,0 +1,81 @@
+use crate::types::{H256, U64};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// The proof-of-work package of the block being mined, as returned by `eth_getWork`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Work {
+    /// The hash of the block header, without the nonce and mix digest
+    pub pow_hash: H256,
+    /// The seed hash used for the DAG
+    pub seed_hash: H256,
+    /// The boundary condition the solution must meet (2^256 / difficulty)
+    pub target: H256,
+    /// The number of the block, which is only returned by some nodes
+    pub number: Option<U64>,
+}
+
+impl Serialize for Work {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.number {
+            Some(number) => {
+                (self.pow_hash, self.seed_hash, self.target, number).serialize(serializer)
+            }
+            None => (self.pow_hash, self.seed_hash, self.target).serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Work {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum WorkTuple {
+            WithNumber(H256, H256, H256, U64),
+            WithoutNumber(H256, H256, H256),
+        }
+
+        Ok(match WorkTuple::deserialize(deserializer)? {
+            WorkTuple::WithNumber(pow_hash, seed_hash, target, number) => Work {
+                pow_hash,
+                seed_hash,
+                target,
+                number: Some(number),
+            },
+            WorkTuple::WithoutNumber(pow_hash, seed_hash, target) => Work {
+                pow_hash,
+                seed_hash,
+                target,
+                number: None,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_work() {
+        let hashes = r#""0x0101010101010101010101010101010101010101010101010101010101010101",
+            "0x0202020202020202020202020202020202020202020202020202020202020202",
+            "0x0303030303030303030303030303030303030303030303030303030303030303""#;
+
+        let work: Work = serde_json::from_str(&format!("[{}]", hashes)).unwrap();
+        assert_eq!(work.pow_hash, H256::repeat_byte(1));
+        assert_eq!(work.target, H256::repeat_byte(3));
+        assert_eq!(work.number, None);
+        let roundtrip: Work = serde_json::from_str(&serde_json::to_string(&work).unwrap()).unwrap();
+        assert_eq!(roundtrip, work);
+
+        let work: Work = serde_json::from_str(&format!("[{}, \"0x10\"]", hashes)).unwrap();
+        assert_eq!(work.seed_hash, H256::repeat_byte(2));
+        assert_eq!(work.number, Some(16.into()));
+    }
+}
@@ -1,5 +1,5 @@
 //! Various utilities for manipulating Ethereum related dat
-use ethabi::ethereum_types::H256;
+use ethabi::ethereum_types::{Address, H256};
 use tiny_keccak::{Hasher, Keccak};
 
 const PREFIX: &str = "\x19Ethereum Signed Message:\n";
@@ -21,6 +21,22 @@ where
     keccak256(&eth_message).into()
 }
 
+/// Hash data for an intended validator according to version `0x00` of EIP-191.
+///
+/// The data is enveloped as `"\x19\x00" + validator + data` and hashed using keccak256,
+/// so that a signature of it is only valid for the contract at the validator's address,
+/// e.g. a multisig wallet.
+pub fn hash_message_with_validator<S>(validator: Address, data: S) -> H256
+where
+    S: AsRef<[u8]>,
+{
+    let mut message = vec![0x19, 0x00];
+    message.extend_from_slice(validator.as_bytes());
+    message.extend_from_slice(data.as_ref());
+
+    keccak256(&message).into()
+}
+
 /// Compute the Keccak-256 hash of input bytes.
 // TODO: Add Solidity Keccak256 packing support
 pub fn keccak256<S>(bytes: S) -> [u8; 32]
@@ -83,6 +99,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_hash_message_with_validator() {
+        let hash = hash_message_with_validator(Address::repeat_byte(0x11), "hello");
+
+        assert_eq!(
+            hash,
+            "1efa12191222b5b77d265ea71874ca96f256435e0d9340fc2bc2fc8710a8cc61"
+                .parse()
+                .unwrap()
+        );
+        assert_ne!(hash, hash_message("hello"));
+    }
+
     #[test]
     fn simple_function_signature() {
         // test vector retrieved from
@@ -25,11 +25,15 @@ mod setup;
 pub use setup::*;
 
 mod hash;
-pub use hash::{hash_message, id, keccak256, serialize};
+pub use hash::{hash_message, hash_message_with_validator, id, keccak256, serialize};
 
 mod units;
 pub use units::Units;
 
+/// Merkle-Patricia trie proof verification
+pub(crate) mod trie;
+pub use trie::{verify_proof, verify_proof_value, ProofError, EMPTY_ROOT_HASH, KECCAK_EMPTY};
+
 /// Re-export RLP
 pub use rlp;
 
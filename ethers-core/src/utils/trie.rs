@@ -0,0 +1,311 @@
+use super::keccak256;
+use crate::types::H256;
+
+use rlp::{DecoderError, Rlp};
+use thiserror::Error;
+
+/// The root hash of an empty Merkle-Patricia trie, e.g. the storage trie of an account
+/// without storage
+pub const EMPTY_ROOT_HASH: H256 = H256([
+    0x56, 0xe8, 0x1f, 0x17, 0x1b, 0xcc, 0x55, 0xa6, 0xff, 0x83, 0x45, 0xe6, 0x92, 0xc0, 0xf8, 0x6e,
+    0x5b, 0x48, 0xe0, 0x1b, 0x99, 0x6c, 0xad, 0xc0, 0x01, 0x62, 0x2f, 0xb5, 0xe3, 0x63, 0xb4, 0x21,
+]);
+
+/// The hash of empty code, i.e. the code hash of externally owned accounts
+pub const KECCAK_EMPTY: H256 = H256([
+    0xc5, 0xd2, 0x46, 0x01, 0x86, 0xf7, 0x23, 0x3c, 0x92, 0x7e, 0x7d, 0xb2, 0xdc, 0xc7, 0x03, 0xc0,
+    0xe5, 0x00, 0xb6, 0x53, 0xca, 0x82, 0x27, 0x3b, 0x7b, 0xfa, 0xd8, 0x04, 0x5d, 0x85, 0xa4, 0x70,
+]);
+
+#[derive(Error, Debug, Clone, PartialEq)]
+/// Error thrown when verifying a Merkle-Patricia proof
+pub enum ProofError {
+    /// Thrown if a node of the proof does not have the hash referenced by its parent
+    #[error("node {0} of the proof does not match the hash referenced by its parent")]
+    InvalidHash(usize),
+
+    /// Thrown if the proof ends before reaching the value or proving its absence
+    #[error("the proof is missing nodes")]
+    MissingNode,
+
+    /// Thrown if a node is neither a branch, an extension nor a leaf
+    #[error("node {0} of the proof is malformed")]
+    InvalidNode(usize),
+
+    /// Thrown if a node could not be decoded
+    #[error("could not decode a node of the proof: {0:?}")]
+    Rlp(DecoderError),
+
+    /// Thrown if the proven value differs from the expected one
+    #[error("the proven value does not match")]
+    ValueMismatch,
+}
+
+impl From<DecoderError> for ProofError {
+    fn from(src: DecoderError) -> Self {
+        ProofError::Rlp(src)
+    }
+}
+
+/// The node referenced by a branch or extension node
+enum Child {
+    /// The child is empty, so the key is not in the trie
+    Empty,
+    /// The child is a node shorter than 32 bytes, which is embedded in its parent
+    Inline(Vec<u8>),
+    /// The child is referenced by its hash and is the next node of the proof
+    Hash(H256),
+}
+
+impl Child {
+    fn decode(rlp: Rlp<'_>, idx: usize) -> Result<Self, ProofError> {
+        if rlp.is_list() {
+            return Ok(Child::Inline(rlp.as_raw().to_vec()));
+        }
+        let data = rlp.data()?;
+        match data.len() {
+            0 => Ok(Child::Empty),
+            32 => Ok(Child::Hash(H256::from_slice(data))),
+            _ => Err(ProofError::InvalidNode(idx)),
+        }
+    }
+}
+
+/// Verifies the Merkle-Patricia `proof` of `key` against the `root` of a trie, e.g.
+/// the `accountProof` of an `eth_getProof` response against the state root of the
+/// block. The key is hashed with keccak256, as in the state and storage tries.
+///
+/// Returns the RLP encoded value stored at the key, or `None` if the proof shows that
+/// the key is not in the trie.
+pub fn verify_proof<T: AsRef<[u8]>>(
+    root: H256,
+    key: &[u8],
+    proof: &[T],
+) -> Result<Option<Vec<u8>>, ProofError> {
+    // the proof of an empty trie has no nodes
+    if root == EMPTY_ROOT_HASH && proof.is_empty() {
+        return Ok(None);
+    }
+
+    let path = to_nibbles(&keccak256(key));
+    let mut path = &path[..];
+
+    let mut nodes = proof.iter().map(AsRef::as_ref).enumerate();
+    let mut child = Child::Hash(root);
+    // the index of the proof node which contains the current node
+    let mut idx = 0;
+    loop {
+        let node = match child {
+            Child::Empty => return Ok(None),
+            Child::Inline(node) => node,
+            Child::Hash(hash) => match nodes.next() {
+                Some((i, node)) => {
+                    idx = i;
+                    if H256::from(keccak256(node)) != hash {
+                        return Err(ProofError::InvalidHash(idx));
+                    }
+                    node.to_vec()
+                }
+                None => return Err(ProofError::MissingNode),
+            },
+        };
+
+        let rlp = Rlp::new(&node);
+        child = match rlp.item_count()? {
+            // branch node
+            17 => match path.split_first() {
+                Some((nibble, rest)) => {
+                    path = rest;
+                    Child::decode(rlp.at(*nibble as usize)?, idx)?
+                }
+                None => {
+                    let value = rlp.at(16)?.data()?;
+                    return Ok(if value.is_empty() {
+                        None
+                    } else {
+                        Some(value.to_vec())
+                    });
+                }
+            },
+            // extension or leaf node
+            2 => {
+                let (is_leaf, node_path) =
+                    decode_compact(rlp.at(0)?.data()?).ok_or(ProofError::InvalidNode(idx))?;
+                if is_leaf {
+                    return Ok(if path == node_path.as_slice() {
+                        Some(rlp.at(1)?.data()?.to_vec())
+                    } else {
+                        None
+                    });
+                }
+                if !path.starts_with(&node_path) {
+                    return Ok(None);
+                }
+                path = &path[node_path.len()..];
+                Child::decode(rlp.at(1)?, idx)?
+            }
+            _ => return Err(ProofError::InvalidNode(idx)),
+        };
+    }
+}
+
+/// Verifies that `proof` shows `key` holding the RLP encoded `value`, or that the key
+/// is not in the trie if `value` is `None`
+pub fn verify_proof_value<T: AsRef<[u8]>>(
+    root: H256,
+    key: &[u8],
+    value: Option<&[u8]>,
+    proof: &[T],
+) -> Result<(), ProofError> {
+    let proven = verify_proof(root, key, proof)?;
+    if proven.as_deref() == value {
+        Ok(())
+    } else {
+        Err(ProofError::ValueMismatch)
+    }
+}
+
+/// Splits the bytes into nibbles, high nibble first
+fn to_nibbles(bytes: &[u8]) -> Vec<u8> {
+    bytes.iter().flat_map(|b| vec![b >> 4, b & 0x0f]).collect()
+}
+
+/// Decodes the hex-prefix encoded path of a leaf or extension node, returning whether
+/// the node is a leaf and the nibbles of the path
+fn decode_compact(encoded: &[u8]) -> Option<(bool, Vec<u8>)> {
+    let (first, rest) = encoded.split_first()?;
+    let flag = first >> 4;
+    let is_leaf = flag & 2 != 0;
+    if flag > 3 {
+        return None;
+    }
+    let mut nibbles = Vec::with_capacity(rest.len() * 2 + 1);
+    match flag & 1 {
+        // odd number of nibbles, the first is in the flag byte
+        1 => nibbles.push(first & 0x0f),
+        _ if first & 0x0f != 0 => return None,
+        _ => {}
+    }
+    nibbles.extend(to_nibbles(rest));
+    Some((is_leaf, nibbles))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use rlp::RlpStream;
+
+    /// Hex-prefix encodes the nibbles of a path
+    pub(crate) fn encode_compact(nibbles: &[u8], is_leaf: bool) -> Vec<u8> {
+        let flag = if is_leaf { 2 } else { 0 } + (nibbles.len() % 2) as u8;
+        let mut encoded = Vec::new();
+        let rest = if nibbles.len() % 2 == 1 {
+            encoded.push(flag << 4 | nibbles[0]);
+            &nibbles[1..]
+        } else {
+            encoded.push(flag << 4);
+            nibbles
+        };
+        encoded.extend(rest.chunks(2).map(|pair| pair[0] << 4 | pair[1]));
+        encoded
+    }
+
+    pub(crate) fn leaf(nibbles: &[u8], value: &[u8]) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(2);
+        stream.append(&encode_compact(nibbles, true));
+        stream.append(&value.to_vec());
+        stream.out().to_vec()
+    }
+
+    /// Builds a trie of two keys whose hashes start with different nibbles, returning
+    /// its root and the proofs of the keys
+    pub(crate) fn two_leaf_trie(
+        (key_a, value_a): (&[u8], &[u8]),
+        (key_b, value_b): (&[u8], &[u8]),
+    ) -> (H256, Vec<Vec<u8>>, Vec<Vec<u8>>) {
+        let path_a = to_nibbles(&keccak256(key_a));
+        let path_b = to_nibbles(&keccak256(key_b));
+        assert_ne!(path_a[0], path_b[0]);
+        let leaf_a = leaf(&path_a[1..], value_a);
+        let leaf_b = leaf(&path_b[1..], value_b);
+
+        let mut branch = RlpStream::new_list(17);
+        for nibble in 0..16 {
+            if nibble == path_a[0] {
+                branch.append(&keccak256(&leaf_a).to_vec());
+            } else if nibble == path_b[0] {
+                branch.append(&keccak256(&leaf_b).to_vec());
+            } else {
+                branch.append_empty_data();
+            }
+        }
+        branch.append_empty_data();
+        let branch = branch.out().to_vec();
+
+        let root = H256::from(keccak256(&branch));
+        (root, vec![branch.clone(), leaf_a], vec![branch, leaf_b])
+    }
+
+    #[test]
+    fn decodes_compact_paths() {
+        assert_eq!(decode_compact(&[0x00, 0x12]), Some((false, vec![1, 2])));
+        assert_eq!(decode_compact(&[0x11, 0x23]), Some((false, vec![1, 2, 3])));
+        assert_eq!(decode_compact(&[0x20, 0x12]), Some((true, vec![1, 2])));
+        assert_eq!(decode_compact(&[0x3f]), Some((true, vec![0xf])));
+        assert_eq!(decode_compact(&[0x01]), None);
+        assert_eq!(decode_compact(&[]), None);
+        for nibbles in &[vec![], vec![1], vec![1, 2, 3], vec![0xa, 0xb, 0xc, 0xd]] {
+            assert_eq!(
+                decode_compact(&encode_compact(nibbles, true)),
+                Some((true, nibbles.clone()))
+            );
+        }
+    }
+
+    #[test]
+    fn verifies_proofs() {
+        // keys whose hashes start with different nibbles
+        let (key_a, key_b) = (&[1u8][..], &[2u8][..]);
+        let value_a = [0xaa; 40];
+        let value_b = [0xbb; 40];
+        let (root, proof_a, proof_b) = two_leaf_trie((key_a, &value_a), (key_b, &value_b));
+
+        assert_eq!(
+            verify_proof(root, key_a, &proof_a).unwrap(),
+            Some(value_a.to_vec())
+        );
+        assert_eq!(
+            verify_proof(root, key_b, &proof_b).unwrap(),
+            Some(value_b.to_vec())
+        );
+        verify_proof_value(root, key_a, Some(&value_a), &proof_a).unwrap();
+        assert_eq!(
+            verify_proof_value(root, key_a, Some(&value_b), &proof_a),
+            Err(ProofError::ValueMismatch)
+        );
+
+        // the wrong leaf does not match the hash in the branch
+        assert_eq!(
+            verify_proof(root, key_a, &proof_b),
+            Err(ProofError::InvalidHash(1))
+        );
+        // the proof must reach the leaf
+        assert_eq!(
+            verify_proof(root, key_a, &proof_a[..1]),
+            Err(ProofError::MissingNode)
+        );
+        // the root must match
+        assert_eq!(
+            verify_proof(H256::zero(), key_a, &proof_a),
+            Err(ProofError::InvalidHash(0))
+        );
+
+        // an empty trie proves the absence of all keys
+        assert_eq!(
+            verify_proof::<Vec<u8>>(EMPTY_ROOT_HASH, key_a, &[]),
+            Ok(None)
+        );
+        assert_eq!(H256::from(keccak256(&rlp::NULL_RLP)), EMPTY_ROOT_HASH);
+        assert_eq!(H256::from(keccak256(&[] as &[u8])), KECCAK_EMPTY);
+    }
+}
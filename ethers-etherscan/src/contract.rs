@@ -0,0 +1,145 @@
+use crate::{Client, EtherscanError};
+
+use ethers_core::{abi::Abi, types::Address};
+
+use serde::Deserialize;
+
+/// The result returned for contracts whose source code was not verified
+const NOT_VERIFIED: &str = "Contract source code not verified";
+
+/// The source code and compilation settings of a verified contract
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "PascalCase")]
+pub struct ContractMetadata {
+    /// The source code of the contract. Contracts verified as multiple files or with
+    /// the standard JSON input have it wrapped in a JSON object.
+    pub source_code: String,
+    /// The JSON ABI of the contract
+    #[serde(rename = "ABI")]
+    pub abi: String,
+    /// The name of the contract
+    pub contract_name: String,
+    /// The version of the compiler, e.g. `v0.8.4+commit.c7e474f2`
+    pub compiler_version: String,
+    /// Whether the optimizer was enabled, `1` if it was
+    pub optimization_used: String,
+    /// The number of runs of the optimizer
+    pub runs: String,
+    /// The ABI encoded arguments passed to the constructor
+    pub constructor_arguments: String,
+    /// The EVM version targeted by the compiler
+    #[serde(rename = "EVMVersion")]
+    pub evm_version: String,
+    /// The libraries linked into the contract
+    pub library: String,
+    /// The SPDX license of the source code
+    pub license_type: String,
+    /// Whether the contract is a proxy, `1` if it is
+    pub proxy: String,
+    /// The address of the implementation of proxy contracts
+    pub implementation: String,
+    /// The swarm hash of the metadata of the contract
+    pub swarm_source: String,
+}
+
+impl ContractMetadata {
+    /// Parses the JSON ABI of the contract
+    pub fn abi(&self) -> Result<Abi, EtherscanError> {
+        Ok(serde_json::from_str(&self.abi)?)
+    }
+
+    /// Returns whether the contract is a proxy, whose logic lives at its implementation
+    /// address
+    pub fn is_proxy(&self) -> bool {
+        self.proxy == "1"
+    }
+}
+
+impl Client {
+    /// Retrieves the ABI of a verified contract
+    pub async fn contract_abi(&self, address: Address) -> Result<Abi, EtherscanError> {
+        let abi: String = self
+            .get(
+                "contract",
+                "getabi",
+                &[("address", &format!("{:?}", address))],
+            )
+            .await
+            .map_err(|err| not_verified(err, address))?;
+        Ok(serde_json::from_str(&abi)?)
+    }
+
+    /// Retrieves the source code and the compilation settings of a verified contract
+    pub async fn contract_source_code(
+        &self,
+        address: Address,
+    ) -> Result<ContractMetadata, EtherscanError> {
+        let mut metadata: Vec<ContractMetadata> = self
+            .get(
+                "contract",
+                "getsourcecode",
+                &[("address", &format!("{:?}", address))],
+            )
+            .await
+            .map_err(|err| not_verified(err, address))?;
+        match metadata.pop() {
+            // unverified contracts are returned with an empty source code
+            Some(metadata) if metadata.abi != NOT_VERIFIED => Ok(metadata),
+            _ => Err(EtherscanError::ContractCodeNotVerified(address)),
+        }
+    }
+}
+
+/// Converts the error response for unverified contracts into a
+/// `ContractCodeNotVerified` error
+fn not_verified(err: EtherscanError, address: Address) -> EtherscanError {
+    match err {
+        EtherscanError::ErrorResponse { ref result, .. } if result == NOT_VERIFIED => {
+            EtherscanError::ContractCodeNotVerified(address)
+        }
+        err => err,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_contract_metadata() {
+        let metadata: ContractMetadata = serde_json::from_str(
+            r#"{
+                "SourceCode": "pragma solidity ^0.8.0; contract Greeter {}",
+                "ABI": "[{\"inputs\":[],\"name\":\"greet\",\"outputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\"}],\"stateMutability\":\"view\",\"type\":\"function\"}]",
+                "ContractName": "Greeter",
+                "CompilerVersion": "v0.8.4+commit.c7e474f2",
+                "OptimizationUsed": "1",
+                "Runs": "200",
+                "ConstructorArguments": "",
+                "EVMVersion": "Default",
+                "Library": "",
+                "LicenseType": "MIT",
+                "Proxy": "0",
+                "Implementation": "",
+                "SwarmSource": ""
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(metadata.contract_name, "Greeter");
+        assert!(!metadata.is_proxy());
+        let abi = metadata.abi().unwrap();
+        assert!(abi.function("greet").is_ok());
+    }
+
+    #[test]
+    fn detects_unverified_contracts() {
+        let err = EtherscanError::ErrorResponse {
+            message: "NOTOK".to_owned(),
+            result: NOT_VERIFIED.to_owned(),
+        };
+        assert!(matches!(
+            not_verified(err, Address::repeat_byte(1)),
+            EtherscanError::ContractCodeNotVerified(addr) if addr == Address::repeat_byte(1)
+        ));
+    }
+}
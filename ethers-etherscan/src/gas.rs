@@ -0,0 +1,73 @@
+use crate::{Client, EtherscanError};
+
+use serde::{Deserialize, Deserializer};
+
+/// The gas prices suggested by the gas tracker, in gwei
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct GasOracle {
+    /// The gas price for a transaction to be included within a few minutes
+    #[serde(rename = "SafeGasPrice", deserialize_with = "deserialize_number")]
+    pub safe_gas_price: f64,
+    /// The suggested gas price
+    #[serde(rename = "ProposeGasPrice", deserialize_with = "deserialize_number")]
+    pub propose_gas_price: f64,
+    /// The gas price for a transaction to be included in the next blocks
+    #[serde(rename = "FastGasPrice", deserialize_with = "deserialize_number")]
+    pub fast_gas_price: f64,
+    /// The suggested base fee of the next block. Only returned on chains which
+    /// implement EIP-1559.
+    #[serde(
+        rename = "suggestBaseFee",
+        default,
+        deserialize_with = "deserialize_optional_number"
+    )]
+    pub suggest_base_fee: Option<f64>,
+}
+
+impl Client {
+    /// Retrieves the gas prices suggested by the gas tracker
+    pub async fn gas_oracle(&self) -> Result<GasOracle, EtherscanError> {
+        self.get("gastracker", "gasoracle", &[]).await
+    }
+}
+
+/// The API returns numbers as strings, which may have decimals
+fn deserialize_number<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    String::deserialize(deserializer)?
+        .parse()
+        .map_err(serde::de::Error::custom)
+}
+
+fn deserialize_optional_number<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer)?
+        .map(|s| s.parse().map_err(serde::de::Error::custom))
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_gas_oracle() {
+        let oracle: GasOracle = serde_json::from_str(
+            r#"{"LastBlock":"13053741","SafeGasPrice":"20","ProposeGasPrice":"22","FastGasPrice":"22","suggestBaseFee":"19.230609716","gasUsedRatio":"0.370119078777807,0.8954731"}"#,
+        )
+        .unwrap();
+        assert_eq!(oracle.propose_gas_price, 22.0);
+        assert_eq!(oracle.suggest_base_fee, Some(19.230609716));
+
+        let oracle: GasOracle = serde_json::from_str(
+            r#"{"LastBlock":"10390330","SafeGasPrice":"5","ProposeGasPrice":"5","FastGasPrice":"6"}"#,
+        )
+        .unwrap();
+        assert_eq!(oracle.fast_gas_price, 6.0);
+        assert_eq!(oracle.suggest_base_fee, None);
+    }
+}
@@ -0,0 +1,204 @@
+//! # Ethers Etherscan
+//!
+//! A client for the [Etherscan](https://etherscan.io/apis) API, and for its deployments on
+//! other chains such as BscScan or PolygonScan.
+//!
+//! ```no_run
+//! use ethers_etherscan::Client;
+//!
+//! # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = Client::new(1, Some("YOUR_API_KEY"))?;
+//! let address = "0xBB9bc244D798123fDe783fCc1C72d3Bb8C189413".parse()?;
+//! let abi = client.contract_abi(address).await?;
+//! # Ok(())
+//! # }
+//! ```
+#![deny(broken_intra_doc_links)]
+
+mod contract;
+pub use contract::ContractMetadata;
+
+mod gas;
+pub use gas::GasOracle;
+
+use ethers_core::types::Address;
+
+use reqwest::Client as HttpClient;
+use serde::{de::DeserializeOwned, Deserialize};
+use std::env;
+use thiserror::Error;
+use url::Url;
+
+const ETHERSCAN_URL: &str = "https://api.etherscan.io/api";
+
+/// The environment variable read by [`Client::new_from_env`]
+pub const ETHERSCAN_API_KEY_ENV: &str = "ETHERSCAN_API_KEY";
+
+#[derive(Error, Debug)]
+/// Errors thrown by the Etherscan client
+pub enum EtherscanError {
+    /// Thrown when there is no Etherscan deployment for the chain
+    #[error("chain {0} is not supported by etherscan")]
+    ChainNotSupported(u64),
+
+    /// Thrown when the source code of the contract is not verified, so its ABI is unknown
+    #[error("the source code of contract {0:?} is not verified")]
+    ContractCodeNotVerified(Address),
+
+    /// Thrown when the API returns an error, e.g. for an invalid API key
+    #[error("etherscan responded with `{message}`: {result}")]
+    ErrorResponse {
+        /// The status message of the response
+        message: String,
+        /// The details of the error
+        result: String,
+    },
+
+    /// Thrown when the HTTP request fails
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+
+    /// Thrown when the response, or the ABI within it, cannot be deserialized
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+
+    /// Thrown when the API url is invalid
+    #[error(transparent)]
+    Url(#[from] url::ParseError),
+}
+
+/// A client over HTTP for the Etherscan API of a chain
+#[derive(Clone, Debug)]
+pub struct Client {
+    client: HttpClient,
+    api_key: Option<String>,
+    url: Url,
+}
+
+/// The envelope of the API's responses. Failed requests have a `0` status and
+/// describe the error in their result.
+#[derive(Deserialize)]
+struct Response {
+    status: String,
+    message: String,
+    result: serde_json::Value,
+}
+
+impl Client {
+    /// Creates a new client for the Etherscan deployment of the chain, e.g. BscScan for
+    /// chain 56. Requests are rate limited without an API key.
+    pub fn new(chain_id: u64, api_key: Option<&str>) -> Result<Self, EtherscanError> {
+        let url = match chain_id {
+            1 => ETHERSCAN_URL,
+            3 => "https://api-ropsten.etherscan.io/api",
+            4 => "https://api-rinkeby.etherscan.io/api",
+            5 => "https://api-goerli.etherscan.io/api",
+            10 => "https://api-optimistic.etherscan.io/api",
+            42 => "https://api-kovan.etherscan.io/api",
+            56 => "https://api.bscscan.com/api",
+            137 => "https://api.polygonscan.com/api",
+            250 => "https://api.ftmscan.com/api",
+            42161 => "https://api.arbiscan.io/api",
+            43114 => "https://api.snowtrace.io/api",
+            _ => return Err(EtherscanError::ChainNotSupported(chain_id)),
+        };
+        Self::with_url(url, api_key)
+    }
+
+    /// Creates a new client for the chain with the API key of the `ETHERSCAN_API_KEY`
+    /// environment variable, if it is set
+    pub fn new_from_env(chain_id: u64) -> Result<Self, EtherscanError> {
+        let api_key = env::var(ETHERSCAN_API_KEY_ENV).ok();
+        Self::new(chain_id, api_key.as_deref())
+    }
+
+    /// Creates a new client for an Etherscan compatible API at `url`
+    pub fn with_url(url: &str, api_key: Option<&str>) -> Result<Self, EtherscanError> {
+        Ok(Self {
+            client: HttpClient::new(),
+            api_key: api_key.map(str::to_owned),
+            url: Url::parse(url)?,
+        })
+    }
+
+    /// Returns the url of the API the client queries
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Sends a GET request for the action of the module, returning the result of the
+    /// response
+    pub(crate) async fn get<T: DeserializeOwned>(
+        &self,
+        module: &str,
+        action: &str,
+        params: &[(&str, &str)],
+    ) -> Result<T, EtherscanError> {
+        let mut url = self.url.clone();
+        url.query_pairs_mut()
+            .append_pair("module", module)
+            .append_pair("action", action)
+            .extend_pairs(params);
+        if let Some(ref key) = self.api_key {
+            url.query_pairs_mut().append_pair("apikey", key);
+        }
+
+        let res = self
+            .client
+            .get(url)
+            .send()
+            .await?
+            .json::<Response>()
+            .await?;
+        res.into_result()
+    }
+}
+
+impl Response {
+    fn into_result<T: DeserializeOwned>(self) -> Result<T, EtherscanError> {
+        if self.status == "1" {
+            return Ok(serde_json::from_value(self.result)?);
+        }
+        let result = match self.result {
+            serde_json::Value::String(result) => result,
+            result => result.to_string(),
+        };
+        Err(EtherscanError::ErrorResponse {
+            message: self.message,
+            result,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_responses() {
+        let res: Response =
+            serde_json::from_str(r#"{"status":"1","message":"OK","result":"hello"}"#).unwrap();
+        assert_eq!(res.into_result::<String>().unwrap(), "hello");
+
+        let res: Response =
+            serde_json::from_str(r#"{"status":"0","message":"NOTOK","result":"Invalid API Key"}"#)
+                .unwrap();
+        match res.into_result::<String>().unwrap_err() {
+            EtherscanError::ErrorResponse { message, result } => {
+                assert_eq!(message, "NOTOK");
+                assert_eq!(result, "Invalid API Key");
+            }
+            err => panic!("unexpected error {:?}", err),
+        }
+    }
+
+    #[test]
+    fn selects_chain_url() {
+        let client = Client::new(137, None).unwrap();
+        assert_eq!(client.url().as_str(), "https://api.polygonscan.com/api");
+        assert!(matches!(
+            Client::new(1337, None).unwrap_err(),
+            EtherscanError::ChainNotSupported(1337)
+        ));
+    }
+}
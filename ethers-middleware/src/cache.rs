@@ -0,0 +1,633 @@
+use async_trait::async_trait;
+use ethers_core::types::*;
+use ethers_providers::{FromErr, JsonRpcError, Middleware};
+use futures_util::lock::Mutex as AsyncMutex;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::{
+    collections::{BTreeMap, HashMap},
+    future::Future,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+use thiserror::Error;
+
+#[derive(Debug)]
+/// Middleware memoizing the responses of queries, so that workloads reading the same
+/// blocks over and over (e.g. backtests) only fetch them once.
+///
+/// The responses of the chain id, of queries pinned to a block hash or to a block number
+/// with enough [`confirmations`](Self::confirmations), and of transactions and receipts
+/// mined in such blocks are assumed to never change, and are cached until evicted. The
+/// responses of the other cached queries (e.g. at the latest block or at a recent block,
+/// which may be reorged) are only cached if a [`ttl`](Self::ttl) is set, and expire after
+/// it. When the cache is full, the least recently used response is evicted. Concurrent
+/// queries of a response which is not cached yet are only sent once.
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers::{
+///     middleware::CacheMiddleware,
+///     providers::{Http, Middleware, Provider},
+///     types::H256,
+/// };
+/// use std::{convert::TryFrom, time::Duration};
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Provider::<Http>::try_from("http://localhost:8545")?;
+/// let provider = CacheMiddleware::new(provider, 10_000).ttl(Duration::from_secs(12));
+///
+/// // only the first query is sent to the node
+/// let block = provider.get_block(H256::zero()).await?;
+/// let block = provider.get_block(H256::zero()).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct CacheMiddleware<M> {
+    inner: M,
+    cache: Mutex<Cache>,
+    ttl: Option<Duration>,
+    confirmations: u64,
+    head: AtomicU64,
+    pending: Mutex<HashMap<String, Arc<AsyncMutex<()>>>>,
+}
+
+impl<M> CacheMiddleware<M>
+where
+    M: Middleware,
+{
+    /// Instantiates the middleware, caching up to `capacity` responses. The responses
+    /// which may change are not cached until a [`ttl`](Self::ttl) is set.
+    pub fn new(inner: M, capacity: usize) -> Self {
+        Self {
+            inner,
+            cache: Mutex::new(Cache::new(capacity)),
+            ttl: None,
+            confirmations: 12,
+            head: AtomicU64::new(0),
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Sets the time after which the cached responses which may change expire
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Sets the number of confirmations after which a block is assumed to never be
+    /// reorged, so that the responses of queries at it never change (default: 12)
+    pub fn confirmations(mut self, confirmations: u64) -> Self {
+        self.confirmations = confirmations;
+        self
+    }
+
+    /// Returns the number of cached responses, including the expired ones which were not
+    /// evicted yet
+    pub fn len(&self) -> usize {
+        self.cache.lock().unwrap().entries.len()
+    }
+
+    /// Returns whether no responses are cached
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Evicts all the cached responses
+    pub fn clear(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+
+    /// Returns the cached response of the query, or fetches and caches it. Responses are
+    /// only cached if they are not null, e.g. blocks which are not known yet.
+    async fn cached<P, T, F>(
+        &self,
+        method: &str,
+        params: P,
+        immutable: bool,
+        fetch: F,
+    ) -> Result<T, CacheError<M>>
+    where
+        P: Serialize,
+        T: Serialize + DeserializeOwned,
+        F: Future<Output = Result<T, M::Error>>,
+    {
+        if !immutable && self.ttl.is_none() {
+            // the response may change and no ttl is set
+            return fetch.await.map_err(CacheError::MiddlewareError);
+        }
+
+        let key = cache_key(method, params)?;
+        if let Some(res) = self.lookup(&key)? {
+            return Ok(res);
+        }
+        self.fetch_once(&key, async {
+            let res = fetch.await.map_err(CacheError::MiddlewareError)?;
+            self.store(&key, &res, immutable)?;
+            Ok(res)
+        })
+        .await
+    }
+
+    /// Runs the query which caches the response of the key, unless a concurrent query
+    /// cached it while waiting for it to complete
+    async fn fetch_once<T, F>(&self, key: &str, fetch: F) -> Result<T, CacheError<M>>
+    where
+        T: DeserializeOwned,
+        F: Future<Output = Result<T, CacheError<M>>>,
+    {
+        let lock = self
+            .pending
+            .lock()
+            .unwrap()
+            .entry(key.to_owned())
+            .or_default()
+            .clone();
+        let res = {
+            let _guard = lock.lock().await;
+            match self.lookup(key) {
+                Ok(Some(res)) => Ok(res),
+                Ok(None) => fetch.await,
+                Err(err) => Err(err),
+            }
+        };
+        // the lock is only removed by the last query holding it, so that the queries
+        // starting meanwhile still wait for the ones holding it
+        let mut pending = self.pending.lock().unwrap();
+        if Arc::strong_count(&lock) == 2 {
+            pending.remove(key);
+        }
+        res
+    }
+
+    /// Returns whether the state at the block never changes, i.e. the block is pinned by
+    /// its hash or has enough confirmations
+    async fn is_immutable(&self, block: Option<BlockId>) -> Result<bool, CacheError<M>> {
+        match block {
+            Some(BlockId::Hash(_)) | Some(BlockId::CanonicalHash(_)) => Ok(true),
+            Some(BlockId::Number(BlockNumber::Number(number))) => self.is_confirmed(number).await,
+            _ => Ok(false),
+        }
+    }
+
+    /// Returns whether the block has enough confirmations, only fetching the latest block
+    /// number if the last one seen is not enough
+    async fn is_confirmed(&self, number: U64) -> Result<bool, CacheError<M>> {
+        let confirmed = |head: u64| number.as_u64().saturating_add(self.confirmations) <= head;
+        if confirmed(self.head.load(Ordering::Relaxed)) {
+            return Ok(true);
+        }
+        let head = self
+            .inner
+            .get_block_number()
+            .await
+            .map_err(CacheError::MiddlewareError)?;
+        self.head.fetch_max(head.as_u64(), Ordering::Relaxed);
+        Ok(confirmed(head.as_u64()))
+    }
+
+    /// Returns the cached response, if it did not expire
+    fn lookup<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>, CacheError<M>> {
+        let value = self.cache.lock().unwrap().get(key);
+        Ok(value.map(serde_json::from_value).transpose()?)
+    }
+
+    /// Caches the response unless it is null. Responses which may change expire after
+    /// the ttl, and are not cached if no ttl is set.
+    fn store<T: Serialize>(
+        &self,
+        key: &str,
+        res: &T,
+        immutable: bool,
+    ) -> Result<(), CacheError<M>> {
+        let expires = match self.ttl {
+            _ if immutable => None,
+            Some(ttl) => Some(Instant::now() + ttl),
+            None => return Ok(()),
+        };
+        let value = serde_json::to_value(res)?;
+        if !value.is_null() {
+            self.cache
+                .lock()
+                .unwrap()
+                .insert(key.to_owned(), value, expires);
+        }
+        Ok(())
+    }
+}
+
+fn cache_key<P: Serialize>(method: &str, params: P) -> Result<String, serde_json::Error> {
+    Ok(format!("{}:{}", method, serde_json::to_string(&params)?))
+}
+
+/// A least recently used cache of serialized responses, whose keys are also ordered by
+/// last use so that evictions do not scan the entries
+#[derive(Debug)]
+struct Cache {
+    capacity: usize,
+    entries: HashMap<String, Entry>,
+    recency: BTreeMap<u64, String>,
+    tick: u64,
+}
+
+#[derive(Debug)]
+struct Entry {
+    value: Value,
+    last_used: u64,
+    expires: Option<Instant>,
+}
+
+impl Cache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            tick: 0,
+        }
+    }
+
+    fn get(&mut self, key: &str) -> Option<Value> {
+        self.tick += 1;
+        let entry = self.entries.get_mut(key)?;
+        match entry.expires {
+            Some(expires) if expires <= Instant::now() => {
+                self.remove(key);
+                None
+            }
+            _ => {
+                self.recency.remove(&entry.last_used);
+                self.recency.insert(self.tick, key.to_owned());
+                entry.last_used = self.tick;
+                Some(entry.value.clone())
+            }
+        }
+    }
+
+    fn insert(&mut self, key: String, value: Value, expires: Option<Instant>) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.remove(&key).is_none() && self.entries.len() >= self.capacity {
+            // evict the least recently used entry
+            let lru = self.recency.values().next().cloned();
+            if let Some(lru) = lru {
+                self.remove(&lru);
+            }
+        }
+
+        self.tick += 1;
+        self.recency.insert(self.tick, key.clone());
+        self.entries.insert(
+            key,
+            Entry {
+                value,
+                last_used: self.tick,
+                expires,
+            },
+        );
+    }
+
+    fn remove(&mut self, key: &str) -> Option<Entry> {
+        let entry = self.entries.remove(key)?;
+        self.recency.remove(&entry.last_used);
+        Some(entry)
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+}
+
+#[derive(Error, Debug)]
+/// Thrown when an error happens at the cache middleware
+pub enum CacheError<M: Middleware> {
+    /// Thrown when the internal middleware errors
+    #[error("{0}")]
+    MiddlewareError(M::Error),
+
+    /// Thrown when a response cannot be (de)serialized to or from the cache
+    #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
+}
+
+impl<M: Middleware> FromErr<M::Error> for CacheError<M> {
+    fn from(src: M::Error) -> Self {
+        CacheError::MiddlewareError(src)
+    }
+}
+
+#[async_trait]
+impl<M> Middleware for CacheMiddleware<M>
+where
+    M: Middleware,
+{
+    type Error = CacheError<M>;
+    type Provider = M::Provider;
+    type Inner = M;
+
+    fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    fn as_error_response<'a>(&self, err: &'a Self::Error) -> Option<&'a JsonRpcError> {
+        match err {
+            CacheError::MiddlewareError(err) => self.inner.as_error_response(err),
+            _ => None,
+        }
+    }
+
+    async fn get_chainid(&self) -> Result<U256, Self::Error> {
+        self.cached("eth_chainId", (), true, self.inner.get_chainid())
+            .await
+    }
+
+    async fn get_block_number(&self) -> Result<U64, Self::Error> {
+        let head = self
+            .cached("eth_blockNumber", (), false, self.inner.get_block_number())
+            .await?;
+        self.head.fetch_max(head.as_u64(), Ordering::Relaxed);
+        Ok(head)
+    }
+
+    async fn get_block<T: Into<BlockId> + Send + Sync>(
+        &self,
+        block_hash_or_number: T,
+    ) -> Result<Option<Block<TxHash>>, Self::Error> {
+        let block = block_hash_or_number.into();
+        self.cached(
+            "eth_getBlock",
+            block,
+            self.is_immutable(Some(block)).await?,
+            self.inner.get_block(block),
+        )
+        .await
+    }
+
+    async fn get_block_with_txs<T: Into<BlockId> + Send + Sync>(
+        &self,
+        block_hash_or_number: T,
+    ) -> Result<Option<Block<Transaction>>, Self::Error> {
+        let block = block_hash_or_number.into();
+        self.cached(
+            "eth_getBlockWithTxs",
+            block,
+            self.is_immutable(Some(block)).await?,
+            self.inner.get_block_with_txs(block),
+        )
+        .await
+    }
+
+    async fn get_transaction<T: Send + Sync + Into<TxHash>>(
+        &self,
+        transaction_hash: T,
+    ) -> Result<Option<Transaction>, Self::Error> {
+        let hash = transaction_hash.into();
+        let key = cache_key("eth_getTransactionByHash", hash)?;
+        if let Some(tx) = self.lookup(&key)? {
+            return Ok(Some(tx));
+        }
+
+        self.fetch_once(&key, async {
+            let tx = self
+                .inner
+                .get_transaction(hash)
+                .await
+                .map_err(CacheError::MiddlewareError)?;
+            // pending transactions are fetched again until they are mined, and mined ones
+            // expire until their block has enough confirmations
+            if let Some(ref tx) = tx {
+                if let Some(block_number) = tx.block_number {
+                    self.store(&key, tx, self.is_confirmed(block_number).await?)?;
+                }
+            }
+            Ok(tx)
+        })
+        .await
+    }
+
+    async fn get_transaction_receipt<T: Send + Sync + Into<TxHash>>(
+        &self,
+        transaction_hash: T,
+    ) -> Result<Option<TransactionReceipt>, Self::Error> {
+        let hash = transaction_hash.into();
+        let key = cache_key("eth_getTransactionReceipt", hash)?;
+        if let Some(receipt) = self.lookup(&key)? {
+            return Ok(Some(receipt));
+        }
+
+        self.fetch_once(&key, async {
+            let receipt = self
+                .inner
+                .get_transaction_receipt(hash)
+                .await
+                .map_err(CacheError::MiddlewareError)?;
+            // the receipt changes if its block is reorged, so it expires until the block
+            // has enough confirmations
+            if let Some(ref receipt) = receipt {
+                if let Some(block_number) = receipt.block_number {
+                    self.store(&key, receipt, self.is_confirmed(block_number).await?)?;
+                }
+            }
+            Ok(receipt)
+        })
+        .await
+    }
+
+    async fn get_code<T: Into<NameOrAddress> + Send + Sync>(
+        &self,
+        at: T,
+        block: Option<BlockId>,
+    ) -> Result<Bytes, Self::Error> {
+        match at.into() {
+            NameOrAddress::Address(address) => {
+                self.cached(
+                    "eth_getCode",
+                    (address, block),
+                    self.is_immutable(block).await?,
+                    self.inner.get_code(address, block),
+                )
+                .await
+            }
+            // the address of an ENS name may change
+            name => self
+                .inner
+                .get_code(name, block)
+                .await
+                .map_err(CacheError::MiddlewareError),
+        }
+    }
+
+    async fn get_storage_at<T: Into<NameOrAddress> + Send + Sync>(
+        &self,
+        from: T,
+        location: H256,
+        block: Option<BlockId>,
+    ) -> Result<H256, Self::Error> {
+        match from.into() {
+            NameOrAddress::Address(address) => {
+                self.cached(
+                    "eth_getStorageAt",
+                    (address, location, block),
+                    self.is_immutable(block).await?,
+                    self.inner.get_storage_at(address, location, block),
+                )
+                .await
+            }
+            // the address of an ENS name may change
+            name => self
+                .inner
+                .get_storage_at(name, location, block)
+                .await
+                .map_err(CacheError::MiddlewareError),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers_providers::Provider;
+
+    #[tokio::test]
+    async fn caches_immutable_queries() {
+        let (provider, mock) = Provider::mocked();
+        let provider = CacheMiddleware::new(provider, 10);
+
+        mock.push_response("eth_chainId", U256::from(1)).unwrap();
+        for code in &[vec![1u8], vec![2u8], vec![3u8]] {
+            mock.push_response("eth_getCode", Bytes::from(code.clone()))
+                .unwrap();
+        }
+
+        assert_eq!(provider.get_chainid().await.unwrap(), 1.into());
+        assert_eq!(provider.get_chainid().await.unwrap(), 1.into());
+
+        let address = Address::repeat_byte(1);
+        let pinned = Some(BlockId::Hash(H256::repeat_byte(2)));
+        let code = provider.get_code(address, pinned).await.unwrap();
+        assert_eq!(code, Bytes::from(vec![1u8]));
+        let code = provider.get_code(address, pinned).await.unwrap();
+        assert_eq!(code, Bytes::from(vec![1u8]));
+
+        // the code at the latest block may change and no ttl is set
+        let code = provider.get_code(address, None).await.unwrap();
+        assert_eq!(code, Bytes::from(vec![2u8]));
+        let code = provider.get_code(address, None).await.unwrap();
+        assert_eq!(code, Bytes::from(vec![3u8]));
+
+        mock.assert_calls(&["eth_chainId", "eth_getCode", "eth_getCode", "eth_getCode"]);
+        assert_eq!(provider.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn caches_mutable_queries_until_expired() {
+        let (provider, mock) = Provider::mocked();
+        let provider = CacheMiddleware::new(provider, 10).ttl(Duration::from_millis(50));
+
+        mock.push_response("eth_blockNumber", U64::from(1)).unwrap();
+        mock.push_response("eth_blockNumber", U64::from(2)).unwrap();
+
+        assert_eq!(provider.get_block_number().await.unwrap(), 1.into());
+        assert_eq!(provider.get_block_number().await.unwrap(), 1.into());
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert_eq!(provider.get_block_number().await.unwrap(), 2.into());
+        assert_eq!(mock.call_count("eth_blockNumber"), 2);
+    }
+
+    #[tokio::test]
+    async fn caches_confirmed_blocks_and_receipts() {
+        let (provider, mock) = Provider::mocked();
+        let provider = CacheMiddleware::new(provider, 10).confirmations(2);
+
+        let receipt = TransactionReceipt {
+            block_number: Some(5.into()),
+            ..Default::default()
+        };
+        mock.push_response("eth_blockNumber", U64::from(6)).unwrap();
+        mock.push_response("eth_getTransactionReceipt", receipt.clone())
+            .unwrap();
+        mock.push_response("eth_blockNumber", U64::from(7)).unwrap();
+        mock.push_response("eth_getTransactionReceipt", receipt.clone())
+            .unwrap();
+
+        // the receipt is fetched again until its block has enough confirmations
+        for _ in 0..3 {
+            let res = provider.get_transaction_receipt(H256::zero()).await;
+            assert_eq!(res.unwrap(), Some(receipt.clone()));
+        }
+        assert_eq!(mock.call_count("eth_getTransactionReceipt"), 2);
+
+        // the blocks below the last seen head are confirmed without fetching it again
+        mock.push_response("eth_getCode", Bytes::from(vec![1u8]))
+            .unwrap();
+        let block = Some(BlockId::Number(BlockNumber::Number(4.into())));
+        for _ in 0..2 {
+            let code = provider.get_code(Address::zero(), block).await.unwrap();
+            assert_eq!(code, Bytes::from(vec![1u8]));
+        }
+        assert_eq!(mock.call_count("eth_blockNumber"), 2);
+        assert_eq!(mock.call_count("eth_getCode"), 1);
+    }
+
+    #[tokio::test]
+    async fn fetches_concurrent_misses_once() {
+        let (provider, _) = Provider::mocked();
+        let provider = CacheMiddleware::new(provider, 10);
+        let fetches = AtomicU64::new(0);
+
+        let fetch = || {
+            provider.fetch_once("key", async {
+                tokio::task::yield_now().await;
+                fetches.fetch_add(1, Ordering::SeqCst);
+                provider.store("key", &1u64, true)?;
+                Ok(1u64)
+            })
+        };
+        let (a, b) = tokio::join!(fetch(), fetch());
+        assert_eq!((a.unwrap(), b.unwrap()), (1, 1));
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+        assert!(provider.pending.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn keeps_the_lock_of_waiting_queries() {
+        let (provider, _) = Provider::mocked();
+        let provider = CacheMiddleware::new(provider, 10);
+
+        // another query waits for the lock of the key, so it is not removed
+        let waiting = provider
+            .pending
+            .lock()
+            .unwrap()
+            .entry("key".to_owned())
+            .or_default()
+            .clone();
+        provider
+            .fetch_once("key", async { Ok(1u64) })
+            .await
+            .unwrap();
+        assert!(Arc::ptr_eq(
+            &provider.pending.lock().unwrap()["key"],
+            &waiting
+        ));
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut cache = Cache::new(2);
+        cache.insert("a".to_owned(), 1.into(), None);
+        cache.insert("b".to_owned(), 2.into(), None);
+        assert_eq!(cache.get("a"), Some(1.into()));
+
+        cache.insert("c".to_owned(), 3.into(), None);
+        assert_eq!(cache.get("a"), Some(1.into()));
+        assert_eq!(cache.get("b"), None);
+        assert_eq!(cache.get("c"), Some(3.into()));
+    }
+}
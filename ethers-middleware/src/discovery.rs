@@ -0,0 +1,101 @@
+use ethers_core::types::{Address, U256};
+use ethers_providers::Middleware;
+use ethers_signers::DeriveAddress;
+use thiserror::Error;
+
+/// The number of consecutive unused accounts after which discovery stops, as
+/// recommended by [BIP-44](https://github.com/bitcoin/bips/blob/master/bip-0044.mediawiki#account-discovery)
+pub const DEFAULT_GAP_LIMIT: u32 = 20;
+
+/// An account which sent transactions or holds a balance
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscoveredAccount {
+    /// The derivation index of the account
+    pub index: u32,
+    /// The address of the account
+    pub address: Address,
+    /// The number of transactions sent by the account
+    pub nonce: U256,
+    /// The balance of the account
+    pub balance: U256,
+}
+
+#[derive(Error, Debug)]
+/// Thrown when discovering accounts
+pub enum DiscoveryError<M: Middleware, D: DeriveAddress> {
+    /// Thrown when the internal middleware errors
+    #[error("{0}")]
+    MiddlewareError(M::Error),
+
+    /// Thrown when an address cannot be derived
+    #[error("{0}")]
+    DerivationError(D::Error),
+}
+
+/// Discovers the used accounts of an HD wallet, e.g. a mnemonic or a Ledger. Accounts are
+/// derived by increasing index and are used if they sent a transaction or hold a balance.
+/// Discovery stops after `gap_limit` consecutive unused accounts.
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers::{
+///     middleware::discovery::{discover_accounts, DEFAULT_GAP_LIMIT},
+///     providers::{Http, Provider},
+///     signers::{coins_bip39::English, MnemonicBuilder},
+/// };
+/// use std::convert::TryFrom;
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Provider::<Http>::try_from("http://localhost:8545")?;
+/// let mnemonic = MnemonicBuilder::<English>::default()
+///     .phrase("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about");
+///
+/// for account in discover_accounts(&provider, &mnemonic, DEFAULT_GAP_LIMIT).await? {
+///     println!("{}: {:?} holds {}", account.index, account.address, account.balance);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn discover_accounts<M, D>(
+    client: &M,
+    wallet: &D,
+    gap_limit: u32,
+) -> Result<Vec<DiscoveredAccount>, DiscoveryError<M, D>>
+where
+    M: Middleware,
+    D: DeriveAddress,
+{
+    let mut accounts = Vec::new();
+    let mut unused = 0;
+    let mut index = 0;
+    while unused < gap_limit {
+        let address = wallet
+            .derive_address(index)
+            .await
+            .map_err(DiscoveryError::DerivationError)?;
+        let nonce = client
+            .get_transaction_count(address, None)
+            .await
+            .map_err(DiscoveryError::MiddlewareError)?;
+        let balance = client
+            .get_balance(address, None)
+            .await
+            .map_err(DiscoveryError::MiddlewareError)?;
+
+        if nonce.is_zero() && balance.is_zero() {
+            unused += 1;
+        } else {
+            unused = 0;
+            accounts.push(DiscoveredAccount {
+                index,
+                address,
+                nonce,
+                balance,
+            });
+        }
+        index += 1;
+    }
+
+    Ok(accounts)
+}
@@ -5,8 +5,10 @@ mod linear;
 pub use linear::LinearGasPrice;
 
 use async_trait::async_trait;
-use ethers_core::types::{BlockId, TransactionRequest, TxHash, U256};
-use ethers_providers::{interval, FromErr, Middleware, PendingTransaction, StreamExt};
+use ethers_core::types::{BlockId, TxHash, TypedTransaction, U256};
+use ethers_providers::{
+    interval, FromErr, JsonRpcError, Middleware, PendingTransaction, StreamExt,
+};
 use futures_util::lock::Mutex;
 use std::sync::Arc;
 use std::{pin::Pin, time::Instant};
@@ -64,7 +66,7 @@ pub struct GasEscalatorMiddleware<M, E> {
     pub(crate) escalator: E,
     /// The transactions which are currently being monitored for escalation
     #[allow(clippy::type_complexity)]
-    pub txs: Arc<Mutex<Vec<(TxHash, TransactionRequest, Instant, Option<BlockId>)>>>,
+    pub txs: Arc<Mutex<Vec<(TxHash, TypedTransaction, Instant, Option<BlockId>)>>>,
     frequency: Frequency,
 }
 
@@ -82,11 +84,25 @@ where
         &self.inner
     }
 
-    async fn send_transaction(
+    fn as_error_response<'a>(&self, err: &'a Self::Error) -> Option<&'a JsonRpcError> {
+        match err {
+            GasEscalatorError::MiddlewareError(err) => self.inner.as_error_response(err),
+            _ => None,
+        }
+    }
+
+    async fn send_transaction<T: Into<TypedTransaction> + Send + Sync>(
         &self,
-        tx: TransactionRequest,
+        tx: T,
         block: Option<BlockId>,
     ) -> Result<PendingTransaction<'_, Self::Provider>, Self::Error> {
+        // the transaction is filled first so that its replacements keep its nonce and
+        // escalate its gas price
+        let mut tx = tx.into();
+        self.inner()
+            .fill_transaction(&mut tx, block)
+            .await
+            .map_err(GasEscalatorError::MiddlewareError)?;
         let pending_tx = self
             .inner()
             .send_transaction(tx.clone(), block)
@@ -164,16 +180,28 @@ where
                 let receipt = self.get_transaction_receipt(tx_hash).await?;
                 tracing::trace!(tx_hash = ?tx_hash, "checking if exists");
                 if receipt.is_none() {
-                    let old_gas_price = replacement_tx.gas_price.expect("gas price must be set");
+                    let old_gas_price = match replacement_tx.gas_price() {
+                        Some(gas_price) => gas_price,
+                        // transactions without a gas price cannot be escalated
+                        None => {
+                            txs.push((tx_hash, replacement_tx, time, priority));
+                            continue;
+                        }
+                    };
                     // Get the new gas price based on how much time passed since the
                     // tx was last broadcast
-                    let new_gas_price = self
-                        .escalator
-                        .get_gas_price(old_gas_price, now.duration_since(time).as_secs());
+                    let time_elapsed = now.duration_since(time).as_secs();
+                    let new_gas_price = self.escalator.get_gas_price(old_gas_price, time_elapsed);
 
                     let new_txhash = if new_gas_price != old_gas_price {
-                        // bump the gas price
-                        replacement_tx.gas_price = Some(new_gas_price);
+                        // bump the gas price, which for EIP-1559 transactions is the max
+                        // fee, whose replacements must also bump the max priority fee
+                        if let TypedTransaction::Eip1559(tx) = &mut replacement_tx {
+                            tx.max_priority_fee_per_gas = tx
+                                .max_priority_fee_per_gas
+                                .map(|fee| self.escalator.get_gas_price(fee, time_elapsed));
+                        }
+                        replacement_tx.set_gas_price(new_gas_price);
 
                         // the tx hash will be different so we need to update it
                         match self
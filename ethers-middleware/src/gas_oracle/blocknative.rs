@@ -0,0 +1,141 @@
+use ethers_core::types::U256;
+
+use async_trait::async_trait;
+use reqwest::{header::AUTHORIZATION, Client};
+use serde::Deserialize;
+use url::Url;
+
+use crate::gas_oracle::{GasCategory, GasOracle, GasOracleError, GWEI_TO_WEI};
+
+const BLOCKNATIVE_URL: &str = "https://api.blocknative.com/gasprices/blockprices";
+
+/// A client over HTTP for the [Blocknative](https://docs.blocknative.com/gas-platform) gas
+/// platform API that implements the `GasOracle` trait. The gas categories map to the
+/// probabilities of inclusion in the next block estimated by Blocknative: 70% for
+/// `SafeLow`, 90% for `Standard`, 95% for `Fast` and 99% for `Fastest`.
+#[derive(Debug)]
+pub struct Blocknative {
+    client: Client,
+    url: Url,
+    api_key: Option<String>,
+    gas_category: GasCategory,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BlocknativeResponse {
+    block_prices: Vec<BlockPrices>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BlockPrices {
+    estimated_prices: Vec<EstimatedPrice>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EstimatedPrice {
+    confidence: u64,
+    price: f64,
+    max_priority_fee_per_gas: f64,
+    max_fee_per_gas: f64,
+}
+
+impl Blocknative {
+    /// Creates a new [Blocknative](https://www.blocknative.com/gas-estimator) gas price
+    /// oracle. Requests without an API key are rate limited.
+    pub fn new(api_key: Option<&str>) -> Self {
+        let url = Url::parse(BLOCKNATIVE_URL).expect("invalid url");
+
+        Blocknative {
+            client: Client::new(),
+            url,
+            api_key: api_key.map(ToOwned::to_owned),
+            gas_category: GasCategory::Standard,
+        }
+    }
+
+    /// Sets the gas price category to be used when fetching the gas price.
+    pub fn category(mut self, gas_category: GasCategory) -> Self {
+        self.gas_category = gas_category;
+        self
+    }
+
+    async fn query(&self) -> Result<EstimatedPrice, GasOracleError> {
+        let mut request = self.client.get(self.url.as_ref());
+        if let Some(api_key) = &self.api_key {
+            request = request.header(AUTHORIZATION, api_key);
+        }
+        let res = request
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<BlocknativeResponse>()
+            .await?;
+        res.estimated_price(self.gas_category)
+    }
+}
+
+impl BlocknativeResponse {
+    /// Returns the estimated price of the next block for the category
+    fn estimated_price(self, gas_category: GasCategory) -> Result<EstimatedPrice, GasOracleError> {
+        let confidence = match gas_category {
+            GasCategory::SafeLow => 70,
+            GasCategory::Standard => 90,
+            GasCategory::Fast => 95,
+            GasCategory::Fastest => 99,
+        };
+        self.block_prices
+            .into_iter()
+            .next()
+            .and_then(|block| {
+                block
+                    .estimated_prices
+                    .into_iter()
+                    .find(|price| price.confidence == confidence)
+            })
+            .ok_or(GasOracleError::GasCategoryNotSupported)
+    }
+}
+
+#[async_trait]
+impl GasOracle for Blocknative {
+    async fn fetch(&self) -> Result<U256, GasOracleError> {
+        Ok(gwei_to_wei(self.query().await?.price))
+    }
+
+    async fn estimate_eip1559_fees(&self) -> Result<(U256, U256), GasOracleError> {
+        let price = self.query().await?;
+        Ok((
+            gwei_to_wei(price.max_fee_per_gas),
+            gwei_to_wei(price.max_priority_fee_per_gas),
+        ))
+    }
+}
+
+/// Converts gwei (which Blocknative returns with decimals) to wei
+fn gwei_to_wei(gwei: f64) -> U256 {
+    U256::from((gwei * GWEI_TO_WEI as f64).round() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_response() {
+        let res: BlocknativeResponse = serde_json::from_str(
+            r#"{"system":"ethereum","network":"main","unit":"gwei","maxPrice":123,"currentBlockNumber":13005095,"msSinceLastBlock":3793,"blockPrices":[{"blockNumber":13005096,"estimatedTransactionCount":137,"baseFeePerGas":32.333589226,"estimatedPrices":[{"confidence":99,"price":48,"maxPriorityFeePerGas":15.69,"maxFeePerGas":80.36},{"confidence":95,"price":47,"maxPriorityFeePerGas":14.5,"maxFeePerGas":79.17},{"confidence":90,"price":46,"maxPriorityFeePerGas":13.5,"maxFeePerGas":78.17},{"confidence":80,"price":45,"maxPriorityFeePerGas":12.5,"maxFeePerGas":77.17},{"confidence":70,"price":44,"maxPriorityFeePerGas":11.5,"maxFeePerGas":76.17}]}]}"#,
+        )
+        .unwrap();
+
+        let price = res.estimated_price(GasCategory::Standard).unwrap();
+        assert_eq!(gwei_to_wei(price.price), 46_000_000_000u64.into());
+        assert_eq!(
+            gwei_to_wei(price.max_priority_fee_per_gas),
+            13_500_000_000u64.into()
+        );
+        assert_eq!(gwei_to_wei(price.max_fee_per_gas), 78_170_000_000u64.into());
+    }
+}
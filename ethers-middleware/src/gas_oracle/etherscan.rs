@@ -1,56 +1,41 @@
 use ethers_core::types::U256;
+use ethers_etherscan::{Client, EtherscanError, GasOracle as GasPrices};
 
 use async_trait::async_trait;
-use reqwest::Client;
-use serde::Deserialize;
-use serde_aux::prelude::*;
-use url::Url;
 
 use crate::gas_oracle::{GasCategory, GasOracle, GasOracleError, GWEI_TO_WEI};
 
-const ETHERSCAN_URL_PREFIX: &str =
-    "https://api.etherscan.io/api?module=gastracker&action=gasoracle";
-
 /// A client over HTTP for the [Etherscan](https://api.etherscan.io/api?module=gastracker&action=gasoracle) gas tracker API
 /// that implements the `GasOracle` trait
 #[derive(Debug)]
 pub struct Etherscan {
     client: Client,
-    url: Url,
     gas_category: GasCategory,
 }
 
-#[derive(Deserialize)]
-struct EtherscanResponse {
-    result: EtherscanResponseInner,
-}
-
-#[derive(Deserialize)]
-struct EtherscanResponseInner {
-    #[serde(deserialize_with = "deserialize_number_from_string")]
-    #[serde(rename = "SafeGasPrice")]
-    safe_gas_price: u64,
-    #[serde(deserialize_with = "deserialize_number_from_string")]
-    #[serde(rename = "ProposeGasPrice")]
-    propose_gas_price: u64,
-    #[serde(deserialize_with = "deserialize_number_from_string")]
-    #[serde(rename = "FastGasPrice")]
-    fast_gas_price: u64,
-}
-
 impl Etherscan {
     /// Creates a new [Etherscan](https://etherscan.io/gastracker) gas price oracle.
     pub fn new(api_key: Option<&str>) -> Self {
-        let url = match api_key {
-            Some(key) => format!("{}&apikey={}", ETHERSCAN_URL_PREFIX, key),
-            None => ETHERSCAN_URL_PREFIX.to_string(),
-        };
+        Self::for_chain(1, api_key).expect("mainnet is supported")
+    }
 
-        let url = Url::parse(&url).expect("invalid url");
+    /// Creates a new gas price oracle for the Etherscan deployment (e.g. BscScan or
+    /// PolygonScan) of the provided chain. Returns `None` if the chain is not supported.
+    pub fn for_chain(chain_id: u64, api_key: Option<&str>) -> Option<Self> {
+        Client::new(chain_id, api_key).ok().map(Self::from_client)
+    }
+
+    /// Creates a new gas price oracle for an Etherscan compatible API at `url`
+    pub fn with_url(url: &str, api_key: Option<&str>) -> Result<Self, url::ParseError> {
+        let url = url::Url::parse(url)?;
+        let client = Client::with_url(url.as_str(), api_key).expect("the url was parsed");
+        Ok(Self::from_client(client))
+    }
 
+    /// Creates a new gas price oracle which queries the gas tracker of the client
+    pub fn from_client(client: Client) -> Self {
         Etherscan {
-            client: Client::new(),
-            url,
+            client,
             gas_category: GasCategory::Standard,
         }
     }
@@ -60,28 +45,129 @@ impl Etherscan {
         self.gas_category = gas_category;
         self
     }
+
+    async fn query(&self) -> Result<GasPrices, GasOracleError> {
+        if matches!(self.gas_category, GasCategory::Fastest) {
+            return Err(GasOracleError::GasCategoryNotSupported);
+        }
+
+        self.client
+            .gas_oracle()
+            .await
+            .map_err(into_gas_oracle_error)
+    }
+}
+
+fn into_gas_oracle_error(err: EtherscanError) -> GasOracleError {
+    match err {
+        EtherscanError::Reqwest(err) => GasOracleError::HttpClientError(err),
+        err => GasOracleError::ErrorResponse(err.to_string()),
+    }
+}
+
+/// Returns the gas price of the category in wei
+fn gas_price(prices: &GasPrices, gas_category: GasCategory) -> Result<U256, GasOracleError> {
+    let gwei = match gas_category {
+        GasCategory::SafeLow => prices.safe_gas_price,
+        GasCategory::Standard => prices.propose_gas_price,
+        GasCategory::Fast => prices.fast_gas_price,
+        _ => return Err(GasOracleError::GasCategoryNotSupported),
+    };
+    Ok(gwei_to_wei(gwei))
+}
+
+/// Returns the max fee and priority fee per gas of the category in wei. The gas
+/// price of the category is the sum of the suggested base fee and of its priority fee.
+fn eip1559_fees(
+    prices: &GasPrices,
+    gas_category: GasCategory,
+) -> Result<(U256, U256), GasOracleError> {
+    let base_fee = prices
+        .suggest_base_fee
+        .map(gwei_to_wei)
+        .ok_or(GasOracleError::Eip1559EstimationNotSupported)?;
+    let gas_price = gas_price(prices, gas_category)?;
+    let max_priority_fee_per_gas = gas_price.saturating_sub(base_fee);
+
+    // leave room for the base fee to double before the transaction gets included
+    let max_fee_per_gas = base_fee * 2 + max_priority_fee_per_gas;
+    Ok((max_fee_per_gas, max_priority_fee_per_gas))
 }
 
 #[async_trait]
 impl GasOracle for Etherscan {
     async fn fetch(&self) -> Result<U256, GasOracleError> {
-        if matches!(self.gas_category, GasCategory::Fastest) {
-            return Err(GasOracleError::GasCategoryNotSupported);
-        }
+        gas_price(&self.query().await?, self.gas_category)
+    }
 
-        let res = self
-            .client
-            .get(self.url.as_ref())
-            .send()
-            .await?
-            .json::<EtherscanResponse>()
-            .await?;
-
-        match self.gas_category {
-            GasCategory::SafeLow => Ok(U256::from(res.result.safe_gas_price * GWEI_TO_WEI)),
-            GasCategory::Standard => Ok(U256::from(res.result.propose_gas_price * GWEI_TO_WEI)),
-            GasCategory::Fast => Ok(U256::from(res.result.fast_gas_price * GWEI_TO_WEI)),
-            _ => Err(GasOracleError::GasCategoryNotSupported),
+    async fn estimate_eip1559_fees(&self) -> Result<(U256, U256), GasOracleError> {
+        eip1559_fees(&self.query().await?, self.gas_category)
+    }
+}
+
+/// Converts gwei (which Etherscan may return with decimals) to wei
+fn gwei_to_wei(gwei: f64) -> U256 {
+    U256::from((gwei * GWEI_TO_WEI as f64).round() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_eip1559_response() {
+        let res: GasPrices = serde_json::from_str(
+            r#"{"LastBlock":"13053741","SafeGasPrice":"20","ProposeGasPrice":"22","FastGasPrice":"22","suggestBaseFee":"19.230609716","gasUsedRatio":"0.370119078777807,0.8954731,0.550911766666667,0.212457033333333,0.552463633333333"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            gas_price(&res, GasCategory::Standard).unwrap(),
+            22_000_000_000u64.into()
+        );
+        let (max_fee, priority_fee) = eip1559_fees(&res, GasCategory::SafeLow).unwrap();
+        assert_eq!(priority_fee, 769_390_284u64.into());
+        assert_eq!(max_fee, (19_230_609_716u64 * 2 + 769_390_284).into());
+    }
+
+    #[test]
+    fn parses_legacy_response() {
+        let res: GasPrices = serde_json::from_str(
+            r#"{"LastBlock":"10390330","SafeGasPrice":"5","ProposeGasPrice":"5","FastGasPrice":"6"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            gas_price(&res, GasCategory::Fast).unwrap(),
+            6_000_000_000u64.into()
+        );
+        assert!(matches!(
+            eip1559_fees(&res, GasCategory::Fast).unwrap_err(),
+            GasOracleError::Eip1559EstimationNotSupported
+        ));
+    }
+
+    #[test]
+    fn converts_error_response() {
+        let err = into_gas_oracle_error(EtherscanError::ErrorResponse {
+            message: "NOTOK".to_owned(),
+            result: "Max rate limit reached".to_owned(),
+        });
+        match err {
+            GasOracleError::ErrorResponse(message) => {
+                assert_eq!(
+                    message,
+                    "etherscan responded with `NOTOK`: Max rate limit reached"
+                )
+            }
+            err => panic!("unexpected error {:?}", err),
         }
     }
+
+    #[test]
+    fn builds_chain_urls() {
+        let oracle = Etherscan::for_chain(56, Some("key")).unwrap();
+        assert_eq!(oracle.client.url().as_str(), "https://api.bscscan.com/api");
+        assert!(Etherscan::for_chain(1337, None).is_none());
+    }
 }
@@ -0,0 +1,175 @@
+use ethers_core::types::U256;
+
+use async_trait::async_trait;
+use futures_util::future::join_all;
+use std::future::Future;
+use tracing::warn;
+
+use crate::gas_oracle::{GasOracle, GasOracleError};
+
+/// A gas oracle which queries multiple underlying oracles concurrently and returns
+/// the weighted median of their suggestions.
+///
+/// Oracles which fail are skipped and outliers have no influence on the median, so
+/// the suggestion stays sane if any single gas API is down or manipulated.
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers::middleware::gas_oracle::{
+///     EthGasStation, Etherchain, Etherscan, GasCategory, GasOracle, Median,
+/// };
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let oracle = Median::new()
+///     .add(EthGasStation::new(None))
+///     .add(Etherchain::new())
+///     // trust etherscan twice as much as the other oracles
+///     .add_weighted(2.0, Etherscan::new(None).category(GasCategory::Standard));
+///
+/// let gas_price = oracle.fetch().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct Median {
+    oracles: Vec<(f32, Box<dyn GasOracle>)>,
+}
+
+impl Median {
+    /// Creates a median gas oracle without any underlying oracles
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an oracle with a weight of 1
+    pub fn add<T: GasOracle + 'static>(self, oracle: T) -> Self {
+        self.add_weighted(1.0, oracle)
+    }
+
+    /// Adds an oracle with the provided weight, relative to the other oracles' weights.
+    /// Oracles whose weight is not positive, e.g. NaN, are never queried.
+    pub fn add_weighted<T: GasOracle + 'static>(mut self, weight: f32, oracle: T) -> Self {
+        self.oracles.push((weight, Box::new(oracle)));
+        self
+    }
+
+    /// Returns the weight and value of each weighted oracle which did not fail
+    async fn query<'a, T, F, Fut>(&'a self, fetch: F) -> Vec<(f32, T)>
+    where
+        F: Fn(&'a dyn GasOracle) -> Fut,
+        Fut: Future<Output = Result<T, GasOracleError>>,
+    {
+        let oracles = self
+            .oracles
+            .iter()
+            .filter(|(weight, _)| *weight > 0.0)
+            .collect::<Vec<_>>();
+        let results = join_all(oracles.iter().map(|&(_, oracle)| fetch(oracle.as_ref()))).await;
+
+        oracles
+            .into_iter()
+            .zip(results)
+            .filter_map(|((weight, oracle), result)| match result {
+                Ok(value) => Some((*weight, value)),
+                Err(err) => {
+                    warn!("failed to fetch gas price from {:?}: {}", oracle, err);
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl GasOracle for Median {
+    async fn fetch(&self) -> Result<U256, GasOracleError> {
+        let values = self.query(|oracle| oracle.fetch()).await;
+        weighted_median(values).ok_or(GasOracleError::NoValues)
+    }
+
+    async fn estimate_eip1559_fees(&self) -> Result<(U256, U256), GasOracleError> {
+        let values = self.query(|oracle| oracle.estimate_eip1559_fees()).await;
+        let (max_fees, priority_fees) = values
+            .into_iter()
+            .map(|(weight, (max_fee, priority_fee))| ((weight, max_fee), (weight, priority_fee)))
+            .unzip();
+        // the fees are aggregated separately
+        weighted_median(max_fees)
+            .zip(weighted_median(priority_fees))
+            .ok_or(GasOracleError::NoValues)
+    }
+}
+
+/// Returns the value at which half of the total weight is reached, `None` if
+/// there are no values
+fn weighted_median(mut values: Vec<(f32, U256)>) -> Option<U256> {
+    values.sort_by(|(_, a), (_, b)| a.cmp(b));
+
+    let half = values.iter().map(|(weight, _)| weight).sum::<f32>() / 2.0;
+    let mut cumulative = 0.0;
+    values
+        .into_iter()
+        .find(|(weight, _)| {
+            cumulative += weight;
+            cumulative >= half
+        })
+        .map(|(_, value)| value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct Fixed(Option<u64>);
+
+    #[async_trait]
+    impl GasOracle for Fixed {
+        async fn fetch(&self) -> Result<U256, GasOracleError> {
+            self.0
+                .map(U256::from)
+                .ok_or(GasOracleError::GasCategoryNotSupported)
+        }
+    }
+
+    #[test]
+    fn computes_weighted_median() {
+        let values = |v: &[(f32, u64)]| v.iter().map(|(w, v)| (*w, U256::from(*v))).collect();
+
+        assert_eq!(weighted_median(vec![]), None);
+        assert_eq!(weighted_median(values(&[(1.0, 5)])), Some(5.into()));
+        assert_eq!(
+            weighted_median(values(&[(1.0, 1000), (1.0, 3), (1.0, 2)])),
+            Some(3.into())
+        );
+        assert_eq!(
+            weighted_median(values(&[(1.0, 1), (1.0, 2), (3.0, 10)])),
+            Some(10.into())
+        );
+    }
+
+    #[tokio::test]
+    async fn skips_failing_oracles() {
+        let oracle = Median::new()
+            .add(Fixed(Some(10)))
+            .add(Fixed(None))
+            .add(Fixed(Some(1_000_000)))
+            .add(Fixed(Some(20)))
+            .add(Fixed(Some(30)));
+        assert_eq!(oracle.fetch().await.unwrap(), 20.into());
+
+        // oracles without a positive weight are ignored
+        let oracle = Median::new()
+            .add(Fixed(Some(10)))
+            .add_weighted(0.0, Fixed(Some(20)))
+            .add_weighted(f32::NAN, Fixed(Some(30)));
+        assert_eq!(oracle.fetch().await.unwrap(), 10.into());
+
+        let oracle = Median::new().add(Fixed(None));
+        assert!(matches!(
+            oracle.fetch().await.unwrap_err(),
+            GasOracleError::NoValues
+        ));
+    }
+}
@@ -1,11 +1,13 @@
 use super::{GasOracle, GasOracleError};
 use async_trait::async_trait;
 use ethers_core::types::*;
-use ethers_providers::{FromErr, Middleware, PendingTransaction};
+use ethers_providers::{FromErr, JsonRpcError, Middleware, PendingTransaction};
 use thiserror::Error;
 
 #[derive(Debug)]
-/// Middleware used for fetching gas prices over an API instead of `eth_gasPrice`
+/// Middleware used for fetching gas prices over an API instead of `eth_gasPrice`. The
+/// fees of EIP-1559 transactions are filled with the oracle's EIP-1559 estimates, if it
+/// provides them.
 pub struct GasOracleMiddleware<M, G> {
     inner: M,
     gas_oracle: G,
@@ -52,17 +54,45 @@ where
         &self.inner
     }
 
+    fn as_error_response<'a>(&self, err: &'a Self::Error) -> Option<&'a JsonRpcError> {
+        match err {
+            MiddlewareError::MiddlewareError(err) => self.inner.as_error_response(err),
+            _ => None,
+        }
+    }
+
     async fn get_gas_price(&self) -> Result<U256, Self::Error> {
         Ok(self.gas_oracle.fetch().await?)
     }
 
-    async fn send_transaction(
+    async fn send_transaction<T: Into<TypedTransaction> + Send + Sync>(
         &self,
-        mut tx: TransactionRequest,
+        tx: T,
         block: Option<BlockId>,
     ) -> Result<PendingTransaction<'_, Self::Provider>, Self::Error> {
-        if tx.gas_price.is_none() {
-            tx.gas_price = Some(self.get_gas_price().await?);
+        let mut tx = tx.into();
+        match tx {
+            // EIP-1559 transactions pay fees instead of a gas price
+            TypedTransaction::Eip1559(ref mut tx) => {
+                if tx.max_fee_per_gas.is_none() || tx.max_priority_fee_per_gas.is_none() {
+                    match self.gas_oracle.estimate_eip1559_fees().await {
+                        Ok((max_fee_per_gas, max_priority_fee_per_gas)) => {
+                            tx.max_fee_per_gas = tx.max_fee_per_gas.or(Some(max_fee_per_gas));
+                            tx.max_priority_fee_per_gas = tx
+                                .max_priority_fee_per_gas
+                                .or(Some(max_priority_fee_per_gas));
+                        }
+                        // leave the fees to be suggested by the inner middleware
+                        Err(GasOracleError::Eip1559EstimationNotSupported) => {}
+                        Err(err) => return Err(err.into()),
+                    }
+                }
+            }
+            _ => {
+                if tx.gas_price().is_none() {
+                    tx.set_gas_price(self.get_gas_price().await?);
+                }
+            }
         }
         self.inner
             .send_transaction(tx, block)
@@ -1,3 +1,6 @@
+mod blocknative;
+pub use blocknative::Blocknative;
+
 mod eth_gas_station;
 pub use eth_gas_station::EthGasStation;
 
@@ -10,6 +13,12 @@ pub use etherscan::Etherscan;
 mod gas_now;
 pub use gas_now::GasNow;
 
+mod median;
+pub use median::Median;
+
+mod provider_oracle;
+pub use provider_oracle::ProviderOracle;
+
 mod middleware;
 pub use middleware::{GasOracleMiddleware, MiddlewareError};
 
@@ -42,6 +51,25 @@ pub enum GasOracleError {
     /// supported by the gas oracle API
     #[error("gas category not supported")]
     GasCategoryNotSupported,
+
+    /// An internal error thrown when the gas oracle API does not provide
+    /// EIP-1559 fee suggestions
+    #[error("EIP-1559 fee estimation not supported")]
+    Eip1559EstimationNotSupported,
+
+    /// Thrown when the gas oracle API responds with an error, e.g. for an invalid
+    /// API key or when rate limited
+    #[error("gas oracle responded with an error: {0}")]
+    ErrorResponse(String),
+
+    /// Thrown when none of the underlying gas oracles of an aggregating oracle
+    /// returned a value
+    #[error("no gas oracle returned a value")]
+    NoValues,
+
+    /// Thrown when the node queried by a [`ProviderOracle`] errors
+    #[error(transparent)]
+    ProviderError(Box<dyn std::error::Error + Send + Sync>),
 }
 
 /// `GasOracle` is a trait that an underlying gas oracle needs to implement.
@@ -80,4 +108,26 @@ pub trait GasOracle: Send + Sync + std::fmt::Debug {
     /// # }
     /// ```
     async fn fetch(&self) -> Result<U256, GasOracleError>;
+
+    /// Makes an asynchronous HTTP query to the underlying `GasOracle` for
+    /// [EIP-1559](https://eips.ethereum.org/EIPS/eip-1559) fee suggestions, returned as
+    /// `(max_fee_per_gas, max_priority_fee_per_gas)`
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ethers::middleware::{
+    ///     gas_oracle::{Etherscan, GasCategory, GasOracle},
+    /// };
+    ///
+    /// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// let etherscan_oracle = Etherscan::new(None).category(GasCategory::Fast);
+    /// let (max_fee_per_gas, max_priority_fee_per_gas) =
+    ///     etherscan_oracle.estimate_eip1559_fees().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn estimate_eip1559_fees(&self) -> Result<(U256, U256), GasOracleError> {
+        Err(GasOracleError::Eip1559EstimationNotSupported)
+    }
 }
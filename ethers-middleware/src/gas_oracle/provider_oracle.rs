@@ -0,0 +1,152 @@
+use ethers_core::types::{BlockNumber, FeeSuggestion, Priority, U256};
+use ethers_providers::Middleware;
+
+use async_trait::async_trait;
+
+use crate::gas_oracle::{GasCategory, GasOracle, GasOracleError};
+
+/// The number of recent blocks whose priority fees are sampled
+const FEE_HISTORY_BLOCKS: u64 = 10;
+
+/// A gas oracle estimating the gas price from the fee history of the node, without
+/// relying on a third party API. The suggested priority fee is the median over recent
+/// blocks of the priority fees paid at the category's percentile: 10% for `SafeLow`,
+/// 25% for `Standard`, 50% for `Fast` and 75% for `Fastest`.
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers::{
+///     middleware::gas_oracle::{GasCategory, GasOracle, ProviderOracle},
+///     providers::{Http, Provider},
+/// };
+/// use std::convert::TryFrom;
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Provider::<Http>::try_from("http://localhost:8545")?;
+/// let oracle = ProviderOracle::new(provider).category(GasCategory::Fast);
+/// let (max_fee_per_gas, max_priority_fee_per_gas) = oracle.estimate_eip1559_fees().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ProviderOracle<M> {
+    provider: M,
+    gas_category: GasCategory,
+}
+
+impl<M: Middleware> ProviderOracle<M> {
+    /// Creates a gas oracle querying the fee history of the provided node
+    pub fn new(provider: M) -> Self {
+        Self {
+            provider,
+            gas_category: GasCategory::Standard,
+        }
+    }
+
+    /// Sets the gas price category to be used when fetching the gas price.
+    pub fn category(mut self, gas_category: GasCategory) -> Self {
+        self.gas_category = gas_category;
+        self
+    }
+
+    fn priority(&self) -> Priority {
+        match self.gas_category {
+            GasCategory::SafeLow => Priority::Slow,
+            GasCategory::Standard => Priority::Standard,
+            GasCategory::Fast => Priority::Fast,
+            GasCategory::Fastest => Priority::Urgent,
+        }
+    }
+}
+
+#[async_trait]
+impl<M> GasOracle for ProviderOracle<M>
+where
+    M: Middleware,
+    M::Error: 'static,
+{
+    /// Returns the base fee of the next block plus the suggested priority fee, or the
+    /// node's `eth_gasPrice` on chains which do not support EIP-1559
+    async fn fetch(&self) -> Result<U256, GasOracleError> {
+        let priority = self.priority();
+        let history = self
+            .provider
+            .fee_history(
+                FEE_HISTORY_BLOCKS,
+                BlockNumber::Latest,
+                &[priority.reward_percentile()],
+            )
+            .await
+            .map_err(|err| GasOracleError::ProviderError(Box::new(err)))?;
+
+        match history.next_base_fee() {
+            Some(base_fee) if !base_fee.is_zero() => {
+                match FeeSuggestion::from_fee_history(&history, priority) {
+                    FeeSuggestion::Eip1559 {
+                        max_priority_fee_per_gas,
+                        ..
+                    } => Ok(base_fee + max_priority_fee_per_gas),
+                    FeeSuggestion::Legacy { gas_price } => Ok(gas_price),
+                }
+            }
+            _ => self
+                .provider
+                .get_gas_price()
+                .await
+                .map_err(|err| GasOracleError::ProviderError(Box::new(err))),
+        }
+    }
+
+    async fn estimate_eip1559_fees(&self) -> Result<(U256, U256), GasOracleError> {
+        let priority = self.priority();
+        let history = self
+            .provider
+            .fee_history(
+                FEE_HISTORY_BLOCKS,
+                BlockNumber::Latest,
+                &[priority.reward_percentile()],
+            )
+            .await
+            .map_err(|err| GasOracleError::ProviderError(Box::new(err)))?;
+        if history.next_base_fee().unwrap_or_default().is_zero() {
+            return Err(GasOracleError::Eip1559EstimationNotSupported);
+        }
+
+        match FeeSuggestion::from_fee_history(&history, priority) {
+            FeeSuggestion::Eip1559 {
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+            } => Ok((max_fee_per_gas, max_priority_fee_per_gas)),
+            FeeSuggestion::Legacy { .. } => Err(GasOracleError::Eip1559EstimationNotSupported),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers_core::types::FeeHistory;
+    use ethers_providers::Provider;
+
+    #[tokio::test]
+    async fn estimates_from_fee_history() {
+        let (provider, mock) = Provider::mocked();
+        let history = FeeHistory {
+            oldest_block: 100.into(),
+            base_fee_per_gas: vec![1_000_000_000u64.into(), 1_000_000_000u64.into()],
+            gas_used_ratio: vec![0.5],
+            reward: vec![vec![2_000_000_000u64.into()]],
+        };
+        mock.push_response("eth_feeHistory", history.clone())
+            .unwrap();
+        mock.push_response("eth_feeHistory", history).unwrap();
+
+        let oracle = ProviderOracle::new(provider).category(GasCategory::SafeLow);
+        assert_eq!(oracle.fetch().await.unwrap(), 3_000_000_000u64.into());
+        let (max_fee, priority_fee) = oracle.estimate_eip1559_fees().await.unwrap();
+        assert_eq!(priority_fee, 2_000_000_000u64.into());
+        // the base fee may grow by 12.5% twice
+        assert_eq!(max_fee, (1_265_625_000u64 + 2_000_000_000).into());
+    }
+}
@@ -15,6 +15,12 @@
 //! places other than `eth_gasPrice`.
 //! - [`Transformer`](crate::transformer): Allows intercepting and transforming a transaction to
 //! be broadcasted via a proxy wallet, e.g. [`DSProxy`](crate::transformer::DsProxy).
+//! - [`Time Lag`](crate::TimeLagMiddleware): Serves the chain's data a number of blocks
+//! behind its head, to only read confirmed blocks
+//! - [`Cache`](crate::CacheMiddleware): Memoizes the responses of queries, e.g. of blocks
+//! pinned by their hash
+//! - [`Private Transactions`](crate::PrivateTransactionMiddleware): Submits transactions
+//! privately to block builders, e.g. via Flashbots Protect
 //!
 //! ## Example of a middleware stack
 //!
@@ -65,15 +71,33 @@ pub mod gas_escalator;
 /// [`GasOracle`](crate::gas_oracle::GasOracle) trait.
 pub mod gas_oracle;
 
+/// Account discovery for HD wallets, returning the accounts which were used
+pub mod discovery;
+
 /// The [Nonce Manager](crate::NonceManagerMiddleware) is used to locally calculate nonces instead of
 /// using eth_getTransactionCount
 pub mod nonce_manager;
-pub use nonce_manager::NonceManagerMiddleware;
+pub use nonce_manager::{NonceManagerMiddleware, NonceStatus};
 
 /// The [Transformer](crate::TransformerMiddleware) is used to intercept transactions and transform
 /// them to be sent via various supported transformers, e.g., [DSProxy](crate::transformer::DsProxy)
 pub mod transformer;
 
+/// The [Time Lag](crate::TimeLagMiddleware) is used to serve the chain's data a number
+/// of blocks behind its head
+pub mod timelag;
+pub use timelag::TimeLagMiddleware;
+
+/// The [Cache](crate::CacheMiddleware) is used to memoize the responses of queries which
+/// are made repeatedly
+pub mod cache;
+pub use cache::CacheMiddleware;
+
+/// The [Private Transaction](crate::PrivateTransactionMiddleware) middleware is used to
+/// submit transactions privately to block builders instead of the public mempool
+pub mod private_tx;
+pub use private_tx::PrivateTransactionMiddleware;
+
 /// The [Signer](crate::SignerMiddleware) is used to locally sign transactions and messages
 /// instead of using eth_sendTransaction and eth_sign
 pub mod signer;
@@ -1,7 +1,13 @@
 use async_trait::async_trait;
 use ethers_core::types::*;
-use ethers_providers::{FromErr, Middleware, PendingTransaction};
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use ethers_providers::{FromErr, JsonRpcError, Middleware, PendingTransaction};
+use std::{
+    collections::BTreeSet,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Mutex,
+    },
+};
 use thiserror::Error;
 
 #[derive(Debug)]
@@ -12,6 +18,8 @@ pub struct NonceManagerMiddleware<M> {
     initialized: AtomicBool,
     nonce: AtomicU64,
     address: Address,
+    /// The nonces of the transactions sent through the manager which may not be mined yet
+    submitted: Mutex<BTreeSet<U256>>,
 }
 
 impl<M> NonceManagerMiddleware<M>
@@ -26,6 +34,7 @@ where
             nonce: 0.into(),
             inner,
             address,
+            submitted: Mutex::new(BTreeSet::new()),
         }
     }
 
@@ -35,6 +44,79 @@ where
         nonce.into()
     }
 
+    /// Discards the locally tracked nonce, so that it is fetched again from the node
+    /// before the next transaction, e.g. after transactions were dropped from the txpool
+    pub fn reset(&self) {
+        self.initialized.store(false, Ordering::SeqCst);
+    }
+
+    /// Returns the mined, pending, locally tracked and pooled nonces of the address,
+    /// to detect the gaps which block its transactions. Requires a node supporting
+    /// `txpool_content`.
+    pub async fn nonce_status(&self) -> Result<NonceStatus, NonceManagerError<M>> {
+        let latest = self
+            .inner
+            .get_transaction_count(self.address, Some(BlockNumber::Latest.into()))
+            .await
+            .map_err(FromErr::from)?;
+        let pending = self
+            .inner
+            .get_transaction_count(self.address, Some(BlockNumber::Pending.into()))
+            .await
+            .map_err(FromErr::from)?;
+        let content = self.inner.txpool_content().await.map_err(FromErr::from)?;
+
+        let pooled = content
+            .pending
+            .get(&self.address)
+            .into_iter()
+            .chain(content.queued.get(&self.address))
+            .flat_map(|txs| txs.values())
+            .filter_map(|tx| tx.nonce)
+            .collect();
+        let local = if self.initialized.load(Ordering::SeqCst) {
+            Some(self.nonce.load(Ordering::SeqCst).into())
+        } else {
+            None
+        };
+        let submitted = {
+            let mut submitted = self.submitted.lock().unwrap();
+            // the mined nonces no longer need to be tracked
+            *submitted = submitted.split_off(&latest);
+            submitted.clone()
+        };
+
+        Ok(NonceStatus {
+            latest,
+            pending,
+            local,
+            pooled,
+            submitted,
+        })
+    }
+
+    /// Fills the nonce gaps of the address with self-sends of no value at the provided
+    /// gas price, unblocking its queued transactions. Returns the hashes of the sent
+    /// transactions.
+    ///
+    /// Only the nonces which were never sent are filled, since the transactions sent
+    /// through the manager may still be in flight to the node's txpool.
+    pub async fn fill_gaps(&self, gas_price: U256) -> Result<Vec<TxHash>, NonceManagerError<M>> {
+        let status = self.nonce_status().await?;
+        let mut tx_hashes = Vec::new();
+        for tx in status.gap_fillers(self.address, gas_price) {
+            let nonce = tx.nonce;
+            let pending_tx = self
+                .inner
+                .send_transaction(tx, None)
+                .await
+                .map_err(FromErr::from)?;
+            self.submitted(nonce.as_ref());
+            tx_hashes.push(*pending_tx);
+        }
+        Ok(tx_hashes)
+    }
+
     async fn get_transaction_count_with_manager(
         &self,
         block: Option<BlockId>,
@@ -52,6 +134,78 @@ where
 
         Ok(self.next())
     }
+
+    /// Records that a transaction with the nonce was sent through the manager
+    fn submitted(&self, nonce: Option<&U256>) {
+        if let Some(nonce) = nonce {
+            self.submitted.lock().unwrap().insert(*nonce);
+        }
+    }
+
+    fn is_nonce_too_low(&self, err: &M::Error) -> bool {
+        match self.inner.as_error_response(err) {
+            Some(err) => err.message.contains("nonce too low"),
+            None => err.to_string().contains("nonce too low"),
+        }
+    }
+}
+
+/// A snapshot of the nonces of an address, see [`NonceManagerMiddleware::nonce_status`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NonceStatus {
+    /// The nonce of the next transaction to be mined, i.e. the number of mined transactions
+    pub latest: U256,
+    /// The nonce following the transactions which the node can mine in sequence
+    pub pending: U256,
+    /// The next nonce the manager will assign, `None` if it was not initialized yet
+    pub local: Option<U256>,
+    /// The nonces of the address' transactions in the txpool
+    pub pooled: BTreeSet<U256>,
+    /// The nonces of the transactions sent through the manager which were not mined yet
+    pub submitted: BTreeSet<U256>,
+}
+
+impl NonceStatus {
+    /// Returns the nonces which are not mined, have no transaction in the txpool and
+    /// were never sent through the manager, while a higher nonce was assigned locally or
+    /// is in the txpool. The transactions after a gap cannot be mined until it is filled.
+    pub fn gaps(&self) -> Vec<U256> {
+        let highest_pooled = self.pooled.iter().next_back().map(|nonce| *nonce + 1);
+        let end = std::cmp::max(
+            self.local.unwrap_or_default(),
+            highest_pooled.unwrap_or_default(),
+        );
+
+        let mut gaps = Vec::new();
+        let mut nonce = self.latest;
+        while nonce < end {
+            if !self.pooled.contains(&nonce) && !self.submitted.contains(&nonce) {
+                gaps.push(nonce);
+            }
+            nonce += U256::one();
+        }
+        gaps
+    }
+
+    /// Returns whether some transactions of the address are stuck behind a gap
+    pub fn is_stuck(&self) -> bool {
+        !self.gaps().is_empty()
+    }
+
+    /// Returns the transactions filling the gaps: self-sends of no value, at the
+    /// provided gas price
+    pub fn gap_fillers(&self, address: Address, gas_price: U256) -> Vec<TransactionRequest> {
+        self.gaps()
+            .into_iter()
+            .map(|nonce| {
+                TransactionRequest::pay(address, 0)
+                    .from(address)
+                    .nonce(nonce)
+                    .gas(21_000)
+                    .gas_price(gas_price)
+            })
+            .collect()
+    }
 }
 
 #[derive(Error, Debug)]
@@ -81,37 +235,53 @@ where
         &self.inner
     }
 
+    fn as_error_response<'a>(&self, err: &'a Self::Error) -> Option<&'a JsonRpcError> {
+        match err {
+            NonceManagerError::MiddlewareError(err) => self.inner.as_error_response(err),
+            _ => None,
+        }
+    }
+
     /// Signs and broadcasts the transaction. The optional parameter `block` can be passed so that
     /// gas cost and nonce calculations take it into account. For simple transactions this can be
     /// left to `None`.
-    async fn send_transaction(
+    async fn send_transaction<T: Into<TypedTransaction> + Send + Sync>(
         &self,
-        mut tx: TransactionRequest,
+        tx: T,
         block: Option<BlockId>,
     ) -> Result<PendingTransaction<'_, Self::Provider>, Self::Error> {
-        if tx.nonce.is_none() {
-            tx.nonce = Some(self.get_transaction_count_with_manager(block).await?);
+        let mut tx = tx.into();
+        let managed = tx.nonce().is_none();
+        if managed {
+            tx.set_nonce(self.get_transaction_count_with_manager(block).await?);
         }
 
         let mut tx_clone = tx.clone();
+        let nonce = tx.nonce().copied();
         match self.inner.send_transaction(tx, block).await {
-            Ok(tx_hash) => Ok(tx_hash),
-            Err(err) => {
-                let nonce = self.get_transaction_count(self.address, block).await?;
-                if nonce != self.nonce.load(Ordering::SeqCst).into() {
-                    // try re-submitting the transaction with the correct nonce if there
-                    // was a nonce mismatch
-                    self.nonce.store(nonce.as_u64(), Ordering::SeqCst);
-                    tx_clone.nonce = Some(nonce);
-                    self.inner
-                        .send_transaction(tx_clone, block)
-                        .await
-                        .map_err(FromErr::from)
-                } else {
-                    // propagate the error otherwise
-                    Err(FromErr::from(err))
-                }
+            Ok(tx_hash) => {
+                self.submitted(nonce.as_ref());
+                Ok(tx_hash)
+            }
+            Err(err) if managed && self.is_nonce_too_low(&err) => {
+                // the address sent transactions without the manager, resync the nonce
+                // from the node and re-submit the transaction with it
+                let nonce = self
+                    .inner
+                    .get_transaction_count(self.address, block)
+                    .await
+                    .map_err(FromErr::from)?;
+                self.nonce.store(nonce.as_u64() + 1, Ordering::SeqCst);
+                tx_clone.set_nonce(nonce);
+                let tx_hash = self
+                    .inner
+                    .send_transaction(tx_clone, block)
+                    .await
+                    .map_err(FromErr::from)?;
+                self.submitted(Some(&nonce));
+                Ok(tx_hash)
             }
+            Err(err) => Err(FromErr::from(err)),
         }
     }
 }
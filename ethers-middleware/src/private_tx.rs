@@ -0,0 +1,224 @@
+use async_trait::async_trait;
+use ethers_core::types::*;
+use ethers_providers::{FromErr, JsonRpcError, Middleware, PendingTransaction};
+use thiserror::Error;
+use tracing::warn;
+
+#[derive(Debug)]
+/// Middleware submitting the signed transactions privately to block builders with
+/// `eth_sendPrivateTransaction` (e.g. via [Flashbots Protect](https://docs.flashbots.net/flashbots-protect/overview)),
+/// instead of broadcasting them to the public mempool where they could be frontrun.
+///
+/// It must be placed below the [`SignerMiddleware`](crate::SignerMiddleware), which
+/// sends the transactions it signs with `eth_sendRawTransaction`. If the node does not
+/// support private transactions, they are broadcast publicly unless the
+/// [`public_fallback`](Self::public_fallback) is disabled.
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers::{
+///     middleware::{PrivateTransactionMiddleware, SignerMiddleware},
+///     providers::{Http, Middleware, Provider},
+///     signers::LocalWallet,
+///     types::TransactionRequest,
+/// };
+/// use std::convert::TryFrom;
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Provider::<Http>::try_from("https://rpc.flashbots.net")?;
+/// // give up on the transaction if it is not included within 25 blocks
+/// let provider = PrivateTransactionMiddleware::new(provider).max_blocks(25);
+///
+/// let wallet: LocalWallet = "380eb0f3d505f087e438eca80bc4df9a7faa24f868e69fc0440261a0fc0567dc"
+///     .parse()?;
+/// let client = SignerMiddleware::new(provider, wallet);
+///
+/// let tx = TransactionRequest::pay("vitalik.eth", 100);
+/// let pending_tx = client.send_transaction(tx, None).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct PrivateTransactionMiddleware<M> {
+    inner: M,
+    max_blocks: Option<u64>,
+    fast: bool,
+    public_fallback: bool,
+}
+
+impl<M> PrivateTransactionMiddleware<M>
+where
+    M: Middleware,
+{
+    /// Instantiates the middleware, which falls back to broadcasting the transactions
+    /// publicly if the node does not support private transactions
+    pub fn new(inner: M) -> Self {
+        Self {
+            inner,
+            max_blocks: None,
+            fast: false,
+            public_fallback: true,
+        }
+    }
+
+    /// Sets the number of blocks after the current one within which the transactions
+    /// must be included, after which the builders stop trying to include them
+    pub fn max_blocks(mut self, max_blocks: u64) -> Self {
+        self.max_blocks = Some(max_blocks);
+        self
+    }
+
+    /// Sets whether the transactions are shared with all the builders, for faster
+    /// inclusion
+    pub fn fast(mut self, fast: bool) -> Self {
+        self.fast = fast;
+        self
+    }
+
+    /// Sets whether the transactions are broadcast publicly if the node does not support
+    /// private transactions
+    pub fn public_fallback(mut self, public_fallback: bool) -> Self {
+        self.public_fallback = public_fallback;
+        self
+    }
+
+    async fn send_private(
+        &self,
+        rlp: Bytes,
+    ) -> Result<PendingTransaction<'_, M::Provider>, PrivateTransactionError<M>> {
+        let mut tx = PrivateTransaction::new(rlp.clone());
+        if let Some(max_blocks) = self.max_blocks {
+            let block = self.inner.get_block_number().await.map_err(FromErr::from)?;
+            tx = tx.max_block_number(block + max_blocks);
+        }
+        if self.fast {
+            tx = tx.fast(true);
+        }
+
+        match self.inner.send_private_transaction(&tx).await {
+            Ok(pending_tx) => Ok(pending_tx),
+            Err(err) if self.public_fallback && self.is_unsupported(&err) => {
+                warn!("private transactions are not supported, broadcasting publicly");
+                self.inner
+                    .send_raw_transaction_bytes(rlp)
+                    .await
+                    .map_err(FromErr::from)
+            }
+            Err(err) => Err(FromErr::from(err)),
+        }
+    }
+
+    fn is_unsupported(&self, err: &M::Error) -> bool {
+        self.inner
+            .as_error_response(err)
+            .map_or(false, JsonRpcError::is_method_not_found)
+    }
+}
+
+#[derive(Error, Debug)]
+/// Thrown when an error happens at the private transaction middleware
+pub enum PrivateTransactionError<M: Middleware> {
+    /// Thrown when the internal middleware errors
+    #[error("{0}")]
+    MiddlewareError(M::Error),
+}
+
+impl<M: Middleware> FromErr<M::Error> for PrivateTransactionError<M> {
+    fn from(src: M::Error) -> Self {
+        PrivateTransactionError::MiddlewareError(src)
+    }
+}
+
+#[async_trait]
+impl<M> Middleware for PrivateTransactionMiddleware<M>
+where
+    M: Middleware,
+{
+    type Error = PrivateTransactionError<M>;
+    type Provider = M::Provider;
+    type Inner = M;
+
+    fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    fn as_error_response<'a>(&self, err: &'a Self::Error) -> Option<&'a JsonRpcError> {
+        match err {
+            PrivateTransactionError::MiddlewareError(err) => self.inner.as_error_response(err),
+        }
+    }
+
+    async fn send_raw_transaction<'a>(
+        &'a self,
+        tx: &Transaction,
+    ) -> Result<PendingTransaction<'a, Self::Provider>, Self::Error> {
+        self.send_private(tx.rlp()).await
+    }
+
+    async fn send_raw_transaction_bytes<'a>(
+        &'a self,
+        rlp: Bytes,
+    ) -> Result<PendingTransaction<'a, Self::Provider>, Self::Error> {
+        self.send_private(rlp).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers_providers::Provider;
+
+    #[tokio::test]
+    async fn sends_private_tx() {
+        let (provider, mock) = Provider::mocked();
+        let provider = PrivateTransactionMiddleware::new(provider)
+            .max_blocks(25)
+            .fast(true);
+        let hash = H256::repeat_byte(1);
+        mock.push_response("eth_blockNumber", U64::from(100))
+            .unwrap();
+        mock.push_response("eth_sendPrivateTransaction", hash)
+            .unwrap();
+
+        let rlp = Bytes::from(vec![1u8, 2, 3]);
+        let pending_tx = provider
+            .send_raw_transaction_bytes(rlp.clone())
+            .await
+            .unwrap();
+        assert_eq!(*pending_tx, hash);
+
+        mock.assert_request("eth_blockNumber", ()).unwrap();
+        let tx = PrivateTransaction::new(rlp)
+            .max_block_number(125u64)
+            .fast(true);
+        mock.assert_request("eth_sendPrivateTransaction", [tx])
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_public_mempool() {
+        let (provider, mock) = Provider::mocked();
+        let provider = PrivateTransactionMiddleware::new(provider);
+        let not_found = || JsonRpcError {
+            code: -32601,
+            message: "the method eth_sendPrivateTransaction does not exist".to_owned(),
+            data: None,
+        };
+        mock.push_error("eth_sendPrivateTransaction", not_found());
+        mock.push_response("eth_sendRawTransaction", H256::repeat_byte(1))
+            .unwrap();
+
+        let rlp = Bytes::from(vec![1u8, 2, 3]);
+        provider
+            .send_raw_transaction_bytes(rlp.clone())
+            .await
+            .unwrap();
+        mock.assert_calls(&["eth_sendPrivateTransaction", "eth_sendRawTransaction"]);
+
+        // the fallback can be disabled
+        let provider = provider.public_fallback(false);
+        mock.push_error("eth_sendPrivateTransaction", not_found());
+        provider.send_raw_transaction_bytes(rlp).await.unwrap_err();
+        assert_eq!(mock.call_count("eth_sendRawTransaction"), 1);
+    }
+}
@@ -1,10 +1,11 @@
 use ethers_core::{
     types::{
-        Address, BlockId, Bytes, NameOrAddress, Signature, Transaction, TransactionRequest, U256,
+        Address, BlockId, Bytes, NameOrAddress, Signature, SignedTransaction, Transaction,
+        TypedTransaction, EIP1559_TX_TYPE, EIP2930_TX_TYPE, U256,
     },
     utils::keccak256,
 };
-use ethers_providers::{FromErr, Middleware, PendingTransaction};
+use ethers_providers::{FromErr, JsonRpcError, Middleware, PendingTransaction};
 use ethers_signers::Signer;
 
 use async_trait::async_trait;
@@ -99,6 +100,9 @@ pub enum SignerMiddlewareError<M: Middleware, S: Signer> {
     /// Thrown if the `gas` field is missing
     #[error("no gas was specified")]
     GasMissing,
+    /// Thrown if the `from` field is not the signer's address
+    #[error("the transaction is from {0:?}, which is not the signer's address")]
+    WrongSigner(Address),
 }
 
 // Helper functions for locally signing transactions
@@ -117,14 +121,23 @@ where
         }
     }
 
-    async fn sign_transaction(
+    async fn sign_transaction_locally(
         &self,
-        tx: TransactionRequest,
+        tx: TypedTransaction,
     ) -> Result<Transaction, SignerMiddlewareError<M, S>> {
+        // The transaction must be sent from the signer's address
+        if let Some(from) = tx.from() {
+            if *from != self.address() {
+                return Err(SignerMiddlewareError::WrongSigner(*from));
+            }
+        }
+
         // The nonce, gas and gasprice fields must already be populated
-        let nonce = tx.nonce.ok_or(SignerMiddlewareError::NonceMissing)?;
-        let gas_price = tx.gas_price.ok_or(SignerMiddlewareError::GasPriceMissing)?;
-        let gas = tx.gas.ok_or(SignerMiddlewareError::GasMissing)?;
+        let nonce = *tx.nonce().ok_or(SignerMiddlewareError::NonceMissing)?;
+        let gas_price = tx
+            .gas_price()
+            .ok_or(SignerMiddlewareError::GasPriceMissing)?;
+        let gas = *tx.gas().ok_or(SignerMiddlewareError::GasMissing)?;
 
         let signature = self
             .signer
@@ -137,26 +150,50 @@ where
         let hash = keccak256(&rlp.as_ref());
 
         // This function should not be called with ENS names
-        let to = tx.to.map(|to| match to {
-            NameOrAddress::Address(inner) => inner,
+        let to = tx.to().map(|to| match to {
+            NameOrAddress::Address(inner) => *inner,
             NameOrAddress::Name(_) => {
                 panic!("Expected `to` to be an Ethereum Address, not an ENS name")
             }
         });
 
+        let (transaction_type, max_fee_per_gas, max_priority_fee_per_gas) = match tx {
+            TypedTransaction::Legacy(_) => (None, None, None),
+            TypedTransaction::Eip2930(_) => (Some(EIP2930_TX_TYPE.into()), None, None),
+            TypedTransaction::Eip1559(ref tx) => (
+                Some(EIP1559_TX_TYPE.into()),
+                tx.max_fee_per_gas,
+                tx.max_priority_fee_per_gas,
+            ),
+        };
+
+        // Celo fields are only part of legacy transactions
+        #[cfg(feature = "celo")]
+        let legacy = match tx {
+            TypedTransaction::Legacy(ref tx) => Some(tx),
+            _ => None,
+        };
+
         Ok(Transaction {
             hash: hash.into(),
             nonce,
             from: self.address(),
             to,
-            value: tx.value.unwrap_or_default(),
+            value: tx.value().copied().unwrap_or_default(),
             gas_price,
             gas,
-            input: tx.data.unwrap_or_default(),
+            input: tx.data().cloned().unwrap_or_default(),
             v: signature.v.into(),
             r: U256::from_big_endian(signature.r.as_bytes()),
             s: U256::from_big_endian(signature.s.as_bytes()),
 
+            // Typed transaction fields
+            transaction_type,
+            chain_id: tx.chain_id(),
+            access_list: tx.access_list().cloned(),
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+
             // Leave these empty as they're only used for included transactions
             block_hash: None,
             block_number: None,
@@ -164,37 +201,32 @@ where
 
             // Celo support
             #[cfg(feature = "celo")]
-            fee_currency: tx.fee_currency,
+            fee_currency: legacy.and_then(|tx| tx.fee_currency),
             #[cfg(feature = "celo")]
-            gateway_fee: tx.gateway_fee,
+            gateway_fee: legacy.and_then(|tx| tx.gateway_fee),
             #[cfg(feature = "celo")]
-            gateway_fee_recipient: tx.gateway_fee_recipient,
+            gateway_fee_recipient: legacy.and_then(|tx| tx.gateway_fee_recipient),
         })
     }
 
-    async fn fill_transaction(
+    /// Sets the chain id of typed transactions, which is part of their signed payload. The
+    /// one of the signer is preferred, so that the node is only queried if it is unset.
+    async fn fill_chain_id(
         &self,
-        tx: &mut TransactionRequest,
-        block: Option<BlockId>,
+        tx: &mut TypedTransaction,
     ) -> Result<(), SignerMiddlewareError<M, S>> {
-        // set the `from` field
-        if tx.from.is_none() {
-            tx.from = Some(self.address());
+        if !matches!(tx, TypedTransaction::Legacy(_)) && tx.chain_id().is_none() {
+            let chain_id = match self.signer.chain_id() {
+                Some(chain_id) => chain_id,
+                None => self
+                    .inner
+                    .get_chainid()
+                    .await
+                    .map_err(SignerMiddlewareError::MiddlewareError)?
+                    .as_u64(),
+            };
+            tx.set_chain_id(chain_id);
         }
-
-        // will poll and await the futures concurrently
-        let (gas_price, gas, nonce) = join!(
-            maybe(tx.gas_price, self.inner.get_gas_price()),
-            maybe(tx.gas, self.inner.estimate_gas(&tx)),
-            maybe(
-                tx.nonce,
-                self.inner.get_transaction_count(self.address(), block)
-            ),
-        );
-        tx.gas_price = Some(gas_price.map_err(SignerMiddlewareError::MiddlewareError)?);
-        tx.gas = Some(gas.map_err(SignerMiddlewareError::MiddlewareError)?);
-        tx.nonce = Some(nonce.map_err(SignerMiddlewareError::MiddlewareError)?);
-
         Ok(())
     }
 
@@ -234,34 +266,71 @@ where
         &self.inner
     }
 
+    fn as_error_response<'a>(&self, err: &'a Self::Error) -> Option<&'a JsonRpcError> {
+        match err {
+            SignerMiddlewareError::MiddlewareError(err) => self.inner.as_error_response(err),
+            _ => None,
+        }
+    }
+
     /// `SignerMiddleware` is instantiated with a signer.
     async fn is_signer(&self) -> bool {
         true
     }
 
+    /// Fills the missing fields of the transaction, including the ones needed to sign it
+    /// locally: its nonce, the chain id of typed transactions and the gas price of
+    /// transactions other than EIP-1559 ones.
+    async fn fill_transaction(
+        &self,
+        tx: &mut TypedTransaction,
+        block: Option<BlockId>,
+    ) -> Result<(), Self::Error> {
+        // set the `from` field
+        if tx.from().is_none() {
+            tx.set_from(self.address());
+        }
+
+        self.fill_chain_id(tx).await?;
+
+        // the gas and the EIP-1559 fees are filled by the inner middleware
+        self.inner
+            .fill_transaction(tx, block)
+            .await
+            .map_err(SignerMiddlewareError::MiddlewareError)?;
+
+        // will poll and await the futures concurrently. the fees of EIP-1559 transactions
+        // have been filled above, so only legacy and EIP-2930 ones may query the gas price
+        let (gas_price, nonce) = join!(
+            maybe(tx.gas_price(), self.inner.get_gas_price()),
+            maybe(
+                tx.nonce().copied(),
+                self.inner.get_transaction_count(self.address(), block)
+            ),
+        );
+        if tx.gas_price().is_none() {
+            tx.set_gas_price(gas_price.map_err(SignerMiddlewareError::MiddlewareError)?);
+        }
+        tx.set_nonce(nonce.map_err(SignerMiddlewareError::MiddlewareError)?);
+
+        Ok(())
+    }
+
     /// Signs and broadcasts the transaction. The optional parameter `block` can be passed so that
     /// gas cost and nonce calculations take it into account. For simple transactions this can be
     /// left to `None`.
-    async fn send_transaction(
+    async fn send_transaction<T: Into<TypedTransaction> + Send + Sync>(
         &self,
-        mut tx: TransactionRequest,
+        tx: T,
         block: Option<BlockId>,
     ) -> Result<PendingTransaction<'_, Self::Provider>, Self::Error> {
-        if let Some(NameOrAddress::Name(ens_name)) = tx.to {
-            let addr = self
-                .inner
-                .resolve_name(&ens_name)
-                .await
-                .map_err(SignerMiddlewareError::MiddlewareError)?;
-            tx.to = Some(addr.into())
-        }
+        let mut tx = tx.into();
 
         // fill any missing fields
         self.fill_transaction(&mut tx, block).await?;
 
-        // if we have a nonce manager set, we should try handling the result in
-        // case there was a nonce mismatch
-        let signed_tx = self.sign_transaction(tx).await?;
+        // sign the transaction with the internal signer
+        let signed_tx = self.sign_transaction_locally(tx).await?;
 
         // Submit the raw transaction
         self.inner
@@ -270,6 +339,19 @@ where
             .map_err(SignerMiddlewareError::MiddlewareError)
     }
 
+    /// Signs the transaction with the internal signer without broadcasting it. Its nonce,
+    /// gas and gas price or fees must already be set, while the chain id of typed
+    /// transactions is filled like in `fill_transaction`.
+    async fn sign_transaction<T: Into<TypedTransaction> + Send + Sync>(
+        &self,
+        tx: T,
+    ) -> Result<SignedTransaction, Self::Error> {
+        let mut tx = tx.into();
+        self.fill_chain_id(&mut tx).await?;
+        let tx = self.sign_transaction_locally(tx).await?;
+        Ok(SignedTransaction { raw: tx.rlp(), tx })
+    }
+
     /// Signs a message with the internal signer, or if none is present it will make a call to
     /// the connected node's `eth_call` API.
     async fn sign<T: Into<Bytes> + Send + Sync>(
@@ -300,6 +382,7 @@ where
 mod tests {
     use super::*;
     use ethers::{providers::Provider, signers::LocalWallet};
+    use ethers_core::types::{Eip1559TransactionRequest, TransactionRequest, U64};
     use std::convert::TryFrom;
 
     #[tokio::test]
@@ -329,7 +412,7 @@ mod tests {
             .set_chain_id(chain_id);
         let client = SignerMiddleware::new(provider, key);
 
-        let tx = client.sign_transaction(tx).await.unwrap();
+        let SignedTransaction { raw, tx } = client.sign_transaction(tx).await.unwrap();
 
         assert_eq!(
             tx.hash,
@@ -340,5 +423,122 @@ mod tests {
 
         let expected_rlp = Bytes::from(hex::decode("f869808504e3b29200831e848094f0109fc8df283027b6285cc889f5aa624eac1f55843b9aca008025a0c9cf86333bcb065d140032ecaab5d9281bde80f21b9687b3e94161de42d51895a0727a108a0b8d101465414033c3f705a9c7b826e596766046ee1183dbc8aeaa68").unwrap());
         assert_eq!(tx.rlp(), expected_rlp);
+        assert_eq!(raw, expected_rlp);
+    }
+
+    #[tokio::test]
+    async fn sends_typed_tx() {
+        let (provider, mock) = Provider::mocked();
+        let key = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+            .parse::<LocalWallet>()
+            .unwrap();
+        let client = SignerMiddleware::new(provider, key);
+
+        let tx = Eip1559TransactionRequest::pay(Address::repeat_byte(1), 100)
+            .nonce(0)
+            .gas(21_000)
+            .max_fee_per_gas(20)
+            .max_priority_fee_per_gas(2);
+        let hash = ethers_core::types::H256::repeat_byte(3);
+        mock.push(hash).unwrap();
+        // the chain id is requested from the node
+        mock.push(U256::from(5)).unwrap();
+
+        let pending = client.send_transaction(tx.clone(), None).await.unwrap();
+        assert_eq!(*pending, hash);
+
+        mock.assert_request("eth_chainId", ()).unwrap();
+
+        let tx = tx.from(client.address()).chain_id(5u64);
+        let rlp = client
+            .signer()
+            .sign_raw_transaction(&tx.into())
+            .await
+            .unwrap();
+        mock.assert_request("eth_sendRawTransaction", [rlp])
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn uses_chain_id_of_signer() {
+        let (provider, mock) = Provider::mocked();
+        let key = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+            .parse::<LocalWallet>()
+            .unwrap()
+            .set_chain_id(7u64);
+        let client = SignerMiddleware::new(provider, key);
+
+        let tx = Eip1559TransactionRequest::pay(Address::repeat_byte(1), 100)
+            .nonce(0)
+            .gas(21_000)
+            .max_fee_per_gas(20)
+            .max_priority_fee_per_gas(2);
+        let hash = ethers_core::types::H256::repeat_byte(3);
+        mock.push(hash).unwrap();
+
+        let pending = client.send_transaction(tx.clone(), None).await.unwrap();
+        assert_eq!(*pending, hash);
+
+        // the node is not asked for its chain id
+        mock.assert_calls(&["eth_sendRawTransaction"]);
+        let tx = tx.from(client.address()).chain_id(7u64);
+        let rlp = client
+            .signer()
+            .sign_raw_transaction(&tx.into())
+            .await
+            .unwrap();
+        mock.assert_request("eth_sendRawTransaction", [rlp])
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn signs_typed_tx_without_chain_id() {
+        let (provider, mock) = Provider::mocked();
+        let key = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+            .parse::<LocalWallet>()
+            .unwrap()
+            .set_chain_id(5u64);
+        let client = SignerMiddleware::new(provider, key);
+
+        let tx = Eip1559TransactionRequest::pay(Address::repeat_byte(1), 100)
+            .nonce(0)
+            .gas(21_000)
+            .max_fee_per_gas(20)
+            .max_priority_fee_per_gas(2);
+        let SignedTransaction { raw, tx: signed } =
+            client.sign_transaction(tx.clone()).await.unwrap();
+        mock.assert_calls(&[]);
+
+        // the chain id of the signer is the first field of the typed payload
+        assert_eq!(raw[0], EIP1559_TX_TYPE);
+        let rlp = ethers_core::utils::rlp::Rlp::new(&raw[1..]);
+        assert_eq!(rlp.val_at::<U64>(0).unwrap(), U64::from(5));
+        assert_eq!(signed.chain_id, Some(U64::from(5)));
+
+        let expected = client
+            .signer()
+            .sign_raw_transaction(&tx.from(client.address()).chain_id(5u64).into())
+            .await
+            .unwrap();
+        assert_eq!(raw, expected);
+    }
+
+    #[tokio::test]
+    async fn rejects_other_sender() {
+        let (provider, mock) = Provider::mocked();
+        let key = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+            .parse::<LocalWallet>()
+            .unwrap();
+        let client = SignerMiddleware::new(provider, key);
+
+        let other = Address::repeat_byte(2);
+        let tx = TransactionRequest::pay(Address::repeat_byte(1), 100)
+            .from(other)
+            .nonce(0)
+            .gas(21_000)
+            .gas_price(20);
+        let err = client.send_transaction(tx, None).await.unwrap_err();
+        assert!(matches!(err, SignerMiddlewareError::WrongSigner(from) if from == other));
+        mock.assert_calls(&[]);
     }
 }
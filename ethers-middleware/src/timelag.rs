@@ -0,0 +1,402 @@
+use async_trait::async_trait;
+use ethers_core::types::*;
+use ethers_providers::{FromErr, JsonRpcError, Middleware};
+use thiserror::Error;
+
+#[derive(Debug)]
+/// Middleware serving the chain's data `lag` blocks behind its head, so that applications
+/// sensitive to reorgs only read sufficiently confirmed blocks.
+///
+/// The latest block number is reported as `head - lag`, and the queries made at the
+/// latest block, either explicitly or by default, are made at that block instead.
+/// Nonces are not lagged since they are used to fill the transactions being sent, and
+/// transactions are sent unchanged.
+///
+/// Filters and subscriptions (e.g. [`Middleware::new_filter`], [`Middleware::watch`],
+/// [`Middleware::watch_blocks`] or [`Middleware::subscribe_logs`]) are not lagged either:
+/// the node installs them and streams its changes as soon as they reach its head. Poll
+/// [`Middleware::get_logs`] with a block range instead to only read lagged logs.
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers::{
+///     middleware::TimeLagMiddleware,
+///     providers::{Http, Middleware, Provider},
+///     types::Address,
+/// };
+/// use std::convert::TryFrom;
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Provider::<Http>::try_from("http://localhost:8545")?;
+/// // only read blocks with at least 12 confirmations
+/// let provider = TimeLagMiddleware::new(provider, 12);
+///
+/// let balance = provider.get_balance(Address::zero(), None).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct TimeLagMiddleware<M> {
+    inner: M,
+    lag: u64,
+}
+
+impl<M> TimeLagMiddleware<M>
+where
+    M: Middleware,
+{
+    /// Instantiates the middleware, serving the data `lag` blocks behind the head
+    pub fn new(inner: M, lag: u64) -> Self {
+        Self { inner, lag }
+    }
+
+    /// Returns the number of blocks the served data lags behind the head
+    pub fn lag(&self) -> u64 {
+        self.lag
+    }
+
+    /// Replaces the latest block with the lagged block number
+    async fn normalize_block_number(
+        &self,
+        block: BlockNumber,
+    ) -> Result<BlockNumber, TimeLagError<M>> {
+        match block {
+            BlockNumber::Latest => Ok(BlockNumber::Number(self.get_block_number().await?)),
+            block => Ok(block),
+        }
+    }
+
+    /// Replaces the latest block, or the default one if `None`, with the lagged block
+    /// number
+    async fn normalize_block_id(&self, block: Option<BlockId>) -> Result<BlockId, TimeLagError<M>> {
+        match block {
+            None | Some(BlockId::Number(BlockNumber::Latest)) => {
+                Ok(self.get_block_number().await?.into())
+            }
+            Some(block) => Ok(block),
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+/// Thrown when an error happens at the time lag middleware
+pub enum TimeLagError<M: Middleware> {
+    /// Thrown when the internal middleware errors
+    #[error("{0}")]
+    MiddlewareError(M::Error),
+}
+
+impl<M: Middleware> FromErr<M::Error> for TimeLagError<M> {
+    fn from(src: M::Error) -> Self {
+        TimeLagError::MiddlewareError(src)
+    }
+}
+
+#[async_trait]
+impl<M> Middleware for TimeLagMiddleware<M>
+where
+    M: Middleware,
+{
+    type Error = TimeLagError<M>;
+    type Provider = M::Provider;
+    type Inner = M;
+
+    fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    fn as_error_response<'a>(&self, err: &'a Self::Error) -> Option<&'a JsonRpcError> {
+        match err {
+            TimeLagError::MiddlewareError(err) => self.inner.as_error_response(err),
+        }
+    }
+
+    /// Returns the number of the block `lag` blocks behind the head
+    async fn get_block_number(&self) -> Result<U64, Self::Error> {
+        let head = self.inner.get_block_number().await.map_err(FromErr::from)?;
+        Ok(head.saturating_sub(self.lag.into()))
+    }
+
+    async fn get_block<T: Into<BlockId> + Send + Sync>(
+        &self,
+        block_hash_or_number: T,
+    ) -> Result<Option<Block<TxHash>>, Self::Error> {
+        let block = self
+            .normalize_block_id(Some(block_hash_or_number.into()))
+            .await?;
+        self.inner.get_block(block).await.map_err(FromErr::from)
+    }
+
+    async fn get_header<T: Into<BlockId> + Send + Sync>(
+        &self,
+        block_hash_or_number: T,
+    ) -> Result<Option<Block<TxHash>>, Self::Error> {
+        let block = self
+            .normalize_block_id(Some(block_hash_or_number.into()))
+            .await?;
+        self.inner.get_header(block).await.map_err(FromErr::from)
+    }
+
+    async fn get_block_with_txs<T: Into<BlockId> + Send + Sync>(
+        &self,
+        block_hash_or_number: T,
+    ) -> Result<Option<Block<Transaction>>, Self::Error> {
+        let block = self
+            .normalize_block_id(Some(block_hash_or_number.into()))
+            .await?;
+        self.inner
+            .get_block_with_txs(block)
+            .await
+            .map_err(FromErr::from)
+    }
+
+    async fn get_block_receipts<T: Into<BlockNumber> + Send + Sync>(
+        &self,
+        block: T,
+    ) -> Result<Vec<TransactionReceipt>, Self::Error> {
+        let block = self.normalize_block_number(block.into()).await?;
+        self.inner
+            .get_block_receipts(block)
+            .await
+            .map_err(FromErr::from)
+    }
+
+    async fn call(
+        &self,
+        tx: &TypedTransaction,
+        block: Option<BlockId>,
+    ) -> Result<Bytes, Self::Error> {
+        let block = Some(self.normalize_block_id(block).await?);
+        self.inner.call(tx, block).await.map_err(FromErr::from)
+    }
+
+    async fn call_with_state_overrides(
+        &self,
+        tx: &TypedTransaction,
+        block: Option<BlockId>,
+        overrides: &StateOverride,
+    ) -> Result<Bytes, Self::Error> {
+        let block = Some(self.normalize_block_id(block).await?);
+        self.inner
+            .call_with_state_overrides(tx, block, overrides)
+            .await
+            .map_err(FromErr::from)
+    }
+
+    async fn call_with_overrides(
+        &self,
+        tx: &TypedTransaction,
+        block: Option<BlockId>,
+        state_overrides: &StateOverride,
+        block_overrides: &BlockOverrides,
+    ) -> Result<Bytes, Self::Error> {
+        let block = Some(self.normalize_block_id(block).await?);
+        self.inner
+            .call_with_overrides(tx, block, state_overrides, block_overrides)
+            .await
+            .map_err(FromErr::from)
+    }
+
+    async fn simulate(
+        &self,
+        payload: &SimulatePayload,
+        block: Option<BlockId>,
+    ) -> Result<Vec<SimulatedBlock>, Self::Error> {
+        let block = Some(self.normalize_block_id(block).await?);
+        self.inner
+            .simulate(payload, block)
+            .await
+            .map_err(FromErr::from)
+    }
+
+    async fn create_access_list(
+        &self,
+        tx: &TypedTransaction,
+        block: Option<BlockId>,
+    ) -> Result<AccessListWithGasUsed, Self::Error> {
+        let block = Some(self.normalize_block_id(block).await?);
+        self.inner
+            .create_access_list(tx, block)
+            .await
+            .map_err(FromErr::from)
+    }
+
+    async fn get_balance<T: Into<NameOrAddress> + Send + Sync>(
+        &self,
+        from: T,
+        block: Option<BlockId>,
+    ) -> Result<U256, Self::Error> {
+        let block = Some(self.normalize_block_id(block).await?);
+        self.inner
+            .get_balance(from, block)
+            .await
+            .map_err(FromErr::from)
+    }
+
+    async fn fee_history<T: Into<U256> + Send + Sync>(
+        &self,
+        block_count: T,
+        last_block: BlockNumber,
+        reward_percentiles: &[f64],
+    ) -> Result<FeeHistory, Self::Error> {
+        let last_block = self.normalize_block_number(last_block).await?;
+        self.inner
+            .fee_history(block_count, last_block, reward_percentiles)
+            .await
+            .map_err(FromErr::from)
+    }
+
+    async fn get_logs(&self, filter: &Filter) -> Result<Vec<Log>, Self::Error> {
+        let mut filter = filter.clone();
+        if let FilterBlockOption::Range {
+            from_block,
+            to_block,
+        } = filter.block_option
+        {
+            let head = self.get_block_number().await?;
+            let lag = |block| match block {
+                None | Some(BlockNumber::Latest) => Some(BlockNumber::Number(head)),
+                block => block,
+            };
+            filter.block_option = FilterBlockOption::Range {
+                from_block: lag(from_block),
+                to_block: lag(to_block),
+            };
+        }
+        self.inner.get_logs(&filter).await.map_err(FromErr::from)
+    }
+
+    async fn get_code<T: Into<NameOrAddress> + Send + Sync>(
+        &self,
+        at: T,
+        block: Option<BlockId>,
+    ) -> Result<Bytes, Self::Error> {
+        let block = Some(self.normalize_block_id(block).await?);
+        self.inner.get_code(at, block).await.map_err(FromErr::from)
+    }
+
+    async fn get_account<T: Into<NameOrAddress> + Send + Sync>(
+        &self,
+        account: T,
+        block: Option<BlockId>,
+    ) -> Result<Account, Self::Error> {
+        let block = Some(self.normalize_block_id(block).await?);
+        self.inner
+            .get_account(account, block)
+            .await
+            .map_err(FromErr::from)
+    }
+
+    async fn get_storage_at<T: Into<NameOrAddress> + Send + Sync>(
+        &self,
+        from: T,
+        location: H256,
+        block: Option<BlockId>,
+    ) -> Result<H256, Self::Error> {
+        let block = Some(self.normalize_block_id(block).await?);
+        self.inner
+            .get_storage_at(from, location, block)
+            .await
+            .map_err(FromErr::from)
+    }
+
+    async fn get_proof<T: Into<NameOrAddress> + Send + Sync>(
+        &self,
+        from: T,
+        locations: Vec<H256>,
+        block: Option<BlockId>,
+    ) -> Result<EIP1186ProofResponse, Self::Error> {
+        let block = Some(self.normalize_block_id(block).await?);
+        self.inner
+            .get_proof(from, locations, block)
+            .await
+            .map_err(FromErr::from)
+    }
+
+    async fn trace_call(
+        &self,
+        req: &TypedTransaction,
+        trace_type: Vec<TraceType>,
+        block: Option<BlockNumber>,
+    ) -> Result<BlockTrace, Self::Error> {
+        let block = self
+            .normalize_block_number(block.unwrap_or(BlockNumber::Latest))
+            .await?;
+        self.inner
+            .trace_call(req, trace_type, Some(block))
+            .await
+            .map_err(FromErr::from)
+    }
+
+    async fn trace_block(&self, block: BlockNumber) -> Result<Vec<Trace>, Self::Error> {
+        let block = self.normalize_block_number(block).await?;
+        self.inner.trace_block(block).await.map_err(FromErr::from)
+    }
+
+    async fn debug_trace_call(
+        &self,
+        req: &TypedTransaction,
+        block: Option<BlockId>,
+        trace_options: GethDebugTracingCallOptions,
+    ) -> Result<GethTrace, Self::Error> {
+        let block = Some(self.normalize_block_id(block).await?);
+        self.inner
+            .debug_trace_call(req, block, trace_options)
+            .await
+            .map_err(FromErr::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers_providers::Provider;
+
+    #[tokio::test]
+    async fn lags_latest_block() {
+        let (provider, mock) = Provider::mocked();
+        let provider = TimeLagMiddleware::new(provider, 5);
+        let address = Address::repeat_byte(1);
+
+        mock.push_response("eth_blockNumber", U64::from(100))
+            .unwrap();
+        mock.push_response("eth_blockNumber", U64::from(100))
+            .unwrap();
+        mock.push_response("eth_getBalance", U256::from(1)).unwrap();
+        mock.push_response("eth_getBalance", U256::from(2)).unwrap();
+
+        assert_eq!(provider.get_block_number().await.unwrap(), 95.into());
+        provider.get_balance(address, None).await.unwrap();
+        // explicit blocks are left untouched
+        provider
+            .get_balance(address, Some(BlockNumber::Number(10.into()).into()))
+            .await
+            .unwrap();
+
+        mock.assert_request("eth_blockNumber", ()).unwrap();
+        mock.assert_request("eth_blockNumber", ()).unwrap();
+        mock.assert_request("eth_getBalance", (address, U64::from(95)))
+            .unwrap();
+        mock.assert_request("eth_getBalance", (address, U64::from(10)))
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn lags_logs_range() {
+        let (provider, mock) = Provider::mocked();
+        let provider = TimeLagMiddleware::new(provider, 5);
+
+        mock.push_response("eth_blockNumber", U64::from(103))
+            .unwrap();
+        mock.push_response::<Vec<Log>, _>("eth_getLogs", Vec::new())
+            .unwrap();
+
+        let filter = Filter::new().from_block(90u64);
+        provider.get_logs(&filter).await.unwrap();
+
+        mock.assert_request("eth_blockNumber", ()).unwrap();
+        // the range implicitly ends at the latest block
+        mock.assert_request("eth_getLogs", [filter.to_block(98u64)])
+            .unwrap();
+    }
+}
@@ -177,18 +177,18 @@ impl DsProxy {
 }
 
 impl Transformer for DsProxy {
-    fn transform(&self, tx: TransactionRequest) -> Result<TransactionRequest, TransformerError> {
+    fn transform(&self, tx: TypedTransaction) -> Result<TypedTransaction, TransformerError> {
         // clone the tx into a new proxy tx.
         let mut proxy_tx = tx.clone();
 
         // the target address cannot be None.
-        let target = match tx.to {
-            Some(NameOrAddress::Address(addr)) => Ok(addr),
+        let target = match tx.to() {
+            Some(NameOrAddress::Address(addr)) => Ok(*addr),
             _ => Err(TransformerError::MissingField("to".into())),
         }?;
 
         // fetch the data field.
-        let data = tx.data.unwrap_or_else(|| vec![].into());
+        let data = tx.data().cloned().unwrap_or_else(|| vec![].into());
 
         // encode data as the ABI encoded data for DSProxy's execute method.
         let selector = id("execute(address,bytes)");
@@ -197,8 +197,8 @@ impl Transformer for DsProxy {
             .encode_with_selector(selector, (target, data))?;
 
         // update appropriate fields of the proxy tx.
-        proxy_tx.data = Some(encoded_data);
-        proxy_tx.to = Some(NameOrAddress::Address(self.address));
+        proxy_tx.set_data(encoded_data);
+        proxy_tx.set_to(self.address);
 
         Ok(proxy_tx)
     }
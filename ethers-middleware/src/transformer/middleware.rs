@@ -1,7 +1,7 @@
 use super::{Transformer, TransformerError};
 use async_trait::async_trait;
 use ethers_core::types::*;
-use ethers_providers::{FromErr, Middleware, PendingTransaction};
+use ethers_providers::{FromErr, JsonRpcError, Middleware, PendingTransaction};
 use thiserror::Error;
 
 #[derive(Debug)]
@@ -22,6 +22,26 @@ where
     pub fn new(inner: M, transformer: T) -> Self {
         Self { inner, transformer }
     }
+
+    /// Resolves the ENS name of the `to` field, then transforms the transaction into one
+    /// executed via the proxy
+    async fn transform(
+        &self,
+        mut tx: TypedTransaction,
+    ) -> Result<TypedTransaction, TransformerMiddlewareError<M>> {
+        // resolve the to field if that's an ENS name.
+        if let Some(NameOrAddress::Name(ens_name)) = tx.to().cloned() {
+            let addr = self
+                .inner
+                .resolve_name(&ens_name)
+                .await
+                .map_err(TransformerMiddlewareError::MiddlewareError)?;
+            tx.set_to(addr);
+        }
+
+        // construct the appropriate proxy tx.
+        Ok(self.transformer.transform(tx)?)
+    }
 }
 
 #[derive(Error, Debug)]
@@ -53,23 +73,29 @@ where
         &self.inner
     }
 
-    async fn send_transaction(
+    fn as_error_response<'a>(&self, err: &'a Self::Error) -> Option<&'a JsonRpcError> {
+        match err {
+            TransformerMiddlewareError::MiddlewareError(err) => self.inner.as_error_response(err),
+            _ => None,
+        }
+    }
+
+    /// Estimates the gas of the transaction once executed via the proxy, which costs more
+    /// than executing it directly
+    async fn estimate_gas(&self, tx: &TypedTransaction) -> Result<U256, Self::Error> {
+        let proxy_tx = self.transform(tx.clone()).await?;
+        self.inner
+            .estimate_gas(&proxy_tx)
+            .await
+            .map_err(TransformerMiddlewareError::MiddlewareError)
+    }
+
+    async fn send_transaction<Tx: Into<TypedTransaction> + Send + Sync>(
         &self,
-        mut tx: TransactionRequest,
+        tx: Tx,
         block: Option<BlockId>,
     ) -> Result<PendingTransaction<'_, Self::Provider>, Self::Error> {
-        // resolve the to field if that's an ENS name.
-        if let Some(NameOrAddress::Name(ens_name)) = tx.to {
-            let addr = self
-                .inner
-                .resolve_name(&ens_name)
-                .await
-                .map_err(TransformerMiddlewareError::MiddlewareError)?;
-            tx.to = Some(addr.into())
-        }
-
-        // construct the appropriate proxy tx.
-        let proxy_tx = self.transformer.transform(tx)?;
+        let proxy_tx = self.transform(tx.into()).await?;
 
         // send the proxy tx.
         self.inner
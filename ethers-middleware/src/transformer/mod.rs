@@ -30,6 +30,6 @@ pub trait Transformer: Send + Sync + std::fmt::Debug {
     /// Transforms a [`transaction request`] into one that can be broadcasted and execute via the
     /// proxy contract.
     ///
-    /// [`transaction request`]: struct@ethers_core::types::TransactionRequest
-    fn transform(&self, tx: TransactionRequest) -> Result<TransactionRequest, TransformerError>;
+    /// [`transaction request`]: enum@ethers_core::types::TypedTransaction
+    fn transform(&self, tx: TypedTransaction) -> Result<TypedTransaction, TransformerError>;
 }
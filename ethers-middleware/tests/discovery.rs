@@ -0,0 +1,33 @@
+use ethers_core::types::U256;
+use ethers_middleware::discovery::discover_accounts;
+use ethers_providers::Provider;
+use ethers_signers::{coins_bip39::English, DeriveAddress, MnemonicBuilder};
+
+#[tokio::test]
+async fn discovers_used_accounts() {
+    let (provider, mock) = Provider::mocked();
+    let mnemonic = MnemonicBuilder::<English>::default().phrase(
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+    );
+
+    // (nonce, balance) of the accounts, account 1 is unused
+    let accounts: [(u64, u64); 5] = [(1, 0), (0, 0), (0, 5), (0, 0), (0, 0)];
+    for (nonce, balance) in &accounts {
+        mock.push_response("eth_getTransactionCount", U256::from(*nonce))
+            .unwrap();
+        mock.push_response("eth_getBalance", U256::from(*balance))
+            .unwrap();
+    }
+
+    // discovery stops after the two unused accounts, before running out of responses
+    let discovered = discover_accounts(&provider, &mnemonic, 2).await.unwrap();
+    assert_eq!(discovered.len(), 2);
+    assert_eq!(discovered[0].index, 0);
+    assert_eq!(discovered[0].nonce, 1.into());
+    assert_eq!(discovered[1].index, 2);
+    assert_eq!(discovered[1].balance, 5.into());
+    assert_eq!(
+        discovered[1].address,
+        mnemonic.derive_address(2).await.unwrap()
+    );
+}
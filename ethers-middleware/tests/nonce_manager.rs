@@ -57,3 +57,175 @@ async fn nonce_manager() {
 
     assert_eq!(nonces, (nonce..nonce + 10).collect::<Vec<_>>())
 }
+
+#[tokio::test]
+async fn resyncs_on_nonce_too_low() {
+    use ethers_core::types::*;
+    use ethers_middleware::nonce_manager::NonceManagerMiddleware;
+    use ethers_providers::{JsonRpcError, Middleware, Provider};
+
+    let (provider, mock) = Provider::mocked();
+    let address = Address::repeat_byte(1);
+    let provider = NonceManagerMiddleware::new(provider, address);
+
+    // the address sent 3 transactions without the manager since it was initialized
+    mock.push_response("eth_getTransactionCount", U256::from(5))
+        .unwrap();
+    mock.push_response("eth_getTransactionCount", U256::from(8))
+        .unwrap();
+    mock.push_error(
+        "eth_sendTransaction",
+        JsonRpcError {
+            code: -32000,
+            message: "nonce too low".to_owned(),
+            data: None,
+        },
+    );
+    for _ in 0..2 {
+        mock.push_response("eth_sendTransaction", H256::repeat_byte(2))
+            .unwrap();
+    }
+
+    let tx = TransactionRequest::pay(address, 1)
+        .from(address)
+        .gas(21_000);
+    provider.send_transaction(tx.clone(), None).await.unwrap();
+    provider.send_transaction(tx.clone(), None).await.unwrap();
+
+    mock.assert_calls(&[
+        "eth_getTransactionCount",
+        "eth_sendTransaction",
+        "eth_getTransactionCount",
+        "eth_sendTransaction",
+        "eth_sendTransaction",
+    ]);
+    let count = (address, BlockNumber::Latest);
+    mock.assert_request("eth_getTransactionCount", count)
+        .unwrap();
+    mock.assert_request(
+        "eth_sendTransaction",
+        [TypedTransaction::Legacy(tx.clone().nonce(5))],
+    )
+    .unwrap();
+    mock.assert_request("eth_getTransactionCount", count)
+        .unwrap();
+    mock.assert_request(
+        "eth_sendTransaction",
+        [TypedTransaction::Legacy(tx.clone().nonce(8))],
+    )
+    .unwrap();
+    mock.assert_request(
+        "eth_sendTransaction",
+        [TypedTransaction::Legacy(tx.nonce(9))],
+    )
+    .unwrap();
+}
+
+#[test]
+fn detects_nonce_gaps() {
+    use ethers_core::types::{Address, U256};
+    use ethers_middleware::NonceStatus;
+
+    let status = NonceStatus {
+        latest: 5.into(),
+        pending: 6.into(),
+        local: Some(10.into()),
+        pooled: vec![5, 7, 8].into_iter().map(U256::from).collect(),
+        submitted: Default::default(),
+    };
+    // the transactions of nonces 6 and 9 failed to be sent
+    assert_eq!(status.gaps(), vec![U256::from(6), U256::from(9)]);
+    assert!(status.is_stuck());
+
+    let fillers = status.gap_fillers(Address::zero(), 100.into());
+    assert_eq!(fillers.len(), 2);
+    assert_eq!(fillers[0].nonce, Some(6.into()));
+    assert_eq!(fillers[1].gas_price, Some(100.into()));
+
+    let status = NonceStatus {
+        latest: 5.into(),
+        pending: 7.into(),
+        local: Some(7.into()),
+        pooled: vec![5, 6].into_iter().map(U256::from).collect(),
+        submitted: Default::default(),
+    };
+    assert!(!status.is_stuck());
+
+    // the transactions sent through the manager may not have reached the txpool yet
+    let status = NonceStatus {
+        latest: 5.into(),
+        pending: 6.into(),
+        local: Some(10.into()),
+        pooled: vec![5, 7, 8].into_iter().map(U256::from).collect(),
+        submitted: vec![5, 6, 7, 8].into_iter().map(U256::from).collect(),
+    };
+    assert_eq!(status.gaps(), vec![U256::from(9)]);
+}
+
+#[tokio::test]
+async fn only_fills_nonces_never_sent() {
+    use ethers_core::types::*;
+    use ethers_middleware::nonce_manager::NonceManagerMiddleware;
+    use ethers_providers::{JsonRpcError, Middleware, Provider};
+
+    let (provider, mock) = Provider::mocked();
+    let address = Address::repeat_byte(1);
+    let provider = NonceManagerMiddleware::new(provider, address);
+
+    // nonce 5 is sent, while sending nonce 6 fails
+    mock.push_response("eth_getTransactionCount", U256::from(5))
+        .unwrap();
+    mock.push_response("eth_sendTransaction", H256::repeat_byte(2))
+        .unwrap();
+    mock.push_error(
+        "eth_sendTransaction",
+        JsonRpcError {
+            code: -32000,
+            message: "insufficient funds".to_owned(),
+            data: None,
+        },
+    );
+    let tx = TransactionRequest::pay(address, 1)
+        .from(address)
+        .gas(21_000);
+    provider.send_transaction(tx.clone(), None).await.unwrap();
+    provider
+        .send_transaction(tx.clone(), None)
+        .await
+        .unwrap_err();
+
+    // the node has not seen nonce 5 yet
+    mock.push_response("eth_getTransactionCount", U256::from(5))
+        .unwrap();
+    mock.push_response("eth_getTransactionCount", U256::from(5))
+        .unwrap();
+    mock.push_response("txpool_content", TxpoolContent::default())
+        .unwrap();
+    mock.push_response("eth_sendTransaction", H256::repeat_byte(3))
+        .unwrap();
+    let filled = provider.fill_gaps(100.into()).await.unwrap();
+    assert_eq!(filled, vec![H256::repeat_byte(3)]);
+
+    let latest = (address, BlockNumber::Latest);
+    mock.assert_request("eth_getTransactionCount", latest)
+        .unwrap();
+    for nonce in 5..7 {
+        mock.assert_request(
+            "eth_sendTransaction",
+            [TypedTransaction::Legacy(tx.clone().nonce(nonce))],
+        )
+        .unwrap();
+    }
+    mock.assert_request("eth_getTransactionCount", latest)
+        .unwrap();
+    mock.assert_request("eth_getTransactionCount", (address, BlockNumber::Pending))
+        .unwrap();
+    mock.assert_request("txpool_content", ()).unwrap();
+    let filler = TransactionRequest::pay(address, 0)
+        .from(address)
+        .nonce(6)
+        .gas(21_000)
+        .gas_price(100);
+    mock.assert_request("eth_sendTransaction", [TypedTransaction::Legacy(filler)])
+        .unwrap();
+}
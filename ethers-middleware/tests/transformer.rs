@@ -42,7 +42,7 @@ async fn ds_proxy_transformer() {
         contract.bytecode.clone(),
         Arc::clone(&provider),
     );
-    let ds_proxy_factory = factory.deploy(()).unwrap().send().await.unwrap();
+    let (ds_proxy_factory, _) = factory.deploy(()).unwrap().send().await.unwrap();
 
     // deploy a new DsProxy contract.
     let ds_proxy = DsProxy::build::<HttpWallet, Arc<HttpWallet>>(
@@ -66,7 +66,7 @@ async fn ds_proxy_transformer() {
         contract.bytecode.clone(),
         Arc::clone(&provider),
     );
-    let simple_storage = factory.deploy(()).unwrap().send().await.unwrap();
+    let (simple_storage, _) = factory.deploy(()).unwrap().send().await.unwrap();
 
     // instantiate a new transformer middleware.
     let provider = TransformerMiddleware::new(signer_middleware, ds_proxy.clone());
@@ -127,7 +127,7 @@ async fn ds_proxy_code() {
         contract.bytecode.clone(),
         Arc::clone(&provider),
     );
-    let ds_proxy_factory = factory.deploy(()).unwrap().send().await.unwrap();
+    let (ds_proxy_factory, _) = factory.deploy(()).unwrap().send().await.unwrap();
 
     // deploy a new DsProxy contract.
     let ds_proxy = DsProxy::build::<HttpWallet, Arc<HttpWallet>>(
@@ -176,3 +176,38 @@ async fn ds_proxy_code() {
     assert_eq!(last_sender, wallet_addr.into());
     assert_eq!(last_value, H256::from_low_u64_be(expected_value));
 }
+
+#[tokio::test]
+async fn estimates_gas_of_transformed_tx() {
+    use ethers_middleware::transformer::{Transformer, TransformerError};
+
+    // forwards every transaction through a relayer contract
+    #[derive(Debug)]
+    struct Relayer(Address);
+
+    impl Transformer for Relayer {
+        fn transform(
+            &self,
+            mut tx: TypedTransaction,
+        ) -> Result<TypedTransaction, TransformerError> {
+            tx.set_to(self.0);
+            Ok(tx)
+        }
+    }
+
+    let (provider, mock) = Provider::mocked();
+    let relayer = Address::repeat_byte(2);
+    let provider = TransformerMiddleware::new(provider, Relayer(relayer));
+    mock.push(U256::from(50_000)).unwrap();
+
+    let tx = TransactionRequest::new()
+        .to(Address::repeat_byte(1))
+        .data(vec![1, 2, 3]);
+    let gas = provider.estimate_gas(&tx.clone().into()).await.unwrap();
+    assert_eq!(gas, 50_000.into());
+    mock.assert_request(
+        "eth_estimateGas",
+        [TypedTransaction::Legacy(tx.to(relayer))],
+    )
+    .unwrap();
+}
@@ -0,0 +1,206 @@
+//! Typed bindings for [Geth's `admin` namespace](https://geth.ethereum.org/docs/rpc/ns-admin),
+//! used to manage the node and its peers.
+//!
+//! These methods are only served by nodes exposing the `admin` namespace, which should
+//! never be the case for public endpoints, so they are exposed as inherent methods on the
+//! [`Provider`](crate::Provider) instead of on the `Middleware` trait.
+use crate::{JsonRpcClient, Provider, ProviderError};
+
+use ethers_core::types::H256;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Information about the node, as returned by `admin_nodeInfo`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeInfo {
+    /// The node's public key, hex encoded
+    pub id: String,
+    /// The name and version of the node's software
+    pub name: String,
+    /// The node's enode URL
+    pub enode: String,
+    /// The node's Ethereum Node Record, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enr: Option<String>,
+    /// The node's IP address
+    pub ip: String,
+    /// The node's networking ports
+    pub ports: Ports,
+    /// The address the node listens on for peer connections
+    pub listen_addr: String,
+    /// The protocols run by the node
+    pub protocols: ProtocolInfo,
+}
+
+/// The networking ports of a node
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ports {
+    /// The UDP port used for peer discovery
+    pub discovery: u16,
+    /// The TCP port accepting peer connections
+    pub listener: u16,
+}
+
+/// The protocols run by a node
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ProtocolInfo {
+    /// The `eth` protocol, if the node runs it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eth: Option<EthProtocolInfo>,
+    /// The other protocols, e.g. `snap`, keyed by their name
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+/// The state of the node's `eth` protocol
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EthProtocolInfo {
+    /// The network id of the chain
+    pub network: u64,
+    /// The total difficulty of the node's head block. It is returned as a JSON number,
+    /// which loses precision for values above 2^53.
+    pub difficulty: serde_json::Number,
+    /// The hash of the genesis block
+    pub genesis: H256,
+    /// The hash of the node's head block
+    pub head: H256,
+    /// The chain configuration
+    pub config: serde_json::Value,
+}
+
+/// A peer of the node, as returned by `admin_peers`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PeerInfo {
+    /// The peer's public key, hex encoded
+    pub id: String,
+    /// The name and version of the peer's software
+    pub name: String,
+    /// The peer's enode URL
+    pub enode: String,
+    /// The peer's Ethereum Node Record, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enr: Option<String>,
+    /// The capabilities advertised by the peer, e.g. `eth/66`
+    pub caps: Vec<String>,
+    /// The connection to the peer
+    pub network: PeerNetworkInfo,
+    /// The state of each protocol run with the peer, keyed by their name
+    pub protocols: BTreeMap<String, serde_json::Value>,
+}
+
+/// The connection to a peer
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerNetworkInfo {
+    /// The local endpoint of the connection
+    pub local_address: String,
+    /// The remote endpoint of the connection
+    pub remote_address: String,
+    /// Whether the peer initiated the connection
+    pub inbound: bool,
+    /// Whether the peer is a trusted peer
+    pub trusted: bool,
+    /// Whether the peer is a static peer
+    #[serde(rename = "static")]
+    pub static_node: bool,
+}
+
+impl<P: JsonRpcClient> Provider<P> {
+    /// Returns information about the node via `admin_nodeInfo`
+    pub async fn admin_node_info(&self) -> Result<NodeInfo, ProviderError> {
+        self.request("admin_nodeInfo", ()).await
+    }
+
+    /// Returns the peers currently connected to the node via `admin_peers`
+    pub async fn admin_peers(&self) -> Result<Vec<PeerInfo>, ProviderError> {
+        self.request("admin_peers", ()).await
+    }
+
+    /// Adds the peer with the provided enode URL as a static peer via `admin_addPeer`,
+    /// which the node will keep trying to connect to. Returns whether the peer was
+    /// accepted.
+    pub async fn admin_add_peer<T: Into<String>>(&self, enode: T) -> Result<bool, ProviderError> {
+        self.request("admin_addPeer", [enode.into()]).await
+    }
+
+    /// Removes the static peer with the provided enode URL via `admin_removePeer`,
+    /// disconnecting from it. Returns whether the peer was removed.
+    pub async fn admin_remove_peer<T: Into<String>>(
+        &self,
+        enode: T,
+    ) -> Result<bool, ProviderError> {
+        self.request("admin_removePeer", [enode.into()]).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn admin_requests() {
+        let (provider, mock) = Provider::mocked();
+        let peer = json!({
+            "enode": "enode://a979fb575495b8d6db44f750317d0f4622bf4c2aa3365d6af7c284339968eef29b69ad0dce72a4d8db5ebb4968de0e3bec910127f134779fbcb0cb6d3331163c@52.16.188.185:30303",
+            "id": "a979fb575495b8d6db44f750317d0f4622bf4c2aa3365d6af7c284339968eef2",
+            "name": "Geth/v1.10.8-stable/linux-amd64/go1.16.7",
+            "caps": ["eth/66", "snap/1"],
+            "network": {
+                "localAddress": "192.168.0.104:53371",
+                "remoteAddress": "52.16.188.185:30303",
+                "inbound": false,
+                "trusted": false,
+                "static": true
+            },
+            "protocols": {
+                "eth": { "version": 66 },
+                "snap": { "version": 1 }
+            }
+        });
+
+        mock.push_response(
+            "admin_nodeInfo",
+            json!({
+                "id": "44826a5d6a55f88a18298bca4773fca5749cdc3a5c9f308aa7d810e9b31123f3",
+                "name": "Geth/v1.10.8-stable/linux-amd64/go1.16.7",
+                "enode": "enode://44826a5d6a55f88a18298bca4773fca5749cdc3a5c9f308aa7d810e9b31123f3e7c5fba0b1d70aac5308426f47df2a128a6747040a3815cc7dd7167d03be320d@[::]:30303",
+                "ip": "::",
+                "ports": { "discovery": 30303, "listener": 30303 },
+                "listenAddr": "[::]:30303",
+                "protocols": {
+                    "eth": {
+                        "network": 1,
+                        "difficulty": 17179869184_u64,
+                        "genesis": "0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3",
+                        "head": "0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3",
+                        "config": { "chainId": 1 }
+                    },
+                    "snap": {}
+                }
+            }),
+        )
+        .unwrap();
+        mock.push_response("admin_peers", json!([peer])).unwrap();
+        mock.push_response("admin_addPeer", true).unwrap();
+
+        let info = provider.admin_node_info().await.unwrap();
+        assert_eq!(info.ports.listener, 30303);
+        let eth = info.protocols.eth.unwrap();
+        assert_eq!(eth.network, 1);
+        assert_eq!(eth.difficulty.as_u64(), Some(17179869184));
+        assert!(info.protocols.other.contains_key("snap"));
+        mock.assert_request("admin_nodeInfo", ()).unwrap();
+
+        let peers = provider.admin_peers().await.unwrap();
+        assert_eq!(peers.len(), 1);
+        assert!(peers[0].network.static_node);
+        assert_eq!(peers[0].caps, vec!["eth/66", "snap/1"]);
+        mock.assert_request("admin_peers", ()).unwrap();
+
+        let enode = peers[0].enode.clone();
+        assert!(provider.admin_add_peer(enode.clone()).await.unwrap());
+        mock.assert_request("admin_addPeer", [enode]).unwrap();
+    }
+}
@@ -0,0 +1,335 @@
+//! Typed bindings for [Alchemy's Enhanced APIs](https://docs.alchemy.com/alchemy/enhanced-apis).
+//!
+//! These methods are only served by Alchemy nodes, so they are exposed as inherent
+//! methods on the [`Provider`](crate::Provider) instead of on the `Middleware` trait.
+use crate::{JsonRpcClient, Provider, ProviderError};
+
+use ethers_core::{
+    types::{Address, BlockNumber, H256, U256, U64},
+    utils,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// The token balances of an address, as returned by `alchemy_getTokenBalances`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenBalances {
+    /// The address whose balances were queried
+    pub address: Address,
+    /// The balance for each of the queried tokens
+    pub token_balances: Vec<TokenBalance>,
+}
+
+/// The balance of a single ERC20 token
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenBalance {
+    /// The token's contract address
+    pub contract_address: Address,
+    /// The raw balance (not adjusted for the token's decimals), `None` if the query failed
+    pub token_balance: Option<U256>,
+    /// The reason for which the query failed, if any
+    pub error: Option<String>,
+}
+
+/// An ERC20 token's metadata, as returned by `alchemy_getTokenMetadata`
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TokenMetadata {
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    pub decimals: Option<u64>,
+    /// URL of the token's logo
+    pub logo: Option<String>,
+}
+
+/// The categories of transfers which can be queried via `alchemy_getAssetTransfers`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AssetTransferCategory {
+    /// Top level ETH transfers
+    #[serde(rename = "external")]
+    External,
+    /// ETH transfers made by internal calls
+    #[serde(rename = "internal")]
+    Internal,
+    #[serde(rename = "erc20")]
+    Erc20,
+    #[serde(rename = "erc721")]
+    Erc721,
+    #[serde(rename = "erc1155")]
+    Erc1155,
+}
+
+/// Parameters for `alchemy_getAssetTransfers`, constructed via a builder
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetTransfersParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_block: Option<BlockNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_block: Option<BlockNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_address: Option<Address>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_address: Option<Address>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contract_addresses: Option<Vec<Address>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<Vec<AssetTransferCategory>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclude_zero_value: Option<bool>,
+    /// Maximum number of results per page (the node defaults to 1000)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_count: Option<U64>,
+    /// The key of the page to fetch, as returned by a previous query
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_key: Option<String>,
+}
+
+impl AssetTransfersParams {
+    /// Creates an empty set of parameters
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the block to start searching from
+    pub fn from_block<T: Into<BlockNumber>>(mut self, block: T) -> Self {
+        self.from_block = Some(block.into());
+        self
+    }
+
+    /// Sets the block to stop searching at
+    pub fn to_block<T: Into<BlockNumber>>(mut self, block: T) -> Self {
+        self.to_block = Some(block.into());
+        self
+    }
+
+    /// Only return transfers sent by this address
+    pub fn from_address<T: Into<Address>>(mut self, address: T) -> Self {
+        self.from_address = Some(address.into());
+        self
+    }
+
+    /// Only return transfers received by this address
+    pub fn to_address<T: Into<Address>>(mut self, address: T) -> Self {
+        self.to_address = Some(address.into());
+        self
+    }
+
+    /// Only return token transfers of these contracts
+    pub fn contract_addresses(mut self, addresses: Vec<Address>) -> Self {
+        self.contract_addresses = Some(addresses);
+        self
+    }
+
+    /// Only return transfers of the given categories
+    pub fn category(mut self, category: Vec<AssetTransferCategory>) -> Self {
+        self.category = Some(category);
+        self
+    }
+
+    /// Whether zero value transfers should be skipped
+    pub fn exclude_zero_value(mut self, exclude: bool) -> Self {
+        self.exclude_zero_value = Some(exclude);
+        self
+    }
+
+    /// Sets the maximum number of transfers returned per page
+    pub fn max_count<T: Into<U64>>(mut self, max_count: T) -> Self {
+        self.max_count = Some(max_count.into());
+        self
+    }
+
+    /// Sets the page to be fetched
+    pub fn page_key<T: Into<String>>(mut self, page_key: T) -> Self {
+        self.page_key = Some(page_key.into());
+        self
+    }
+}
+
+/// A page of transfers, as returned by `alchemy_getAssetTransfers`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetTransfers {
+    pub transfers: Vec<AssetTransfer>,
+    /// Set if there are more results to be fetched
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page_key: Option<String>,
+}
+
+/// A single asset transfer
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetTransfer {
+    pub block_num: U64,
+    pub hash: H256,
+    pub from: Address,
+    /// `None` for contract creations
+    pub to: Option<Address>,
+    /// The transferred value, adjusted for the asset's decimals if known
+    pub value: Option<f64>,
+    /// The asset's symbol, if known
+    pub asset: Option<String>,
+    pub category: AssetTransferCategory,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub erc721_token_id: Option<U256>,
+    pub raw_contract: RawContract,
+}
+
+/// The raw, un-adjusted details of a transfer
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RawContract {
+    pub value: Option<U256>,
+    /// The token's contract address, `None` for ETH transfers
+    pub address: Option<Address>,
+    pub decimal: Option<U256>,
+}
+
+impl<P: JsonRpcClient> Provider<P> {
+    /// Returns the balances of `address` for each of the provided ERC20 `tokens`
+    /// via `alchemy_getTokenBalances`
+    pub async fn alchemy_get_token_balances<T: Into<Address>>(
+        &self,
+        address: T,
+        tokens: Vec<Address>,
+    ) -> Result<TokenBalances, ProviderError> {
+        let address = utils::serialize(&address.into());
+        let tokens = utils::serialize(&tokens);
+        self.request("alchemy_getTokenBalances", [address, tokens])
+            .await
+    }
+
+    /// Returns the name, symbol, decimals and logo of an ERC20 token via
+    /// `alchemy_getTokenMetadata`
+    pub async fn alchemy_get_token_metadata<T: Into<Address>>(
+        &self,
+        token: T,
+    ) -> Result<TokenMetadata, ProviderError> {
+        let token = utils::serialize(&token.into());
+        self.request("alchemy_getTokenMetadata", [token]).await
+    }
+
+    /// Returns a single page of asset transfers matching the provided parameters via
+    /// `alchemy_getAssetTransfers`. The next page can be fetched by setting the returned
+    /// `page_key` on the parameters.
+    pub async fn alchemy_get_asset_transfers(
+        &self,
+        params: &AssetTransfersParams,
+    ) -> Result<AssetTransfers, ProviderError> {
+        self.request("alchemy_getAssetTransfers", [params]).await
+    }
+
+    /// Returns all asset transfers matching the provided parameters, fetching every page
+    /// until no `page_key` is returned. Errors if a `page_key` is returned twice, which
+    /// would otherwise fetch the same pages forever.
+    pub async fn alchemy_get_all_asset_transfers(
+        &self,
+        mut params: AssetTransfersParams,
+    ) -> Result<Vec<AssetTransfer>, ProviderError> {
+        let mut transfers = Vec::new();
+        let mut page_keys = HashSet::new();
+        loop {
+            let page = self.alchemy_get_asset_transfers(&params).await?;
+            transfers.extend(page.transfers);
+            match page.page_key {
+                Some(page_key) if !page_keys.insert(page_key.clone()) => {
+                    return Err(ProviderError::CustomError(format!(
+                        "alchemy_getAssetTransfers returned the page key {} twice",
+                        page_key
+                    )))
+                }
+                Some(page_key) => params.page_key = Some(page_key),
+                None => break,
+            }
+        }
+        Ok(transfers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn serializes_params() {
+        let params = AssetTransfersParams::new()
+            .from_block(10u64)
+            .from_address(Address::zero())
+            .category(vec![
+                AssetTransferCategory::External,
+                AssetTransferCategory::Erc20,
+            ])
+            .max_count(5u64);
+        assert_eq!(
+            serde_json::to_value(&params).unwrap(),
+            json!({
+                "fromBlock": "0xa",
+                "fromAddress": "0x0000000000000000000000000000000000000000",
+                "category": ["external", "erc20"],
+                "maxCount": "0x5",
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn paginates_asset_transfers() {
+        let (provider, mock) = Provider::mocked();
+        let transfer = json!({
+            "blockNum": "0x1",
+            "hash": "0x0000000000000000000000000000000000000000000000000000000000000001",
+            "from": "0x0000000000000000000000000000000000000001",
+            "to": "0x0000000000000000000000000000000000000002",
+            "value": 1.5,
+            "asset": "ETH",
+            "category": "external",
+            "rawContract": { "value": "0x14d1120d7b160000", "address": null, "decimal": "0x12" }
+        });
+
+        mock.push_response(
+            "alchemy_getAssetTransfers",
+            json!({ "transfers": [transfer], "pageKey": "next" }),
+        )
+        .unwrap();
+        mock.push_response(
+            "alchemy_getAssetTransfers",
+            json!({ "transfers": [transfer] }),
+        )
+        .unwrap();
+
+        let transfers = provider
+            .alchemy_get_all_asset_transfers(AssetTransfersParams::new())
+            .await
+            .unwrap();
+        assert_eq!(transfers.len(), 2);
+        assert_eq!(transfers[0].value, Some(1.5));
+        assert_eq!(transfers[0].raw_contract.decimal, Some(18.into()));
+
+        mock.assert_request("alchemy_getAssetTransfers", [json!({})])
+            .unwrap();
+        mock.assert_request("alchemy_getAssetTransfers", [json!({ "pageKey": "next" })])
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn rejects_repeated_page_keys() {
+        let (provider, mock) = Provider::mocked();
+        for _ in 0..2 {
+            mock.push_response(
+                "alchemy_getAssetTransfers",
+                json!({ "transfers": [], "pageKey": "next" }),
+            )
+            .unwrap();
+        }
+
+        let err = provider
+            .alchemy_get_all_asset_transfers(AssetTransfersParams::new())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ProviderError::CustomError(_)));
+        mock.assert_request("alchemy_getAssetTransfers", [json!({})])
+            .unwrap();
+        mock.assert_request("alchemy_getAssetTransfers", [json!({ "pageKey": "next" })])
+            .unwrap();
+    }
+}
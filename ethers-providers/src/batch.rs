@@ -0,0 +1,159 @@
+use crate::{Http, JsonRpcError, Provider, ProviderError};
+
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+/// A batch of JSON-RPC requests, sent to the node in a single HTTP request. This
+/// avoids the per-request overhead when making many independent calls, e.g. reading
+/// hundreds of storage slots at a block.
+///
+/// Created with [`Provider::batch`](crate::Provider::batch).
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers::{
+///     providers::{Http, Provider},
+///     types::{Address, BlockNumber, U256},
+/// };
+/// use std::convert::TryFrom;
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Provider::<Http>::try_from("http://localhost:8545")?;
+/// let block = BlockNumber::from(12_000_000u64);
+///
+/// let mut batch = provider.batch();
+/// for i in 0..100 {
+///     batch.add_request("eth_getBalance", (Address::from_low_u64_be(i), block))?;
+/// }
+/// let responses = batch.send().await?;
+///
+/// for i in 0..responses.len() {
+///     let balance: U256 = responses.get(i)?;
+///     println!("{}: {}", i, balance);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+#[must_use = "batches do nothing unless sent"]
+pub struct BatchRequest<'a> {
+    provider: &'a Provider<Http>,
+    requests: Vec<(String, Value)>,
+}
+
+impl<'a> BatchRequest<'a> {
+    pub(crate) fn new(provider: &'a Provider<Http>) -> Self {
+        Self {
+            provider,
+            requests: Vec::new(),
+        }
+    }
+
+    /// Adds a request to the batch, returning the index of its response
+    pub fn add_request<T: Serialize>(
+        &mut self,
+        method: &str,
+        params: T,
+    ) -> Result<usize, ProviderError> {
+        self.requests
+            .push((method.to_owned(), serde_json::to_value(params)?));
+        Ok(self.requests.len() - 1)
+    }
+
+    /// Returns the number of requests in the batch
+    pub fn len(&self) -> usize {
+        self.requests.len()
+    }
+
+    /// Returns whether the batch has no requests
+    pub fn is_empty(&self) -> bool {
+        self.requests.is_empty()
+    }
+
+    /// Sends the batch, failing only if the batch as a whole failed. The errors of the
+    /// individual requests are returned by [`BatchResponse::get`].
+    pub async fn send(self) -> Result<BatchResponse, ProviderError> {
+        if self.requests.is_empty() {
+            return Ok(BatchResponse {
+                responses: Vec::new(),
+            });
+        }
+        let responses = self.provider.as_ref().request_batch(&self.requests).await?;
+        Ok(BatchResponse { responses })
+    }
+}
+
+/// The responses to a [`BatchRequest`], in the order of its requests
+#[derive(Debug, Clone)]
+pub struct BatchResponse {
+    responses: Vec<Result<Value, JsonRpcError>>,
+}
+
+impl BatchResponse {
+    /// Returns the number of responses
+    pub fn len(&self) -> usize {
+        self.responses.len()
+    }
+
+    /// Returns whether there are no responses
+    pub fn is_empty(&self) -> bool {
+        self.responses.is_empty()
+    }
+
+    /// Deserializes the result of the request at `index`, or returns its error
+    pub fn get<R: DeserializeOwned>(&self, index: usize) -> Result<R, ProviderError> {
+        match self.responses.get(index) {
+            Some(Ok(value)) => Ok(serde_json::from_value(value.clone())?),
+            Some(Err(err)) => Err(ProviderError::JsonRpcClientError(Box::new(err.clone()))),
+            None => Err(ProviderError::CustomError(format!(
+                "the batch has no request {}",
+                index
+            ))),
+        }
+    }
+
+    /// Returns the raw results and errors of the requests
+    pub fn into_results(self) -> Vec<Result<Value, JsonRpcError>> {
+        self.responses
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "test-util")]
+mod tests {
+    use super::*;
+    use crate::MockServer;
+    use ethers_core::types::{Address, U256};
+    use std::convert::TryFrom;
+
+    #[tokio::test]
+    async fn sends_batches() {
+        let server = MockServer::spawn().await.unwrap();
+        server.handle("eth_getBalance", |params| {
+            let address: Address = serde_json::from_value(params[0].clone()).unwrap();
+            Ok(serde_json::json!(U256::from(address.to_low_u64_be())))
+        });
+        let provider = Provider::<Http>::try_from(server.http_endpoint()).unwrap();
+
+        let mut batch = provider.batch();
+        for i in 1..=3 {
+            let idx = batch
+                .add_request("eth_getBalance", (Address::from_low_u64_be(i), "latest"))
+                .unwrap();
+            assert_eq!(idx, i as usize - 1);
+        }
+        batch.add_request("eth_unknown", ()).unwrap();
+
+        let responses = batch.send().await.unwrap();
+        assert_eq!(responses.len(), 4);
+        for i in 0..3 {
+            assert_eq!(responses.get::<U256>(i).unwrap(), U256::from(i + 1));
+        }
+        let err = responses.get::<U256>(3).unwrap_err();
+        assert!(err.is_method_not_found());
+        assert!(responses.get::<U256>(4).is_err());
+
+        assert_eq!(server.requests().len(), 4);
+    }
+}
@@ -0,0 +1,211 @@
+//! [CCIP-Read](https://eips.ethereum.org/EIPS/eip-3668) support, which lets contracts
+//! (e.g. offchain ENS resolvers) answer calls with data fetched from gateways
+use crate::ProviderError;
+
+use ethers_core::{
+    abi::{self, ParamType, Token},
+    types::{Address, Bytes, Selector},
+};
+use serde::Deserialize;
+use serde_json::json;
+use std::time::Duration;
+
+/// The selector of `OffchainLookup(address,string[],bytes,bytes4,bytes)`, the error
+/// contracts revert with to request an offchain lookup
+pub(crate) const OFFCHAIN_LOOKUP_SELECTOR: Selector = [0x55, 0x6f, 0x18, 0x30];
+
+/// The number of offchain lookups a single call may chain before giving up
+pub(crate) const MAX_CCIP_REDIRECTS: usize = 4;
+
+/// The time after which a gateway which did not respond is skipped
+const GATEWAY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The arguments of an `OffchainLookup` revert
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct OffchainLookup {
+    /// The contract which requested the lookup, which must be the called contract
+    pub sender: Address,
+    /// The gateway URL templates, tried in order
+    pub urls: Vec<String>,
+    /// The data to send to the gateway
+    pub call_data: Bytes,
+    /// The function of the sender to call back with the gateway's response
+    pub callback: Selector,
+    /// Data the sender passes on to its callback
+    pub extra_data: Bytes,
+}
+
+/// The body of successful gateway responses
+#[derive(Deserialize)]
+struct GatewayResponse {
+    data: Bytes,
+}
+
+impl OffchainLookup {
+    /// Decodes the revert data of a call, returning `None` if it is not an
+    /// `OffchainLookup` error
+    pub(crate) fn decode(data: &[u8]) -> Option<Self> {
+        if data.len() < 4 || data[..4] != OFFCHAIN_LOOKUP_SELECTOR {
+            return None;
+        }
+        let params = [
+            ParamType::Address,
+            ParamType::Array(Box::new(ParamType::String)),
+            ParamType::Bytes,
+            ParamType::FixedBytes(4),
+            ParamType::Bytes,
+        ];
+        let mut tokens = abi::decode(&params, &data[4..]).ok()?.into_iter();
+        let sender = tokens.next()?.into_address()?;
+        let urls = tokens
+            .next()?
+            .into_array()?
+            .into_iter()
+            .map(Token::into_string)
+            .collect::<Option<Vec<_>>>()?;
+        let call_data = tokens.next()?.into_bytes()?.into();
+        let mut callback = [0; 4];
+        callback.copy_from_slice(&tokens.next()?.into_fixed_bytes()?);
+        let extra_data = tokens.next()?.into_bytes()?.into();
+        Some(Self {
+            sender,
+            urls,
+            call_data,
+            callback,
+            extra_data,
+        })
+    }
+
+    /// Returns the calldata of the callback with the gateway's response
+    pub(crate) fn callback_data(&self, response: &[u8]) -> Bytes {
+        let params = abi::encode(&[
+            Token::Bytes(response.to_vec()),
+            Token::Bytes(self.extra_data.to_vec()),
+        ]);
+        [&self.callback[..], &params].concat().into()
+    }
+
+    /// Returns the URL of the request to the gateway, and whether it is a GET request.
+    /// URLs with a `{data}` placeholder are queried with a GET, the others with a POST
+    /// of the sender and data.
+    fn gateway_url(&self, url: &str) -> (String, bool) {
+        let url = url.replace("{sender}", &format!("{:?}", self.sender));
+        if url.contains("{data}") {
+            let data = format!("0x{}", hex::encode(self.call_data.as_ref()));
+            (url.replace("{data}", &data), true)
+        } else {
+            (url, false)
+        }
+    }
+
+    /// Fetches the response from the gateways, trying the next one if a gateway could
+    /// not be reached, timed out or responded with a server error
+    pub(crate) async fn fetch(&self) -> Result<Bytes, ProviderError> {
+        let client = reqwest::Client::builder()
+            .timeout(GATEWAY_TIMEOUT)
+            .build()?;
+        let mut error = "no gateway URL".to_owned();
+        for url in &self.urls {
+            let (url, is_get) = self.gateway_url(url);
+            let req = if is_get {
+                client.get(&url)
+            } else {
+                let body = json!({
+                    "data": self.call_data,
+                    "sender": self.sender,
+                });
+                client.post(&url).json(&body)
+            };
+            let res = match req.send().await {
+                Ok(res) => res,
+                Err(err) => {
+                    error = format!("gateway {} failed: {}", url, err);
+                    continue;
+                }
+            };
+
+            let status = res.status();
+            if status.is_success() {
+                return Ok(res.json::<GatewayResponse>().await?.data);
+            }
+            error = format!("gateway {} responded with {}", url, status);
+            // client errors are final, while other gateways may recover from server errors
+            if status.is_client_error() {
+                break;
+            }
+        }
+        Err(ProviderError::CcipError(error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup_data() -> Vec<u8> {
+        let params = abi::encode(&[
+            Token::Address(Address::repeat_byte(1)),
+            Token::Array(vec![
+                Token::String("https://example.com/{sender}/{data}.json".to_owned()),
+                Token::String("https://example.com/gateway".to_owned()),
+            ]),
+            Token::Bytes(vec![0xab, 0xcd]),
+            Token::FixedBytes(vec![1, 2, 3, 4]),
+            Token::Bytes(vec![0xef]),
+        ]);
+        [&OFFCHAIN_LOOKUP_SELECTOR[..], &params].concat()
+    }
+
+    #[test]
+    fn decodes_offchain_lookup() {
+        let lookup = OffchainLookup::decode(&lookup_data()).unwrap();
+        assert_eq!(lookup.sender, Address::repeat_byte(1));
+        assert_eq!(lookup.urls.len(), 2);
+        assert_eq!(lookup.call_data, vec![0xab, 0xcd].into());
+        assert_eq!(lookup.callback, [1, 2, 3, 4]);
+        assert_eq!(lookup.extra_data, vec![0xef].into());
+
+        // other errors are not lookups
+        assert!(OffchainLookup::decode(&[0x08, 0xc3, 0x79, 0xa0]).is_none());
+    }
+
+    #[test]
+    fn builds_gateway_requests() {
+        let lookup = OffchainLookup::decode(&lookup_data()).unwrap();
+        assert_eq!(
+            lookup.gateway_url(&lookup.urls[0]),
+            (
+                "https://example.com/0x0101010101010101010101010101010101010101/0xabcd.json"
+                    .to_owned(),
+                true
+            )
+        );
+        assert_eq!(
+            lookup.gateway_url(&lookup.urls[1]),
+            ("https://example.com/gateway".to_owned(), false)
+        );
+
+        let data = lookup.callback_data(&[0x12]);
+        assert_eq!(&data.as_ref()[..4], &[1, 2, 3, 4]);
+        assert_eq!(
+            abi::decode(&[ParamType::Bytes, ParamType::Bytes], &data.as_ref()[4..]).unwrap(),
+            vec![Token::Bytes(vec![0x12]), Token::Bytes(vec![0xef])]
+        );
+    }
+
+    #[tokio::test]
+    async fn tries_next_gateway_on_network_errors() {
+        let mut lookup = OffchainLookup::decode(&lookup_data()).unwrap();
+        // nothing listens on the port, so both gateways are unreachable
+        lookup.urls = vec![
+            "http://127.0.0.1:1/{sender}/{data}.json".to_owned(),
+            "http://127.0.0.1:1/gateway".to_owned(),
+        ];
+        match lookup.fetch().await.unwrap_err() {
+            ProviderError::CcipError(err) => {
+                assert!(err.contains("http://127.0.0.1:1/gateway"), "{}", err)
+            }
+            err => panic!("unexpected error {:?}", err),
+        }
+    }
+}
@@ -1,9 +1,12 @@
+use bech32::{u5, ToBase32, Variant};
 /// [Ethereum Name Service](https://docs.ens.domains/) support
 // Adapted from https://github.com/hhatto/rust-ens/blob/master/src/lib.rs
 use ethers_core::{
-    types::{Address, NameOrAddress, Selector, TransactionRequest, H160, H256},
-    utils::keccak256,
+    abi::{self, Token},
+    types::{Address, NameOrAddress, Selector, TransactionRequest, H160, H256, U256},
+    utils::{keccak256, to_checksum},
 };
+use url::Url;
 
 // 0x00000000000C2E074eC69A0dFb2997BA6C7d2e1e
 pub const ENS_ADDRESS: Address = H160([
@@ -23,6 +26,33 @@ pub const ADDR_SELECTOR: Selector = [59, 59, 87, 222];
 /// name(bytes32)
 pub const NAME_SELECTOR: Selector = [105, 31, 52, 49];
 
+/// addr(bytes32,uint256)
+pub const COIN_ADDR_SELECTOR: Selector = [241, 203, 126, 6];
+
+/// text(bytes32,string)
+pub const FIELD_SELECTOR: Selector = [89, 209, 212, 60];
+
+/// ownerOf(uint256)
+pub const ERC721_OWNER_SELECTOR: Selector = [99, 82, 33, 30];
+
+/// tokenURI(uint256)
+pub const ERC721_URI_SELECTOR: Selector = [200, 123, 86, 221];
+
+/// balanceOf(address,uint256)
+pub const ERC1155_BALANCE_SELECTOR: Selector = [0, 253, 213, 142];
+
+/// uri(uint256)
+pub const ERC1155_URI_SELECTOR: Selector = [14, 137, 52, 28];
+
+/// supportsInterface(bytes4)
+pub const SUPPORTS_INTERFACE_SELECTOR: Selector = [1, 255, 201, 167];
+
+/// resolve(bytes,bytes), which is also the interface id of ENSIP-10 extended resolvers
+pub const WILDCARD_RESOLVE_SELECTOR: Selector = [144, 97, 185, 35];
+
+/// The gateway used to fetch `ipfs://` and `ipns://` URIs over HTTP
+const IPFS_GATEWAY: &str = "https://ipfs.io";
+
 /// Returns a transaction request for calling the `resolver` method on the ENS server
 pub fn get_resolver<T: Into<Address>>(ens_address: T, name: &str) -> TransactionRequest {
     // keccak256('resolver(bytes32)')
@@ -34,13 +64,37 @@ pub fn get_resolver<T: Into<Address>>(ens_address: T, name: &str) -> Transaction
     }
 }
 
-/// Returns a transaction request for calling
+/// Returns a transaction request for calling the resolver's method with the name's node,
+/// followed by the ABI encoded parameters of the method if it has others
 pub fn resolve<T: Into<Address>>(
     resolver_address: T,
     selector: Selector,
     name: &str,
+    parameters: Option<&[u8]>,
+) -> TransactionRequest {
+    let data = [
+        &selector[..],
+        &namehash(name).0,
+        parameters.unwrap_or_default(),
+    ]
+    .concat();
+    TransactionRequest {
+        data: Some(data.into()),
+        to: Some(NameOrAddress::Address(resolver_address.into())),
+        ..Default::default()
+    }
+}
+
+/// Returns a transaction request for calling `supportsInterface` on the resolver
+pub fn supports_interface<T: Into<Address>>(
+    resolver_address: T,
+    interface: Selector,
 ) -> TransactionRequest {
-    let data = [&selector[..], &namehash(name).0].concat();
+    let data = [
+        &SUPPORTS_INTERFACE_SELECTOR[..],
+        &abi::encode(&[Token::FixedBytes(interface.to_vec())]),
+    ]
+    .concat();
     TransactionRequest {
         data: Some(data.into()),
         to: Some(NameOrAddress::Address(resolver_address.into())),
@@ -48,11 +102,109 @@ pub fn resolve<T: Into<Address>>(
     }
 }
 
+/// Returns a transaction request for resolving the name through the `resolve(bytes,bytes)`
+/// method of an extended resolver as specified in
+/// [ENSIP-10](https://docs.ens.domains/ens-improvement-proposals/ensip-10-wildcard-resolution),
+/// which wraps the call to the resolver's method. Returns `None` if the name cannot be
+/// DNS-encoded.
+pub fn resolve_wildcard<T: Into<Address>>(
+    resolver_address: T,
+    selector: Selector,
+    name: &str,
+    parameters: Option<&[u8]>,
+) -> Option<TransactionRequest> {
+    let mut tx = resolve(resolver_address, selector, name, parameters);
+    let call = tx.data.take().unwrap_or_default();
+    let data = [
+        &WILDCARD_RESOLVE_SELECTOR[..],
+        &abi::encode(&[Token::Bytes(dns_encode(name)?), Token::Bytes(call.to_vec())]),
+    ]
+    .concat();
+    tx.data = Some(data.into());
+    Some(tx)
+}
+
+/// Returns the parent of the name, e.g. `eth` for `foo.eth`, or `None` for top level names
+pub fn parent(name: &str) -> Option<&str> {
+    name.splitn(2, '.').nth(1)
+}
+
+/// Encodes the name in the DNS wire format, where each label is prefixed by its length
+/// and the name is terminated by the empty root label. Returns `None` if a label is empty
+/// or longer than 255 bytes.
+pub fn dns_encode(name: &str) -> Option<Vec<u8>> {
+    let mut encoded = Vec::with_capacity(name.len() + 2);
+    if !name.is_empty() {
+        for label in name.split('.') {
+            if label.is_empty() || label.len() > 255 {
+                return None;
+            }
+            encoded.push(label.len() as u8);
+            encoded.extend_from_slice(label.as_bytes());
+        }
+    }
+    encoded.push(0);
+    Some(encoded)
+}
+
+/// Returns the ABI encoded `key` parameter of `text(bytes32,string)`, which follows the
+/// name's node
+pub fn field_parameters(field: &str) -> Vec<u8> {
+    let encoded = abi::encode(&[
+        Token::FixedBytes(vec![0; 32]),
+        Token::String(field.to_owned()),
+    ]);
+    encoded[32..].to_vec()
+}
+
+/// Returns the ABI encoded `coinType` parameter of `addr(bytes32,uint256)`
+pub fn coin_parameters(coin_type: u64) -> Vec<u8> {
+    abi::encode(&[Token::Uint(coin_type.into())])
+}
+
 pub fn reverse_address(addr: Address) -> String {
     format!("{:?}.{}", addr, ENS_REVERSE_REGISTRAR_DOMAIN)[2..].to_string()
 }
 
-/// Returns the ENS namehash as specified in [EIP-137](https://eips.ethereum.org/EIPS/eip-137)
+/// Normalizes the name as specified in [UTS-46](https://unicode.org/reports/tr46/), which
+/// ENS uses so that names which look alike hash identically: the name is case folded and
+/// NFC normalized, and compatibility characters are mapped, e.g. `ＥＴＨ` to `eth`.
+/// Returns `None` if the name contains disallowed characters such as spaces, or an empty
+/// label.
+///
+/// Unlike DNS names, ASCII labels may contain the characters allowed by
+/// [ENSIP-15](https://docs.ens.domains/ensip/15): letters, digits, hyphens, `$` and
+/// underscores at their start, e.g. `_foo.eth`.
+pub fn normalize_name(name: &str) -> Option<String> {
+    if name.is_empty() {
+        return Some(String::new());
+    }
+    // the STD3 rules of DNS would reject underscores, so the ASCII characters are
+    // checked below instead
+    let (normalized, result) = idna::Config::default()
+        .use_std3_ascii_rules(false)
+        .transitional_processing(false)
+        .check_hyphens(false)
+        .verify_dns_length(false)
+        .to_unicode(name);
+    if result.is_err() || !normalized.split('.').all(is_valid_label) {
+        return None;
+    }
+    Some(normalized)
+}
+
+/// Returns whether the normalized label is not empty and only contains the ASCII
+/// characters allowed by ENSIP-15, with underscores only at its start
+fn is_valid_label(label: &str) -> bool {
+    let unprefixed = label.trim_start_matches('_');
+    !label.is_empty()
+        && unprefixed.chars().all(|c| {
+            !c.is_ascii() || c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '$'
+        })
+}
+
+/// Returns the ENS namehash as specified in [EIP-137](https://eips.ethereum.org/EIPS/eip-137).
+/// The name must already be normalized, see [`normalize_name`].
 pub fn namehash(name: &str) -> H256 {
     if name.is_empty() {
         return H256::zero();
@@ -66,6 +218,174 @@ pub fn namehash(name: &str) -> H256 {
         .into()
 }
 
+/// The token standard of an NFT used as an ENS avatar
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NftStandard {
+    Erc721,
+    Erc1155,
+}
+
+/// An NFT referenced by an ENS avatar record as specified in
+/// [ENSIP-12](https://docs.ens.domains/ens-improvement-proposals/ensip-12-avatar-text-records),
+/// e.g. `eip155:1/erc721:0xb47e3cd837dDF8e4c57F05d70Ab865de6e193BBB/2430`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AvatarNft {
+    pub chain_id: u64,
+    pub standard: NftStandard,
+    pub contract: Address,
+    pub token_id: U256,
+}
+
+impl AvatarNft {
+    /// Parses a CAIP-22/29 asset reference, returning `None` if it is not one
+    pub fn parse(avatar: &str) -> Option<Self> {
+        // eip155:{chain_id}/{standard}:{contract}/{token_id}
+        let mut parts = avatar.strip_prefix("eip155:")?.splitn(2, '/');
+        let chain = parts.next()?;
+        let mut parts = parts.next()?.splitn(2, ':');
+        let standard = parts.next()?;
+        let mut parts = parts.next()?.splitn(2, '/');
+        let (contract, token_id) = (parts.next()?, parts.next()?);
+        let standard = match standard.to_ascii_lowercase().as_str() {
+            "erc721" => NftStandard::Erc721,
+            "erc1155" => NftStandard::Erc1155,
+            _ => return None,
+        };
+        Some(Self {
+            chain_id: chain.parse().ok()?,
+            standard,
+            contract: contract.parse().ok()?,
+            token_id: U256::from_dec_str(token_id).ok()?,
+        })
+    }
+
+    /// Returns a transaction request for calling the NFT's contract, with the token id
+    /// as the last parameter
+    pub fn call(&self, selector: Selector, owner: Option<Address>) -> TransactionRequest {
+        let mut tokens = owner.map(Token::Address).into_iter().collect::<Vec<_>>();
+        tokens.push(Token::Uint(self.token_id));
+        let data = [&selector[..], &abi::encode(&tokens)].concat();
+        TransactionRequest {
+            data: Some(data.into()),
+            to: Some(NameOrAddress::Address(self.contract)),
+            ..Default::default()
+        }
+    }
+
+    /// Substitutes the `{id}` placeholder of ERC-1155 metadata URIs with the token id,
+    /// as a 64 characters long lowercase hex string
+    pub fn substitute_id(&self, uri: &str) -> String {
+        uri.replace("{id}", &format!("{:064x}", self.token_id))
+    }
+}
+
+/// Resolves the URI of an avatar or of NFT metadata to an URL it can be fetched from.
+/// HTTP(S) and `data:` URIs are returned as is, while IPFS URIs are served by a gateway.
+pub fn resolve_uri(uri: &str) -> Option<Url> {
+    let uri = uri.trim();
+    if let Some(path) = uri.strip_prefix("ipfs://") {
+        // the path may be prefixed with `ipfs/`, e.g. `ipfs://ipfs/Qm..`
+        let path = path.strip_prefix("ipfs/").unwrap_or(path);
+        return Url::parse(&format!("{}/ipfs/{}", IPFS_GATEWAY, path)).ok();
+    }
+    if let Some(path) = uri.strip_prefix("ipns://") {
+        return Url::parse(&format!("{}/ipns/{}", IPFS_GATEWAY, path)).ok();
+    }
+    let url = Url::parse(uri).ok()?;
+    match url.scheme() {
+        "https" | "http" | "data" => Some(url),
+        _ => None,
+    }
+}
+
+/// Decodes the content of a `data:` URL, e.g. the inline JSON metadata of an NFT
+pub fn decode_data_url(url: &Url) -> Option<Vec<u8>> {
+    let mut parts = url.path().splitn(2, ',');
+    let (media_type, data) = (parts.next()?, parts.next()?);
+    if media_type.ends_with(";base64") {
+        base64::decode(data).ok()
+    } else {
+        Some(data.as_bytes().to_vec())
+    }
+}
+
+/// The [SLIP-44](https://github.com/satoshilabs/slips/blob/master/slip-0044.md) coin type
+/// of Bitcoin
+pub const COIN_TYPE_BTC: u64 = 0;
+/// The SLIP-44 coin type of Litecoin
+pub const COIN_TYPE_LTC: u64 = 2;
+/// The SLIP-44 coin type of Dogecoin
+pub const COIN_TYPE_DOGE: u64 = 3;
+/// The SLIP-44 coin type of Ether
+pub const COIN_TYPE_ETH: u64 = 60;
+
+/// Bit set in the coin types of EVM chains, whose lower bits are the chain id as specified
+/// in [ENSIP-11](https://docs.ens.domains/ens-improvement-proposals/ensip-11-evmchain-address-resolution)
+const EVM_COIN_TYPE_FLAG: u64 = 0x8000_0000;
+
+/// Encodes the binary address stored by a resolver for the coin type into its text
+/// representation, as specified in
+/// [ENSIP-9](https://docs.ens.domains/ens-improvement-proposals/ensip-9-multichain-address-resolution).
+/// Returns `None` if the coin type is not supported or the address is malformed.
+pub fn encode_coin_address(coin_type: u64, addr: &[u8]) -> Option<String> {
+    // (P2PKH version, P2SH version, segwit human readable part)
+    let (p2pkh, p2sh, hrp) = match coin_type {
+        COIN_TYPE_BTC => (0x00, 0x05, Some("bc")),
+        COIN_TYPE_LTC => (0x30, 0x32, Some("ltc")),
+        COIN_TYPE_DOGE => (0x1e, 0x16, None),
+        COIN_TYPE_ETH => return evm_address(addr),
+        _ if coin_type & EVM_COIN_TYPE_FLAG != 0 => return evm_address(addr),
+        _ => return None,
+    };
+
+    // the address is stored as the script public key of its outputs
+    match addr {
+        // OP_DUP OP_HASH160 <hash> OP_EQUALVERIFY OP_CHECKSIG
+        [0x76, 0xa9, 0x14, hash @ .., 0x88, 0xac] if hash.len() == 20 => {
+            Some(base58check(p2pkh, hash))
+        }
+        // OP_HASH160 <hash> OP_EQUAL
+        [0xa9, 0x14, hash @ .., 0x87] if hash.len() == 20 => Some(base58check(p2sh, hash)),
+        // OP_0..OP_16 <program>
+        [version, len, program @ ..]
+            if (*version == 0 || (0x51..=0x60).contains(version))
+                && *len as usize == program.len()
+                && (2..=40).contains(&program.len()) =>
+        {
+            let version = if *version == 0 { 0 } else { version - 0x50 };
+            segwit_address(hrp?, version, program)
+        }
+        _ => None,
+    }
+}
+
+fn evm_address(addr: &[u8]) -> Option<String> {
+    if addr.len() != 20 {
+        return None;
+    }
+    Some(to_checksum(&Address::from_slice(addr), None))
+}
+
+/// Encodes the versioned payload in base58, followed by a checksum
+fn base58check(version: u8, payload: &[u8]) -> String {
+    bs58::encode(payload)
+        .with_check_version(version)
+        .into_string()
+}
+
+/// Encodes a segwit address in bech32 (version 0) or bech32m (later versions), as
+/// specified in BIP-173 and BIP-350
+fn segwit_address(hrp: &str, version: u8, program: &[u8]) -> Option<String> {
+    let variant = if version == 0 {
+        Variant::Bech32
+    } else {
+        Variant::Bech32m
+    };
+    let mut data = vec![u5::try_from_u8(version).ok()?];
+    data.extend(program.to_base32());
+    bech32::encode(hrp, data, variant).ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -80,6 +400,73 @@ mod tests {
         assert_eq!(hash.0.to_vec(), hex::decode(v).unwrap());
     }
 
+    #[test]
+    fn dns_encodes_names() {
+        assert_eq!(dns_encode("").unwrap(), vec![0]);
+        assert_eq!(
+            dns_encode("foo.eth").unwrap(),
+            b"\x03foo\x03eth\x00".to_vec()
+        );
+        assert!(dns_encode("foo..eth").is_none());
+        assert!(dns_encode(&"a".repeat(256)).is_none());
+
+        assert_eq!(parent("sub.foo.eth"), Some("foo.eth"));
+        assert_eq!(parent("eth"), None);
+    }
+
+    #[test]
+    fn wraps_wildcard_calls() {
+        let resolver = Address::repeat_byte(1);
+        let tx = resolve_wildcard(resolver, ADDR_SELECTOR, "sub.foo.eth", None).unwrap();
+        let data = tx.data.unwrap();
+        assert_eq!(&data.as_ref()[..4], &WILDCARD_RESOLVE_SELECTOR);
+
+        let tokens = abi::decode(
+            &[abi::ParamType::Bytes, abi::ParamType::Bytes],
+            &data.as_ref()[4..],
+        )
+        .unwrap();
+        assert_eq!(tokens[0], Token::Bytes(dns_encode("sub.foo.eth").unwrap()));
+        assert_eq!(
+            tokens[1],
+            Token::Bytes([&ADDR_SELECTOR[..], &namehash("sub.foo.eth").0].concat())
+        );
+    }
+
+    #[test]
+    fn normalizes_names() {
+        for (name, expected) in &[
+            ("", ""),
+            ("foo.eth", "foo.eth"),
+            ("Foo.ETH", "foo.eth"),
+            ("ＦＯＯ.eth", "foo.eth"),
+            ("ÖBB.eth", "öbb.eth"),
+            // deviation characters are kept as is
+            ("faß.eth", "faß.eth"),
+            ("xn--bb-eka.eth", "öbb.eth"),
+            // underscores are allowed at the start of labels
+            ("_foo.eth", "_foo.eth"),
+            ("__Foo.eth", "__foo.eth"),
+            ("$foo.eth", "$foo.eth"),
+        ] {
+            assert_eq!(normalize_name(name).unwrap(), *expected);
+        }
+        for name in &[
+            "foo..eth",
+            ".eth",
+            "foo bar.eth",
+            "foo/bar.eth",
+            "foo_bar.eth",
+        ] {
+            assert!(normalize_name(name).is_none(), "{}", name);
+        }
+
+        assert_eq!(
+            namehash(&normalize_name("Alice.ETH").unwrap()),
+            namehash("alice.eth")
+        );
+    }
+
     #[test]
     fn test_namehash() {
         for (name, expected) in &[
@@ -103,4 +490,128 @@ mod tests {
             assert_hex(namehash(name), expected);
         }
     }
+
+    #[test]
+    fn encodes_field_parameters() {
+        let params = field_parameters("avatar");
+        // the offset of the string follows the node
+        assert_eq!(U256::from_big_endian(&params[..32]), U256::from(64));
+        assert_eq!(U256::from_big_endian(&params[32..64]), U256::from(6));
+        assert_eq!(&params[64..70], b"avatar");
+        assert_eq!(params.len(), 96);
+    }
+
+    #[test]
+    fn encodes_coin_addresses() {
+        // test vectors from ENSIP-9, BIP-173 and BIP-350
+        for (coin_type, addr, expected) in &[
+            (
+                COIN_TYPE_BTC,
+                "76a91462e907b15cbf27d5425399ebf6f0fb50ebb88f1888ac",
+                "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa",
+            ),
+            (
+                COIN_TYPE_BTC,
+                "a91462e907b15cbf27d5425399ebf6f0fb50ebb88f1887",
+                "3Ai1JZ8pdJb2ksieUV8FsxSNVJCpoPi8W6",
+            ),
+            (
+                COIN_TYPE_BTC,
+                "0014751e76e8199196d454941c45d1b3a323f1433bd6",
+                "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+            ),
+            (
+                COIN_TYPE_BTC,
+                "512079be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+            ),
+            (
+                COIN_TYPE_LTC,
+                "76a914a5f4d12ce3685781b227c1f39548ddef429e978388ac",
+                "LaMT348PWRnrqeeWArpwQPbuanpXDZGEUz",
+            ),
+            (
+                COIN_TYPE_ETH,
+                "314159265dd8dbb310642f98f50c066173c1259b",
+                "0x314159265dD8dbb310642f98f50C066173C1259b",
+            ),
+            (
+                // Optimism
+                0x8000_000a,
+                "314159265dd8dbb310642f98f50c066173c1259b",
+                "0x314159265dD8dbb310642f98f50C066173C1259b",
+            ),
+        ] {
+            let addr = hex::decode(addr).unwrap();
+            assert_eq!(
+                encode_coin_address(*coin_type, &addr).as_deref(),
+                Some(*expected)
+            );
+        }
+
+        // unsupported coins and malformed scripts
+        assert!(encode_coin_address(501, &[1; 32]).is_none());
+        assert!(encode_coin_address(COIN_TYPE_BTC, &[0x76, 0xa9, 0x14]).is_none());
+        assert!(encode_coin_address(
+            COIN_TYPE_DOGE,
+            &hex::decode("0014751e76e8199196d454941c45d1b3a323f1433bd6").unwrap()
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn parses_avatar_nfts() {
+        let nft =
+            AvatarNft::parse("eip155:1/erc721:0xb47e3cd837dDF8e4c57F05d70Ab865de6e193BBB/2430")
+                .unwrap();
+        assert_eq!(nft.chain_id, 1);
+        assert_eq!(nft.standard, NftStandard::Erc721);
+        assert_eq!(
+            nft.contract,
+            "b47e3cd837dDF8e4c57F05d70Ab865de6e193BBB".parse().unwrap()
+        );
+        assert_eq!(nft.token_id, 2430.into());
+
+        let nft = AvatarNft::parse(
+            "eip155:1/erc1155:0x495f947276749ce646f68ac8c248420045cb7b5e/8112316025873927737505937898915153732580103913704334048512380490797008551937",
+        )
+        .unwrap();
+        assert_eq!(nft.standard, NftStandard::Erc1155);
+        assert_eq!(
+            nft.substitute_id("https://api.opensea.io/api/v1/metadata/{id}"),
+            "https://api.opensea.io/api/v1/metadata/11ef687cfeb2e353670479f2dcc76af2bc6b3935000000000002c40000000001"
+        );
+
+        assert!(AvatarNft::parse("https://example.com/avatar.png").is_none());
+        assert!(
+            AvatarNft::parse("eip155:1/erc20:0xb47e3cd837dDF8e4c57F05d70Ab865de6e193BBB/1")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn resolves_uris() {
+        assert_eq!(
+            resolve_uri("ipfs://ipfs/QmQsQgpda6JAYkFoeVcj5iPbwV3xRcvaiXv3bhp1VuYUqw")
+                .unwrap()
+                .as_str(),
+            "https://ipfs.io/ipfs/QmQsQgpda6JAYkFoeVcj5iPbwV3xRcvaiXv3bhp1VuYUqw"
+        );
+        assert_eq!(
+            resolve_uri("ipfs://QmQsQgpda6JAYkFoeVcj5iPbwV3xRcvaiXv3bhp1VuYUqw")
+                .unwrap()
+                .as_str(),
+            "https://ipfs.io/ipfs/QmQsQgpda6JAYkFoeVcj5iPbwV3xRcvaiXv3bhp1VuYUqw"
+        );
+        assert_eq!(
+            resolve_uri("https://example.com/avatar.png")
+                .unwrap()
+                .as_str(),
+            "https://example.com/avatar.png"
+        );
+        assert!(resolve_uri("ftp://example.com/avatar.png").is_none());
+
+        let url = resolve_uri("data:application/json;base64,eyJpbWFnZSI6ImEifQ==").unwrap();
+        assert_eq!(decode_data_url(&url).unwrap(), br#"{"image":"a"}"#);
+    }
 }
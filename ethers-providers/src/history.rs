@@ -0,0 +1,286 @@
+use crate::Middleware;
+use ethers_core::types::{Address, BlockNumber, H256, U256, U64};
+use futures_util::stream::{self, StreamExt, TryStreamExt};
+use std::{collections::HashMap, future::Future};
+
+/// The default number of queries which are in flight at once when sampling historical state
+pub const DEFAULT_HISTORY_CONCURRENCY: usize = 10;
+
+/// Runs `query` at each of the `blocks`, with at most `concurrency` queries in flight at
+/// once. The samples are returned in the order of the `blocks`, each paired with the
+/// block it was sampled at. Fails with the first error encountered.
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers::providers::{history::sample_history, Http, Middleware, Provider};
+/// use std::convert::TryFrom;
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Provider::<Http>::try_from("http://localhost:8545")?;
+/// let code_sizes = sample_history((12_000_000u64..12_000_100).step_by(10), 5, |block| {
+///     let provider = &provider;
+///     async move {
+///         let code = provider
+///             .get_code("0x6b175474e89094c44da98b954eedeac495271d0f", Some(block.into()))
+///             .await?;
+///         Ok::<_, Box<dyn std::error::Error>>(code.as_ref().len())
+///     }
+/// })
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn sample_history<B, I, F, Fut, T, E>(
+    blocks: I,
+    concurrency: usize,
+    query: F,
+) -> Result<Vec<(BlockNumber, T)>, E>
+where
+    B: Into<BlockNumber>,
+    I: IntoIterator<Item = B>,
+    F: Fn(BlockNumber) -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    stream::iter(blocks)
+        .map(|block| {
+            let block = block.into();
+            let fut = query(block);
+            async move { fut.await.map(|sample| (block, sample)) }
+        })
+        .buffered(concurrency.max(1))
+        .try_collect()
+        .await
+}
+
+/// Returns the balance of `address` at each of the `blocks`, with at most `concurrency`
+/// queries in flight at once
+pub async fn get_balance_history<M, B, I>(
+    client: &M,
+    address: Address,
+    blocks: I,
+    concurrency: usize,
+) -> Result<Vec<(BlockNumber, U256)>, M::Error>
+where
+    M: Middleware,
+    B: Into<BlockNumber>,
+    I: IntoIterator<Item = B>,
+{
+    sample_history(blocks, concurrency, |block| {
+        client.get_balance(address, Some(block.into()))
+    })
+    .await
+}
+
+/// Returns the nonce of `address` at each of the `blocks`, with at most `concurrency`
+/// queries in flight at once
+pub async fn get_transaction_count_history<M, B, I>(
+    client: &M,
+    address: Address,
+    blocks: I,
+    concurrency: usize,
+) -> Result<Vec<(BlockNumber, U256)>, M::Error>
+where
+    M: Middleware,
+    B: Into<BlockNumber>,
+    I: IntoIterator<Item = B>,
+{
+    sample_history(blocks, concurrency, |block| {
+        client.get_transaction_count(address, Some(block.into()))
+    })
+    .await
+}
+
+/// Returns the value of the storage slot `location` of `address` at each of the `blocks`,
+/// with at most `concurrency` queries in flight at once
+pub async fn get_storage_history<M, B, I>(
+    client: &M,
+    address: Address,
+    location: H256,
+    blocks: I,
+    concurrency: usize,
+) -> Result<Vec<(BlockNumber, H256)>, M::Error>
+where
+    M: Middleware,
+    B: Into<BlockNumber>,
+    I: IntoIterator<Item = B>,
+{
+    sample_history(blocks, concurrency, |block| {
+        client.get_storage_at(address, location, Some(block.into()))
+    })
+    .await
+}
+
+/// Which block to pick when looking up a block by timestamp
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// The last block mined at or before the timestamp
+    Before,
+    /// The first block mined at or after the timestamp
+    After,
+}
+
+/// Looks up blocks by timestamp, with a binary search over the block numbers. The block
+/// timestamps fetched are cached, so that repeated lookups get cheaper.
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers::providers::{
+///     history::{BlockFinder, Strategy},
+///     Http, Provider,
+/// };
+/// use std::convert::TryFrom;
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Provider::<Http>::try_from("http://localhost:8545")?;
+/// let mut finder = BlockFinder::new(&provider);
+/// // the first block of 2021
+/// let block = finder.get_block_by_timestamp(1_609_459_200, Strategy::After).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct BlockFinder<'a, M> {
+    client: &'a M,
+    timestamps: HashMap<u64, U256>,
+}
+
+impl<'a, M: Middleware> BlockFinder<'a, M> {
+    /// Creates a block finder with an empty cache
+    pub fn new(client: &'a M) -> Self {
+        Self {
+            client,
+            timestamps: HashMap::new(),
+        }
+    }
+
+    /// Returns the number of the block closest to the timestamp according to the
+    /// strategy, or `None` if there is no such block (e.g. for a timestamp in the future
+    /// with [`Strategy::After`])
+    pub async fn get_block_by_timestamp<T: Into<U256>>(
+        &mut self,
+        timestamp: T,
+        strategy: Strategy,
+    ) -> Result<Option<U64>, M::Error> {
+        let timestamp = timestamp.into();
+        let (mut lo, mut hi) = (0, self.client.get_block_number().await?.as_u64());
+        while lo < hi {
+            match strategy {
+                Strategy::Before => {
+                    let mid = lo + (hi - lo + 1) / 2;
+                    if self.timestamp(mid).await? <= timestamp {
+                        lo = mid;
+                    } else {
+                        hi = mid - 1;
+                    }
+                }
+                Strategy::After => {
+                    let mid = lo + (hi - lo) / 2;
+                    if self.timestamp(mid).await? >= timestamp {
+                        hi = mid;
+                    } else {
+                        lo = mid + 1;
+                    }
+                }
+            }
+        }
+
+        let found = match strategy {
+            Strategy::Before => self.timestamp(lo).await? <= timestamp,
+            Strategy::After => self.timestamp(lo).await? >= timestamp,
+        };
+        Ok(if found { Some(lo.into()) } else { None })
+    }
+
+    async fn timestamp(&mut self, number: u64) -> Result<U256, M::Error> {
+        if let Some(timestamp) = self.timestamps.get(&number) {
+            return Ok(*timestamp);
+        }
+        // a block which is not found (e.g. after a reorg) is treated as not mined yet
+        let timestamp = match self.client.get_header(number).await? {
+            Some(header) => header.timestamp,
+            None => U256::MAX,
+        };
+        self.timestamps.insert(number, timestamp);
+        Ok(timestamp)
+    }
+}
+
+/// Returns the number of the block closest to the timestamp according to the strategy.
+/// Use a [`BlockFinder`] to cache the block timestamps across lookups.
+pub async fn get_block_by_timestamp<M: Middleware, T: Into<U256>>(
+    client: &M,
+    timestamp: T,
+    strategy: Strategy,
+) -> Result<Option<U64>, M::Error> {
+    BlockFinder::new(client)
+        .get_block_by_timestamp(timestamp, strategy)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Provider;
+    use ethers_core::{
+        types::{Block, BlockId, TxHash},
+        utils,
+    };
+
+    #[tokio::test]
+    async fn samples_balances() {
+        let (provider, mock) = Provider::mocked();
+        let address = Address::repeat_byte(1);
+        for balance in &[100u64, 200, 300] {
+            mock.push_response("eth_getBalance", U256::from(*balance))
+                .unwrap();
+        }
+
+        let history = get_balance_history(&provider, address, vec![10u64, 20, 30], 1)
+            .await
+            .unwrap();
+        assert_eq!(
+            history,
+            vec![
+                (10u64.into(), 100.into()),
+                (20u64.into(), 200.into()),
+                (30u64.into(), 300.into()),
+            ]
+        );
+        for block in &[10u64, 20, 30] {
+            let block = BlockId::from(*block);
+            mock.assert_request(
+                "eth_getBalance",
+                [utils::serialize(&address), utils::serialize(&block)],
+            )
+            .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn finds_blocks_by_timestamp() {
+        let (provider, mock) = Provider::mocked();
+        let header = |timestamp: u64| Block::<TxHash> {
+            timestamp: timestamp.into(),
+            ..Default::default()
+        };
+        // blocks 0 to 4 are mined every 10 seconds
+        for _ in 0..3 {
+            mock.push_response("eth_blockNumber", U64::from(4)).unwrap();
+        }
+        for timestamp in &[20, 30, 40] {
+            mock.push_response("eth_getHeaderByNumber", header(*timestamp))
+                .unwrap();
+        }
+
+        let mut finder = BlockFinder::new(&provider);
+        let block = finder.get_block_by_timestamp(25, Strategy::Before).await;
+        assert_eq!(block.unwrap(), Some(2.into()));
+        // the timestamps of blocks 2 and 3 are cached
+        let block = finder.get_block_by_timestamp(25, Strategy::After).await;
+        assert_eq!(block.unwrap(), Some(3.into()));
+        let block = finder.get_block_by_timestamp(50, Strategy::After).await;
+        assert_eq!(block.unwrap(), None);
+    }
+}
@@ -69,24 +69,72 @@ pub use transports::*;
 mod provider;
 
 // ENS support
+mod ccip;
+
 mod ens;
+pub use ens::{
+    namehash, normalize_name, COIN_TYPE_BTC, COIN_TYPE_DOGE, COIN_TYPE_ETH, COIN_TYPE_LTC,
+    ENS_ADDRESS,
+};
+
+mod batch;
+pub use batch::{BatchRequest, BatchResponse};
 
 mod pending_transaction;
 pub use pending_transaction::PendingTransaction;
 
 mod stream;
 pub use futures_util::StreamExt;
-pub use stream::{interval, FilterWatcher, DEFAULT_POLL_INTERVAL};
+pub use stream::{interval, validate_block_numbers, FilterWatcher, DEFAULT_POLL_INTERVAL};
+
+/// Helpers for querying historical state, e.g. sampling it across many blocks or looking
+/// up blocks by timestamp
+pub mod history;
+
+mod multiplexer;
+pub use multiplexer::{ConsumerId, LogMultiplexer};
+
+mod log_query;
+pub use log_query::{
+    replay_and_subscribe_logs, replay_and_watch_logs, LogQuery, DEFAULT_LOG_PAGE_SIZE,
+};
+
+mod reorg;
+pub use reorg::{Reorg, ReorgWatcher, DEFAULT_REORG_WINDOW};
 
 mod pubsub;
 pub use pubsub::{PubsubClient, SubscriptionStream};
 
+#[cfg(feature = "test-util")]
+#[cfg_attr(docsrs, doc(cfg(feature = "test-util")))]
+mod mock_server;
+#[cfg(feature = "test-util")]
+pub use mock_server::MockServer;
+
+#[cfg(feature = "alchemy")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alchemy")))]
+/// Typed bindings for Alchemy's enhanced APIs
+pub mod alchemy;
+
+#[cfg(feature = "admin")]
+#[cfg_attr(docsrs, doc(cfg(feature = "admin")))]
+/// Typed bindings for Geth's `admin` namespace
+pub mod admin;
+
+#[cfg(feature = "otterscan")]
+#[cfg_attr(docsrs, doc(cfg(feature = "otterscan")))]
+/// Typed bindings for Erigon's Otterscan `ots_` namespace
+pub mod otterscan;
+
 use async_trait::async_trait;
 use auto_impl::auto_impl;
 use serde::{de::DeserializeOwned, Serialize};
 use std::{error::Error, fmt::Debug, future::Future, pin::Pin};
+use url::Url;
 
-pub use provider::{FilterKind, Provider, ProviderError};
+pub use provider::{
+    FilterKind, Provider, ProviderBuilder, ProviderError, ValidationError, FEE_HISTORY_BLOCKS,
+};
 
 // Helper type alias
 pub(crate) type PinBoxFut<'a, T> =
@@ -122,7 +170,7 @@ pub trait FromErr<T> {
 /// 3. implementing any of the methods you want to override
 ///
 /// ```rust
-/// use ethers::{providers::{Middleware, FromErr}, types::{U64, TransactionRequest, U256}};
+/// use ethers::{providers::{Middleware, FromErr}, types::{U64, TypedTransaction, U256}};
 /// use thiserror::Error;
 /// use async_trait::async_trait;
 ///
@@ -163,7 +211,7 @@ pub trait FromErr<T> {
 ///
 ///     /// Overrides the default `estimate_gas` method to log that it was called,
 ///     /// before forwarding the call to the next layer.
-///     async fn estimate_gas(&self, tx: &TransactionRequest) -> Result<U256, Self::Error> {
+///     async fn estimate_gas(&self, tx: &TypedTransaction) -> Result<U256, Self::Error> {
 ///         println!("Estimating gas...");
 ///         self.inner().estimate_gas(tx).await.map_err(FromErr::from)
 ///     }
@@ -182,13 +230,34 @@ pub trait Middleware: Sync + Send + Debug {
         self.inner().provider()
     }
 
+    /// Returns the JSON-RPC error response of the node which caused `err`, if any. Middlewares
+    /// which wrap the errors of their inner middleware should delegate to it.
+    fn as_error_response<'a>(&self, _err: &'a Self::Error) -> Option<&'a JsonRpcError> {
+        None
+    }
+
     async fn get_block_number(&self) -> Result<U64, Self::Error> {
         self.inner().get_block_number().await.map_err(FromErr::from)
     }
 
-    async fn send_transaction(
+    /// Fills the missing fields of the transaction which are needed to send it, e.g. its
+    /// sender, gas and fees. The optional parameter `block` can be passed so that gas
+    /// cost and nonce calculations take it into account.
+    async fn fill_transaction(
         &self,
-        tx: TransactionRequest,
+        tx: &mut TypedTransaction,
+        block: Option<BlockId>,
+    ) -> Result<(), Self::Error> {
+        self.inner()
+            .fill_transaction(tx, block)
+            .await
+            .map_err(FromErr::from)
+    }
+
+    /// Sends a transaction of any type, filling its missing fields first
+    async fn send_transaction<T: Into<TypedTransaction> + Send + Sync>(
+        &self,
+        tx: T,
         block: Option<BlockId>,
     ) -> Result<PendingTransaction<'_, Self::Provider>, Self::Error> {
         self.inner()
@@ -211,6 +280,49 @@ pub trait Middleware: Sync + Send + Debug {
             .map_err(FromErr::from)
     }
 
+    async fn resolve_name_for_coin(
+        &self,
+        ens_name: &str,
+        coin_type: u64,
+    ) -> Result<String, Self::Error> {
+        self.inner()
+            .resolve_name_for_coin(ens_name, coin_type)
+            .await
+            .map_err(FromErr::from)
+    }
+
+    /// Returns the `field` text record of the ENS name, e.g. its `url` or `avatar`
+    async fn resolve_field(&self, ens_name: &str, field: &str) -> Result<String, Self::Error> {
+        self.inner()
+            .resolve_field(ens_name, field)
+            .await
+            .map_err(FromErr::from)
+    }
+
+    /// Returns the URL of the avatar of the ENS name, as specified in ENSIP-12. Avatars which
+    /// are NFTs must be owned by the address of the name, on the chain of the provider.
+    async fn resolve_avatar(&self, ens_name: &str) -> Result<Url, Self::Error> {
+        self.inner()
+            .resolve_avatar(ens_name)
+            .await
+            .map_err(FromErr::from)
+    }
+
+    /// Returns whether the signature of the hash is valid for the address. ECDSA signatures
+    /// of externally owned accounts are recovered locally, while smart-contract wallets are
+    /// asked to validate the signature as per EIP-1271.
+    async fn verify_signature(
+        &self,
+        address: Address,
+        hash: H256,
+        signature: &Bytes,
+    ) -> Result<bool, Self::Error> {
+        self.inner()
+            .verify_signature(address, hash, signature)
+            .await
+            .map_err(FromErr::from)
+    }
+
     async fn get_block<T: Into<BlockId> + Send + Sync>(
         &self,
         block_hash_or_number: T,
@@ -221,6 +333,16 @@ pub trait Middleware: Sync + Send + Debug {
             .map_err(FromErr::from)
     }
 
+    async fn get_header<T: Into<BlockId> + Send + Sync>(
+        &self,
+        block_hash_or_number: T,
+    ) -> Result<Option<Block<TxHash>>, Self::Error> {
+        self.inner()
+            .get_header(block_hash_or_number)
+            .await
+            .map_err(FromErr::from)
+    }
+
     async fn get_block_with_txs<T: Into<BlockId> + Send + Sync>(
         &self,
         block_hash_or_number: T,
@@ -242,22 +364,143 @@ pub trait Middleware: Sync + Send + Debug {
             .map_err(FromErr::from)
     }
 
-    async fn estimate_gas(&self, tx: &TransactionRequest) -> Result<U256, Self::Error> {
+    async fn estimate_gas(&self, tx: &TypedTransaction) -> Result<U256, Self::Error> {
         self.inner().estimate_gas(tx).await.map_err(FromErr::from)
     }
 
     async fn call(
         &self,
-        tx: &TransactionRequest,
+        tx: &TypedTransaction,
         block: Option<BlockId>,
     ) -> Result<Bytes, Self::Error> {
         self.inner().call(tx, block).await.map_err(FromErr::from)
     }
 
+    /// Executes the call against the state at `block` modified by the account overrides
+    async fn call_with_state_overrides(
+        &self,
+        tx: &TypedTransaction,
+        block: Option<BlockId>,
+        overrides: &StateOverride,
+    ) -> Result<Bytes, Self::Error> {
+        self.inner()
+            .call_with_state_overrides(tx, block, overrides)
+            .await
+            .map_err(FromErr::from)
+    }
+
+    /// Executes the call against the state at `block` modified by the account overrides,
+    /// in a block context modified by the block overrides
+    async fn call_with_overrides(
+        &self,
+        tx: &TypedTransaction,
+        block: Option<BlockId>,
+        state_overrides: &StateOverride,
+        block_overrides: &BlockOverrides,
+    ) -> Result<Bytes, Self::Error> {
+        self.inner()
+            .call_with_overrides(tx, block, state_overrides, block_overrides)
+            .await
+            .map_err(FromErr::from)
+    }
+
+    /// Simulates the payload's blocks of calls on top of `block` (defaults to the latest
+    /// block) with `eth_simulateV1`, returning the simulated blocks along with the
+    /// results of their calls
+    async fn simulate(
+        &self,
+        payload: &SimulatePayload,
+        block: Option<BlockId>,
+    ) -> Result<Vec<SimulatedBlock>, Self::Error> {
+        self.inner()
+            .simulate(payload, block)
+            .await
+            .map_err(FromErr::from)
+    }
+
+    /// Returns the access list of the transaction, e.g. to send it as an EIP-2930
+    /// transaction which accesses the listed storage more cheaply
+    async fn create_access_list(
+        &self,
+        tx: &TypedTransaction,
+        block: Option<BlockId>,
+    ) -> Result<AccessListWithGasUsed, Self::Error> {
+        self.inner()
+            .create_access_list(tx, block)
+            .await
+            .map_err(FromErr::from)
+    }
+
     async fn get_chainid(&self) -> Result<U256, Self::Error> {
         self.inner().get_chainid().await.map_err(FromErr::from)
     }
 
+    /// Returns the sync status of the node
+    async fn syncing(&self) -> Result<SyncingStatus, Self::Error> {
+        self.inner().syncing().await.map_err(FromErr::from)
+    }
+
+    /// Returns the network id of the node
+    async fn net_version(&self) -> Result<String, Self::Error> {
+        self.inner().net_version().await.map_err(FromErr::from)
+    }
+
+    /// Returns the number of peers connected to the node
+    async fn peer_count(&self) -> Result<U64, Self::Error> {
+        self.inner().peer_count().await.map_err(FromErr::from)
+    }
+
+    /// Returns the name and version of the node's software
+    async fn client_version(&self) -> Result<String, Self::Error> {
+        self.inner().client_version().await.map_err(FromErr::from)
+    }
+
+    /// Hashes the data with Keccak-256 on the node
+    async fn web3_sha3<T: Into<Bytes> + Send + Sync>(&self, data: T) -> Result<H256, Self::Error> {
+        self.inner().web3_sha3(data).await.map_err(FromErr::from)
+    }
+
+    /// Returns true if the node is mining
+    async fn mining(&self) -> Result<bool, Self::Error> {
+        self.inner().mining().await.map_err(FromErr::from)
+    }
+
+    /// Returns the number of hashes per second the node is mining with
+    async fn hashrate(&self) -> Result<U256, Self::Error> {
+        self.inner().hashrate().await.map_err(FromErr::from)
+    }
+
+    /// Returns the address receiving the rewards of the blocks mined by the node
+    async fn coinbase(&self) -> Result<Address, Self::Error> {
+        self.inner().coinbase().await.map_err(FromErr::from)
+    }
+
+    /// Returns the proof-of-work package of the block being mined
+    async fn get_work(&self) -> Result<Work, Self::Error> {
+        self.inner().get_work().await.map_err(FromErr::from)
+    }
+
+    /// Submits a proof-of-work solution, returning whether it was accepted
+    async fn submit_work(
+        &self,
+        nonce: H64,
+        pow_hash: H256,
+        mix_digest: H256,
+    ) -> Result<bool, Self::Error> {
+        self.inner()
+            .submit_work(nonce, pow_hash, mix_digest)
+            .await
+            .map_err(FromErr::from)
+    }
+
+    /// Submits the hashrate of an external miner, returning whether it was accepted
+    async fn submit_hashrate(&self, hashrate: U256, id: H256) -> Result<bool, Self::Error> {
+        self.inner()
+            .submit_hashrate(hashrate, id)
+            .await
+            .map_err(FromErr::from)
+    }
+
     async fn get_balance<T: Into<NameOrAddress> + Send + Sync>(
         &self,
         from: T,
@@ -289,10 +532,44 @@ pub trait Middleware: Sync + Send + Debug {
             .map_err(FromErr::from)
     }
 
+    /// Returns all the receipts of the block
+    async fn get_block_receipts<T: Into<BlockNumber> + Send + Sync>(
+        &self,
+        block: T,
+    ) -> Result<Vec<TransactionReceipt>, Self::Error> {
+        self.inner()
+            .get_block_receipts(block)
+            .await
+            .map_err(FromErr::from)
+    }
+
     async fn get_gas_price(&self) -> Result<U256, Self::Error> {
         self.inner().get_gas_price().await.map_err(FromErr::from)
     }
 
+    /// Returns the base fees and the priority fees paid at the given percentiles for
+    /// `block_count` blocks ending at `last_block`
+    async fn fee_history<T: Into<U256> + Send + Sync>(
+        &self,
+        block_count: T,
+        last_block: BlockNumber,
+        reward_percentiles: &[f64],
+    ) -> Result<FeeHistory, Self::Error> {
+        self.inner()
+            .fee_history(block_count, last_block, reward_percentiles)
+            .await
+            .map_err(FromErr::from)
+    }
+
+    /// Suggests the fees of a transaction with the given priority: a max fee and max
+    /// priority fee per gas if the chain supports EIP-1559, a gas price otherwise
+    async fn suggest_fees(&self, priority: Priority) -> Result<FeeSuggestion, Self::Error> {
+        self.inner()
+            .suggest_fees(priority)
+            .await
+            .map_err(FromErr::from)
+    }
+
     async fn get_accounts(&self) -> Result<Vec<Address>, Self::Error> {
         self.inner().get_accounts().await.map_err(FromErr::from)
     }
@@ -307,6 +584,30 @@ pub trait Middleware: Sync + Send + Debug {
             .map_err(FromErr::from)
     }
 
+    /// Broadcasts an RLP encoded signed transaction, e.g. one signed offline with
+    /// `Signer::sign_raw_transaction`
+    async fn send_raw_transaction_bytes<'a>(
+        &'a self,
+        rlp: Bytes,
+    ) -> Result<PendingTransaction<'a, Self::Provider>, Self::Error> {
+        self.inner()
+            .send_raw_transaction_bytes(rlp)
+            .await
+            .map_err(FromErr::from)
+    }
+
+    /// Submits a signed transaction privately to block builders with
+    /// `eth_sendPrivateTransaction`, keeping it out of the public mempool
+    async fn send_private_transaction<'a>(
+        &'a self,
+        tx: &PrivateTransaction,
+    ) -> Result<PendingTransaction<'a, Self::Provider>, Self::Error> {
+        self.inner()
+            .send_private_transaction(tx)
+            .await
+            .map_err(FromErr::from)
+    }
+
     /// This returns true if either the middleware stack contains a `SignerMiddleware`, or the
     /// JSON-RPC provider has an unlocked key that can sign using the `eth_sign` call. If none of
     /// the above conditions are met, then the middleware stack is not capable of signing data.
@@ -322,12 +623,34 @@ pub trait Middleware: Sync + Send + Debug {
         self.inner().sign(data, from).await.map_err(FromErr::from)
     }
 
+    /// Signs the transaction without broadcasting it, returning its signed encoding
+    /// along with the decoded transaction
+    async fn sign_transaction<T: Into<TypedTransaction> + Send + Sync>(
+        &self,
+        tx: T,
+    ) -> Result<SignedTransaction, Self::Error> {
+        self.inner()
+            .sign_transaction(tx)
+            .await
+            .map_err(FromErr::from)
+    }
+
     ////// Contract state
 
     async fn get_logs(&self, filter: &Filter) -> Result<Vec<Log>, Self::Error> {
         self.inner().get_logs(filter).await.map_err(FromErr::from)
     }
 
+    /// Returns a stream of the logs matching the filter, querying its block range in pages
+    /// of at most `page_size` blocks, see [`LogQuery`]
+    #[auto_impl(keep_default_for(&, Box, Arc))]
+    fn get_logs_paginated<'a>(&'a self, filter: &Filter, page_size: u64) -> LogQuery<'a, Self>
+    where
+        Self: Sized,
+    {
+        LogQuery::new(self, filter).page_size(page_size)
+    }
+
     async fn new_filter(&self, filter: FilterKind<'_>) -> Result<U256, Self::Error> {
         self.inner().new_filter(filter).await.map_err(FromErr::from)
     }
@@ -384,6 +707,17 @@ pub trait Middleware: Sync + Send + Debug {
             .map_err(FromErr::from)
     }
 
+    async fn get_account<T: Into<NameOrAddress> + Send + Sync>(
+        &self,
+        account: T,
+        block: Option<BlockId>,
+    ) -> Result<Account, Self::Error> {
+        self.inner()
+            .get_account(account, block)
+            .await
+            .map_err(FromErr::from)
+    }
+
     async fn get_storage_at<T: Into<NameOrAddress> + Send + Sync>(
         &self,
         from: T,
@@ -396,6 +730,21 @@ pub trait Middleware: Sync + Send + Debug {
             .map_err(FromErr::from)
     }
 
+    /// Returns the account and the storage slots at `locations` with their Merkle
+    /// proofs, which can be verified against the state root of the block with
+    /// [`EIP1186ProofResponse::verify`]
+    async fn get_proof<T: Into<NameOrAddress> + Send + Sync>(
+        &self,
+        from: T,
+        locations: Vec<H256>,
+        block: Option<BlockId>,
+    ) -> Result<EIP1186ProofResponse, Self::Error> {
+        self.inner()
+            .get_proof(from, locations, block)
+            .await
+            .map_err(FromErr::from)
+    }
+
     // Mempool inspection for Geth's API
 
     async fn txpool_content(&self) -> Result<TxpoolContent, Self::Error> {
@@ -415,7 +764,7 @@ pub trait Middleware: Sync + Send + Debug {
     /// Executes the given call and returns a number of possible traces for it
     async fn trace_call(
         &self,
-        req: TransactionRequest,
+        req: &TypedTransaction,
         trace_type: Vec<TraceType>,
         block: Option<BlockNumber>,
     ) -> Result<BlockTrace, Self::Error> {
@@ -425,6 +774,33 @@ pub trait Middleware: Sync + Send + Debug {
             .map_err(FromErr::from)
     }
 
+    /// Executes the given calls in sequence, each on top of the state changes of the
+    /// previous ones, and returns the traces of each call
+    async fn trace_call_many(
+        &self,
+        req: Vec<(TypedTransaction, Vec<TraceType>)>,
+        block: Option<BlockNumber>,
+    ) -> Result<Vec<BlockTrace>, Self::Error> {
+        self.inner()
+            .trace_call_many(req, block)
+            .await
+            .map_err(FromErr::from)
+    }
+
+    /// Executes the given call on top of the currently pending transactions of the
+    /// txpool, ordered as a miner would include them, and returns its traces. This
+    /// predicts the outcome of the transaction before broadcasting it.
+    async fn simulate_on_pending(
+        &self,
+        req: &TypedTransaction,
+        trace_type: Vec<TraceType>,
+    ) -> Result<BlockTrace, Self::Error> {
+        self.inner()
+            .simulate_on_pending(req, trace_type)
+            .await
+            .map_err(FromErr::from)
+    }
+
     /// Traces a call to `eth_sendRawTransaction` without making the call, returning the traces
     async fn trace_raw_transaction(
         &self,
@@ -494,6 +870,88 @@ pub trait Middleware: Sync + Send + Debug {
             .map_err(FromErr::from)
     }
 
+    // Geth `debug` namespace
+
+    /// Replays a transaction, returning the trace of the selected tracer
+    async fn debug_trace_transaction(
+        &self,
+        tx_hash: TxHash,
+        trace_options: GethDebugTracingOptions,
+    ) -> Result<GethTrace, Self::Error> {
+        self.inner()
+            .debug_trace_transaction(tx_hash, trace_options)
+            .await
+            .map_err(FromErr::from)
+    }
+
+    /// Executes the given call without sending it, returning the trace of the selected
+    /// tracer
+    async fn debug_trace_call(
+        &self,
+        req: &TypedTransaction,
+        block: Option<BlockId>,
+        trace_options: GethDebugTracingCallOptions,
+    ) -> Result<GethTrace, Self::Error> {
+        self.inner()
+            .debug_trace_call(req, block, trace_options)
+            .await
+            .map_err(FromErr::from)
+    }
+
+    /// Returns the RLP encoding of the block
+    async fn debug_get_raw_block<T: Into<BlockId> + Send + Sync>(
+        &self,
+        block_hash_or_number: T,
+    ) -> Result<Bytes, Self::Error> {
+        self.inner()
+            .debug_get_raw_block(block_hash_or_number)
+            .await
+            .map_err(FromErr::from)
+    }
+
+    /// Returns the consensus encodings of the receipts of the block
+    async fn debug_get_raw_receipts<T: Into<BlockId> + Send + Sync>(
+        &self,
+        block_hash_or_number: T,
+    ) -> Result<Vec<Bytes>, Self::Error> {
+        self.inner()
+            .debug_get_raw_receipts(block_hash_or_number)
+            .await
+            .map_err(FromErr::from)
+    }
+
+    /// Returns the signed encoding of the transaction
+    async fn debug_get_raw_transaction(&self, tx_hash: TxHash) -> Result<Bytes, Self::Error> {
+        self.inner()
+            .debug_get_raw_transaction(tx_hash)
+            .await
+            .map_err(FromErr::from)
+    }
+
+    /// Returns the blocks recently rejected by the node
+    async fn debug_get_bad_blocks(&self) -> Result<Vec<BadBlock>, Self::Error> {
+        self.inner()
+            .debug_get_bad_blocks()
+            .await
+            .map_err(FromErr::from)
+    }
+
+    /// Returns a page of the storage of the account as of after the transaction at
+    /// `tx_index` of the block
+    async fn debug_storage_range_at(
+        &self,
+        block_hash: H256,
+        tx_index: u64,
+        address: Address,
+        start_key: H256,
+        max_results: u64,
+    ) -> Result<StorageRange, Self::Error> {
+        self.inner()
+            .debug_storage_range_at(block_hash, tx_index, address, start_key, max_results)
+            .await
+            .map_err(FromErr::from)
+    }
+
     // Parity namespace
 
     /// Returns all receipts for that block. Must be done on a parity node.
@@ -507,6 +965,62 @@ pub trait Middleware: Sync + Send + Debug {
             .map_err(FromErr::from)
     }
 
+    // ERC-4337 bundler namespace
+
+    /// Submits a user operation to the bundler's mempool, returning its hash
+    async fn send_user_operation(
+        &self,
+        user_op: &UserOperation,
+        entry_point: Address,
+    ) -> Result<H256, Self::Error> {
+        self.inner()
+            .send_user_operation(user_op, entry_point)
+            .await
+            .map_err(FromErr::from)
+    }
+
+    /// Estimates the gas limits required by a user operation
+    async fn estimate_user_operation_gas(
+        &self,
+        user_op: &UserOperation,
+        entry_point: Address,
+    ) -> Result<UserOperationGasEstimation, Self::Error> {
+        self.inner()
+            .estimate_user_operation_gas(user_op, entry_point)
+            .await
+            .map_err(FromErr::from)
+    }
+
+    /// Returns the user operation with the provided hash, if it has been included
+    async fn get_user_operation<T: Send + Sync + Into<H256>>(
+        &self,
+        user_op_hash: T,
+    ) -> Result<Option<UserOperationByHash>, Self::Error> {
+        self.inner()
+            .get_user_operation(user_op_hash)
+            .await
+            .map_err(FromErr::from)
+    }
+
+    /// Returns the receipt of the user operation with the provided hash, if it has been included
+    async fn get_user_operation_receipt<T: Send + Sync + Into<H256>>(
+        &self,
+        user_op_hash: T,
+    ) -> Result<Option<UserOperationReceipt>, Self::Error> {
+        self.inner()
+            .get_user_operation_receipt(user_op_hash)
+            .await
+            .map_err(FromErr::from)
+    }
+
+    /// Returns the EntryPoint contracts supported by the bundler
+    async fn supported_entry_points(&self) -> Result<Vec<Address>, Self::Error> {
+        self.inner()
+            .supported_entry_points()
+            .await
+            .map_err(FromErr::from)
+    }
+
     async fn subscribe<T, R>(
         &self,
         params: T,
@@ -0,0 +1,522 @@
+use crate::{Middleware, PubsubClient};
+use ethers_core::types::{BlockNumber, Filter, FilterBlockOption, Log, U256, U64};
+use futures_core::stream::Stream;
+use futures_util::{future, StreamExt};
+use std::{
+    collections::VecDeque,
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// The default number of blocks queried at once by a [`LogQuery`]
+pub const DEFAULT_LOG_PAGE_SIZE: u64 = 10_000;
+
+/// Fragments of the error messages nodes respond with when a query spans too many blocks
+/// or matches too many logs
+const TOO_MANY_RESULTS_ERRORS: &[&str] = &[
+    "query returned more than",
+    "too many",
+    "limit exceeded",
+    "response size exceeded",
+    "block range",
+    "range is too large",
+];
+
+type QueryFut<'a, T, E> = Pin<Box<dyn Future<Output = Result<T, E>> + Send + 'a>>;
+
+/// A stream of the logs matching a filter, which queries the filter's block range in pages
+/// so that nodes do not reject the queries for spanning too many blocks. When a page
+/// matches too many logs, it is split in halves until the node accepts it. The logs are
+/// yielded in order, and the stream ends after the last page, or after the first error.
+///
+/// The range's bounds default to the latest block, as with `eth_getLogs`.
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers::{
+///     providers::{Http, Middleware, Provider},
+///     types::Filter,
+/// };
+/// use futures_util::StreamExt;
+/// use std::convert::TryFrom;
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Provider::<Http>::try_from("http://localhost:8545")?;
+/// let filter = Filter::new()
+///     .address("0x6b175474e89094c44da98b954eedeac495271d0f".parse::<ethers::types::Address>()?)
+///     .from_block(10_000_000)
+///     .event("Transfer(address,address,uint256)");
+///
+/// let mut logs = provider.get_logs_paginated(&filter, 2_000);
+/// while let Some(log) = logs.next().await {
+///     println!("{:?}", log?);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct LogQuery<'a, M: Middleware> {
+    client: &'a M,
+    filter: Filter,
+    page_size: u64,
+    /// The first block of the next page, and the last block of the range
+    from_block: U64,
+    to_block: U64,
+    /// The last block of the page being queried
+    page_end: U64,
+    logs: VecDeque<Log>,
+    state: LogQueryState<'a, M::Error>,
+}
+
+enum LogQueryState<'a, E> {
+    Initial,
+    LoadingLastBlock(QueryFut<'a, U64, E>),
+    LoadingLogs(QueryFut<'a, Vec<Log>, E>),
+    Consuming,
+    Done,
+}
+
+impl<'a, M: Middleware> LogQuery<'a, M> {
+    /// Creates a query of the logs matching the filter, in pages of
+    /// [`DEFAULT_LOG_PAGE_SIZE`] blocks
+    pub fn new(client: &'a M, filter: &Filter) -> Self {
+        Self {
+            client,
+            filter: filter.clone(),
+            page_size: DEFAULT_LOG_PAGE_SIZE,
+            from_block: U64::zero(),
+            to_block: U64::zero(),
+            page_end: U64::zero(),
+            logs: VecDeque::new(),
+            state: LogQueryState::Initial,
+        }
+    }
+
+    /// Sets the number of blocks queried at once
+    pub fn page_size(mut self, page_size: u64) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    fn load_logs(&mut self, filter: Filter) {
+        let client = self.client;
+        let fut = Box::pin(async move { client.get_logs(&filter).await });
+        self.state = LogQueryState::LoadingLogs(fut);
+    }
+
+    /// Queries the next page, or ends the stream after the last one
+    fn next_page(&mut self) {
+        if self.from_block > self.to_block {
+            self.state = LogQueryState::Done;
+            return;
+        }
+        self.page_end = self
+            .to_block
+            .min(self.from_block + self.page_size.saturating_sub(1));
+        let filter = self
+            .filter
+            .clone()
+            .from_block(self.from_block)
+            .to_block(self.page_end);
+        self.load_logs(filter);
+    }
+}
+
+/// Returns the block number of a range bound, or `None` if it is relative to the head
+fn block_number(block: Option<BlockNumber>) -> Option<U64> {
+    match block {
+        Some(BlockNumber::Number(number)) => Some(number),
+        Some(BlockNumber::Earliest) => Some(U64::zero()),
+        _ => None,
+    }
+}
+
+fn is_too_many_results<E: fmt::Display>(err: &E) -> bool {
+    let message = err.to_string().to_lowercase();
+    TOO_MANY_RESULTS_ERRORS
+        .iter()
+        .any(|fragment| message.contains(fragment))
+}
+
+impl<'a, M: Middleware> Stream for LogQuery<'a, M> {
+    type Item = Result<Log, M::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            match &mut this.state {
+                LogQueryState::Initial => match this.filter.block_option {
+                    // a single block is queried at once, the empty range ending the
+                    // stream after it
+                    FilterBlockOption::AtBlockHash(_) => {
+                        let filter = this.filter.clone();
+                        this.load_logs(filter);
+                    }
+                    FilterBlockOption::Range {
+                        from_block,
+                        to_block,
+                    } => match (block_number(from_block), block_number(to_block)) {
+                        (Some(from), Some(to)) => {
+                            this.from_block = from;
+                            this.to_block = to;
+                            this.next_page();
+                        }
+                        _ => {
+                            let client = this.client;
+                            let fut = Box::pin(async move { client.get_block_number().await });
+                            this.state = LogQueryState::LoadingLastBlock(fut);
+                        }
+                    },
+                },
+                LogQueryState::LoadingLastBlock(fut) => {
+                    let last_block = match futures_util::ready!(fut.as_mut().poll(ctx)) {
+                        Ok(last_block) => last_block,
+                        Err(err) => {
+                            this.state = LogQueryState::Done;
+                            return Poll::Ready(Some(Err(err)));
+                        }
+                    };
+                    if let FilterBlockOption::Range {
+                        from_block,
+                        to_block,
+                    } = this.filter.block_option
+                    {
+                        this.from_block = block_number(from_block).unwrap_or(last_block);
+                        this.to_block = block_number(to_block).unwrap_or(last_block);
+                    }
+                    this.next_page();
+                }
+                LogQueryState::LoadingLogs(fut) => {
+                    match futures_util::ready!(fut.as_mut().poll(ctx)) {
+                        Ok(logs) => {
+                            this.logs.extend(logs);
+                            this.from_block = this.page_end + 1;
+                            this.state = LogQueryState::Consuming;
+                        }
+                        // split the page until the node accepts it
+                        Err(err)
+                            if is_too_many_results(&err) && this.page_end > this.from_block =>
+                        {
+                            let blocks = (this.page_end - this.from_block).as_u64() + 1;
+                            this.page_size = blocks / 2;
+                            tracing::trace!(page_size = this.page_size, "splitting log query");
+                            this.next_page();
+                        }
+                        Err(err) => {
+                            this.state = LogQueryState::Done;
+                            return Poll::Ready(Some(Err(err)));
+                        }
+                    }
+                }
+                LogQueryState::Consuming => match this.logs.pop_front() {
+                    Some(log) => return Poll::Ready(Some(Ok(log))),
+                    None => this.next_page(),
+                },
+                LogQueryState::Done => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+impl<'a, M: Middleware> fmt::Debug for LogQuery<'a, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LogQuery")
+            .field("filter", &self.filter)
+            .field("page_size", &self.page_size)
+            .field("from_block", &self.from_block)
+            .field("to_block", &self.to_block)
+            .finish()
+    }
+}
+
+/// Returns a stream of the logs matching the filter, which first replays the past logs
+/// from the filter's `from_block` in pages of `page_size` blocks, and then follows the new
+/// logs with [`watch`](crate::Middleware::watch). The filter's `to_block` is ignored.
+///
+/// The live filter is installed before the past logs are queried, so that no log is
+/// missed in between, and the logs which are both replayed and watched are only yielded
+/// once, based on their block number and log index. Logs of pending blocks, and logs
+/// removed by a reorg, are always yielded, and the logs replacing the removed ones are
+/// yielded again. The stream ends after the first error of the replay, since the logs
+/// following it would be missing.
+///
+/// With pubsub clients, [`replay_and_subscribe_logs`] follows the new logs with a
+/// subscription instead of polling a filter.
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers::{
+///     providers::{replay_and_watch_logs, Http, Provider},
+///     types::Filter,
+/// };
+/// use futures_util::StreamExt;
+/// use std::convert::TryFrom;
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Provider::<Http>::try_from("http://localhost:8545")?;
+/// let filter = Filter::new()
+///     .from_block(10_000_000)
+///     .event("Transfer(address,address,uint256)");
+///
+/// let mut logs = replay_and_watch_logs(&provider, &filter, 2_000).await?;
+/// while let Some(log) = logs.next().await {
+///     println!("{:?}", log?);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn replay_and_watch_logs<'a, M: Middleware>(
+    client: &'a M,
+    filter: &Filter,
+    page_size: u64,
+) -> Result<impl Stream<Item = Result<Log, M::Error>> + Send + 'a, M::Error> {
+    let live = client.watch(filter).await?;
+    let last_block = client.get_block_number().await?;
+    let past = LogQuery::new(client, &filter.clone().to_block(last_block)).page_size(page_size);
+    Ok(replay_then(past, live))
+}
+
+/// Returns a stream of the logs matching the filter, which first replays the past logs
+/// like [`replay_and_watch_logs`], and then follows the new logs with
+/// [`subscribe_logs`](crate::Middleware::subscribe_logs).
+pub async fn replay_and_subscribe_logs<'a, M: Middleware>(
+    client: &'a M,
+    filter: &Filter,
+    page_size: u64,
+) -> Result<impl Stream<Item = Result<Log, M::Error>> + 'a, M::Error>
+where
+    M::Provider: PubsubClient,
+{
+    let live = client.subscribe_logs(filter).await?;
+    let last_block = client.get_block_number().await?;
+    let past = LogQuery::new(client, &filter.clone().to_block(last_block)).page_size(page_size);
+    Ok(replay_then(past, live))
+}
+
+/// Chains the replayed logs with the live ones, skipping the live logs which were
+/// already replayed and ending after the first error of the replay
+fn replay_then<'a, M, L>(
+    past: LogQuery<'a, M>,
+    live: L,
+) -> impl Stream<Item = Result<Log, M::Error>> + 'a
+where
+    M: Middleware,
+    L: Stream<Item = Log> + 'a,
+{
+    // the position from which logs are new
+    let mut next: Option<(U64, U256)> = None;
+    past.chain(live.map(Ok))
+        .scan(false, |failed, log| {
+            if *failed {
+                return future::ready(None);
+            }
+            *failed = log.is_err();
+            future::ready(Some(log))
+        })
+        .filter(move |log| {
+            let log = match log {
+                Ok(log) => log,
+                Err(_) => return future::ready(true),
+            };
+            let position = match log.block_number.zip(log.log_index) {
+                Some(position) => position,
+                None => return future::ready(true),
+            };
+            if log.removed == Some(true) {
+                // the logs from the removed one onwards are replaced by the reorg
+                next = Some(next.map_or(position, |next| next.min(position)));
+                return future::ready(true);
+            }
+            let is_new = next.map_or(true, |next| position >= next);
+            if is_new {
+                next = Some((position.0, position.1 + 1));
+            }
+            future::ready(is_new)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{JsonRpcError, Provider};
+    use futures_util::StreamExt;
+
+    fn log(block: u64) -> Log {
+        Log {
+            address: Default::default(),
+            topics: vec![],
+            data: Default::default(),
+            block_hash: None,
+            block_number: Some(block.into()),
+            transaction_hash: None,
+            transaction_index: None,
+            log_index: None,
+            transaction_log_index: None,
+            log_type: None,
+            removed: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn paginates_block_range() {
+        let (provider, mock) = Provider::mocked();
+        mock.push_response::<Vec<Log>, _>("eth_getLogs", vec![log(1), log(2)])
+            .unwrap();
+        // the second page matches too many logs, and is split in halves
+        mock.push_error(
+            "eth_getLogs",
+            JsonRpcError {
+                code: -32005,
+                message: "query returned more than 10000 results".to_owned(),
+                data: None,
+            },
+        );
+        mock.push_response::<Vec<Log>, _>("eth_getLogs", vec![log(5)])
+            .unwrap();
+        mock.push_response::<Vec<Log>, _>("eth_getLogs", vec![log(7)])
+            .unwrap();
+        mock.push_response::<Vec<Log>, _>("eth_getLogs", Vec::new())
+            .unwrap();
+
+        let filter = Filter::new().from_block(1).to_block(9);
+        let logs = LogQuery::new(&provider, &filter)
+            .page_size(4)
+            .map(|log| log.unwrap().block_number.unwrap().as_u64())
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(logs, vec![1, 2, 5, 7]);
+
+        let filter = |from: u64, to: u64| Filter::new().from_block(from).to_block(to);
+        for range in &[(1, 4), (5, 8), (5, 6), (7, 8), (9, 9)] {
+            mock.assert_request("eth_getLogs", [filter(range.0, range.1)])
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn replays_then_watches_logs() {
+        let (provider, mock) = Provider::mocked();
+        let provider = provider.interval(std::time::Duration::from_millis(1));
+        let indexed = |block: u64, index: u64| Log {
+            log_index: Some(index.into()),
+            ..log(block)
+        };
+        mock.push_response("eth_newFilter", U256::one()).unwrap();
+        mock.push_response("eth_blockNumber", U64::from(5)).unwrap();
+        mock.push_response::<Vec<Log>, _>("eth_getLogs", vec![indexed(3, 0), indexed(5, 1)])
+            .unwrap();
+        // the filter was installed before block 5 was queried
+        mock.push_response::<Vec<Log>, _>(
+            "eth_getFilterChanges",
+            vec![indexed(5, 1), indexed(6, 0)],
+        )
+        .unwrap();
+
+        let filter = Filter::new().from_block(1);
+        let logs = replay_and_watch_logs(&provider, &filter, 10)
+            .await
+            .unwrap()
+            .take(3)
+            .map(|log| {
+                let log = log.unwrap();
+                (
+                    log.block_number.unwrap().as_u64(),
+                    log.log_index.unwrap().as_u64(),
+                )
+            })
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(logs, vec![(3, 0), (5, 1), (6, 0)]);
+    }
+
+    #[tokio::test]
+    async fn replays_logs_replaced_by_reorgs() {
+        let (provider, mock) = Provider::mocked();
+        let provider = provider.interval(std::time::Duration::from_millis(1));
+        let indexed = |block: u64, index: u64| Log {
+            log_index: Some(index.into()),
+            ..log(block)
+        };
+        mock.push_response("eth_newFilter", U256::one()).unwrap();
+        mock.push_response("eth_blockNumber", U64::from(5)).unwrap();
+        mock.push_response::<Vec<Log>, _>("eth_getLogs", vec![indexed(4, 0), indexed(5, 0)])
+            .unwrap();
+        // block 5 is reorged with another log at the same position
+        let removed = Log {
+            removed: Some(true),
+            ..indexed(5, 0)
+        };
+        mock.push_response::<Vec<Log>, _>(
+            "eth_getFilterChanges",
+            vec![indexed(5, 0), removed, indexed(5, 0), indexed(6, 0)],
+        )
+        .unwrap();
+
+        let filter = Filter::new().from_block(1);
+        let logs = replay_and_watch_logs(&provider, &filter, 10)
+            .await
+            .unwrap()
+            .take(5)
+            .map(|log| {
+                let log = log.unwrap();
+                (
+                    log.block_number.unwrap().as_u64(),
+                    log.removed == Some(true),
+                )
+            })
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(
+            logs,
+            vec![(4, false), (5, false), (5, true), (5, false), (6, false)]
+        );
+    }
+
+    #[tokio::test]
+    async fn stops_replaying_at_errors() {
+        let (provider, mock) = Provider::mocked();
+        let provider = provider.interval(std::time::Duration::from_millis(1));
+        mock.push_response("eth_newFilter", U256::one()).unwrap();
+        mock.push_response("eth_blockNumber", U64::from(5)).unwrap();
+        mock.push_error(
+            "eth_getLogs",
+            JsonRpcError {
+                code: -32000,
+                message: "internal error".to_owned(),
+                data: None,
+            },
+        );
+        mock.push_response::<Vec<Log>, _>("eth_getFilterChanges", vec![log(6)])
+            .unwrap();
+
+        // the live logs would miss the logs of the failed page
+        let mut logs = replay_and_watch_logs(&provider, &Filter::new().from_block(1), 10)
+            .await
+            .unwrap()
+            .boxed();
+        assert!(logs.next().await.unwrap().is_err());
+        assert!(logs.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn stops_at_other_errors() {
+        let (provider, mock) = Provider::mocked();
+        mock.push_response("eth_blockNumber", U64::from(100))
+            .unwrap();
+        mock.push_error(
+            "eth_getLogs",
+            JsonRpcError {
+                code: -32000,
+                message: "internal error".to_owned(),
+                data: None,
+            },
+        );
+
+        let mut logs = LogQuery::new(&provider, &Filter::new().from_block(50));
+        assert!(logs.next().await.unwrap().is_err());
+        assert!(logs.next().await.is_none());
+    }
+}
@@ -0,0 +1,374 @@
+//! A lightweight JSON-RPC server for end-to-end integration tests
+use crate::JsonRpcError;
+
+use ethers_core::types::U256;
+
+use futures_channel::mpsc;
+use futures_util::{SinkExt, StreamExt};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    fmt, io,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    task::JoinHandle,
+};
+use tokio_tungstenite::{
+    accept_async,
+    tungstenite::{self, Message},
+};
+
+type Handler = Arc<dyn Fn(Value) -> Result<Value, JsonRpcError> + Send + Sync>;
+
+/// A JSON-RPC server listening on localhost over HTTP and Websockets, with programmable
+/// per-method handlers. This allows testing retry, reconnection and subscription
+/// codepaths end-to-end without running a real node.
+///
+/// By default, the server serves canned data for `eth_chainId` (1337), `net_version`,
+/// `eth_blockNumber` (0) and `eth_subscribe`/`eth_unsubscribe`. Any other method
+/// returns a "method not found" error until a handler is registered for it.
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers::{
+///     providers::{Http, Middleware, MockServer, Provider, Ws},
+///     types::{TxHash, U64},
+/// };
+/// use std::convert::TryFrom;
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let server = MockServer::spawn().await?;
+/// server.respond("eth_blockNumber", U64::from(10));
+///
+/// let provider = Provider::<Http>::try_from(server.http_endpoint())?;
+/// assert_eq!(provider.get_block_number().await?, U64::from(10));
+///
+/// // push notifications to the subscribed websocket clients
+/// let provider = Provider::<Ws>::connect(server.ws_endpoint()).await?;
+/// let stream = provider.subscribe_pending_txs().await?;
+/// server.notify(stream.id, TxHash::zero());
+/// # Ok(())
+/// # }
+/// ```
+pub struct MockServer {
+    http_addr: SocketAddr,
+    ws_addr: SocketAddr,
+    state: Arc<State>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+#[derive(Default)]
+struct State {
+    handlers: Mutex<HashMap<String, Handler>>,
+    requests: Mutex<Vec<(String, Value)>>,
+    subscribers: Mutex<Vec<mpsc::UnboundedSender<Message>>>,
+}
+
+impl fmt::Debug for MockServer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MockServer")
+            .field("http_addr", &self.http_addr)
+            .field("ws_addr", &self.ws_addr)
+            .finish()
+    }
+}
+
+impl MockServer {
+    /// Binds the server to random local ports and starts serving requests in the
+    /// background. The server is shut down when dropped.
+    pub async fn spawn() -> io::Result<Self> {
+        let http = TcpListener::bind("127.0.0.1:0").await?;
+        let ws = TcpListener::bind("127.0.0.1:0").await?;
+
+        let state = Arc::new(State::default());
+        let mut server = Self {
+            http_addr: http.local_addr()?,
+            ws_addr: ws.local_addr()?,
+            state: state.clone(),
+            tasks: Vec::new(),
+        };
+        server.chain_id(1337);
+        server.respond("eth_blockNumber", U256::zero());
+        let ids = AtomicU64::new(1);
+        server.handle("eth_subscribe", move |_| {
+            let id = ids.fetch_add(1, Ordering::SeqCst);
+            Ok(json!(U256::from(id)))
+        });
+        server.respond("eth_unsubscribe", true);
+
+        server.tasks = vec![
+            tokio::spawn(accept_http(http, state.clone())),
+            tokio::spawn(accept_ws(ws, state)),
+        ];
+        Ok(server)
+    }
+
+    /// Returns the URL of the HTTP endpoint
+    pub fn http_endpoint(&self) -> String {
+        format!("http://{}", self.http_addr)
+    }
+
+    /// Returns the URL of the Websocket endpoint
+    pub fn ws_endpoint(&self) -> String {
+        format!("ws://{}", self.ws_addr)
+    }
+
+    /// Registers a handler for `method`, replacing any existing one. The handler receives
+    /// the request's params and returns either the result or a JSON-RPC error.
+    pub fn handle<F>(&self, method: &str, handler: F)
+    where
+        F: Fn(Value) -> Result<Value, JsonRpcError> + Send + Sync + 'static,
+    {
+        self.state
+            .handlers
+            .lock()
+            .unwrap()
+            .insert(method.to_owned(), Arc::new(handler));
+    }
+
+    /// Always responds to `method` with the provided value
+    pub fn respond<T: Serialize>(&self, method: &str, value: T) {
+        let value = serde_json::to_value(value).expect("could not serialize response");
+        self.handle(method, move |_| Ok(value.clone()));
+    }
+
+    /// Sets the chain id returned by `eth_chainId` and `net_version`
+    pub fn chain_id(&self, chain_id: u64) {
+        self.respond("eth_chainId", U256::from(chain_id));
+        self.respond("net_version", chain_id.to_string());
+    }
+
+    /// Sends an `eth_subscription` notification to every connected websocket client
+    pub fn notify<T: Into<U256>, R: Serialize>(&self, subscription: T, result: R) {
+        let notification = json!({
+            "jsonrpc": "2.0",
+            "method": "eth_subscription",
+            "params": {
+                "subscription": subscription.into(),
+                "result": result,
+            },
+        });
+        let msg = Message::Text(notification.to_string());
+        self.state
+            .subscribers
+            .lock()
+            .unwrap()
+            .retain(|tx| tx.unbounded_send(msg.clone()).is_ok());
+    }
+
+    /// Closes all websocket connections, e.g. to test reconnection logic
+    pub fn disconnect_ws(&self) {
+        for tx in self.state.subscribers.lock().unwrap().drain(..) {
+            let _ = tx.unbounded_send(Message::Close(None));
+        }
+    }
+
+    /// Returns the `(method, params)` of all requests received so far, in order
+    pub fn requests(&self) -> Vec<(String, Value)> {
+        self.state.requests.lock().unwrap().clone()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+        self.disconnect_ws();
+    }
+}
+
+async fn accept_http(listener: TcpListener, state: Arc<State>) {
+    while let Ok((stream, _)) = listener.accept().await {
+        tokio::spawn(serve_http(stream, state.clone()));
+    }
+}
+
+async fn accept_ws(listener: TcpListener, state: Arc<State>) {
+    while let Ok((stream, _)) = listener.accept().await {
+        tokio::spawn(serve_ws(stream, state.clone()));
+    }
+}
+
+/// Serves the HTTP/1.1 requests of a (possibly kept-alive) connection
+async fn serve_http(mut stream: TcpStream, state: Arc<State>) -> io::Result<()> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    loop {
+        // read until the end of the headers
+        let header_end = loop {
+            if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                break pos + 4;
+            }
+            let n = stream.read(&mut chunk).await?;
+            if n == 0 {
+                return Ok(());
+            }
+            buf.extend_from_slice(&chunk[..n]);
+        };
+
+        let headers = String::from_utf8_lossy(&buf[..header_end]).to_lowercase();
+        let len = headers
+            .lines()
+            .find_map(|line| line.strip_prefix("content-length:"))
+            .and_then(|len| len.trim().parse::<usize>().ok())
+            .unwrap_or(0);
+
+        // read the body
+        while buf.len() < header_end + len {
+            let n = stream.read(&mut chunk).await?;
+            if n == 0 {
+                return Ok(());
+            }
+            buf.extend_from_slice(&chunk[..n]);
+        }
+        let body: Vec<u8> = buf.drain(..header_end + len).skip(header_end).collect();
+
+        let res = serde_json::to_vec(&handle_payload(&state, &body))?;
+        let head = format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n",
+            res.len()
+        );
+        stream.write_all(head.as_bytes()).await?;
+        stream.write_all(&res).await?;
+    }
+}
+
+async fn serve_ws(stream: TcpStream, state: Arc<State>) -> Result<(), tungstenite::Error> {
+    let (mut sink, mut stream) = accept_async(stream).await?.split();
+
+    // responses and notifications are all funneled through the same channel
+    let (tx, mut rx) = mpsc::unbounded::<Message>();
+    state.subscribers.lock().unwrap().push(tx.clone());
+    let writer = tokio::spawn(async move {
+        while let Some(msg) = rx.next().await {
+            let close = msg.is_close();
+            if sink.send(msg).await.is_err() || close {
+                break;
+            }
+        }
+    });
+
+    while let Some(msg) = stream.next().await {
+        match msg? {
+            Message::Text(text) => {
+                let res = handle_payload(&state, text.as_bytes());
+                if tx.unbounded_send(Message::Text(res.to_string())).is_err() {
+                    break;
+                }
+            }
+            Message::Close(_) => break,
+            _ => {}
+        }
+    }
+
+    writer.abort();
+    Ok(())
+}
+
+/// Handles a single or batched JSON-RPC payload
+fn handle_payload(state: &State, payload: &[u8]) -> Value {
+    match serde_json::from_slice::<Value>(payload) {
+        Ok(Value::Array(requests)) => requests
+            .into_iter()
+            .map(|request| dispatch(state, request))
+            .collect(),
+        Ok(request) => dispatch(state, request),
+        Err(err) => json!({
+            "jsonrpc": "2.0",
+            "id": null,
+            "error": { "code": -32700, "message": err.to_string() },
+        }),
+    }
+}
+
+fn dispatch(state: &State, request: Value) -> Value {
+    let id = request.get("id").cloned().unwrap_or(Value::Null);
+    let method = request
+        .get("method")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_owned();
+    let params = request.get("params").cloned().unwrap_or(Value::Null);
+    state
+        .requests
+        .lock()
+        .unwrap()
+        .push((method.clone(), params.clone()));
+
+    // clone the handler so that it can register other handlers without deadlocking
+    let handler = state.handlers.lock().unwrap().get(&method).cloned();
+    let res = match handler {
+        Some(handler) => handler(params),
+        None => Err(JsonRpcError {
+            code: -32601,
+            message: format!("the method {} does not exist/is not available", method),
+            data: None,
+        }),
+    };
+
+    match res {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => json!({ "jsonrpc": "2.0", "id": id, "error": error }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Http, Middleware, Provider, Ws};
+    use ethers_core::types::{TxHash, U64};
+    use std::convert::TryFrom;
+
+    #[tokio::test]
+    async fn serves_http_requests() {
+        let server = MockServer::spawn().await.unwrap();
+        let provider = Provider::<Http>::try_from(server.http_endpoint()).unwrap();
+
+        assert_eq!(provider.get_chainid().await.unwrap(), 1337.into());
+
+        server.respond("eth_blockNumber", U64::from(10));
+        assert_eq!(provider.get_block_number().await.unwrap(), 10.into());
+
+        server.handle("eth_gasPrice", |_| {
+            Err(JsonRpcError {
+                code: -32000,
+                message: "overloaded".to_owned(),
+                data: None,
+            })
+        });
+        provider.get_gas_price().await.unwrap_err();
+
+        let methods = server
+            .requests()
+            .into_iter()
+            .map(|(method, _)| method)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            methods,
+            vec!["eth_chainId", "eth_blockNumber", "eth_gasPrice"]
+        );
+    }
+
+    #[tokio::test]
+    async fn serves_ws_subscriptions() {
+        let server = MockServer::spawn().await.unwrap();
+        let provider = Provider::<Ws>::connect(server.ws_endpoint()).await.unwrap();
+
+        assert_eq!(provider.get_block_number().await.unwrap(), 0.into());
+
+        let mut stream = provider.subscribe_pending_txs().await.unwrap();
+        let hash = TxHash::repeat_byte(1);
+        server.notify(stream.id, hash);
+        assert_eq!(stream.next().await.unwrap(), hash);
+    }
+}
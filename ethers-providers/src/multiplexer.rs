@@ -0,0 +1,201 @@
+use ethers_core::types::{Filter, Log, ValueOrArray};
+use futures_channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use futures_util::{pin_mut, stream::Stream, StreamExt};
+
+/// The id of a consumer registered with a [`LogMultiplexer`]
+pub type ConsumerId = usize;
+
+#[derive(Debug)]
+struct Consumer {
+    id: ConsumerId,
+    filter: Filter,
+    sender: UnboundedSender<Log>,
+}
+
+/// Routes the logs of a single subscription (or filter watcher) to many consumers, each
+/// with its own filter, so that watching many contracts does not require a subscription
+/// per contract.
+///
+/// The multiplexer's [`filter`](LogMultiplexer::filter) merges the consumers' filters, and
+/// each log of the merged stream is sent to every consumer whose filter it matches.
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers::{
+///     providers::{LogMultiplexer, Middleware, Provider, Ws},
+///     types::{Address, Filter},
+/// };
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Provider::new(Ws::connect("ws://localhost:8545").await?);
+///
+/// let mut multiplexer = LogMultiplexer::new();
+/// let (_, transfers) = multiplexer.register(Filter::new().event("Transfer(address,address,uint256)"));
+/// let (_, token_logs) = multiplexer.register(Filter::new().address(Address::repeat_byte(1)));
+/// // hand the receivers over to the consumers' tasks
+///
+/// let stream = provider.subscribe_logs(&multiplexer.filter()).await?;
+/// multiplexer.run(stream).await;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct LogMultiplexer {
+    consumers: Vec<Consumer>,
+    next_id: ConsumerId,
+}
+
+impl LogMultiplexer {
+    /// Creates a multiplexer without any consumers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a consumer of the logs matching `filter`, returning its id and the
+    /// receiving end of its logs. The consumer is unregistered once the receiver is
+    /// dropped. The block range of the filter is ignored.
+    pub fn register(&mut self, filter: Filter) -> (ConsumerId, UnboundedReceiver<Log>) {
+        let (sender, receiver) = unbounded();
+        let id = self.next_id;
+        self.next_id += 1;
+        self.consumers.push(Consumer { id, filter, sender });
+        (id, receiver)
+    }
+
+    /// Unregisters a consumer, returning whether it was registered
+    pub fn unregister(&mut self, id: ConsumerId) -> bool {
+        let len = self.consumers.len();
+        self.consumers.retain(|consumer| consumer.id != id);
+        self.consumers.len() != len
+    }
+
+    /// Returns the number of registered consumers
+    pub fn len(&self) -> usize {
+        self.consumers.len()
+    }
+
+    /// Returns whether there are no registered consumers
+    pub fn is_empty(&self) -> bool {
+        self.consumers.is_empty()
+    }
+
+    /// Returns a filter matching the logs of all consumers, to be used for the multiplexed
+    /// subscription or filter watcher. It may match more logs than the consumers' filters,
+    /// which are discarded when routing.
+    pub fn filter(&self) -> Filter {
+        let mut filter = Filter::new();
+        if let Some(address) = merge(self.consumers.iter().map(|c| c.filter.get_address())) {
+            filter = filter.address(address);
+        }
+        for i in 0..filter.topics.len() {
+            filter.topics[i] = merge(self.consumers.iter().map(|c| c.filter.topics[i].as_ref()));
+        }
+        filter
+    }
+
+    /// Sends the log to every consumer whose filter it matches, returning the number of
+    /// consumers it was sent to. Consumers whose receivers were dropped are unregistered.
+    pub fn route(&mut self, log: &Log) -> usize {
+        let mut routed = 0;
+        self.consumers.retain(|consumer| {
+            if !consumer.filter.matches(log) {
+                return !consumer.sender.is_closed();
+            }
+            let sent = consumer.sender.unbounded_send(log.clone()).is_ok();
+            if sent {
+                routed += 1;
+            }
+            sent
+        });
+        routed
+    }
+
+    /// Routes the logs of the stream to the consumers until the stream ends or all
+    /// consumers are unregistered
+    pub async fn run<S: Stream<Item = Log>>(&mut self, stream: S) {
+        pin_mut!(stream);
+        while let Some(log) = stream.next().await {
+            self.route(&log);
+            if self.is_empty() {
+                break;
+            }
+        }
+    }
+}
+
+/// Merges the filter values of the consumers into a single one, which is `None` (i.e.
+/// matches anything) if any of the consumers does not filter on it
+fn merge<'a, T: 'a + Clone + PartialEq>(
+    values: impl Iterator<Item = Option<&'a ValueOrArray<T>>>,
+) -> Option<ValueOrArray<T>> {
+    let mut merged = Vec::new();
+    for value in values {
+        let items = match value? {
+            ValueOrArray::Value(item) => std::slice::from_ref(item),
+            ValueOrArray::Array(items) => items.as_slice(),
+        };
+        for item in items {
+            if !merged.contains(item) {
+                merged.push(item.clone());
+            }
+        }
+    }
+
+    match merged.len() {
+        0 => None,
+        1 => merged.pop().map(ValueOrArray::Value),
+        _ => Some(ValueOrArray::Array(merged)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers_core::types::{Address, H256};
+
+    fn log(address: Address, topics: Vec<H256>) -> Log {
+        Log {
+            address,
+            topics,
+            data: Default::default(),
+            block_hash: None,
+            block_number: None,
+            transaction_hash: None,
+            transaction_index: None,
+            log_index: None,
+            transaction_log_index: None,
+            log_type: None,
+            removed: None,
+        }
+    }
+
+    #[test]
+    fn routes_logs_to_consumers() {
+        let (a, b) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let (t0, t1) = (H256::repeat_byte(3), H256::repeat_byte(4));
+
+        let mut multiplexer = LogMultiplexer::new();
+        let (_, mut rx_a) = multiplexer.register(Filter::new().address(a).topic0(t0));
+        let (_, mut rx_b) = multiplexer.register(Filter::new().address(b).topic0(t1));
+        let (id, _rx) = multiplexer.register(Filter::new().address(vec![a, b]));
+        assert_eq!(multiplexer.filter(), Filter::new().address(vec![a, b]));
+        assert!(multiplexer.unregister(id));
+        assert_eq!(
+            multiplexer.filter(),
+            Filter::new().address(vec![a, b]).topic0(vec![t0, t1])
+        );
+
+        assert_eq!(multiplexer.route(&log(a, vec![t0])), 1);
+        assert_eq!(multiplexer.route(&log(b, vec![t1])), 1);
+        // matched by the merged filter, but not by any consumer
+        assert_eq!(multiplexer.route(&log(a, vec![t1])), 0);
+        assert_eq!(rx_a.try_next().unwrap(), Some(log(a, vec![t0])));
+        assert_eq!(rx_b.try_next().unwrap(), Some(log(b, vec![t1])));
+        assert!(rx_a.try_next().is_err());
+
+        drop(rx_b);
+        assert_eq!(multiplexer.route(&log(b, vec![t1])), 0);
+        assert_eq!(multiplexer.len(), 1);
+    }
+}
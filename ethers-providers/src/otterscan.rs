@@ -0,0 +1,209 @@
+//! Typed bindings for the [Otterscan](https://github.com/wmitsuda/otterscan) `ots_`
+//! namespace served by Erigon, which queries the history of an address far more
+//! efficiently than scanning logs.
+//!
+//! The bindings are provided by the [`OtterscanExt`] extension trait, which is
+//! implemented for every [`Middleware`] and has to be imported to be used.
+use crate::{Middleware, ProviderError};
+
+use async_trait::async_trait;
+use ethers_core::{
+    types::{Address, Block, Transaction, TransactionReceipt, TxHash, H256, U256, U64},
+    utils,
+};
+use serde::{Deserialize, Serialize};
+
+/// A block with its issuance and fees, as returned by `ots_getBlockDetails`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockDetails {
+    /// The block, without its transactions and logs bloom
+    pub block: OtsBlock,
+    /// The ether issued by the block
+    pub issuance: BlockIssuance,
+    /// The total fees paid by the block's transactions
+    pub total_fees: U256,
+}
+
+/// A block without its transactions, as returned by the `ots_` namespace
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OtsBlock {
+    /// The block, whose transactions are left empty
+    #[serde(flatten)]
+    pub block: Block<TxHash>,
+    /// The number of transactions in the block
+    pub transaction_count: u64,
+}
+
+/// The ether issued by a block
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockIssuance {
+    /// The reward of the block's miner
+    pub block_reward: U256,
+    /// The rewards of the block's uncles
+    pub uncle_reward: U256,
+    /// The total issuance of the block
+    pub issuance: U256,
+}
+
+/// A page of the transactions of an address, as returned by
+/// `ots_searchTransactionsBefore` and `ots_searchTransactionsAfter`
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionsWithReceipts {
+    /// The transactions of the page
+    pub txs: Vec<Transaction>,
+    /// The receipts of the transactions, in the same order
+    pub receipts: Vec<OtsReceipt>,
+    /// Whether the page contains the most recent transactions of the address
+    pub first_page: bool,
+    /// Whether the page contains the oldest transactions of the address
+    pub last_page: bool,
+}
+
+/// A transaction receipt along with the timestamp of its block
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OtsReceipt {
+    /// The receipt
+    #[serde(flatten)]
+    pub receipt: TransactionReceipt,
+    /// The timestamp of the block including the transaction
+    pub timestamp: u64,
+}
+
+/// Typed bindings for the `ots_` namespace, implemented for every [`Middleware`]. They
+/// are sent to the underlying [`Provider`](crate::Provider), which must be connected
+/// to an Erigon node with the namespace enabled.
+#[async_trait]
+pub trait OtterscanExt: Middleware {
+    /// Returns the hash of the transaction sent by `sender` with the given nonce, if any
+    async fn ots_get_transaction_by_sender_and_nonce<T: Into<U64> + Send + Sync>(
+        &self,
+        sender: Address,
+        nonce: T,
+    ) -> Result<Option<H256>, ProviderError> {
+        let sender = utils::serialize(&sender);
+        let nonce = utils::serialize(&nonce.into());
+        self.provider()
+            .request("ots_getTransactionBySenderAndNonce", [sender, nonce])
+            .await
+    }
+
+    /// Returns up to `page_size` transactions of `address`, along with their receipts,
+    /// sent or received before `block`, most recent first. A `block` of 0 starts from the
+    /// most recent transactions.
+    async fn ots_search_transactions_before<T: Into<U64> + Send + Sync>(
+        &self,
+        address: Address,
+        block: T,
+        page_size: u64,
+    ) -> Result<TransactionsWithReceipts, ProviderError> {
+        let address = utils::serialize(&address);
+        let block = utils::serialize(&block.into().as_u64());
+        let page_size = utils::serialize(&page_size);
+        self.provider()
+            .request("ots_searchTransactionsBefore", [address, block, page_size])
+            .await
+    }
+
+    /// Returns up to `page_size` transactions of `address`, along with their receipts,
+    /// sent or received after `block`, most recent first. A `block` of 0 starts from the
+    /// oldest transactions.
+    async fn ots_search_transactions_after<T: Into<U64> + Send + Sync>(
+        &self,
+        address: Address,
+        block: T,
+        page_size: u64,
+    ) -> Result<TransactionsWithReceipts, ProviderError> {
+        let address = utils::serialize(&address);
+        let block = utils::serialize(&block.into().as_u64());
+        let page_size = utils::serialize(&page_size);
+        self.provider()
+            .request("ots_searchTransactionsAfter", [address, block, page_size])
+            .await
+    }
+
+    /// Returns the block with its issuance and total fees, without its transactions
+    async fn ots_get_block_details<T: Into<U64> + Send + Sync>(
+        &self,
+        block: T,
+    ) -> Result<BlockDetails, ProviderError> {
+        let block = utils::serialize(&block.into().as_u64());
+        self.provider()
+            .request("ots_getBlockDetails", [block])
+            .await
+    }
+}
+
+impl<M: Middleware> OtterscanExt for M {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Provider;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn ots_requests() {
+        let (provider, mock) = Provider::mocked();
+
+        mock.push_response("ots_getTransactionBySenderAndNonce", H256::repeat_byte(1))
+            .unwrap();
+        mock.push_response(
+            "ots_getBlockDetails",
+            json!({
+                "block": {
+                    "number": "0xa",
+                    "hash": "0x0101010101010101010101010101010101010101010101010101010101010101",
+                    "logsBloom": null,
+                    "totalDifficulty": "0x1",
+                    "size": "0x200",
+                    "transactionCount": 3
+                },
+                "issuance": {
+                    "blockReward": "0x1bc16d674ec80000",
+                    "uncleReward": "0x0",
+                    "issuance": "0x1bc16d674ec80000"
+                },
+                "totalFees": "0x100"
+            }),
+        )
+        .unwrap();
+        mock.push_response(
+            "ots_searchTransactionsBefore",
+            json!({
+                "txs": [],
+                "receipts": [],
+                "firstPage": true,
+                "lastPage": false
+            }),
+        )
+        .unwrap();
+
+        let sender = Address::repeat_byte(2);
+        let hash = provider
+            .ots_get_transaction_by_sender_and_nonce(sender, 5u64)
+            .await
+            .unwrap();
+        assert_eq!(hash, Some(H256::repeat_byte(1)));
+        mock.assert_request("ots_getTransactionBySenderAndNonce", (sender, U64::from(5)))
+            .unwrap();
+
+        let details = provider.ots_get_block_details(10u64).await.unwrap();
+        assert_eq!(details.block.transaction_count, 3);
+        assert_eq!(details.block.block.number, Some(10.into()));
+        assert_eq!(details.total_fees, 256.into());
+        mock.assert_request("ots_getBlockDetails", [10]).unwrap();
+
+        let page = provider
+            .ots_search_transactions_before(sender, 0u64, 25)
+            .await
+            .unwrap();
+        assert!(page.first_page);
+        assert!(page.txs.is_empty());
+        mock.assert_request("ots_searchTransactionsBefore", (sender, 0, 25))
+            .unwrap();
+    }
+}
@@ -3,8 +3,9 @@ use crate::{
     stream::{interval, DEFAULT_POLL_INTERVAL},
     JsonRpcClient, PinBoxFut, Provider, ProviderError,
 };
-use ethers_core::types::{TransactionReceipt, TxHash, U64};
+use ethers_core::types::{Transaction, TransactionReceipt, TxHash, U64};
 use futures_core::stream::Stream;
+use futures_timer::Delay;
 use futures_util::stream::StreamExt;
 use pin_project::pin_project;
 use std::{
@@ -21,6 +22,14 @@ use std::{
 /// once the transaction has enough `confirmations`. The default number of confirmations
 /// is 1, but may be adjusted with the `confirmations` method. If the transaction does not
 /// have enough confirmations or is not mined, the future will stay in the pending state.
+/// With more than 1 confirmation, the receipt is fetched again once the transaction is
+/// deep enough, and the confirmations are counted again if a reorg moved the transaction
+/// to another block or dropped it.
+///
+/// By default the future polls until the transaction is mined. A
+/// [`timeout`](PendingTransaction::timeout) or a number of
+/// [`retries`](PendingTransaction::retries) bound the wait, after which it resolves to a
+/// [`ProviderError::Timeout`] or [`ProviderError::TxDropped`] error.
 #[pin_project]
 pub struct PendingTransaction<'a, P> {
     tx_hash: TxHash,
@@ -28,6 +37,9 @@ pub struct PendingTransaction<'a, P> {
     provider: &'a Provider<P>,
     state: PendingTxState<'a>,
     interval: Box<dyn Stream<Item = ()> + Send + Unpin>,
+    retries: Option<usize>,
+    retries_left: usize,
+    timeout: Option<Pin<Box<Delay>>>,
 }
 
 impl<'a, P: JsonRpcClient> PendingTransaction<'a, P> {
@@ -40,6 +52,9 @@ impl<'a, P: JsonRpcClient> PendingTransaction<'a, P> {
             provider,
             state: PendingTxState::GettingReceipt(fut),
             interval: Box::new(interval(DEFAULT_POLL_INTERVAL)),
+            retries: None,
+            retries_left: 0,
+            timeout: None,
         }
     }
 
@@ -55,6 +70,21 @@ impl<'a, P: JsonRpcClient> PendingTransaction<'a, P> {
         self.interval = Box::new(interval(duration.into()));
         self
     }
+
+    /// Sets the number of consecutive times the transaction may be missing from the node,
+    /// i.e. neither mined nor in its mempool, before it is considered dropped
+    pub fn retries(mut self, retries: usize) -> Self {
+        self.retries = Some(retries);
+        self.retries_left = retries;
+        self
+    }
+
+    /// Sets the maximum duration to wait for the transaction to be mined and confirmed,
+    /// starting from now
+    pub fn timeout<T: Into<Duration>>(mut self, duration: T) -> Self {
+        self.timeout = Some(Box::pin(Delay::new(duration.into())));
+        self
+    }
 }
 
 impl<'a, P: JsonRpcClient> Future for PendingTransaction<'a, P> {
@@ -63,6 +93,13 @@ impl<'a, P: JsonRpcClient> Future for PendingTransaction<'a, P> {
     fn poll(self: Pin<&mut Self>, ctx: &mut Context) -> Poll<Self::Output> {
         let this = self.project();
 
+        if let Some(timeout) = this.timeout {
+            if timeout.as_mut().poll(ctx).is_ready() {
+                *this.state = PendingTxState::Completed;
+                return Poll::Ready(Err(ProviderError::Timeout(*this.tx_hash)));
+            }
+        }
+
         match this.state {
             PendingTxState::PausedGettingReceipt => {
                 // Wait the polling period so that we do not spam the chain when no
@@ -75,11 +112,29 @@ impl<'a, P: JsonRpcClient> Future for PendingTransaction<'a, P> {
             PendingTxState::GettingReceipt(fut) => {
                 if let Ok(Some(receipt)) = futures_util::ready!(fut.as_mut().poll(ctx)) {
                     *this.state = PendingTxState::CheckingReceipt(Box::new(receipt))
+                } else if this.retries.is_some() {
+                    // check whether the transaction is still known before retrying
+                    let fut = Box::pin(this.provider.get_transaction(*this.tx_hash));
+                    *this.state = PendingTxState::GettingTx(fut)
                 } else {
                     *this.state = PendingTxState::PausedGettingReceipt
                 }
                 ctx.waker().wake_by_ref();
             }
+            PendingTxState::GettingTx(fut) => {
+                match futures_util::ready!(fut.as_mut().poll(ctx)) {
+                    Ok(None) if *this.retries_left == 0 => {
+                        *this.state = PendingTxState::Completed;
+                        return Poll::Ready(Err(ProviderError::TxDropped(*this.tx_hash)));
+                    }
+                    Ok(None) => *this.retries_left -= 1,
+                    // the transaction is still known, only consecutive misses drop it
+                    Ok(Some(_)) => *this.retries_left = this.retries.unwrap_or_default(),
+                    Err(_) => {}
+                }
+                *this.state = PendingTxState::PausedGettingReceipt;
+                ctx.waker().wake_by_ref();
+            }
             PendingTxState::CheckingReceipt(receipt) => {
                 // If we requested more than 1 confirmation, we need to compare the receipt's
                 // block number and the current block
@@ -115,18 +170,38 @@ impl<'a, P: JsonRpcClient> Future for PendingTransaction<'a, P> {
 
                 let current_block = futures_util::ready!(fut.as_mut().poll(ctx))?;
 
-                // if the transaction has at least K confirmations, return the receipt
-                // (subtract 1 since the tx already has 1 conf when it's mined)
+                // if the transaction has at least K confirmations, check that the receipt
+                // is still canonical (subtract 1 since the tx already has 1 conf when it's mined)
                 if current_block > inclusion_block + *this.confirmations - 1 {
-                    let receipt = *receipt.clone();
-                    *this.state = PendingTxState::Completed;
-                    return Poll::Ready(Ok(receipt));
+                    let fut = Box::pin(this.provider.get_transaction_receipt(*this.tx_hash));
+                    *this.state = PendingTxState::RecheckingReceipt(fut, receipt.clone());
+                    ctx.waker().wake_by_ref();
                 } else {
                     tracing::trace!(tx_hash = ?this.tx_hash, "confirmations {}/{}", current_block - inclusion_block + 1, this.confirmations);
                     *this.state = PendingTxState::PausedGettingBlockNumber(receipt.clone());
                     ctx.waker().wake_by_ref();
                 }
             }
+            PendingTxState::RecheckingReceipt(fut, receipt) => {
+                match futures_util::ready!(fut.as_mut().poll(ctx))? {
+                    Some(canonical) if canonical.block_hash == receipt.block_hash => {
+                        *this.state = PendingTxState::Completed;
+                        return Poll::Ready(Ok(canonical));
+                    }
+                    // the transaction was reorged into another block, count its
+                    // confirmations again
+                    Some(reorged) => {
+                        tracing::debug!(tx_hash = ?this.tx_hash, block_hash = ?reorged.block_hash, "transaction reorged");
+                        *this.state = PendingTxState::CheckingReceipt(Box::new(reorged));
+                    }
+                    // the transaction was reorged out, wait for it to be mined again
+                    None => {
+                        tracing::debug!(tx_hash = ?this.tx_hash, "transaction dropped by a reorg");
+                        *this.state = PendingTxState::PausedGettingReceipt;
+                    }
+                }
+                ctx.waker().wake_by_ref();
+            }
             PendingTxState::Completed => {
                 panic!("polled pending transaction future after completion")
             }
@@ -176,12 +251,22 @@ enum PendingTxState<'a> {
     /// Polling the blockchain for the receipt
     GettingReceipt(PinBoxFut<'a, Option<TransactionReceipt>>),
 
+    /// Checking whether the node still knows the transaction, which has no receipt yet
+    GettingTx(PinBoxFut<'a, Option<Transaction>>),
+
     /// Waiting for interval to elapse before calling API again
     PausedGettingBlockNumber(Box<TransactionReceipt>),
 
     /// Polling the blockchain for the current block number
     GettingBlockNumber(PinBoxFut<'a, U64>, Box<TransactionReceipt>),
 
+    /// Fetching the receipt again once the tx has enough confirmations, to check that it
+    /// was not reorged
+    RecheckingReceipt(
+        PinBoxFut<'a, Option<TransactionReceipt>>,
+        Box<TransactionReceipt>,
+    ),
+
     /// If the pending tx required only 1 conf, it will return early. Otherwise it will
     /// proceed to the next state which will poll the block number until there have been
     /// enough confirmations
@@ -196,8 +281,10 @@ impl<'a> fmt::Debug for PendingTxState<'a> {
         let state = match self {
             PendingTxState::GettingReceipt(_) => "GettingReceipt",
             PendingTxState::PausedGettingReceipt => "PausedGettingReceipt",
+            PendingTxState::GettingTx(_) => "GettingTx",
             PendingTxState::GettingBlockNumber(_, _) => "GettingBlockNumber",
             PendingTxState::PausedGettingBlockNumber(_) => "PausedGettingBlockNumber",
+            PendingTxState::RecheckingReceipt(_, _) => "RecheckingReceipt",
             PendingTxState::CheckingReceipt(_) => "CheckingReceipt",
             PendingTxState::Completed => "Completed",
         };
@@ -207,3 +294,95 @@ impl<'a> fmt::Debug for PendingTxState<'a> {
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers_core::types::H256;
+
+    #[tokio::test]
+    async fn waits_for_canonical_confirmations() {
+        let (provider, mock) = Provider::mocked();
+        let receipt = |block: u64, hash: u8| TransactionReceipt {
+            block_number: Some(block.into()),
+            block_hash: Some(H256::repeat_byte(hash)),
+            ..Default::default()
+        };
+        // mined in block 10, which is reorged by the time block 12 is mined
+        mock.push_response::<TransactionReceipt, _>("eth_getTransactionReceipt", receipt(10, 1))
+            .unwrap();
+        mock.push_response::<TransactionReceipt, _>("eth_getTransactionReceipt", receipt(11, 2))
+            .unwrap();
+        // the confirmations of the new block are counted again
+        mock.push_response::<TransactionReceipt, _>("eth_getTransactionReceipt", receipt(11, 2))
+            .unwrap();
+        for block in &[11u64, 12, 12, 13] {
+            mock.push_response::<U64, _>("eth_blockNumber", U64::from(*block))
+                .unwrap();
+        }
+
+        let mined = PendingTransaction::new(TxHash::zero(), &provider)
+            .confirmations(2)
+            .interval(Duration::from_millis(1))
+            .await
+            .unwrap();
+        assert_eq!(mined, receipt(11, 2));
+        assert_eq!(mock.call_count("eth_getTransactionReceipt"), 3);
+    }
+
+    #[tokio::test]
+    async fn gives_up_on_dropped_transactions() {
+        let (provider, mock) = Provider::mocked();
+        for _ in 0..2 {
+            mock.push_response::<Option<TransactionReceipt>, _>("eth_getTransactionReceipt", None)
+                .unwrap();
+            mock.push_response::<Option<Transaction>, _>("eth_getTransactionByHash", None)
+                .unwrap();
+        }
+
+        let err = PendingTransaction::new(TxHash::zero(), &provider)
+            .retries(1)
+            .interval(Duration::from_millis(1))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ProviderError::TxDropped(hash) if hash == TxHash::zero()));
+    }
+
+    #[tokio::test]
+    async fn resets_retries_when_the_transaction_is_seen() {
+        let (provider, mock) = Provider::mocked();
+        for tx in [None, Some(Transaction::default()), None] {
+            mock.push_response::<Option<TransactionReceipt>, _>("eth_getTransactionReceipt", None)
+                .unwrap();
+            mock.push_response::<Option<Transaction>, _>("eth_getTransactionByHash", tx)
+                .unwrap();
+        }
+        let receipt = TransactionReceipt {
+            block_number: Some(10.into()),
+            ..Default::default()
+        };
+        mock.push_response("eth_getTransactionReceipt", receipt.clone())
+            .unwrap();
+
+        // the transaction was missing twice, but not in a row
+        let mined = PendingTransaction::new(TxHash::zero(), &provider)
+            .retries(1)
+            .interval(Duration::from_millis(1))
+            .await
+            .unwrap();
+        assert_eq!(mined, receipt);
+        assert_eq!(mock.call_count("eth_getTransactionByHash"), 3);
+    }
+
+    #[tokio::test]
+    async fn times_out() {
+        // the node never responds with a receipt
+        let (provider, _mock) = Provider::mocked();
+        let err = PendingTransaction::new(TxHash::zero(), &provider)
+            .interval(Duration::from_millis(1))
+            .timeout(Duration::from_millis(20))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ProviderError::Timeout(_)));
+    }
+}
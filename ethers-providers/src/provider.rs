@@ -1,24 +1,33 @@
 use crate::{
+    batch::BatchRequest,
+    ccip::{OffchainLookup, MAX_CCIP_REDIRECTS},
     ens,
     pubsub::{PubsubClient, SubscriptionStream},
     stream::{FilterWatcher, DEFAULT_POLL_INTERVAL},
-    FromErr, Http as HttpProvider, JsonRpcClient, MockProvider, PendingTransaction,
+    FromErr, Http as HttpProvider, JsonRpcClient, JsonRpcError, MockProvider, PendingTransaction,
+    RetryClient,
 };
 
 use ethers_core::{
     abi::{self, Detokenize, ParamType},
     types::{
-        Address, Block, BlockId, BlockNumber, BlockTrace, Bytes, Filter, Log, NameOrAddress,
-        Selector, Signature, Trace, TraceFilter, TraceType, Transaction, TransactionReceipt,
-        TransactionRequest, TxHash, TxpoolContent, TxpoolInspect, TxpoolStatus, H256, U256, U64,
+        AccessListWithGasUsed, Account, Address, BadBlock, Block, BlockId, BlockNumber,
+        BlockOverrides, BlockTrace, Bytes, EIP1186ProofResponse, FeeHistory, FeeSuggestion, Filter,
+        GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, Log, NameOrAddress,
+        Priority, PrivateTransaction, Selector, Signature, SignedTransaction, SimulatePayload,
+        SimulatedBlock, StateOverride, StorageRange, SyncingStatus, Trace, TraceFilter, TraceType,
+        Transaction, TransactionReceipt, TransactionRequest, TxHash, TxpoolContent, TxpoolInspect,
+        TxpoolStatus, TypedTransaction, UserOperation, UserOperationByHash,
+        UserOperationGasEstimation, UserOperationReceipt, Work, H256, H64, U256, U64,
     },
     utils,
 };
 
 use crate::Middleware;
 use async_trait::async_trait;
+use futures_util::future::try_join_all;
 use hex::FromHex;
-use serde::{de::DeserializeOwned, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use thiserror::Error;
 use url::{ParseError, Url};
 
@@ -48,15 +57,133 @@ use tracing_futures::Instrument;
 /// # }
 /// ```
 #[derive(Clone, Debug)]
-// TODO: Convert to proper struct
-pub struct Provider<P>(P, Option<Address>, Option<Duration>, Option<Address>);
+pub struct Provider<P> {
+    inner: P,
+    ens: Option<Address>,
+    interval: Option<Duration>,
+    from: Option<Address>,
+    strict: bool,
+    chain_id: Option<U256>,
+}
 
 impl<P> AsRef<P> for Provider<P> {
     fn as_ref(&self) -> &P {
-        &self.0
+        &self.inner
+    }
+}
+
+/// Configures a [`Provider`] in a single fluent chain, e.g. when the options are read
+/// from a config file.
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers::{
+///     providers::{Http, ProviderBuilder, RateLimitRetryPolicy},
+///     types::Address,
+/// };
+/// use std::{str::FromStr, time::Duration};
+///
+/// # fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = ProviderBuilder::new(Http::from_str("http://localhost:8545")?)
+///     .interval(Duration::from_millis(500))
+///     .sender(Address::random())
+///     .chain_id(1u64)
+///     .retry(RateLimitRetryPolicy)
+///     .build();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+#[must_use = "builders do nothing unless built"]
+pub struct ProviderBuilder<P> {
+    inner: P,
+    ens: Option<Address>,
+    interval: Option<Duration>,
+    from: Option<Address>,
+    strict: bool,
+    chain_id: Option<U256>,
+}
+
+impl<P> ProviderBuilder<P> {
+    /// Starts configuring a provider over the transport
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            ens: None,
+            interval: None,
+            from: None,
+            strict: false,
+            chain_id: None,
+        }
+    }
+
+    /// Sets the ENS Address (default: mainnet)
+    pub fn ens<T: Into<Address>>(mut self, ens: T) -> Self {
+        self.ens = Some(ens.into());
+        self
+    }
+
+    /// Sets the polling interval for event filters and pending transactions (default: 7
+    /// seconds)
+    pub fn interval<T: Into<Duration>>(mut self, interval: T) -> Self {
+        self.interval = Some(interval.into());
+        self
+    }
+
+    /// Sets the default sender of transactions and calls
+    pub fn sender(mut self, address: impl Into<Address>) -> Self {
+        self.from = Some(address.into());
+        self
+    }
+
+    /// Enables the validation of responses, see [`Provider::strict`]
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Sets the chain id, which is then returned by `get_chainid` without querying the
+    /// node
+    pub fn chain_id<T: Into<U256>>(mut self, chain_id: T) -> Self {
+        self.chain_id = Some(chain_id.into());
+        self
+    }
+
+    /// Retries the requests which fail with the errors classified as transient by the
+    /// policy, with the default settings of [`RetryClient`]
+    pub fn retry<R>(self, policy: R) -> ProviderBuilder<RetryClient<P, R>> {
+        self.map_transport(|inner| RetryClient::new(inner, policy))
+    }
+
+    /// Wraps the transport, e.g. in a [`RetryClient`] with custom settings
+    pub fn map_transport<T>(self, f: impl FnOnce(P) -> T) -> ProviderBuilder<T> {
+        ProviderBuilder {
+            inner: f(self.inner),
+            ens: self.ens,
+            interval: self.interval,
+            from: self.from,
+            strict: self.strict,
+            chain_id: self.chain_id,
+        }
+    }
+
+    /// Builds the provider
+    pub fn build(self) -> Provider<P> {
+        Provider {
+            inner: self.inner,
+            ens: self.ens,
+            interval: self.interval,
+            from: self.from,
+            strict: self.strict,
+            chain_id: self.chain_id,
+        }
     }
 }
 
+/// The number of recent blocks whose fee history is used to suggest fees
+pub const FEE_HISTORY_BLOCKS: u64 = 10;
+
 impl FromErr<ProviderError> for ProviderError {
     fn from(src: ProviderError) -> Self {
         src
@@ -74,6 +201,32 @@ pub enum ProviderError {
     #[error("ens name not found: {0}")]
     EnsError(String),
 
+    /// The ENS name cannot be normalized, e.g. because it contains disallowed characters
+    #[error("invalid ens name: {0}")]
+    EnsInvalidName(String),
+
+    /// The NFT of an ENS avatar is not owned by the address the name resolves to
+    #[error("the avatar NFT of {0} is not owned by its address")]
+    EnsNotOwned(String),
+
+    /// An error in an HTTP request made outside of the JSON RPC client, e.g. to fetch the
+    /// metadata of an NFT
+    #[error(transparent)]
+    HttpError(#[from] reqwest::Error),
+
+    /// The transaction awaited by a [`PendingTransaction`] is neither mined nor known by
+    /// the node anymore
+    #[error("transaction {0:?} was dropped")]
+    TxDropped(TxHash),
+
+    /// A [`PendingTransaction`] was not mined and confirmed before its timeout
+    #[error("timed out waiting for transaction {0:?}")]
+    Timeout(TxHash),
+
+    /// An offchain lookup requested by a contract through CCIP-Read failed
+    #[error("CCIP-Read failed: {0}")]
+    CcipError(String),
+
     #[error(transparent)]
     SerdeJson(#[from] serde_json::Error),
 
@@ -82,6 +235,56 @@ pub enum ProviderError {
 
     #[error("custom error: {0}")]
     CustomError(String),
+
+    /// A response of the node violated an invariant, see [`Provider::strict`]
+    #[error(transparent)]
+    ValidationError(#[from] ValidationError),
+}
+
+/// An inconsistent response, returned by a buggy or malicious node
+#[derive(Debug, Error)]
+pub enum ValidationError {
+    /// The transaction does not have the requested hash
+    #[error("requested transaction {expected:?}, got {actual:?}")]
+    TransactionHashMismatch { expected: TxHash, actual: TxHash },
+
+    /// The receipt is not for the requested transaction
+    #[error("requested the receipt of transaction {expected:?}, got {actual:?}")]
+    ReceiptHashMismatch { expected: TxHash, actual: TxHash },
+
+    /// The receipt of a mined transaction has no block hash
+    #[error("the receipt of transaction {0:?} has no block hash")]
+    MissingBlockHash(TxHash),
+
+    /// The block does not have the requested hash or number
+    #[error("requested block {expected:?}, got block {number:?} with hash {hash:?}")]
+    BlockMismatch {
+        expected: BlockId,
+        number: Option<U64>,
+        hash: Option<H256>,
+    },
+
+    /// A stream of blocks went back to a lower block number
+    #[error("block {current} was streamed after block {previous}")]
+    NonMonotonicBlock { previous: U64, current: U64 },
+}
+
+impl ProviderError {
+    /// Returns the JSON-RPC error response of the node, e.g. to inspect why a call reverted
+    pub fn as_error_response(&self) -> Option<&JsonRpcError> {
+        match self {
+            ProviderError::JsonRpcClientError(err) => {
+                crate::transports::as_error_response(err.as_ref())
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns whether the node does not support the requested method
+    pub fn is_method_not_found(&self) -> bool {
+        self.as_error_response()
+            .map_or(false, JsonRpcError::is_method_not_found)
+    }
 }
 
 /// Types of filters supported by the JSON-RPC.
@@ -101,15 +304,21 @@ pub enum FilterKind<'a> {
 impl<P: JsonRpcClient> Provider<P> {
     /// Instantiate a new provider with a backend.
     pub fn new(provider: P) -> Self {
-        Self(provider, None, None, None)
+        ProviderBuilder::new(provider).build()
     }
 
+    /// Returns a builder configuring a provider over the transport
+    pub fn builder(provider: P) -> ProviderBuilder<P> {
+        ProviderBuilder::new(provider)
+    }
+
+    /// Sets the default sender of transactions and calls
     pub fn with_sender(mut self, address: impl Into<Address>) -> Self {
-        self.3 = Some(address.into());
+        self.from = Some(address.into());
         self
     }
 
-    async fn request<T, R>(&self, method: &str, params: T) -> Result<R, ProviderError>
+    pub(crate) async fn request<T, R>(&self, method: &str, params: T) -> Result<R, ProviderError>
     where
         T: Debug + Serialize + Send + Sync,
         R: Serialize + DeserializeOwned + Debug,
@@ -119,7 +328,11 @@ impl<P: JsonRpcClient> Provider<P> {
         // https://docs.rs/tracing/0.1.22/tracing/span/struct.Span.html#in-asynchronous-code
         let res = async move {
             trace!("tx");
-            let res: R = self.0.request(method, params).await.map_err(Into::into)?;
+            let res: R = self
+                .inner
+                .request(method, params)
+                .await
+                .map_err(Into::into)?;
             trace!(rx = ?serde_json::to_string(&res)?);
             Ok::<_, ProviderError>(res)
         }
@@ -135,8 +348,8 @@ impl<P: JsonRpcClient> Provider<P> {
     ) -> Result<Option<Block<Tx>>, ProviderError> {
         let include_txs = utils::serialize(&include_txs);
 
-        Ok(match id {
-            BlockId::Hash(hash) => {
+        let block: Option<Block<Tx>> = match id {
+            BlockId::Hash(hash) | BlockId::CanonicalHash(hash) => {
                 let hash = utils::serialize(&hash);
                 self.request("eth_getBlockByHash", [hash, include_txs])
                     .await?
@@ -146,7 +359,24 @@ impl<P: JsonRpcClient> Provider<P> {
                 self.request("eth_getBlockByNumber", [num, include_txs])
                     .await?
             }
-        })
+        };
+
+        if let (true, Some(block)) = (self.is_strict(), &block) {
+            let matches = match id {
+                BlockId::Hash(hash) | BlockId::CanonicalHash(hash) => block.hash == Some(hash),
+                BlockId::Number(BlockNumber::Number(num)) => block.number == Some(num),
+                BlockId::Number(_) => true,
+            };
+            if !matches {
+                return Err(ValidationError::BlockMismatch {
+                    expected: id,
+                    number: block.number,
+                    hash: block.hash,
+                }
+                .into());
+            }
+        }
+        Ok(block)
     }
 }
 
@@ -164,6 +394,10 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         self
     }
 
+    fn as_error_response<'a>(&self, err: &'a ProviderError) -> Option<&'a JsonRpcError> {
+        err.as_error_response()
+    }
+
     ////// Blockchain Status
     //
     // Functions for querying the state of the blockchain
@@ -181,6 +415,30 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         self.get_block_gen(block_hash_or_number.into(), false).await
     }
 
+    /// Gets the header of the block at `block_hash_or_number` with Geth's
+    /// `eth_getHeaderByNumber` / `eth_getHeaderByHash`, falling back to the block
+    /// without transactions if the node does not support them
+    async fn get_header<T: Into<BlockId> + Send + Sync>(
+        &self,
+        block_hash_or_number: T,
+    ) -> Result<Option<Block<TxHash>>, Self::Error> {
+        let id = block_hash_or_number.into();
+        let header = match id {
+            BlockId::Hash(hash) | BlockId::CanonicalHash(hash) => {
+                let hash = utils::serialize(&hash);
+                self.request("eth_getHeaderByHash", [hash]).await
+            }
+            BlockId::Number(num) => {
+                let num = utils::serialize(&num);
+                self.request("eth_getHeaderByNumber", [num]).await
+            }
+        };
+        match header {
+            Err(err) if err.is_method_not_found() => self.get_block_gen(id, false).await,
+            header => header,
+        }
+    }
+
     /// Gets the block at `block_hash_or_number` (full transactions included)
     async fn get_block_with_txs<T: Into<BlockId> + Send + Sync>(
         &self,
@@ -195,7 +453,17 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         transaction_hash: T,
     ) -> Result<Option<Transaction>, ProviderError> {
         let hash = transaction_hash.into();
-        self.request("eth_getTransactionByHash", [hash]).await
+        let tx: Option<Transaction> = self.request("eth_getTransactionByHash", [hash]).await?;
+        if let (true, Some(tx)) = (self.is_strict(), &tx) {
+            if tx.hash != hash {
+                return Err(ValidationError::TransactionHashMismatch {
+                    expected: hash,
+                    actual: tx.hash,
+                }
+                .into());
+            }
+        }
+        Ok(tx)
     }
 
     /// Gets the transaction receipt with `transaction_hash`
@@ -204,7 +472,52 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         transaction_hash: T,
     ) -> Result<Option<TransactionReceipt>, ProviderError> {
         let hash = transaction_hash.into();
-        self.request("eth_getTransactionReceipt", [hash]).await
+        let receipt: Option<TransactionReceipt> =
+            self.request("eth_getTransactionReceipt", [hash]).await?;
+        if let (true, Some(receipt)) = (self.is_strict(), &receipt) {
+            if receipt.transaction_hash != hash {
+                return Err(ValidationError::ReceiptHashMismatch {
+                    expected: hash,
+                    actual: receipt.transaction_hash,
+                }
+                .into());
+            }
+            if receipt.block_hash.is_none() {
+                return Err(ValidationError::MissingBlockHash(hash).into());
+            }
+        }
+        Ok(receipt)
+    }
+
+    /// Returns all the receipts of the block with `eth_getBlockReceipts`. If the node
+    /// does not support it, falls back to `parity_getBlockReceipts` and then to
+    /// requesting the receipts of the block's transactions concurrently. The fallback
+    /// returns no receipts if the block does not exist.
+    async fn get_block_receipts<T: Into<BlockNumber> + Send + Sync>(
+        &self,
+        block: T,
+    ) -> Result<Vec<TransactionReceipt>, ProviderError> {
+        let block = block.into();
+        match self.request("eth_getBlockReceipts", [block]).await {
+            Err(err) if err.is_method_not_found() => {}
+            res => return res,
+        }
+        match self.parity_block_receipts(block).await {
+            Err(err) if err.is_method_not_found() => {}
+            res => return res,
+        }
+
+        let hashes = match self.get_block(block).await? {
+            Some(block) => block.transactions,
+            None => return Ok(Vec::new()),
+        };
+        let receipts = try_join_all(hashes.into_iter().map(|hash| async move {
+            self.get_transaction_receipt(hash).await?.ok_or_else(|| {
+                ProviderError::CustomError(format!("missing receipt of transaction {:?}", hash))
+            })
+        }))
+        .await?;
+        Ok(receipts)
     }
 
     /// Gets the current gas price as estimated by the node
@@ -212,6 +525,47 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         self.request("eth_gasPrice", ()).await
     }
 
+    /// Returns the base fees and the priority fees paid at the given percentiles for
+    /// `block_count` blocks ending at `last_block`
+    async fn fee_history<T: Into<U256> + Send + Sync>(
+        &self,
+        block_count: T,
+        last_block: BlockNumber,
+        reward_percentiles: &[f64],
+    ) -> Result<FeeHistory, ProviderError> {
+        let block_count = utils::serialize(&block_count.into());
+        let last_block = utils::serialize(&last_block);
+        let reward_percentiles = utils::serialize(&reward_percentiles);
+        self.request(
+            "eth_feeHistory",
+            [block_count, last_block, reward_percentiles],
+        )
+        .await
+    }
+
+    /// Suggests the fees of a transaction with the given priority. If the latest block has
+    /// a base fee, the fees are derived from the fee history of the last
+    /// [`FEE_HISTORY_BLOCKS`] blocks, otherwise the node's gas price is returned.
+    async fn suggest_fees(&self, priority: Priority) -> Result<FeeSuggestion, ProviderError> {
+        let block = self
+            .get_block(BlockNumber::Latest)
+            .await?
+            .ok_or_else(|| ProviderError::CustomError("latest block not found".to_owned()))?;
+        if block.base_fee_per_gas.is_none() {
+            let gas_price = self.get_gas_price().await?;
+            return Ok(FeeSuggestion::Legacy { gas_price });
+        }
+
+        let history = self
+            .fee_history(
+                FEE_HISTORY_BLOCKS,
+                BlockNumber::Latest,
+                &[priority.reward_percentile()],
+            )
+            .await?;
+        Ok(FeeSuggestion::from_fee_history(&history, priority))
+    }
+
     /// Gets the accounts on the node
     async fn get_accounts(&self) -> Result<Vec<Address>, ProviderError> {
         self.request("eth_accounts", ()).await
@@ -252,9 +606,92 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
     /// Returns the currently configured chain id, a value used in replay-protected
     /// transaction signing as introduced by EIP-155.
     async fn get_chainid(&self) -> Result<U256, ProviderError> {
+        if let Some(chain_id) = self.chain_id {
+            return Ok(chain_id);
+        }
         self.request("eth_chainId", ()).await
     }
 
+    /// Returns the sync status of the node, i.e. whether it is still catching up with
+    /// the chain and its progress if so
+    async fn syncing(&self) -> Result<SyncingStatus, ProviderError> {
+        self.request("eth_syncing", ()).await
+    }
+
+    /// Returns the network id of the node as a decimal string. It usually matches the
+    /// chain id, but may differ on private networks.
+    async fn net_version(&self) -> Result<String, ProviderError> {
+        self.request("net_version", ()).await
+    }
+
+    /// Returns the number of peers connected to the node
+    async fn peer_count(&self) -> Result<U64, ProviderError> {
+        self.request("net_peerCount", ()).await
+    }
+
+    /// Returns the name and version of the node's software, e.g.
+    /// `Geth/v1.10.8-stable/linux-amd64/go1.16.7`
+    async fn client_version(&self) -> Result<String, ProviderError> {
+        self.request("web3_clientVersion", ()).await
+    }
+
+    /// Hashes the data with Keccak-256 on the node. Prefer
+    /// [`keccak256`](ethers_core::utils::keccak256) which does not need a round trip.
+    async fn web3_sha3<T: Into<Bytes> + Send + Sync>(
+        &self,
+        data: T,
+    ) -> Result<H256, ProviderError> {
+        let data = utils::serialize(&data.into());
+        self.request("web3_sha3", [data]).await
+    }
+
+    ////// Mining
+    // These methods are only relevant for proof-of-work chains, e.g. private networks
+
+    /// Returns true if the node is mining
+    async fn mining(&self) -> Result<bool, ProviderError> {
+        self.request("eth_mining", ()).await
+    }
+
+    /// Returns the number of hashes per second the node is mining with
+    async fn hashrate(&self) -> Result<U256, ProviderError> {
+        self.request("eth_hashrate", ()).await
+    }
+
+    /// Returns the address receiving the rewards of the blocks mined by the node
+    async fn coinbase(&self) -> Result<Address, ProviderError> {
+        self.request("eth_coinbase", ()).await
+    }
+
+    /// Returns the proof-of-work package of the block being mined, to be solved by an
+    /// external miner
+    async fn get_work(&self) -> Result<Work, ProviderError> {
+        self.request("eth_getWork", ()).await
+    }
+
+    /// Submits the nonce and mix digest solving the proof-of-work package `pow_hash`,
+    /// returning whether the solution was accepted
+    async fn submit_work(
+        &self,
+        nonce: H64,
+        pow_hash: H256,
+        mix_digest: H256,
+    ) -> Result<bool, ProviderError> {
+        let nonce = utils::serialize(&nonce);
+        let pow_hash = utils::serialize(&pow_hash);
+        let mix_digest = utils::serialize(&mix_digest);
+        self.request("eth_submitWork", [nonce, pow_hash, mix_digest])
+            .await
+    }
+
+    /// Submits the hashrate of the external miner identified by the random `id`,
+    /// returning whether it was accepted
+    async fn submit_hashrate(&self, hashrate: U256, id: H256) -> Result<bool, ProviderError> {
+        let hashrate = utils::serialize(&hashrate);
+        let id = utils::serialize(&id);
+        self.request("eth_submitHashrate", [hashrate, id]).await
+    }
+
     ////// Contract Execution
     //
     // These are relatively low-level calls. The Contracts API should usually be used instead.
@@ -263,7 +700,7 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
     /// This is free, since it does not change any state on the blockchain.
     async fn call(
         &self,
-        tx: &TransactionRequest,
+        tx: &TypedTransaction,
         block: Option<BlockId>,
     ) -> Result<Bytes, ProviderError> {
         let tx = utils::serialize(tx);
@@ -271,36 +708,132 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         self.request("eth_call", [tx, block]).await
     }
 
+    /// Sends the read-only (constant) transaction to a single Ethereum node and return the
+    /// result (as bytes) of executing it, after applying the account overrides to the state
+    /// at `block`. This allows simulating the call with modified balances, nonces, code or
+    /// storage, and is supported by Geth and Erigon.
+    async fn call_with_state_overrides(
+        &self,
+        tx: &TypedTransaction,
+        block: Option<BlockId>,
+        overrides: &StateOverride,
+    ) -> Result<Bytes, ProviderError> {
+        let tx = utils::serialize(tx);
+        let block = utils::serialize(&block.unwrap_or_else(|| BlockNumber::Latest.into()));
+        let overrides = utils::serialize(overrides);
+        self.request("eth_call", [tx, block, overrides]).await
+    }
+
+    /// Like [`call_with_state_overrides`](Self::call_with_state_overrides), but also
+    /// overrides the context of the block the call is executed in, e.g. its timestamp to
+    /// simulate time-dependent logic in the future. Requires Geth.
+    async fn call_with_overrides(
+        &self,
+        tx: &TypedTransaction,
+        block: Option<BlockId>,
+        state_overrides: &StateOverride,
+        block_overrides: &BlockOverrides,
+    ) -> Result<Bytes, ProviderError> {
+        let tx = utils::serialize(tx);
+        let block = utils::serialize(&block.unwrap_or_else(|| BlockNumber::Latest.into()));
+        let state_overrides = utils::serialize(state_overrides);
+        let block_overrides = utils::serialize(block_overrides);
+        self.request("eth_call", [tx, block, state_overrides, block_overrides])
+            .await
+    }
+
+    async fn simulate(
+        &self,
+        payload: &SimulatePayload,
+        block: Option<BlockId>,
+    ) -> Result<Vec<SimulatedBlock>, ProviderError> {
+        let payload = utils::serialize(payload);
+        let block = utils::serialize(&block.unwrap_or_else(|| BlockNumber::Latest.into()));
+        self.request("eth_simulateV1", [payload, block]).await
+    }
+
+    /// Returns the addresses and storage keys the transaction accesses when executed at
+    /// `block` (defaults to the latest block), and the gas it uses when sent with them
+    /// as the access list of an EIP-2930 transaction
+    async fn create_access_list(
+        &self,
+        tx: &TypedTransaction,
+        block: Option<BlockId>,
+    ) -> Result<AccessListWithGasUsed, ProviderError> {
+        let tx = utils::serialize(tx);
+        let block = utils::serialize(&block.unwrap_or_else(|| BlockNumber::Latest.into()));
+        self.request("eth_createAccessList", [tx, block]).await
+    }
+
     /// Sends a transaction to a single Ethereum node and return the estimated amount of gas required (as a U256) to send it
     /// This is free, but only an estimate. Providing too little gas will result in a transaction being rejected
     /// (while still consuming all provided gas).
-    async fn estimate_gas(&self, tx: &TransactionRequest) -> Result<U256, ProviderError> {
+    async fn estimate_gas(&self, tx: &TypedTransaction) -> Result<U256, ProviderError> {
         self.request("eth_estimateGas", [tx]).await
     }
 
-    /// Sends the transaction to the entire Ethereum network and returns the transaction's hash
-    /// This will consume gas from the account that signed the transaction.
-    async fn send_transaction(
+    /// Fills the sender, gas and fees of the transaction if they are not set, and
+    /// resolves its recipient if it is an ENS name. The fees of EIP-1559 transactions are
+    /// suggested with [`Priority::Standard`], whereas the gas price of other transactions
+    /// is left to the node.
+    async fn fill_transaction(
         &self,
-        mut tx: TransactionRequest,
+        tx: &mut TypedTransaction,
         _: Option<BlockId>,
-    ) -> Result<PendingTransaction<'_, P>, ProviderError> {
-        if tx.from.is_none() {
-            tx.from = self.3;
-        }
-
-        if tx.gas.is_none() {
-            tx.gas = Some(self.estimate_gas(&tx).await?);
+    ) -> Result<(), ProviderError> {
+        if tx.from().is_none() {
+            if let Some(from) = self.from {
+                tx.set_from(from);
+            }
         }
 
-        if let Some(NameOrAddress::Name(ref ens_name)) = tx.to {
+        if let Some(NameOrAddress::Name(ens_name)) = tx.to().cloned() {
             // resolve to an address
             let addr = self.resolve_name(&ens_name).await?;
+            tx.set_to(addr);
+        }
 
-            // set the value
-            tx.to = Some(addr.into())
+        if tx.gas().is_none() {
+            let gas = self.estimate_gas(tx).await?;
+            tx.set_gas(gas);
         }
 
+        if let TypedTransaction::Eip1559(tx) = tx {
+            if tx.max_fee_per_gas.is_none() || tx.max_priority_fee_per_gas.is_none() {
+                match self.suggest_fees(Priority::Standard).await? {
+                    FeeSuggestion::Eip1559 {
+                        max_fee_per_gas,
+                        max_priority_fee_per_gas,
+                    } => {
+                        let max_fee_per_gas = tx.max_fee_per_gas.unwrap_or(max_fee_per_gas);
+                        tx.max_fee_per_gas = Some(max_fee_per_gas);
+                        // the tip can never exceed the max fee set by the caller
+                        tx.max_priority_fee_per_gas = tx
+                            .max_priority_fee_per_gas
+                            .or_else(|| Some(max_priority_fee_per_gas.min(max_fee_per_gas)));
+                    }
+                    FeeSuggestion::Legacy { .. } => {
+                        return Err(ProviderError::CustomError(
+                            "the chain does not support EIP-1559 transactions".to_owned(),
+                        ))
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sends the transaction to the entire Ethereum network and returns the transaction's hash
+    /// This will consume gas from the account that signed the transaction.
+    async fn send_transaction<T: Into<TypedTransaction> + Send + Sync>(
+        &self,
+        tx: T,
+        block: Option<BlockId>,
+    ) -> Result<PendingTransaction<'_, P>, ProviderError> {
+        let mut tx = tx.into();
+        self.fill_transaction(&mut tx, block).await?;
+
         let tx_hash = self.request("eth_sendTransaction", [tx]).await?;
 
         Ok(PendingTransaction::new(tx_hash, self).interval(self.get_interval()))
@@ -312,15 +845,32 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         &'a self,
         tx: &Transaction,
     ) -> Result<PendingTransaction<'a, P>, ProviderError> {
-        let rlp = utils::serialize(&tx.rlp());
+        self.send_raw_transaction_bytes(tx.rlp()).await
+    }
+
+    /// Broadcasts an RLP encoded signed transaction
+    async fn send_raw_transaction_bytes<'a>(
+        &'a self,
+        rlp: Bytes,
+    ) -> Result<PendingTransaction<'a, P>, ProviderError> {
+        let rlp = utils::serialize(&rlp);
         let tx_hash = self.request("eth_sendRawTransaction", [rlp]).await?;
         Ok(PendingTransaction::new(tx_hash, self).interval(self.get_interval()))
     }
 
+    async fn send_private_transaction<'a>(
+        &'a self,
+        tx: &PrivateTransaction,
+    ) -> Result<PendingTransaction<'a, P>, ProviderError> {
+        let tx = utils::serialize(tx);
+        let tx_hash = self.request("eth_sendPrivateTransaction", [tx]).await?;
+        Ok(PendingTransaction::new(tx_hash, self).interval(self.get_interval()))
+    }
+
     /// The JSON-RPC provider is at the bottom-most position in the middleware stack. Here we check
     /// if it has the key for the sender address unlocked, as well as supports the `eth_sign` call.
     async fn is_signer(&self) -> bool {
-        match self.3 {
+        match self.from {
             Some(sender) => self.sign(vec![], &sender).await.is_ok(),
             None => false,
         }
@@ -335,14 +885,50 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         let data = utils::serialize(&data.into());
         let from = utils::serialize(from);
 
-        // get the response from `eth_sign` call and trim the 0x-prefix if present.
+        // get the response from `eth_sign` call and decode it.
         let sig: String = self.request("eth_sign", [from, data]).await?;
-        let sig = sig.strip_prefix("0x").unwrap_or(&sig);
+        decode_signature(&sig)
+    }
+
+    /// Signs the transaction with `eth_signTransaction` using the key of its sender, which
+    /// must be unlocked on the node, without broadcasting it. The nonce, gas and fees of
+    /// the transaction must be set. The returned encoding may be broadcast later, e.g.
+    /// through another endpoint.
+    async fn sign_transaction<T: Into<TypedTransaction> + Send + Sync>(
+        &self,
+        tx: T,
+    ) -> Result<SignedTransaction, ProviderError> {
+        #[derive(Debug, Serialize, Deserialize)]
+        struct SignTransactionResult {
+            raw: Bytes,
+            tx: serde_json::Map<String, serde_json::Value>,
+        }
+
+        let mut tx = tx.into();
+        if tx.from().is_none() {
+            if let Some(from) = self.from {
+                tx.set_from(from);
+            }
+        }
+        let from = tx.from().copied();
+
+        let res: SignTransactionResult = self.request("eth_signTransaction", [tx]).await?;
+        let mut fields = res.tx;
+        // the node does not return the sender of the transaction, nor the gas price of
+        // EIP-1559 transactions, whose upper bound is their max fee per gas
+        if let Some(from) = from {
+            fields
+                .entry("from")
+                .or_insert_with(|| utils::serialize(&from));
+        }
+        if let Some(max_fee_per_gas) = fields.get("maxFeePerGas").cloned() {
+            fields.entry("gasPrice").or_insert(max_fee_per_gas);
+        }
 
-        // decode the signature.
-        let sig = hex::decode(sig)?;
-        Ok(Signature::try_from(sig.as_slice())
-            .map_err(|e| ProviderError::CustomError(e.to_string()))?)
+        Ok(SignedTransaction {
+            raw: res.raw,
+            tx: serde_json::from_value(fields.into())?,
+        })
     }
 
     ////// Contract state
@@ -446,6 +1032,26 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         Ok(H256::from_slice(&Vec::from_hex(value)?))
     }
 
+    /// Returns the account and the storage slots at `locations` with their Merkle
+    /// proofs, which can be verified against the state root of the block with
+    /// [`EIP1186ProofResponse::verify`]
+    async fn get_proof<T: Into<NameOrAddress> + Send + Sync>(
+        &self,
+        from: T,
+        locations: Vec<H256>,
+        block: Option<BlockId>,
+    ) -> Result<EIP1186ProofResponse, ProviderError> {
+        let from = match from.into() {
+            NameOrAddress::Name(ens_name) => self.resolve_name(&ens_name).await?,
+            NameOrAddress::Address(addr) => addr,
+        };
+
+        let from = utils::serialize(&from);
+        let locations = utils::serialize(&locations);
+        let block = utils::serialize(&block.unwrap_or_else(|| BlockNumber::Latest.into()));
+        self.request("eth_getProof", [from, locations, block]).await
+    }
+
     /// Returns the deployed code at a given address
     async fn get_code<T: Into<NameOrAddress> + Send + Sync>(
         &self,
@@ -462,6 +1068,39 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         self.request("eth_getCode", [at, block]).await
     }
 
+    /// Returns the nonce, balance, code hash and storage root of an account with Geth's
+    /// `eth_getAccount`. If the node does not support it, the account is queried with the
+    /// portable endpoints, which do not return its storage root.
+    async fn get_account<T: Into<NameOrAddress> + Send + Sync>(
+        &self,
+        account: T,
+        block: Option<BlockId>,
+    ) -> Result<Account, ProviderError> {
+        let account = match account.into() {
+            NameOrAddress::Name(ens_name) => self.resolve_name(&ens_name).await?,
+            NameOrAddress::Address(addr) => addr,
+        };
+
+        let params = [
+            utils::serialize(&account),
+            utils::serialize(&block.unwrap_or_else(|| BlockNumber::Latest.into())),
+        ];
+        match self.request("eth_getAccount", params).await {
+            Err(err) if err.is_method_not_found() => {}
+            res => return res,
+        }
+
+        let nonce = self.get_transaction_count(account, block).await?;
+        let balance = self.get_balance(account, block).await?;
+        let code = self.get_code(account, block).await?;
+        Ok(Account {
+            nonce,
+            balance,
+            code_hash: utils::keccak256(code.as_ref()).into(),
+            storage_root: None,
+        })
+    }
+
     ////// Ethereum Naming Service
     // The Ethereum Naming Service (ENS) allows easy to remember and use names to
     // be assigned to Ethereum addresses. Any provider operation which takes an address
@@ -476,7 +1115,7 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
     /// If the bytes returned from the ENS registrar/resolver cannot be interpreted as
     /// an address. This should theoretically never happen.
     async fn resolve_name(&self, ens_name: &str) -> Result<Address, ProviderError> {
-        self.query_resolver(ParamType::Address, ens_name, ens::ADDR_SELECTOR)
+        self.query_resolver(ParamType::Address, ens_name, ens::ADDR_SELECTOR, None)
             .await
     }
 
@@ -487,44 +1126,244 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
     /// a string. This should theoretically never happen.
     async fn lookup_address(&self, address: Address) -> Result<String, ProviderError> {
         let ens_name = ens::reverse_address(address);
-        self.query_resolver(ParamType::String, &ens_name, ens::NAME_SELECTOR)
+        self.query_resolver(ParamType::String, &ens_name, ens::NAME_SELECTOR, None)
             .await
     }
 
-    /// Returns the details of all transactions currently pending for inclusion in the next
-    /// block(s), as well as the ones that are being scheduled for future execution only.
-    /// Ref: [Here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_content)
-    async fn txpool_content(&self) -> Result<TxpoolContent, ProviderError> {
-        self.request("txpool_content", ()).await
+    /// Returns the address of the ENS name on another chain, as specified in
+    /// [ENSIP-9](https://docs.ens.domains/ens-improvement-proposals/ensip-9-multichain-address-resolution).
+    /// The `coin_type` is the chain's [SLIP-44](https://github.com/satoshilabs/slips/blob/master/slip-0044.md)
+    /// coin type, e.g. `0` for Bitcoin, or `0x80000000 | chain_id` for EVM chains. The
+    /// address is encoded for the chain, e.g. in base58 or bech32 for Bitcoin.
+    async fn resolve_name_for_coin(
+        &self,
+        ens_name: &str,
+        coin_type: u64,
+    ) -> Result<String, ProviderError> {
+        let parameters = ens::coin_parameters(coin_type);
+        let addr: Bytes = self
+            .query_resolver(
+                ParamType::Bytes,
+                ens_name,
+                ens::COIN_ADDR_SELECTOR,
+                Some(&parameters),
+            )
+            .await?;
+        if addr.as_ref().is_empty() {
+            return Err(ProviderError::EnsError(ens_name.to_owned()));
+        }
+        ens::encode_coin_address(coin_type, addr.as_ref()).ok_or_else(|| {
+            ProviderError::CustomError(format!(
+                "cannot encode address 0x{} of coin type {}",
+                hex::encode(addr.as_ref()),
+                coin_type
+            ))
+        })
     }
 
-    /// Returns a summary of all the transactions currently pending for inclusion in the next
-    /// block(s), as well as the ones that are being scheduled for future execution only.
-    /// Ref: [Here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_inspect)
-    async fn txpool_inspect(&self) -> Result<TxpoolInspect, ProviderError> {
-        self.request("txpool_inspect", ()).await
-    }
+    /// Returns the `field` text record of the ENS name, e.g. its `url` or `avatar`
+    async fn resolve_field(&self, ens_name: &str, field: &str) -> Result<String, ProviderError> {
+        let parameters = ens::field_parameters(field);
+        self.query_resolver(
+            ParamType::String,
+            ens_name,
+            ens::FIELD_SELECTOR,
+            Some(&parameters),
+        )
+        .await
+    }
+
+    /// Returns the URL of the avatar of the ENS name, following
+    /// [ENSIP-12](https://docs.ens.domains/ens-improvement-proposals/ensip-12-avatar-text-records).
+    /// IPFS URIs are resolved through a gateway. If the avatar is an NFT, it must be on the
+    /// chain of the provider and owned by the address the name resolves to, and the URL is
+    /// its metadata's image.
+    async fn resolve_avatar(&self, ens_name: &str) -> Result<Url, ProviderError> {
+        let avatar = self.resolve_field(ens_name, "avatar").await?;
+        let nft = match ens::AvatarNft::parse(&avatar) {
+            Some(nft) => nft,
+            None => {
+                return ens::resolve_uri(&avatar).ok_or_else(|| {
+                    ProviderError::CustomError(format!("unsupported avatar `{}`", avatar))
+                })
+            }
+        };
 
-    /// Returns the number of transactions currently pending for inclusion in the next block(s), as
-    /// well as the ones that are being scheduled for future execution only.
-    /// Ref: [Here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_status)
-    async fn txpool_status(&self) -> Result<TxpoolStatus, ProviderError> {
-        self.request("txpool_status", ()).await
-    }
+        // the NFT can only be read on its own chain
+        let chain_id = self.get_chainid().await?;
+        if chain_id != U256::from(nft.chain_id) {
+            return Err(ProviderError::CustomError(format!(
+                "the avatar NFT is on chain {}, but the provider is connected to chain {}",
+                nft.chain_id, chain_id
+            )));
+        }
 
-    /// Executes the given call and returns a number of possible traces for it
-    async fn trace_call(
+        // the NFT must be owned by the address of the name
+        let owner = self.resolve_name(ens_name).await?;
+        let (owned, uri) = match nft.standard {
+            ens::NftStandard::Erc721 => {
+                let data = self
+                    .call(&nft.call(ens::ERC721_OWNER_SELECTOR, None).into(), None)
+                    .await?;
+                let token_owner: Address = decode_ens(ParamType::Address, &data, ens_name)?;
+                let data = self
+                    .call(&nft.call(ens::ERC721_URI_SELECTOR, None).into(), None)
+                    .await?;
+                let uri: String = decode_ens(ParamType::String, &data, ens_name)?;
+                (token_owner == owner, uri)
+            }
+            ens::NftStandard::Erc1155 => {
+                let data = self
+                    .call(
+                        &nft.call(ens::ERC1155_BALANCE_SELECTOR, Some(owner)).into(),
+                        None,
+                    )
+                    .await?;
+                let balance: U256 = decode_ens(ParamType::Uint(256), &data, ens_name)?;
+                let data = self
+                    .call(&nft.call(ens::ERC1155_URI_SELECTOR, None).into(), None)
+                    .await?;
+                let uri: String = decode_ens(ParamType::String, &data, ens_name)?;
+                (!balance.is_zero(), nft.substitute_id(&uri))
+            }
+        };
+        if !owned {
+            return Err(ProviderError::EnsNotOwned(ens_name.to_owned()));
+        }
+
+        // fetch the metadata of the NFT, which links to its image
+        let url = ens::resolve_uri(&uri).ok_or_else(|| {
+            ProviderError::CustomError(format!("unsupported NFT metadata URI `{}`", uri))
+        })?;
+        let metadata: serde_json::Value = if url.scheme() == "data" {
+            let data = ens::decode_data_url(&url).ok_or_else(|| {
+                ProviderError::CustomError(format!("invalid NFT metadata URI `{}`", uri))
+            })?;
+            serde_json::from_slice(&data)?
+        } else {
+            reqwest::get(url).await?.json().await?
+        };
+        let image = ["image", "image_url", "image_data"]
+            .iter()
+            .find_map(|key| metadata.get(key)?.as_str())
+            .ok_or_else(|| ProviderError::CustomError("the NFT has no image".to_owned()))?;
+        ens::resolve_uri(image)
+            .ok_or_else(|| ProviderError::CustomError(format!("unsupported NFT image `{}`", image)))
+    }
+
+    /// Returns whether the signature of the hash is valid for the address. The signature is
+    /// first recovered as an ECDSA signature. If it does not recover to the address and the
+    /// address is a contract, its EIP-1271 `isValidSignature(bytes32,bytes)` is called,
+    /// which is how smart-contract wallets (e.g. Safe) validate signatures.
+    async fn verify_signature(
+        &self,
+        address: Address,
+        hash: H256,
+        signature: &Bytes,
+    ) -> Result<bool, ProviderError> {
+        if let Ok(sig) = Signature::try_from(signature.as_ref()) {
+            if sig.recover(hash).ok() == Some(address) {
+                return Ok(true);
+            }
+        }
+
+        let code = self.get_code(address, None).await?;
+        if code.as_ref().is_empty() {
+            return Ok(false);
+        }
+
+        let data = [
+            &EIP1271_MAGIC_VALUE[..],
+            &abi::encode(&[
+                abi::Token::FixedBytes(hash.as_bytes().to_vec()),
+                abi::Token::Bytes(signature.to_vec()),
+            ]),
+        ]
+        .concat();
+        let tx = TransactionRequest::new().to(address).data(data);
+        match self.call(&tx.into(), None).await {
+            // the magic value is returned left-aligned as a `bytes4`
+            Ok(result) => Ok(result.as_ref().get(..4) == Some(&EIP1271_MAGIC_VALUE[..])),
+            // contracts which do not implement EIP-1271 revert
+            Err(err)
+                if err
+                    .as_error_response()
+                    .map_or(false, JsonRpcError::is_revert) =>
+            {
+                Ok(false)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Returns the details of all transactions currently pending for inclusion in the next
+    /// block(s), as well as the ones that are being scheduled for future execution only.
+    /// Ref: [Here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_content)
+    async fn txpool_content(&self) -> Result<TxpoolContent, ProviderError> {
+        self.request("txpool_content", ()).await
+    }
+
+    /// Returns a summary of all the transactions currently pending for inclusion in the next
+    /// block(s), as well as the ones that are being scheduled for future execution only.
+    /// Ref: [Here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_inspect)
+    async fn txpool_inspect(&self) -> Result<TxpoolInspect, ProviderError> {
+        self.request("txpool_inspect", ()).await
+    }
+
+    /// Returns the number of transactions currently pending for inclusion in the next block(s), as
+    /// well as the ones that are being scheduled for future execution only.
+    /// Ref: [Here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_status)
+    async fn txpool_status(&self) -> Result<TxpoolStatus, ProviderError> {
+        self.request("txpool_status", ()).await
+    }
+
+    /// Executes the given call and returns a number of possible traces for it
+    async fn trace_call(
         &self,
-        req: TransactionRequest,
+        req: &TypedTransaction,
         trace_type: Vec<TraceType>,
         block: Option<BlockNumber>,
     ) -> Result<BlockTrace, ProviderError> {
-        let req = utils::serialize(&req);
+        let req = utils::serialize(req);
         let block = utils::serialize(&block.unwrap_or(BlockNumber::Latest));
         let trace_type = utils::serialize(&trace_type);
         self.request("trace_call", [req, trace_type, block]).await
     }
 
+    /// Executes the given calls in sequence, each on top of the state changes of the
+    /// previous ones, and returns the traces of each call
+    async fn trace_call_many(
+        &self,
+        req: Vec<(TypedTransaction, Vec<TraceType>)>,
+        block: Option<BlockNumber>,
+    ) -> Result<Vec<BlockTrace>, ProviderError> {
+        let req = utils::serialize(&req);
+        let block = utils::serialize(&block.unwrap_or(BlockNumber::Latest));
+        self.request("trace_callMany", [req, block]).await
+    }
+
+    /// Executes the given call on top of the currently pending transactions of the
+    /// txpool, ordered as a miner would include them, and returns its traces. This
+    /// requires a node supporting both `txpool_content` and `trace_callMany`, e.g. Erigon.
+    async fn simulate_on_pending(
+        &self,
+        req: &TypedTransaction,
+        trace_type: Vec<TraceType>,
+    ) -> Result<BlockTrace, ProviderError> {
+        let content = self.txpool_content().await?;
+        let mut calls = content
+            .pending_by_price()
+            .into_iter()
+            .map(|tx| (tx.into(), Vec::new()))
+            .collect::<Vec<_>>();
+        calls.push((req.clone(), trace_type));
+
+        let mut traces = self.trace_call_many(calls, None).await?;
+        traces.pop().ok_or_else(|| {
+            ProviderError::CustomError("trace_callMany returned no traces".to_owned())
+        })
+    }
+
     /// Traces a call to `eth_sendRawTransaction` without making the call, returning the traces
     async fn trace_raw_transaction(
         &self,
@@ -591,6 +1430,89 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         self.request("trace_transaction", vec![hash]).await
     }
 
+    /// Replays a transaction, returning the trace of the selected tracer. Requires a
+    /// node supporting Geth's `debug` namespace.
+    async fn debug_trace_transaction(
+        &self,
+        tx_hash: TxHash,
+        trace_options: GethDebugTracingOptions,
+    ) -> Result<GethTrace, ProviderError> {
+        let tx_hash = utils::serialize(&tx_hash);
+        let trace_options = utils::serialize(&trace_options);
+        self.request("debug_traceTransaction", [tx_hash, trace_options])
+            .await
+    }
+
+    /// Executes the given call at `block` (defaults to the latest block) without
+    /// sending it, returning the trace of the selected tracer. Requires a node
+    /// supporting Geth's `debug` namespace.
+    async fn debug_trace_call(
+        &self,
+        req: &TypedTransaction,
+        block: Option<BlockId>,
+        trace_options: GethDebugTracingCallOptions,
+    ) -> Result<GethTrace, ProviderError> {
+        let req = utils::serialize(req);
+        let block = utils::serialize(&block.unwrap_or_else(|| BlockNumber::Latest.into()));
+        let trace_options = utils::serialize(&trace_options);
+        self.request("debug_traceCall", [req, block, trace_options])
+            .await
+    }
+
+    /// Returns the RLP encoding of the block
+    async fn debug_get_raw_block<T: Into<BlockId> + Send + Sync>(
+        &self,
+        block_hash_or_number: T,
+    ) -> Result<Bytes, ProviderError> {
+        let block = utils::serialize(&block_hash_or_number.into());
+        self.request("debug_getRawBlock", [block]).await
+    }
+
+    /// Returns the consensus encodings of the receipts of the block, which are
+    /// prefixed with their type for typed transactions
+    async fn debug_get_raw_receipts<T: Into<BlockId> + Send + Sync>(
+        &self,
+        block_hash_or_number: T,
+    ) -> Result<Vec<Bytes>, ProviderError> {
+        let block = utils::serialize(&block_hash_or_number.into());
+        self.request("debug_getRawReceipts", [block]).await
+    }
+
+    /// Returns the signed encoding of the transaction, which is empty if the node does
+    /// not know the transaction
+    async fn debug_get_raw_transaction(&self, tx_hash: TxHash) -> Result<Bytes, ProviderError> {
+        self.request("debug_getRawTransaction", [tx_hash]).await
+    }
+
+    /// Returns the blocks recently rejected by the node, e.g. to investigate a consensus
+    /// issue
+    async fn debug_get_bad_blocks(&self) -> Result<Vec<BadBlock>, ProviderError> {
+        self.request("debug_getBadBlocks", ()).await
+    }
+
+    /// Returns up to `max_results` storage slots of the account, starting at the slot
+    /// with the hashed key `start_key`, as of after the transaction at `tx_index` of the
+    /// block. The slots are ordered by their hashed key.
+    async fn debug_storage_range_at(
+        &self,
+        block_hash: H256,
+        tx_index: u64,
+        address: Address,
+        start_key: H256,
+        max_results: u64,
+    ) -> Result<StorageRange, ProviderError> {
+        let block_hash = utils::serialize(&block_hash);
+        let tx_index = utils::serialize(&tx_index);
+        let address = utils::serialize(&address);
+        let start_key = utils::serialize(&start_key);
+        let max_results = utils::serialize(&max_results);
+        self.request(
+            "debug_storageRangeAt",
+            [block_hash, tx_index, address, start_key, max_results],
+        )
+        .await
+    }
+
     /// Returns all receipts for that block. Must be done on a parity node.
     async fn parity_block_receipts<T: Into<BlockNumber> + Send + Sync>(
         &self,
@@ -600,6 +1522,55 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
             .await
     }
 
+    /// Submits a user operation to the bundler's mempool via `eth_sendUserOperation`,
+    /// returning its hash. The provider must be connected to an ERC-4337 bundler.
+    async fn send_user_operation(
+        &self,
+        user_op: &UserOperation,
+        entry_point: Address,
+    ) -> Result<H256, ProviderError> {
+        let user_op = utils::serialize(user_op);
+        let entry_point = utils::serialize(&entry_point);
+        self.request("eth_sendUserOperation", [user_op, entry_point])
+            .await
+    }
+
+    /// Estimates the gas limits required by a user operation via `eth_estimateUserOperationGas`
+    async fn estimate_user_operation_gas(
+        &self,
+        user_op: &UserOperation,
+        entry_point: Address,
+    ) -> Result<UserOperationGasEstimation, ProviderError> {
+        let user_op = utils::serialize(user_op);
+        let entry_point = utils::serialize(&entry_point);
+        self.request("eth_estimateUserOperationGas", [user_op, entry_point])
+            .await
+    }
+
+    /// Returns the user operation with the provided hash via `eth_getUserOperationByHash`
+    async fn get_user_operation<T: Send + Sync + Into<H256>>(
+        &self,
+        user_op_hash: T,
+    ) -> Result<Option<UserOperationByHash>, ProviderError> {
+        let hash = user_op_hash.into();
+        self.request("eth_getUserOperationByHash", [hash]).await
+    }
+
+    /// Returns the receipt of the user operation with the provided hash via
+    /// `eth_getUserOperationReceipt`
+    async fn get_user_operation_receipt<T: Send + Sync + Into<H256>>(
+        &self,
+        user_op_hash: T,
+    ) -> Result<Option<UserOperationReceipt>, ProviderError> {
+        let hash = user_op_hash.into();
+        self.request("eth_getUserOperationReceipt", [hash]).await
+    }
+
+    /// Returns the EntryPoint contracts supported by the bundler via `eth_supportedEntryPoints`
+    async fn supported_entry_points(&self) -> Result<Vec<Address>, ProviderError> {
+        self.request("eth_supportedEntryPoints", ()).await
+    }
+
     async fn subscribe<T, R>(
         &self,
         params: T,
@@ -658,34 +1629,108 @@ impl<P: JsonRpcClient> Provider<P> {
         param: ParamType,
         ens_name: &str,
         selector: Selector,
+        parameters: Option<&[u8]>,
     ) -> Result<T, ProviderError> {
+        // names are hashed in their normalized form
+        let normalized = ens::normalize_name(ens_name)
+            .ok_or_else(|| ProviderError::EnsInvalidName(ens_name.to_owned()))?;
+        let ens_name = normalized.as_str();
+
         // Get the ENS address, prioritize the local override variable
-        let ens_addr = self.1.unwrap_or(ens::ENS_ADDRESS);
+        let ens_addr = self.ens.unwrap_or(ens::ENS_ADDRESS);
+
+        // first get the resolver responsible for this name, falling back to the resolvers
+        // of its parents which may resolve it as a wildcard
+        let mut name = ens_name;
+        let resolver_address = loop {
+            // the call will return a Bytes array which we convert to an address
+            let data = self
+                .call(&ens::get_resolver(ens_addr, name).into(), None)
+                .await?;
+            let resolver_address: Address = decode_ens(ParamType::Address, &data, ens_name)?;
+            if resolver_address != Address::zero() {
+                break resolver_address;
+            }
+            name = ens::parent(name).ok_or_else(|| ProviderError::EnsError(ens_name.to_owned()))?;
+        };
 
-        // first get the resolver responsible for this name
-        // the call will return a Bytes array which we convert to an address
-        let data = self
-            .call(&ens::get_resolver(ens_addr, ens_name), None)
-            .await?;
+        // resolve, following the offchain lookups of offchain resolvers
+        if name == ens_name {
+            let data = self
+                .call_ccip(&ens::resolve(
+                    resolver_address,
+                    selector,
+                    ens_name,
+                    parameters,
+                ))
+                .await?;
+            return decode_ens(param, &data, ens_name);
+        }
 
-        let resolver_address: Address = decode_bytes(ParamType::Address, data);
-        if resolver_address == Address::zero() {
+        // the resolver of a parent may only resolve the name through `resolve(bytes,bytes)`
+        if !self.supports_wildcard(resolver_address).await {
             return Err(ProviderError::EnsError(ens_name.to_owned()));
         }
+        let tx = ens::resolve_wildcard(resolver_address, selector, ens_name, parameters)
+            .ok_or_else(|| ProviderError::EnsError(ens_name.to_owned()))?;
+        let data: Bytes = decode_ens(ParamType::Bytes, &self.call_ccip(&tx).await?, ens_name)?;
+
+        decode_ens(param, &data, ens_name)
+    }
+
+    /// Returns whether the resolver implements ENSIP-10's `resolve(bytes,bytes)`. Resolvers
+    /// which do not implement ERC-165 do not support it.
+    async fn supports_wildcard(&self, resolver_address: Address) -> bool {
+        let tx = ens::supports_interface(resolver_address, ens::WILDCARD_RESOLVE_SELECTOR);
+        match self.call(&tx.into(), None).await {
+            Ok(data) => abi::decode(&[ParamType::Bool], data.as_ref())
+                .ok()
+                .and_then(|mut tokens| tokens.pop()?.into_bool())
+                .unwrap_or(false),
+            Err(_) => false,
+        }
+    }
 
-        // resolve
-        let data = self
-            .call(&ens::resolve(resolver_address, selector, ens_name), None)
-            .await?;
-
-        Ok(decode_bytes(param, data))
+    /// Calls the contract, and if it reverts with an `OffchainLookup` error, fetches the
+    /// response from its gateways and calls its callback with it, as specified in
+    /// [EIP-3668](https://eips.ethereum.org/EIPS/eip-3668)
+    async fn call_ccip(&self, tx: &TransactionRequest) -> Result<Bytes, ProviderError> {
+        let mut tx = tx.clone();
+        for _ in 0..=MAX_CCIP_REDIRECTS {
+            let err = match self.call(&tx.clone().into(), None).await {
+                Ok(data) => return Ok(data),
+                Err(err) => err,
+            };
+            let lookup = match err
+                .as_error_response()
+                .and_then(|res| res.as_revert_data())
+                .and_then(|data| OffchainLookup::decode(data.as_ref()))
+            {
+                Some(lookup) => lookup,
+                None => return Err(err),
+            };
+
+            // only the called contract may request a lookup, not one of its callees
+            if tx.to != Some(NameOrAddress::Address(lookup.sender)) {
+                return Err(ProviderError::CcipError(format!(
+                    "lookup requested by {:?} instead of the called contract",
+                    lookup.sender
+                )));
+            }
+            let response = lookup.fetch().await?;
+            tx.data = Some(lookup.callback_data(response.as_ref()));
+        }
+        Err(ProviderError::CcipError(format!(
+            "more than {} offchain lookups",
+            MAX_CCIP_REDIRECTS
+        )))
     }
 
     #[cfg(test)]
     /// ganache-only function for mining empty blocks
     pub async fn mine(&self, num_blocks: usize) -> Result<(), ProviderError> {
         for _ in 0..num_blocks {
-            self.0
+            self.inner
                 .request::<_, U256>("evm_mine", None::<()>)
                 .await
                 .map_err(Into::into)?;
@@ -693,23 +1738,58 @@ impl<P: JsonRpcClient> Provider<P> {
         Ok(())
     }
 
+    /// Requests access to the accounts of the connected wallet via `eth_requestAccounts`.
+    /// With browser wallets, this prompts the user to connect their wallet to the page.
+    pub async fn request_accounts(&self) -> Result<Vec<Address>, ProviderError> {
+        self.request("eth_requestAccounts", ()).await
+    }
+
+    /// Signs data using a specific account via `personal_sign`, which prefixes the data
+    /// with the `Ethereum Signed Message` domain separator. This is the signing method
+    /// supported by browser wallets.
+    pub async fn personal_sign<T: Into<Bytes>>(
+        &self,
+        data: T,
+        from: &Address,
+    ) -> Result<Signature, ProviderError> {
+        let data = utils::serialize(&data.into());
+        let from = utils::serialize(from);
+        let sig: String = self.request("personal_sign", [data, from]).await?;
+        decode_signature(&sig)
+    }
+
     /// Sets the ENS Address (default: mainnet)
     pub fn ens<T: Into<Address>>(mut self, ens: T) -> Self {
-        self.1 = Some(ens.into());
+        self.ens = Some(ens.into());
         self
     }
 
     /// Sets the default polling interval for event filters and pending transactions
     /// (default: 7 seconds)
     pub fn interval<T: Into<Duration>>(mut self, interval: T) -> Self {
-        self.2 = Some(interval.into());
+        self.interval = Some(interval.into());
         self
     }
 
     /// Gets the polling interval which the provider currently uses for event filters
     /// and pending transactions (default: 7 seconds)
     pub fn get_interval(&self) -> Duration {
-        self.2.unwrap_or(DEFAULT_POLL_INTERVAL)
+        self.interval.unwrap_or(DEFAULT_POLL_INTERVAL)
+    }
+
+    /// Enables the validation of responses (default: disabled). Transactions and receipts
+    /// must have the requested hash, receipts must have a block hash and blocks must have
+    /// the requested hash or number, otherwise a [`ValidationError`] is returned instead of
+    /// the inconsistent response. Block streams can be validated with
+    /// [`validate_block_numbers`](crate::validate_block_numbers).
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Returns whether the provider validates responses
+    pub fn is_strict(&self) -> bool {
+        self.strict
     }
 }
 
@@ -724,6 +1804,24 @@ impl Provider<crate::Ws> {
     }
 }
 
+impl Provider<crate::Http> {
+    /// Returns a builder for a batch of requests, which are sent to the node in a single
+    /// HTTP request
+    pub fn batch(&self) -> BatchRequest<'_> {
+        BatchRequest::new(self)
+    }
+}
+
+#[cfg(feature = "ipc")]
+impl Provider<crate::Ipc> {
+    /// Direct connection to an IPC endpoint, a Unix domain socket or a named pipe on
+    /// Windows
+    pub async fn connect_ipc(path: impl AsRef<std::path::Path>) -> Result<Self, ProviderError> {
+        let ipc = crate::Ipc::connect(path).await?;
+        Ok(Self::new(ipc))
+    }
+}
+
 impl Provider<MockProvider> {
     /// Returns a `Provider` instantiated with an internal "mock" transport.
     ///
@@ -752,27 +1850,34 @@ impl Provider<MockProvider> {
     }
 }
 
-/// infallible conversion of Bytes to Address/String
-///
-/// # Panics
-///
-/// If the provided bytes were not an interpretation of an address
-fn decode_bytes<T: Detokenize>(param: ParamType, bytes: Bytes) -> T {
-    let tokens = abi::decode(&[param], &bytes.as_ref())
-        .expect("could not abi-decode bytes to address tokens");
-    T::from_tokens(tokens).expect("could not parse tokens as address")
+/// Decodes the result of a call made while resolving the ENS name, which is not found if
+/// the result cannot be decoded
+fn decode_ens<T: Detokenize>(
+    param: ParamType,
+    bytes: &Bytes,
+    ens_name: &str,
+) -> Result<T, ProviderError> {
+    abi::decode(&[param], bytes.as_ref())
+        .ok()
+        .and_then(|tokens| T::from_tokens(tokens).ok())
+        .ok_or_else(|| ProviderError::EnsError(ens_name.to_owned()))
+}
+
+/// The selector of EIP-1271's `isValidSignature(bytes32,bytes)`, which is also the value
+/// returned by contracts for valid signatures
+const EIP1271_MAGIC_VALUE: Selector = [0x16, 0x26, 0xba, 0x7e];
+
+/// Decodes a hex encoded signature, trimming the 0x-prefix if present
+fn decode_signature(sig: &str) -> Result<Signature, ProviderError> {
+    let sig = hex::decode(sig.strip_prefix("0x").unwrap_or(sig))?;
+    Signature::try_from(sig.as_slice()).map_err(|e| ProviderError::CustomError(e.to_string()))
 }
 
 impl TryFrom<&str> for Provider<HttpProvider> {
     type Error = ParseError;
 
     fn try_from(src: &str) -> Result<Self, Self::Error> {
-        Ok(Provider(
-            HttpProvider::new(Url::parse(src)?),
-            None,
-            None,
-            None,
-        ))
+        Ok(Provider::new(HttpProvider::new(Url::parse(src)?)))
     }
 }
 
@@ -837,7 +1942,10 @@ mod ens_tests {
 mod tests {
     use super::*;
     use crate::Http;
-    use ethers_core::types::H256;
+    use ethers_core::types::{
+        AccessListItem, AccountOverride, DefaultFrame, Eip1559TransactionRequest,
+        SimulatedBlockCalls, SyncProgress, H256,
+    };
     use futures_util::StreamExt;
 
     #[tokio::test]
@@ -946,6 +2054,974 @@ mod tests {
         assert_eq!(receipt.transaction_hash, hash);
     }
 
+    #[tokio::test]
+    async fn simulates_on_pending_txs() {
+        let (provider, mock) = Provider::mocked();
+        let pending = TransactionRequest::new()
+            .from(Address::repeat_byte(1))
+            .nonce(0)
+            .gas_price(10);
+        let mut content = TxpoolContent::default();
+        content.pending.insert(
+            Address::repeat_byte(1),
+            vec![("0".to_owned(), pending.clone())]
+                .into_iter()
+                .collect(),
+        );
+        let trace = BlockTrace {
+            output: vec![1u8].into(),
+            trace: None,
+            vm_trace: None,
+            state_diff: None,
+            transaction_hash: None,
+        };
+        let pending_trace = BlockTrace {
+            output: Default::default(),
+            ..trace.clone()
+        };
+        mock.push_response("txpool_content", content).unwrap();
+        mock.push_response::<Vec<BlockTrace>, _>(
+            "trace_callMany",
+            vec![pending_trace, trace.clone()],
+        )
+        .unwrap();
+
+        let tx: TypedTransaction = TransactionRequest::pay(Address::repeat_byte(2), 100).into();
+        let res = provider
+            .simulate_on_pending(&tx, vec![TraceType::Trace])
+            .await
+            .unwrap();
+        assert_eq!(res, trace);
+
+        mock.assert_request("txpool_content", ()).unwrap();
+        let calls = vec![(pending.into(), vec![]), (tx, vec![TraceType::Trace])];
+        mock.assert_request(
+            "trace_callMany",
+            [
+                utils::serialize(&calls),
+                utils::serialize(&BlockNumber::Latest),
+            ],
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn suggests_fees() {
+        let (provider, mock) = Provider::mocked();
+        mock.push_response("eth_getBlockByNumber", Block::<TxHash>::default())
+            .unwrap();
+        mock.push_response("eth_gasPrice", U256::from(100)).unwrap();
+        let fees = provider.suggest_fees(Priority::Standard).await.unwrap();
+        assert_eq!(
+            fees,
+            FeeSuggestion::Legacy {
+                gas_price: 100.into()
+            }
+        );
+
+        let history = FeeHistory {
+            base_fee_per_gas: vec![64.into()],
+            reward: vec![vec![10.into()]],
+            ..Default::default()
+        };
+        mock.push_response(
+            "eth_getBlockByNumber",
+            Block::<TxHash> {
+                base_fee_per_gas: Some(64.into()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        mock.push_response("eth_feeHistory", history).unwrap();
+        let fees = provider.suggest_fees(Priority::Slow).await.unwrap();
+        assert_eq!(
+            fees,
+            FeeSuggestion::Eip1559 {
+                max_fee_per_gas: (81 + 10).into(),
+                max_priority_fee_per_gas: 10.into(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn geth_state_queries() {
+        let (provider, mock) = Provider::mocked();
+        let account = Account {
+            nonce: 1.into(),
+            balance: 100.into(),
+            code_hash: H256::repeat_byte(1),
+            storage_root: Some(H256::repeat_byte(2)),
+        };
+        let header = Block::<TxHash> {
+            hash: Some(H256::repeat_byte(3)),
+            ..Default::default()
+        };
+        mock.push_response("eth_getHeaderByNumber", header.clone())
+            .unwrap();
+        mock.push_response("eth_getAccount", account.clone())
+            .unwrap();
+
+        assert_eq!(provider.get_header(7u64).await.unwrap(), Some(header));
+        let address = Address::repeat_byte(4);
+        assert_eq!(provider.get_account(address, None).await.unwrap(), account);
+
+        mock.assert_request("eth_getHeaderByNumber", [U64::from(7)])
+            .unwrap();
+        mock.assert_request(
+            "eth_getAccount",
+            [
+                utils::serialize(&address),
+                utils::serialize(&BlockNumber::Latest),
+            ],
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn validates_responses() {
+        let (provider, mock) = Provider::mocked();
+        let provider = provider.strict(true);
+        let hash = H256::repeat_byte(1);
+        let receipt = TransactionReceipt {
+            transaction_hash: hash,
+            block_hash: Some(H256::repeat_byte(2)),
+            ..Default::default()
+        };
+        let block = Block::<TxHash> {
+            hash: Some(H256::repeat_byte(3)),
+            number: Some(10.into()),
+            ..Default::default()
+        };
+        mock.push_response("eth_getTransactionReceipt", receipt.clone())
+            .unwrap();
+        mock.push_response(
+            "eth_getTransactionReceipt",
+            TransactionReceipt {
+                block_hash: None,
+                ..receipt.clone()
+            },
+        )
+        .unwrap();
+        mock.push_response("eth_getBlockByNumber", block.clone())
+            .unwrap();
+        mock.push_response("eth_getBlockByNumber", block).unwrap();
+
+        assert_eq!(
+            provider.get_transaction_receipt(hash).await.unwrap(),
+            Some(receipt)
+        );
+        match provider.get_transaction_receipt(hash).await.unwrap_err() {
+            ProviderError::ValidationError(ValidationError::MissingBlockHash(missing)) => {
+                assert_eq!(missing, hash)
+            }
+            err => panic!("unexpected error {}", err),
+        }
+        assert!(provider.get_block(10u64).await.unwrap().is_some());
+        assert!(matches!(
+            provider.get_block(11u64).await.unwrap_err(),
+            ProviderError::ValidationError(ValidationError::BlockMismatch { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn validates_block_numbers() {
+        let block = |number: u64| Block::<TxHash> {
+            number: Some(number.into()),
+            ..Default::default()
+        };
+        let stream = futures_util::stream::iter(vec![block(1), block(2), block(2), block(1)]);
+        let results = crate::validate_block_numbers(stream)
+            .map(|res| res.is_ok())
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(results, vec![true, true, true, false]);
+    }
+
+    #[tokio::test]
+    async fn builds_providers() {
+        let mock = MockProvider::new();
+        let provider = Provider::builder(mock.clone())
+            .interval(Duration::from_millis(10))
+            .sender(Address::from_low_u64_be(1))
+            .strict(true)
+            .chain_id(5u64)
+            .build();
+        assert_eq!(provider.get_interval(), Duration::from_millis(10));
+        assert!(provider.is_strict());
+
+        // the chain id is not queried
+        assert_eq!(provider.get_chainid().await.unwrap(), 5.into());
+        mock.assert_request("eth_chainId", ()).unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn fee_history_request() {
+        let (provider, mock) = Provider::mocked();
+        let history = FeeHistory {
+            oldest_block: 100.into(),
+            base_fee_per_gas: vec![10.into(), 11.into(), 12.into()],
+            gas_used_ratio: vec![0.6, 0.9],
+            reward: vec![vec![1.into(), 2.into()], vec![3.into(), 4.into()]],
+        };
+        mock.push(history.clone()).unwrap();
+
+        let res = provider
+            .fee_history(2u64, BlockNumber::Latest, &[25.0, 75.0])
+            .await
+            .unwrap();
+        assert_eq!(res, history);
+        assert_eq!(res.next_base_fee(), Some(12.into()));
+        mock.assert_request(
+            "eth_feeHistory",
+            (U256::from(2), BlockNumber::Latest, [25.0, 75.0]),
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn create_access_list_request() {
+        let (provider, mock) = Provider::mocked();
+        let res = AccessListWithGasUsed {
+            access_list: vec![AccessListItem {
+                address: Address::repeat_byte(1),
+                storage_keys: vec![H256::repeat_byte(2)],
+            }]
+            .into(),
+            gas_used: 30_000.into(),
+        };
+        mock.push(res.clone()).unwrap();
+
+        let tx: TypedTransaction = Eip1559TransactionRequest::new()
+            .to(Address::repeat_byte(1))
+            .max_fee_per_gas(20)
+            .into();
+        assert_eq!(provider.create_access_list(&tx, None).await.unwrap(), res);
+        mock.assert_request("eth_createAccessList", (tx, BlockNumber::Latest))
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn debug_trace_transaction_request() {
+        let (provider, mock) = Provider::mocked();
+        let trace = GethTrace::Default(DefaultFrame {
+            failed: false,
+            gas: 21_000,
+            return_value: String::new(),
+            struct_logs: Vec::new(),
+        });
+        mock.push(trace.clone()).unwrap();
+
+        let hash = H256::repeat_byte(1);
+        let opts = GethDebugTracingOptions::default().timeout("10s");
+        assert_eq!(
+            provider
+                .debug_trace_transaction(hash, opts.clone())
+                .await
+                .unwrap(),
+            trace
+        );
+        mock.assert_request("debug_traceTransaction", (hash, opts))
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn debug_trace_call_request() {
+        let (provider, mock) = Provider::mocked();
+        let frame = DefaultFrame {
+            failed: true,
+            gas: 30_000,
+            return_value: String::new(),
+            struct_logs: Vec::new(),
+        };
+        mock.push(GethTrace::Default(frame.clone())).unwrap();
+
+        let tx: TypedTransaction = TransactionRequest::new().to(Address::repeat_byte(1)).into();
+        let mut overrides = StateOverride::new();
+        overrides.insert(
+            Address::repeat_byte(1),
+            AccountOverride::default().code(vec![0u8]),
+        );
+        let opts = GethDebugTracingCallOptions::from(GethDebugTracingOptions::default())
+            .state_overrides(overrides);
+        let trace = provider
+            .debug_trace_call(&tx, None, opts.clone())
+            .await
+            .unwrap();
+        assert_eq!(trace, GethTrace::Default(frame));
+        mock.assert_request("debug_traceCall", (tx, BlockNumber::Latest, opts))
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn geth_debug_queries() {
+        let (provider, mock) = Provider::mocked();
+        let range = StorageRange {
+            storage: Default::default(),
+            next_key: Some(H256::repeat_byte(3)),
+        };
+        mock.push(range.clone()).unwrap();
+        mock.push::<Vec<Bytes>, _>(vec![Bytes::from(vec![2u8])])
+            .unwrap();
+        mock.push(Bytes::from(vec![1u8])).unwrap();
+
+        let hash = H256::repeat_byte(1);
+        assert_eq!(
+            provider.debug_get_raw_block(hash).await.unwrap(),
+            Bytes::from(vec![1u8])
+        );
+        mock.assert_request(
+            "debug_getRawBlock",
+            [serde_json::json!({ "blockHash": hash })],
+        )
+        .unwrap();
+
+        let receipts = provider
+            .debug_get_raw_receipts(BlockNumber::Number(5.into()))
+            .await
+            .unwrap();
+        assert_eq!(receipts, vec![Bytes::from(vec![2u8])]);
+        mock.assert_request("debug_getRawReceipts", ["0x5"])
+            .unwrap();
+
+        let address = Address::repeat_byte(2);
+        let res = provider
+            .debug_storage_range_at(hash, 1, address, H256::zero(), 10)
+            .await
+            .unwrap();
+        assert_eq!(res, range);
+        mock.assert_request("debug_storageRangeAt", (hash, 1, address, H256::zero(), 10))
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn syncing_request() {
+        let (provider, mock) = Provider::mocked();
+        let progress = SyncProgress {
+            current_block: 10.into(),
+            highest_block: 20.into(),
+            ..Default::default()
+        };
+        mock.push(SyncingStatus::IsSyncing(Box::new(progress.clone())))
+            .unwrap();
+        mock.push(false).unwrap();
+
+        assert_eq!(provider.syncing().await.unwrap(), SyncingStatus::IsFalse);
+        mock.assert_request("eth_syncing", ()).unwrap();
+        assert_eq!(
+            provider.syncing().await.unwrap(),
+            SyncingStatus::IsSyncing(Box::new(progress))
+        );
+    }
+
+    #[tokio::test]
+    async fn net_requests() {
+        let (provider, mock) = Provider::mocked();
+        mock.push(U64::from(25)).unwrap();
+        mock.push::<&str, _>("1").unwrap();
+
+        assert_eq!(provider.net_version().await.unwrap(), "1");
+        mock.assert_request("net_version", ()).unwrap();
+        assert_eq!(provider.peer_count().await.unwrap(), 25.into());
+        mock.assert_request("net_peerCount", ()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn web3_requests() {
+        let (provider, mock) = Provider::mocked();
+        let hash = H256::from(utils::keccak256(&[1u8, 2]));
+        mock.push(hash).unwrap();
+        mock.push::<&str, _>("Geth/v1.10.8-stable/linux-amd64/go1.16.7")
+            .unwrap();
+
+        let version = provider.client_version().await.unwrap();
+        assert!(version.starts_with("Geth/"));
+        mock.assert_request("web3_clientVersion", ()).unwrap();
+
+        assert_eq!(provider.web3_sha3(vec![1u8, 2]).await.unwrap(), hash);
+        mock.assert_request("web3_sha3", ["0x0102"]).unwrap();
+    }
+
+    #[tokio::test]
+    async fn call_with_state_overrides_request() {
+        let (provider, mock) = Provider::mocked();
+        mock.push(Bytes::from(vec![1u8])).unwrap();
+
+        let token = Address::repeat_byte(1);
+        let mut overrides = StateOverride::new();
+        overrides.insert(
+            token,
+            AccountOverride::default().state_diff(H256::zero(), H256::from_low_u64_be(100)),
+        );
+        let tx: TypedTransaction = TransactionRequest::new().to(token).into();
+        let res = provider
+            .call_with_state_overrides(&tx, None, &overrides)
+            .await
+            .unwrap();
+        assert_eq!(res, Bytes::from(vec![1u8]));
+        mock.assert_request("eth_call", (tx, BlockNumber::Latest, overrides))
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn simulate_request() {
+        let (provider, mock) = Provider::mocked();
+        mock.push(serde_json::json!([{
+            "number": "0x10",
+            "hash": null,
+            "logsBloom": null,
+            "totalDifficulty": null,
+            "size": null,
+            "mixHash": null,
+            "nonce": null,
+            "calls": [{ "returnData": "0x01", "logs": [], "gasUsed": "0x5208", "status": "0x1" }]
+        }]))
+        .unwrap();
+
+        let payload = SimulatePayload::default()
+            .block(
+                SimulatedBlockCalls::default()
+                    .call(TransactionRequest::new().to(Address::repeat_byte(1))),
+            )
+            .trace_transfers(true);
+        let blocks = provider.simulate(&payload, None).await.unwrap();
+        assert_eq!(blocks.len(), 1);
+        assert!(blocks[0].calls[0].is_success());
+        assert_eq!(blocks[0].calls[0].return_data, Bytes::from(vec![1u8]));
+        mock.assert_request("eth_simulateV1", (payload, BlockNumber::Latest))
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn call_with_block_overrides_request() {
+        let (provider, mock) = Provider::mocked();
+        mock.push(Bytes::from(vec![1u8])).unwrap();
+
+        let tx: TypedTransaction = TransactionRequest::new().to(Address::repeat_byte(1)).into();
+        let block_overrides = BlockOverrides::default().time(2_000_000_000u64);
+        let res = provider
+            .call_with_overrides(&tx, None, &StateOverride::new(), &block_overrides)
+            .await
+            .unwrap();
+        assert_eq!(res, Bytes::from(vec![1u8]));
+        mock.assert_request(
+            "eth_call",
+            (
+                tx,
+                BlockNumber::Latest,
+                serde_json::json!({}),
+                serde_json::json!({ "time": "0x77359400" }),
+            ),
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn block_receipts_fallback() {
+        let (provider, mock) = Provider::mocked();
+        let not_found = |method: &str| JsonRpcError {
+            code: -32601,
+            message: format!("the method {} does not exist", method),
+            data: None,
+        };
+        mock.push_error("eth_getBlockReceipts", not_found("eth_getBlockReceipts"));
+        mock.push_error(
+            "parity_getBlockReceipts",
+            not_found("parity_getBlockReceipts"),
+        );
+
+        let hashes = vec![H256::repeat_byte(1), H256::repeat_byte(2)];
+        let block = Block::<TxHash> {
+            number: Some(5.into()),
+            transactions: hashes.clone(),
+            ..Default::default()
+        };
+        mock.push_response("eth_getBlockByNumber", block).unwrap();
+        for hash in &hashes {
+            let receipt = TransactionReceipt {
+                transaction_hash: *hash,
+                ..Default::default()
+            };
+            mock.push_response("eth_getTransactionReceipt", receipt)
+                .unwrap();
+        }
+
+        let receipts = provider.get_block_receipts(5).await.unwrap();
+        let receipt_hashes = receipts
+            .iter()
+            .map(|r| r.transaction_hash)
+            .collect::<Vec<_>>();
+        assert_eq!(receipt_hashes.len(), 2);
+        assert!(hashes.iter().all(|hash| receipt_hashes.contains(hash)));
+        assert_eq!(mock.call_count("eth_getTransactionReceipt"), 2);
+    }
+
+    #[tokio::test]
+    async fn sign_transaction_request() {
+        let (provider, mock) = Provider::mocked();
+        let from = Address::repeat_byte(1);
+        let raw = Bytes::from(vec![2u8, 0xc0]);
+        mock.push(serde_json::json!({
+            "raw": raw,
+            "tx": {
+                "type": "0x2",
+                "nonce": "0x0",
+                "gas": "0x5208",
+                "maxPriorityFeePerGas": "0x1",
+                "maxFeePerGas": "0x2",
+                "value": "0x0",
+                "input": "0x",
+                "v": "0x0",
+                "r": "0x1",
+                "s": "0x2",
+                "to": "0x0202020202020202020202020202020202020202",
+                "chainId": "0x1",
+                "accessList": [],
+                "hash": "0x0303030303030303030303030303030303030303030303030303030303030303"
+            }
+        }))
+        .unwrap();
+
+        let tx = Eip1559TransactionRequest::pay(Address::repeat_byte(2), 0)
+            .from(from)
+            .nonce(0)
+            .gas(21_000)
+            .max_priority_fee_per_gas(1)
+            .max_fee_per_gas(2);
+        let signed = provider.sign_transaction(tx.clone()).await.unwrap();
+        assert_eq!(signed.raw, raw);
+        assert_eq!(signed.tx.from, from);
+        assert_eq!(signed.tx.gas_price, 2.into());
+        assert_eq!(signed.tx.hash, H256::repeat_byte(3));
+        mock.assert_request("eth_signTransaction", [TypedTransaction::Eip1559(tx)])
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn verifies_signatures() {
+        use ethers::signers::{LocalWallet, Signer};
+
+        let (provider, mock) = Provider::mocked();
+        let wallet: LocalWallet =
+            "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+                .parse()
+                .unwrap();
+        let hash = utils::hash_message("hello");
+        let signature = Bytes::from(wallet.sign_message("hello").await.unwrap().to_vec());
+
+        // signatures of externally owned accounts are recovered locally
+        assert!(provider
+            .verify_signature(wallet.address(), hash, &signature)
+            .await
+            .unwrap());
+        mock.assert_calls(&[]);
+
+        // contracts are asked whether the signature is valid
+        let wallet_contract = Address::repeat_byte(1);
+        mock.push_response("eth_getCode", Bytes::from(vec![0x60, 0x80]))
+            .unwrap();
+        let mut magic_value = [0u8; 32];
+        magic_value[..4].copy_from_slice(&EIP1271_MAGIC_VALUE);
+        mock.push_response("eth_call", Bytes::from(magic_value.to_vec()))
+            .unwrap();
+        assert!(provider
+            .verify_signature(wallet_contract, hash, &signature)
+            .await
+            .unwrap());
+        mock.assert_calls(&["eth_getCode", "eth_call"]);
+
+        // as well as rejecting the signatures when reverting
+        mock.push_response("eth_getCode", Bytes::from(vec![0x60, 0x80]))
+            .unwrap();
+        mock.push_error(
+            "eth_call",
+            JsonRpcError {
+                code: 3,
+                message: "execution reverted".to_owned(),
+                data: None,
+            },
+        );
+        assert!(!provider
+            .verify_signature(wallet_contract, hash, &signature)
+            .await
+            .unwrap());
+
+        // while other errors are returned
+        mock.push_response("eth_getCode", Bytes::from(vec![0x60, 0x80]))
+            .unwrap();
+        mock.push_error(
+            "eth_call",
+            JsonRpcError {
+                code: -32000,
+                message: "header not found".to_owned(),
+                data: None,
+            },
+        );
+        provider
+            .verify_signature(wallet_contract, hash, &signature)
+            .await
+            .unwrap_err();
+
+        // other accounts have no code
+        mock.push_response("eth_getCode", Bytes::default()).unwrap();
+        assert!(!provider
+            .verify_signature(Address::repeat_byte(2), hash, &signature)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn personal_sign_request() {
+        let (provider, mock) = Provider::mocked();
+        let from = Address::repeat_byte(1);
+        let signature = Signature {
+            r: H256::repeat_byte(2),
+            s: H256::repeat_byte(3),
+            v: 28,
+        };
+        mock.push::<String, _>(format!("0x{}", hex::encode(signature.to_vec())))
+            .unwrap();
+
+        let sig = provider
+            .personal_sign(b"hello".to_vec(), &from)
+            .await
+            .unwrap();
+        assert_eq!(sig, signature);
+        // the data precedes the account, unlike with `eth_sign`
+        mock.assert_request("personal_sign", (Bytes::from(b"hello".to_vec()), from))
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn uninstalls_watched_filters() {
+        let (provider, mock) = Provider::mocked();
+        mock.push_response("eth_newFilter", U256::from(7)).unwrap();
+        mock.push_response("eth_uninstallFilter", true).unwrap();
+
+        let watcher = provider.watch(&Filter::new()).await.unwrap();
+        assert!(watcher.uninstall().await.unwrap());
+
+        mock.assert_request("eth_newFilter", [Filter::new()])
+            .unwrap();
+        mock.assert_request("eth_uninstallFilter", [U256::from(7)])
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn resolves_wildcard_names() {
+        let (provider, mock) = Provider::mocked();
+        let resolver = Address::repeat_byte(1);
+        let addr = Address::repeat_byte(2);
+        let encode = |token| Bytes::from(abi::encode(&[token]));
+        for response in vec![
+            // no resolver for the name itself, but one for its parent
+            encode(abi::Token::Address(Address::zero())),
+            encode(abi::Token::Address(resolver)),
+            // which supports wildcards
+            encode(abi::Token::Bool(true)),
+            encode(abi::Token::Bytes(abi::encode(&[abi::Token::Address(addr)]))),
+        ] {
+            mock.push_response::<Bytes, _>("eth_call", response)
+                .unwrap();
+        }
+
+        assert_eq!(provider.resolve_name("sub.wild.eth").await.unwrap(), addr);
+
+        let block = BlockNumber::Latest;
+        for tx in vec![
+            ens::get_resolver(ens::ENS_ADDRESS, "sub.wild.eth"),
+            ens::get_resolver(ens::ENS_ADDRESS, "wild.eth"),
+            ens::supports_interface(resolver, ens::WILDCARD_RESOLVE_SELECTOR),
+            ens::resolve_wildcard(resolver, ens::ADDR_SELECTOR, "sub.wild.eth", None).unwrap(),
+        ] {
+            mock.assert_request("eth_call", (TypedTransaction::Legacy(tx), block))
+                .unwrap();
+        }
+
+        // the parent's resolver does not support wildcards
+        for response in vec![
+            encode(abi::Token::Address(Address::zero())),
+            encode(abi::Token::Address(resolver)),
+            encode(abi::Token::Bool(false)),
+        ] {
+            mock.push_response::<Bytes, _>("eth_call", response)
+                .unwrap();
+        }
+        assert!(matches!(
+            provider.resolve_name("sub.wild.eth").await.unwrap_err(),
+            ProviderError::EnsError(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn validates_offchain_lookup_sender() {
+        let (provider, mock) = Provider::mocked();
+        let resolver = Address::repeat_byte(1);
+        let params = abi::encode(&[
+            // the lookup is requested by another contract than the resolver
+            abi::Token::Address(Address::repeat_byte(2)),
+            abi::Token::Array(vec![abi::Token::String(
+                "https://example.com/{data}".to_owned(),
+            )]),
+            abi::Token::Bytes(vec![]),
+            abi::Token::FixedBytes(vec![0; 4]),
+            abi::Token::Bytes(vec![]),
+        ]);
+        let data = [&[0x55, 0x6f, 0x18, 0x30][..], &params].concat();
+        mock.push_response::<Bytes, _>(
+            "eth_call",
+            Bytes::from(abi::encode(&[abi::Token::Address(resolver)])),
+        )
+        .unwrap();
+        mock.push_error(
+            "eth_call",
+            JsonRpcError {
+                code: 3,
+                message: "execution reverted".to_owned(),
+                data: Some(format!("0x{}", hex::encode(data)).into()),
+            },
+        );
+
+        assert!(matches!(
+            provider.resolve_name("offchain.eth").await.unwrap_err(),
+            ProviderError::CcipError(_)
+        ));
+        // the gateway was not queried, so the callback was not called
+        assert_eq!(mock.call_count("eth_call"), 2);
+    }
+
+    #[tokio::test]
+    async fn resolves_name_for_coin() {
+        let (provider, mock) = Provider::mocked();
+        let resolver = Address::repeat_byte(1);
+        let script = hex::decode("76a91462e907b15cbf27d5425399ebf6f0fb50ebb88f1888ac").unwrap();
+        mock.push(Bytes::from(abi::encode(&[abi::Token::Bytes(script)])))
+            .unwrap();
+        mock.push(Bytes::from(abi::encode(&[abi::Token::Address(resolver)])))
+            .unwrap();
+
+        let addr = provider
+            .resolve_name_for_coin("satoshi.eth", ens::COIN_TYPE_BTC)
+            .await
+            .unwrap();
+        assert_eq!(addr, "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa");
+        mock.assert_request(
+            "eth_call",
+            (
+                TypedTransaction::Legacy(ens::get_resolver(ens::ENS_ADDRESS, "satoshi.eth")),
+                BlockNumber::Latest,
+            ),
+        )
+        .unwrap();
+        let parameters = ens::coin_parameters(ens::COIN_TYPE_BTC);
+        mock.assert_request(
+            "eth_call",
+            (
+                TypedTransaction::Legacy(ens::resolve(
+                    resolver,
+                    ens::COIN_ADDR_SELECTOR,
+                    "satoshi.eth",
+                    Some(&parameters),
+                )),
+                BlockNumber::Latest,
+            ),
+        )
+        .unwrap();
+
+        // names without an address for the coin have an empty record
+        mock.push(Bytes::from(abi::encode(&[abi::Token::Bytes(vec![])])))
+            .unwrap();
+        mock.push(Bytes::from(abi::encode(&[abi::Token::Address(resolver)])))
+            .unwrap();
+        assert!(matches!(
+            provider
+                .resolve_name_for_coin("satoshi.eth", ens::COIN_TYPE_LTC)
+                .await
+                .unwrap_err(),
+            ProviderError::EnsError(_)
+        ));
+
+        // resolvers without `addr(bytes32,uint256)` return no data
+        mock.push(Bytes::default()).unwrap();
+        mock.push(Bytes::from(abi::encode(&[abi::Token::Address(resolver)])))
+            .unwrap();
+        assert!(matches!(
+            provider
+                .resolve_name_for_coin("satoshi.eth", ens::COIN_TYPE_LTC)
+                .await
+                .unwrap_err(),
+            ProviderError::EnsError(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn resolves_avatar_nft() {
+        let (provider, mock) = Provider::mocked();
+        let resolver = Address::repeat_byte(1);
+        let owner = Address::repeat_byte(2);
+        let encode = |token| Bytes::from(abi::encode(&[token]));
+        let metadata = format!(
+            "data:application/json;base64,{}",
+            base64::encode(
+                r#"{"name":"punk","image":"ipfs://QmQsQgpda6JAYkFoeVcj5iPbwV3xRcvaiXv3bhp1VuYUqw"}"#
+            )
+        );
+        let avatar = "eip155:1/erc721:0xb47e3cd837dDF8e4c57F05d70Ab865de6e193BBB/2430";
+
+        // the avatar record of the name, then the owner of the NFT and its metadata
+        let push_calls = |mock: &MockProvider, token_owner: Bytes, chain_id: u64| {
+            mock.push_response("eth_chainId", U256::from(chain_id))
+                .unwrap();
+            for data in [
+                encode(abi::Token::Address(resolver)),
+                encode(abi::Token::String(avatar.to_owned())),
+                encode(abi::Token::Address(resolver)),
+                encode(abi::Token::Address(owner)),
+                token_owner,
+                encode(abi::Token::String(metadata.clone())),
+            ] {
+                mock.push_response("eth_call", data).unwrap();
+            }
+        };
+
+        push_calls(&mock, encode(abi::Token::Address(owner)), 1);
+        let url = provider.resolve_avatar("punk.eth").await.unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://ipfs.io/ipfs/QmQsQgpda6JAYkFoeVcj5iPbwV3xRcvaiXv3bhp1VuYUqw"
+        );
+        // the text record is read with the `avatar` key
+        mock.assert_request(
+            "eth_call",
+            (
+                TypedTransaction::Legacy(ens::get_resolver(ens::ENS_ADDRESS, "punk.eth")),
+                BlockNumber::Latest,
+            ),
+        )
+        .unwrap();
+        let parameters = ens::field_parameters("avatar");
+        mock.assert_request(
+            "eth_call",
+            (
+                TypedTransaction::Legacy(ens::resolve(
+                    resolver,
+                    ens::FIELD_SELECTOR,
+                    "punk.eth",
+                    Some(&parameters),
+                )),
+                BlockNumber::Latest,
+            ),
+        )
+        .unwrap();
+
+        // the NFT must be owned by the name's address
+        let (provider, mock) = Provider::mocked();
+        push_calls(
+            &mock,
+            encode(abi::Token::Address(Address::repeat_byte(3))),
+            1,
+        );
+        assert!(matches!(
+            provider.resolve_avatar("punk.eth").await.unwrap_err(),
+            ProviderError::EnsNotOwned(name) if name == "punk.eth"
+        ));
+
+        // malformed results of the NFT contract are errors
+        let (provider, mock) = Provider::mocked();
+        push_calls(&mock, Bytes::default(), 1);
+        assert!(matches!(
+            provider.resolve_avatar("punk.eth").await.unwrap_err(),
+            ProviderError::EnsError(name) if name == "punk.eth"
+        ));
+
+        // and NFTs of other chains are rejected
+        let (provider, mock) = Provider::mocked();
+        push_calls(&mock, encode(abi::Token::Address(owner)), 5);
+        assert!(matches!(
+            provider.resolve_avatar("punk.eth").await.unwrap_err(),
+            ProviderError::CustomError(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn mining_requests() {
+        let (provider, mock) = Provider::mocked();
+        let work = Work {
+            pow_hash: H256::repeat_byte(1),
+            seed_hash: H256::repeat_byte(2),
+            target: H256::repeat_byte(3),
+            number: None,
+        };
+        mock.push(true).unwrap();
+        mock.push(work).unwrap();
+        mock.push(Address::repeat_byte(4)).unwrap();
+        mock.push(U256::from(1000)).unwrap();
+        mock.push(true).unwrap();
+
+        assert!(provider.mining().await.unwrap());
+        mock.assert_request("eth_mining", ()).unwrap();
+        assert_eq!(provider.hashrate().await.unwrap(), 1000.into());
+        mock.assert_request("eth_hashrate", ()).unwrap();
+        assert_eq!(provider.coinbase().await.unwrap(), Address::repeat_byte(4));
+        mock.assert_request("eth_coinbase", ()).unwrap();
+        assert_eq!(provider.get_work().await.unwrap(), work);
+        mock.assert_request("eth_getWork", ()).unwrap();
+
+        let nonce = H64::from_low_u64_be(42);
+        let mix_digest = H256::repeat_byte(5);
+        assert!(provider
+            .submit_work(nonce, work.pow_hash, mix_digest)
+            .await
+            .unwrap());
+        mock.assert_request(
+            "eth_submitWork",
+            ("0x000000000000002a", work.pow_hash, mix_digest),
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn sends_typed_transactions() {
+        let (provider, mock) = Provider::mocked();
+        let tx = Eip1559TransactionRequest::pay(Address::repeat_byte(1), 100)
+            .from(Address::repeat_byte(2))
+            .gas(21_000)
+            .max_fee_per_gas(20)
+            .max_priority_fee_per_gas(2);
+        let hash = H256::repeat_byte(3);
+        mock.push(hash).unwrap();
+
+        let pending = provider.send_transaction(tx.clone(), None).await.unwrap();
+        assert_eq!(*pending, hash);
+        mock.assert_request(
+            "eth_sendTransaction",
+            [TypedTransaction::Eip1559(tx.clone())],
+        )
+        .unwrap();
+
+        // the fees can not be suggested on chains without a base fee
+        let tx = Eip1559TransactionRequest {
+            max_fee_per_gas: None,
+            ..tx
+        };
+        mock.push(U256::from(10)).unwrap();
+        mock.push(Block::<TxHash>::default()).unwrap();
+        provider.send_transaction(tx, None).await.unwrap_err();
+
+        // the gas of transactions is estimated
+        let (provider, mock) = Provider::mocked();
+        let tx = TransactionRequest::pay(Address::repeat_byte(1), 100);
+        mock.push(hash).unwrap();
+        mock.push(U256::from(21_000)).unwrap();
+        provider.send_transaction(tx.clone(), None).await.unwrap();
+        mock.assert_request("eth_estimateGas", [TypedTransaction::Legacy(tx.clone())])
+            .unwrap();
+        mock.assert_request(
+            "eth_sendTransaction",
+            [TypedTransaction::Legacy(tx.gas(21_000))],
+        )
+        .unwrap();
+    }
+
     #[tokio::test]
     async fn parity_block_receipts() {
         let url = match std::env::var("PARITY") {
@@ -20,13 +20,16 @@ pub trait PubsubClient: JsonRpcClient {
     /// Add a subscription to this transport
     fn subscribe<T: Into<U256>>(&self, id: T) -> Result<Self::NotificationStream, Self::Error>;
 
-    /// Remove a subscription from this transport
+    /// Remove a subscription from this transport, and cancel it on the node
     fn unsubscribe<T: Into<U256>>(&self, id: T) -> Result<(), Self::Error>;
 }
 
 #[must_use = "subscriptions do nothing unless you stream them"]
 #[pin_project(PinnedDrop)]
 /// Streams data from an installed filter via `eth_subscribe`
+///
+/// The subscription is cancelled on the node when the stream is dropped, so it does not
+/// need to be [unsubscribed](SubscriptionStream::unsubscribe) explicitly.
 pub struct SubscriptionStream<'a, P: PubsubClient, R: DeserializeOwned> {
     /// The subscription's installed id on the ethereum node
     pub id: U256,
@@ -91,9 +94,8 @@ where
     R: DeserializeOwned,
 {
     fn drop(self: Pin<&mut Self>) {
-        // on drop it removes the handler from the websocket so that it stops
-        // getting populated. We need to call `unsubscribe` explicitly to cancel
-        // the subscription
+        // on drop it removes the handler from the transport so that it stops
+        // getting populated, and the transport cancels the subscription on the node
         let _ = (*self.provider).as_ref().unsubscribe(self.id);
     }
 }
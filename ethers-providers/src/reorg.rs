@@ -0,0 +1,269 @@
+use crate::Middleware;
+use ethers_core::types::{Block, TxHash, H256, U64};
+use futures_channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use futures_util::{pin_mut, stream::Stream, StreamExt};
+use std::collections::VecDeque;
+
+/// The default number of recent blocks tracked by a [`ReorgWatcher`]
+pub const DEFAULT_REORG_WINDOW: usize = 64;
+
+/// A chain reorganization, replacing the most recent blocks of the canonical chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reorg {
+    /// The number of blocks which were removed from the canonical chain. If the reorg is
+    /// deeper than the watcher's window, this is the size of the window.
+    pub depth: usize,
+    /// The hashes of the removed blocks, oldest first
+    pub old: Vec<H256>,
+    /// The hashes of the blocks which replaced them, oldest first
+    pub new: Vec<H256>,
+    /// Whether the reorg is deeper than the watcher's window, in which case the common
+    /// ancestor was not found and the blocks before `old` and `new` may differ as well
+    pub exceeds_window: bool,
+}
+
+/// Follows the new heads of the chain and detects chain reorganizations, by keeping a
+/// rolling window of the hashes of the most recent canonical blocks.
+///
+/// Each new head is linked to the window through its parent hash, fetching at most a
+/// window's worth of missing ancestors. When the head does not extend the tip of the
+/// window, the blocks after the common ancestor were reorged out and a [`Reorg`] is sent
+/// to all subscribers. If no common ancestor is found, the tip of the window is checked
+/// against the canonical chain to tell a reorg deeper than the window from a gap between
+/// the heads.
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers::providers::{Middleware, Provider, ReorgWatcher, Ws};
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Provider::new(Ws::connect("ws://localhost:8545").await?);
+///
+/// let mut watcher = ReorgWatcher::new(&provider);
+/// let mut reorgs = watcher.subscribe();
+/// // hand the receiver over to the task handling reorgs, e.g. rolling back indexed data
+///
+/// let heads = provider.subscribe_blocks().await?;
+/// watcher.run(heads).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ReorgWatcher<'a, M> {
+    client: &'a M,
+    window: VecDeque<(U64, H256)>,
+    window_size: usize,
+    subscribers: Vec<UnboundedSender<Reorg>>,
+}
+
+impl<'a, M: Middleware> ReorgWatcher<'a, M> {
+    /// Creates a watcher tracking the last [`DEFAULT_REORG_WINDOW`] blocks
+    pub fn new(client: &'a M) -> Self {
+        Self {
+            client,
+            window: VecDeque::new(),
+            window_size: DEFAULT_REORG_WINDOW,
+            subscribers: Vec::new(),
+        }
+    }
+
+    /// Sets the number of recent blocks tracked, i.e. the deepest reorg which is fully
+    /// reported
+    pub fn window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size.max(1);
+        self
+    }
+
+    /// Returns a receiver of the detected reorgs
+    pub fn subscribe(&mut self) -> UnboundedReceiver<Reorg> {
+        let (sender, receiver) = unbounded();
+        self.subscribers.push(sender);
+        receiver
+    }
+
+    /// Returns the number and hash of the tip of the tracked canonical chain
+    pub fn tip(&self) -> Option<(U64, H256)> {
+        self.window.back().copied()
+    }
+
+    /// Processes the new heads of the stream until it ends
+    pub async fn run<S: Stream<Item = Block<TxHash>>>(&mut self, heads: S) -> Result<(), M::Error> {
+        pin_mut!(heads);
+        while let Some(head) = heads.next().await {
+            self.process_head(&head).await?;
+        }
+        Ok(())
+    }
+
+    /// Processes a new head, returning the reorg it caused, if any. Pending blocks and
+    /// blocks which are already tracked are ignored.
+    pub async fn process_head(&mut self, head: &Block<TxHash>) -> Result<Option<Reorg>, M::Error> {
+        let (number, hash) = match (head.number, head.hash) {
+            (Some(number), Some(hash)) => (number, hash),
+            _ => return Ok(None),
+        };
+        if self.window.iter().any(|(_, h)| *h == hash) {
+            return Ok(None);
+        }
+
+        // walk back from the head until reaching a tracked block, fetching no more
+        // ancestors than the window can hold
+        let mut branch = vec![(number, hash)];
+        let mut parent = head.parent_hash;
+        let ancestor = loop {
+            if let Some(pos) = self.window.iter().position(|(_, h)| *h == parent) {
+                break Some(pos);
+            }
+            if branch.len() >= self.window_size {
+                break None;
+            }
+            let lowest = branch.last().map(|(number, _)| *number).unwrap_or_default();
+            match self.window.front() {
+                Some((oldest, _)) if lowest > *oldest => {}
+                _ => break None,
+            }
+            match self.client.get_block(parent).await? {
+                Some(Block {
+                    number: Some(number),
+                    hash: Some(hash),
+                    parent_hash,
+                    ..
+                }) => {
+                    branch.push((number, hash));
+                    parent = parent_hash;
+                }
+                _ => break None,
+            }
+        };
+
+        // without a common ancestor, the window was either reorged out entirely or the
+        // branch is too long to be linked to it, in which case its tip is still canonical
+        let exceeds_window = match (ancestor, self.window.back()) {
+            (None, Some((tip_number, tip_hash))) => {
+                let tip_hash = *tip_hash;
+                let canonical = self.client.get_block(*tip_number).await?;
+                canonical.and_then(|block| block.hash) != Some(tip_hash)
+            }
+            _ => false,
+        };
+
+        // the blocks after the common ancestor are replaced by the branch, or the whole
+        // window if the reorg is deeper than it
+        let old: Vec<H256> = match ancestor {
+            Some(pos) => self.window.drain(pos + 1..),
+            None => self.window.drain(..),
+        }
+        .map(|(_, hash)| hash)
+        .collect();
+        branch.reverse();
+        let new = branch.iter().map(|(_, hash)| *hash).collect();
+        self.window.extend(branch);
+        while self.window.len() > self.window_size {
+            self.window.pop_front();
+        }
+
+        if old.is_empty() || (ancestor.is_none() && !exceeds_window) {
+            return Ok(None);
+        }
+        let reorg = Reorg {
+            depth: old.len(),
+            old,
+            new,
+            exceeds_window,
+        };
+        self.subscribers
+            .retain(|subscriber| subscriber.unbounded_send(reorg.clone()).is_ok());
+        Ok(Some(reorg))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Provider;
+
+    fn block(number: u64, hash: u8, parent: u8) -> Block<TxHash> {
+        Block {
+            number: Some(number.into()),
+            hash: Some(H256::repeat_byte(hash)),
+            parent_hash: H256::repeat_byte(parent),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn detects_reorgs() {
+        let (provider, mock) = Provider::mocked();
+        let mut watcher = ReorgWatcher::new(&provider);
+        let mut reorgs = watcher.subscribe();
+
+        for head in &[block(1, 1, 0), block(2, 2, 1), block(3, 3, 2)] {
+            assert_eq!(watcher.process_head(head).await.unwrap(), None);
+        }
+        // the head which was already seen is ignored
+        assert_eq!(watcher.process_head(&block(2, 2, 1)).await.unwrap(), None);
+
+        // block 3' builds on 2', which replaced 2
+        mock.push(block(2, 0x22, 1)).unwrap();
+        let reorg = watcher
+            .process_head(&block(3, 0x33, 0x22))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            reorg,
+            Reorg {
+                depth: 2,
+                old: vec![H256::repeat_byte(2), H256::repeat_byte(3)],
+                new: vec![H256::repeat_byte(0x22), H256::repeat_byte(0x33)],
+                exceeds_window: false,
+            }
+        );
+        assert_eq!(reorgs.try_next().unwrap(), Some(reorg));
+        assert_eq!(watcher.tip(), Some((3.into(), H256::repeat_byte(0x33))));
+
+        // a skipped block is fetched without reporting a reorg
+        mock.push(block(4, 4, 0x33)).unwrap();
+        assert_eq!(watcher.process_head(&block(5, 5, 4)).await.unwrap(), None);
+        assert!(reorgs.try_next().is_err());
+        assert_eq!(watcher.tip(), Some((5.into(), H256::repeat_byte(5))));
+    }
+
+    #[tokio::test]
+    async fn bounds_the_ancestor_walk() {
+        let (provider, mock) = Provider::mocked();
+        let mut watcher = ReorgWatcher::new(&provider).window_size(2);
+        let mut reorgs = watcher.subscribe();
+
+        for head in &[block(1, 1, 0), block(2, 2, 1)] {
+            assert_eq!(watcher.process_head(head).await.unwrap(), None);
+        }
+
+        // a gap longer than the window is not a reorg while the tip is still canonical
+        mock.push(block(2, 2, 1)).unwrap();
+        mock.push(block(9, 9, 8)).unwrap();
+        assert_eq!(watcher.process_head(&block(10, 10, 9)).await.unwrap(), None);
+        assert!(reorgs.try_next().is_err());
+        assert_eq!(watcher.tip(), Some((10.into(), H256::repeat_byte(10))));
+
+        // a reorg deeper than the window replaces it entirely
+        mock.push(block(10, 0x10, 0x09)).unwrap();
+        mock.push(block(10, 0xa0, 0x90)).unwrap();
+        let reorg = watcher
+            .process_head(&block(11, 0xb0, 0xa0))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            reorg,
+            Reorg {
+                depth: 2,
+                old: vec![H256::repeat_byte(9), H256::repeat_byte(10)],
+                new: vec![H256::repeat_byte(0xa0), H256::repeat_byte(0xb0)],
+                exceeds_window: true,
+            }
+        );
+        assert_eq!(reorgs.try_next().unwrap(), Some(reorg));
+    }
+}
@@ -1,6 +1,6 @@
-use crate::{JsonRpcClient, Middleware, PinBoxFut, Provider};
+use crate::{JsonRpcClient, Middleware, PinBoxFut, Provider, ProviderError, ValidationError};
 
-use ethers_core::types::U256;
+use ethers_core::types::{Block, U256, U64};
 
 use futures_core::stream::Stream;
 use futures_timer::Delay;
@@ -23,6 +23,30 @@ pub fn interval(duration: Duration) -> impl Stream<Item = ()> + Send + Unpin {
 /// The default polling interval for filters and pending transactions
 pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(7000);
 
+/// Validates that a stream of blocks, e.g. from `subscribe_blocks`, never goes back to a
+/// lower block number. Blocks without a number (pending blocks) are not validated.
+pub fn validate_block_numbers<S, TX>(
+    stream: S,
+) -> impl Stream<Item = Result<Block<TX>, ValidationError>>
+where
+    S: Stream<Item = Block<TX>>,
+{
+    stream.scan(None, |last: &mut Option<U64>, block| {
+        let res = match (*last, block.number) {
+            (Some(previous), Some(current)) if current < previous => {
+                Err(ValidationError::NonMonotonicBlock { previous, current })
+            }
+            (_, number) => {
+                if number.is_some() {
+                    *last = number;
+                }
+                Ok(block)
+            }
+        };
+        futures_util::future::ready(Some(res))
+    })
+}
+
 enum FilterWatcherState<'a, R> {
     WaitForInterval,
     GetFilterChanges(PinBoxFut<'a, Vec<R>>),
@@ -32,6 +56,11 @@ enum FilterWatcherState<'a, R> {
 #[must_use = "filters do nothing unless you stream them"]
 #[pin_project]
 /// Streams data from an installed filter via `eth_getFilterChanges`
+///
+/// The filter is not uninstalled when the watcher is dropped, as that requires a request
+/// to the node. Nodes expire filters which are not polled for a few minutes, but
+/// long-running services should [`uninstall`](FilterWatcher::uninstall) the watchers they
+/// stop using.
 pub struct FilterWatcher<'a, P, R> {
     /// The filter's installed id on the ethereum node
     pub id: U256,
@@ -70,6 +99,12 @@ where
     pub fn stream(self) -> Pin<Box<Self>> {
         Box::pin(self)
     }
+
+    /// Stops the watcher and uninstalls its filter from the node, returning whether the
+    /// filter was still installed
+    pub async fn uninstall(self) -> Result<bool, ProviderError> {
+        self.provider.uninstall_filter(self.id).await
+    }
 }
 
 // Pattern for flattening the returned Vec of filter changes taken from
@@ -0,0 +1,147 @@
+use hmac::{Hmac, Mac, NewMac};
+use sha2::Sha256;
+use std::{
+    fmt,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The credentials sent in the `Authorization` header of the requests of a transport
+#[derive(Clone, PartialEq, Eq)]
+pub enum Authorization {
+    /// HTTP basic authentication with the base64 encoded `username:password`
+    Basic(String),
+    /// A bearer token, e.g. the API key of an RPC vendor
+    Bearer(String),
+    /// A JWT signed with the shared secret of the node, as required by the authenticated
+    /// (engine API) endpoints of geth. A new token is issued for every request, since the
+    /// node rejects tokens issued more than a minute ago.
+    Jwt(JwtKey),
+}
+
+impl Authorization {
+    /// Basic authentication with the username and password
+    pub fn basic(username: impl AsRef<str>, password: impl AsRef<str>) -> Self {
+        let credentials = format!("{}:{}", username.as_ref(), password.as_ref());
+        Authorization::Basic(base64::encode(credentials))
+    }
+
+    /// Authentication with the bearer token
+    pub fn bearer(token: impl Into<String>) -> Self {
+        Authorization::Bearer(token.into())
+    }
+
+    /// Authentication with JWTs signed by the secret
+    pub fn jwt(key: JwtKey) -> Self {
+        Authorization::Jwt(key)
+    }
+
+    /// Returns the value of the `Authorization` header
+    pub fn header_value(&self) -> String {
+        match self {
+            Authorization::Basic(credentials) => format!("Basic {}", credentials),
+            Authorization::Bearer(token) => format!("Bearer {}", token),
+            Authorization::Jwt(key) => format!("Bearer {}", key.issue()),
+        }
+    }
+}
+
+// the credentials must not end up in logs
+impl fmt::Debug for Authorization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            Authorization::Basic(_) => "Basic",
+            Authorization::Bearer(_) => "Bearer",
+            Authorization::Jwt(_) => "Jwt",
+        };
+        write!(f, "Authorization::{}(..)", kind)
+    }
+}
+
+/// The 256 bit secret shared with a node, used to sign HS256 JWTs
+#[derive(Clone, PartialEq, Eq)]
+pub struct JwtKey([u8; 32]);
+
+impl JwtKey {
+    /// Instantiates the key from the raw secret
+    pub fn new(secret: [u8; 32]) -> Self {
+        Self(secret)
+    }
+
+    /// Parses the hex encoded secret, e.g. the contents of geth's `jwtsecret` file
+    pub fn from_hex(secret: &str) -> Result<Self, hex::FromHexError> {
+        let secret = secret.trim();
+        let secret = secret.strip_prefix("0x").unwrap_or(secret);
+        let mut key = [0; 32];
+        hex::decode_to_slice(secret, &mut key)?;
+        Ok(Self(key))
+    }
+
+    /// Issues a token for the current time
+    pub fn issue(&self) -> String {
+        let iat = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("time went backwards")
+            .as_secs();
+        self.issue_at(iat)
+    }
+
+    /// Issues a token with the `iat` claim set to the unix timestamp
+    fn issue_at(&self, iat: u64) -> String {
+        let header =
+            base64::encode_config(r#"{"alg":"HS256","typ":"JWT"}"#, base64::URL_SAFE_NO_PAD);
+        let claims =
+            base64::encode_config(format!(r#"{{"iat":{}}}"#, iat), base64::URL_SAFE_NO_PAD);
+        let message = format!("{}.{}", header, claims);
+
+        let mut mac = Hmac::<Sha256>::new_varkey(&self.0).expect("HMAC accepts keys of any size");
+        mac.update(message.as_bytes());
+        let signature = base64::encode_config(mac.finalize().into_bytes(), base64::URL_SAFE_NO_PAD);
+        format!("{}.{}", message, signature)
+    }
+}
+
+impl fmt::Debug for JwtKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("JwtKey(..)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_credentials() {
+        assert_eq!(
+            Authorization::basic("user", "pass").header_value(),
+            "Basic dXNlcjpwYXNz"
+        );
+        assert_eq!(
+            Authorization::bearer("token").header_value(),
+            "Bearer token"
+        );
+    }
+
+    #[test]
+    fn issues_jwts() {
+        let key = JwtKey::from_hex(
+            "0x7365637265747365637265747365637265747365637265747365637265747365\n",
+        )
+        .unwrap();
+        let token = key.issue_at(1_600_000_000);
+        let parts: Vec<&str> = token.split('.').collect();
+        assert_eq!(parts.len(), 3);
+        assert_eq!(parts[0], "eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9");
+        assert_eq!(
+            base64::decode_config(parts[1], base64::URL_SAFE_NO_PAD).unwrap(),
+            br#"{"iat":1600000000}"#
+        );
+
+        let mut mac = Hmac::<Sha256>::new_varkey(&key.0).unwrap();
+        mac.update(format!("{}.{}", parts[0], parts[1]).as_bytes());
+        let signature = base64::decode_config(parts[2], base64::URL_SAFE_NO_PAD).unwrap();
+        mac.verify(&signature).unwrap();
+
+        assert!(JwtKey::from_hex("0x1234").is_err());
+    }
+}
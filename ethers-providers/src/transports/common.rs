@@ -1,5 +1,5 @@
 // Code adapted from: https://github.com/althea-net/guac_rs/tree/master/web3/src/jsonrpc
-use ethers_core::types::U256;
+use ethers_core::types::{Bytes, U256};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::fmt;
@@ -26,6 +26,36 @@ impl fmt::Display for JsonRpcError {
     }
 }
 
+impl JsonRpcError {
+    /// Returns whether the node does not support the requested method
+    pub fn is_method_not_found(&self) -> bool {
+        self.code == -32601
+    }
+
+    /// Returns whether the error is the revert of the execution of a call or transaction.
+    /// Geth uses the code 3 for reverts with data, but -32000 for the ones without.
+    pub fn is_revert(&self) -> bool {
+        self.code == 3
+            || self.as_revert_data().is_some()
+            || self.message.to_lowercase().contains("execution reverted")
+    }
+
+    /// Returns the data the call reverted with, if the node included it in the error.
+    /// Geth returns it as a hex string, while Ganache nests it in a `return` field.
+    pub fn as_revert_data(&self) -> Option<Bytes> {
+        let data = match self.data.as_ref()? {
+            Value::String(data) => data.as_str(),
+            Value::Object(map) => map
+                .values()
+                .find_map(|value| value.get("return")?.as_str())?,
+            _ => return None,
+        };
+        hex::decode(data.trim_start_matches("0x"))
+            .ok()
+            .map(Into::into)
+    }
+}
+
 fn is_zst<T>(_t: &T) -> bool {
     std::mem::size_of::<T>() == 0
 }
@@ -54,6 +84,11 @@ pub struct Subscription<R> {
     pub result: R,
 }
 
+/// The id of the `eth_unsubscribe` requests sent by the pubsub transports when a
+/// subscription stream is dropped. Their responses are ignored, as the ids of other
+/// requests count up from 0.
+pub(crate) const UNSUBSCRIBE_REQUEST_ID: u64 = u64::MAX;
+
 impl<'a, T> Request<'a, T> {
     /// Creates a new JSON RPC request
     pub fn new(id: u64, method: &'a str, params: T) -> Self {
@@ -117,4 +152,28 @@ mod tests {
             r#"{"id":300,"jsonrpc":"2.0","method":"method_name","params":1}"#
         );
     }
+
+    #[test]
+    fn extracts_revert_data() {
+        let geth: JsonRpcError = serde_json::from_str(
+            r#"{"code":3,"message":"execution reverted","data":"0x4e487b710000000000000000000000000000000000000000000000000000000000000001"}"#,
+        )
+        .unwrap();
+        let data = geth.as_revert_data().unwrap();
+        assert_eq!(data.as_ref().len(), 36);
+        assert_eq!(&data.as_ref()[..4], &[0x4e, 0x48, 0x7b, 0x71]);
+
+        let ganache: JsonRpcError = serde_json::from_str(
+            r#"{"code":-32000,"message":"VM Exception while processing transaction: revert","data":{"0x1bd5e6f5b6e1bb2f1ef5d2c5bbd03dfbe5bd64a7f9ad0fd8cb7c4c3d5a6d7e8f":{"error":"revert","program_counter":130,"return":"0xdeadbeef"},"stack":"RuntimeError: VM Exception","name":"RuntimeError"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            ganache.as_revert_data().unwrap().as_ref(),
+            &[0xde, 0xad, 0xbe, 0xef]
+        );
+
+        let other: JsonRpcError =
+            serde_json::from_str(r#"{"code":-32601,"message":"method not found"}"#).unwrap();
+        assert!(other.as_revert_data().is_none());
+    }
 }
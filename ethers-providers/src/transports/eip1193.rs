@@ -0,0 +1,238 @@
+use crate::{provider::ProviderError, JsonRpcClient};
+
+use async_trait::async_trait;
+use js_sys::{Function, Promise, Reflect, JSON};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use thiserror::Error;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+use super::common::JsonRpcError;
+
+/// A JSON-RPC Client which proxies all requests through the
+/// [EIP-1193](https://eips.ethereum.org/EIPS/eip-1193) provider injected by browser wallets
+/// (e.g. MetaMask) at `window.ethereum`.
+///
+/// Any request which requires a signature, such as `eth_sendTransaction` or `personal_sign`,
+/// will prompt the user in their wallet, so the same contract bindings can be used in
+/// a browser dApp.
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers::providers::{Eip1193, Middleware, Provider};
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Provider::new(Eip1193::new());
+///
+/// // ask the user to connect their wallet
+/// let accounts = provider.request_accounts().await?;
+/// let provider = provider.with_sender(accounts[0]);
+///
+/// let signature = provider.personal_sign(b"hello".to_vec(), &accounts[0]).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Eip1193 {
+    _private: (),
+}
+
+#[derive(Error, Debug)]
+/// Error thrown when sending a request through the injected provider
+pub enum Eip1193Error {
+    /// Thrown if there is no provider injected at `window.ethereum`
+    #[error("no injected ethereum provider found")]
+    ProviderNotFound,
+
+    /// Thrown if the wallet rejected the request
+    #[error(transparent)]
+    JsonRpcError(#[from] JsonRpcError),
+
+    /// Thrown if a JavaScript exception was raised while making the request
+    #[error("javascript error: {0}")]
+    JsError(String),
+
+    /// Serde JSON Error
+    #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
+}
+
+impl From<Eip1193Error> for ProviderError {
+    fn from(src: Eip1193Error) -> Self {
+        ProviderError::JsonRpcClientError(Box::new(src))
+    }
+}
+
+impl From<JsValue> for Eip1193Error {
+    fn from(src: JsValue) -> Self {
+        // wallets reject requests with `{ code, message, data }` objects
+        let field = |name: &str| Reflect::get(&src, &JsValue::from_str(name)).ok();
+        let code = field("code").and_then(|code| code.as_f64());
+        let message = field("message").and_then(|message| message.as_string());
+        // the data is kept as JSON, e.g. for the revert data to be decoded
+        let data = field("data")
+            .filter(|data| !data.is_undefined())
+            .and_then(|data| JSON::stringify(&data).ok())
+            .and_then(|data| data.as_string())
+            .and_then(|data| serde_json::from_str(&data).ok());
+        match (code, message) {
+            (Some(code), Some(message)) => Eip1193Error::JsonRpcError(JsonRpcError {
+                code: code as i64,
+                message,
+                data,
+            }),
+            _ => Eip1193Error::JsError(format!("{:?}", src)),
+        }
+    }
+}
+
+#[async_trait]
+impl JsonRpcClient for Eip1193 {
+    type Error = Eip1193Error;
+
+    /// Sends the request to the injected provider's `request` method
+    async fn request<T: Serialize + Send + Sync, R: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: T,
+    ) -> Result<R, Eip1193Error> {
+        // EIP-1193 expects the params to be omitted or passed as an array
+        let params = match serde_json::to_value(params)? {
+            Value::Null => Value::Array(vec![]),
+            params => params,
+        };
+        let args = serde_json::to_string(&serde_json::json!({
+            "method": method,
+            "params": params,
+        }))?;
+
+        let res = UnsafeSendFuture(Box::pin(async move {
+            let ethereum = injected_provider()?;
+            let request: Function = Reflect::get(&ethereum, &JsValue::from_str("request"))?
+                .dyn_into()
+                .map_err(|_| Eip1193Error::ProviderNotFound)?;
+            let promise: Promise = request.call1(&ethereum, &JSON::parse(&args)?)?.into();
+            let res = JsFuture::from(promise).await?;
+
+            // `undefined` cannot be stringified, so it is mapped to `null`
+            Ok::<_, Eip1193Error>(
+                JSON::stringify(&res)?
+                    .as_string()
+                    .unwrap_or_else(|| "null".to_string()),
+            )
+        }))
+        .await?;
+
+        Ok(serde_json::from_str(&res)?)
+    }
+}
+
+impl Eip1193 {
+    /// Instantiates a client which sends its requests to `window.ethereum`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns true if a wallet has injected a provider in the current page
+    pub fn is_available() -> bool {
+        injected_provider().is_ok()
+    }
+}
+
+fn injected_provider() -> Result<JsValue, Eip1193Error> {
+    let window = web_sys::window().ok_or(Eip1193Error::ProviderNotFound)?;
+    let ethereum = Reflect::get(&window, &JsValue::from_str("ethereum"))?;
+    if ethereum.is_undefined() || ethereum.is_null() {
+        return Err(Eip1193Error::ProviderNotFound);
+    }
+    Ok(ethereum)
+}
+
+/// Wraps a future holding JavaScript values, which are not `Send`, so that it can be
+/// awaited in the `Send` futures of [`JsonRpcClient`]
+struct UnsafeSendFuture<F>(Pin<Box<F>>);
+
+// SAFETY: this module is only compiled for wasm32 targets without the `atomics` target
+// feature, whose code runs on a single thread. The future can thus never be sent to, or
+// polled from, another thread than the one which created its JavaScript values.
+unsafe impl<F> Send for UnsafeSendFuture<F> {}
+
+impl<F: Future> Future for UnsafeSendFuture<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0.as_mut().poll(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Provider;
+    use ethers_core::types::Address;
+    use js_sys::Object;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    /// Injects a mock provider at `window.ethereum`, whose `request` method runs `body`
+    /// with the request arguments as `args`
+    fn inject_provider(body: &str) {
+        let ethereum = Object::new();
+        let request = Function::new_with_args("args", body);
+        Reflect::set(&ethereum, &JsValue::from_str("request"), &request).unwrap();
+        let window = web_sys::window().unwrap();
+        Reflect::set(&window, &JsValue::from_str("ethereum"), &ethereum).unwrap();
+    }
+
+    #[wasm_bindgen_test]
+    async fn proxies_requests() {
+        inject_provider(
+            "if (args.method !== 'eth_requestAccounts' || args.params.length !== 0) {
+                return Promise.reject(new Error('unexpected request'));
+            }
+            return Promise.resolve(['0x0101010101010101010101010101010101010101']);",
+        );
+        assert!(Eip1193::is_available());
+
+        let provider = Provider::new(Eip1193::new());
+        let accounts = provider.request_accounts().await.unwrap();
+        assert_eq!(accounts, vec![Address::repeat_byte(1)]);
+    }
+
+    #[wasm_bindgen_test]
+    async fn surfaces_rejections() {
+        inject_provider(
+            "return Promise.reject({ code: 4001, message: 'User rejected the request.' });",
+        );
+
+        let err = Eip1193::new()
+            .request::<_, Vec<Address>>("eth_requestAccounts", ())
+            .await
+            .unwrap_err();
+        match err {
+            Eip1193Error::JsonRpcError(err) => assert_eq!(err.code, 4001),
+            err => panic!("unexpected error {:?}", err),
+        }
+    }
+
+    #[wasm_bindgen_test]
+    async fn requires_injected_provider() {
+        let window = web_sys::window().unwrap();
+        Reflect::delete_property(&window, &JsValue::from_str("ethereum")).unwrap();
+        assert!(!Eip1193::is_available());
+
+        let err = Eip1193::new()
+            .request::<_, Vec<Address>>("eth_requestAccounts", ())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Eip1193Error::ProviderNotFound));
+    }
+}
@@ -0,0 +1,350 @@
+use super::{as_error_response, http, JsonRpcError};
+use crate::{provider::ProviderError, JsonRpcClient};
+
+use async_trait::async_trait;
+use futures_timer::Delay;
+use futures_util::future::{select, Either};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    fmt::Debug,
+    str::FromStr,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+use thiserror::Error;
+use tracing::trace;
+
+#[derive(Error, Debug)]
+/// Error thrown by a [`FallbackProvider`]
+pub enum FallbackError<E: std::error::Error + 'static> {
+    /// Thrown if the node returned an error response. These are returned as is, since
+    /// the other providers would return the same error.
+    #[error(transparent)]
+    ClientError(E),
+
+    /// Thrown if all providers failed or timed out. Contains the errors of the providers
+    /// which failed, in the order in which they were tried.
+    #[error("all providers failed ({} errors, {timeouts} timeouts)", .errors.len())]
+    AllProvidersFailed { errors: Vec<E>, timeouts: usize },
+
+    /// Thrown if the params could not be serialized
+    #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
+}
+
+impl<E> FallbackError<E>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    /// Returns the JSON-RPC error response of the node, if a provider returned one
+    pub fn as_error_response(&self) -> Option<&JsonRpcError> {
+        match self {
+            FallbackError::ClientError(err) => as_error_response(err),
+            _ => None,
+        }
+    }
+}
+
+impl<E> From<FallbackError<E>> for ProviderError
+where
+    E: std::error::Error + Send + Sync + Into<ProviderError> + 'static,
+{
+    fn from(src: FallbackError<E>) -> Self {
+        match src {
+            FallbackError::ClientError(err) => err.into(),
+            src => ProviderError::JsonRpcClientError(Box::new(src)),
+        }
+    }
+}
+
+/// Methods which must not be sent twice, since the provider may have processed the
+/// request before failing or timing out
+const NON_IDEMPOTENT_METHODS: &[&str] = &["eth_sendTransaction", "eth_sendRawTransaction"];
+
+/// The health of a provider, based on its most recent requests
+#[derive(Debug, Clone, Copy, Default)]
+struct Health {
+    /// The time at which the provider is used again after failing
+    retry_at: Option<Instant>,
+}
+
+impl Health {
+    fn is_healthy(&self, now: Instant) -> bool {
+        self.retry_at.map_or(true, |retry_at| now >= retry_at)
+    }
+}
+
+/// A transport which sends requests to the first healthy provider of an ordered list,
+/// failing over to the next one if it errors or times out. This keeps an application
+/// running when a single RPC vendor has an outage.
+///
+/// A provider which fails is skipped for a cooldown period, after which it is used
+/// again, so that the preferred providers take over once they recover. If all
+/// providers are unhealthy, they are all tried anyway. Error responses of the node,
+/// e.g. reverts, are returned without failing over.
+///
+/// Transactions (`eth_sendTransaction` and `eth_sendRawTransaction`) are only sent to
+/// the first provider and never fail over: a provider which timed out or whose
+/// connection dropped may still have broadcast them.
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers::providers::{FallbackProvider, Http, Provider};
+/// use std::time::Duration;
+///
+/// # fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let fallback = FallbackProvider::<Http>::from_urls(&[
+///     "https://mainnet.infura.io/v3/c60b0bb42f8a4c6481ecd229eddaca27",
+///     "https://eth.llamarpc.com",
+/// ])?
+/// .timeout(Duration::from_secs(5));
+/// let provider = Provider::new(fallback);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct FallbackProvider<T> {
+    providers: Vec<T>,
+    health: Mutex<Vec<Health>>,
+    timeout: Duration,
+    cooldown: Duration,
+}
+
+impl<T> FallbackProvider<T> {
+    /// Instantiates the fallback over the providers, in order of preference, with a
+    /// request timeout of 10 seconds and a cooldown of 30 seconds
+    pub fn new(providers: impl IntoIterator<Item = T>) -> Self {
+        let providers: Vec<T> = providers.into_iter().collect();
+        Self {
+            health: Mutex::new(vec![Health::default(); providers.len()]),
+            providers,
+            timeout: Duration::from_secs(10),
+            cooldown: Duration::from_secs(30),
+        }
+    }
+
+    /// Sets the timeout of a request to a single provider
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the time for which a failed provider is skipped
+    pub fn cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Returns the providers, in order of preference
+    pub fn providers(&self) -> &[T] {
+        &self.providers
+    }
+
+    /// Returns whether the provider at `index` is currently used
+    pub fn is_healthy(&self, index: usize) -> bool {
+        self.health
+            .lock()
+            .unwrap()
+            .get(index)
+            .map_or(false, |health| health.is_healthy(Instant::now()))
+    }
+
+    /// Returns the indices of the providers in the order in which they are tried: the
+    /// healthy providers first, then the unhealthy ones
+    fn order(&self) -> Vec<usize> {
+        let now = Instant::now();
+        let health = self.health.lock().unwrap();
+        let (mut healthy, unhealthy): (Vec<usize>, Vec<usize>) =
+            (0..self.providers.len()).partition(|&idx| health[idx].is_healthy(now));
+        healthy.extend(unhealthy);
+        healthy
+    }
+
+    fn record_success(&self, index: usize) {
+        self.health.lock().unwrap()[index] = Health::default();
+    }
+
+    fn record_failure(&self, index: usize) {
+        self.health.lock().unwrap()[index].retry_at = Some(Instant::now() + self.cooldown);
+    }
+}
+
+impl FallbackProvider<http::Provider> {
+    /// Instantiates the fallback over HTTP providers, in order of preference
+    pub fn from_urls<S: AsRef<str>>(
+        urls: impl IntoIterator<Item = S>,
+    ) -> Result<Self, url::ParseError> {
+        let providers = urls
+            .into_iter()
+            .map(|url| http::Provider::from_str(url.as_ref()))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::new(providers))
+    }
+}
+
+#[async_trait]
+impl<T> JsonRpcClient for FallbackProvider<T>
+where
+    T: JsonRpcClient,
+    T::Error: Send + Sync + 'static,
+{
+    type Error = FallbackError<T::Error>;
+
+    async fn request<A, R>(&self, method: &str, params: A) -> Result<R, Self::Error>
+    where
+        A: Debug + Serialize + Send + Sync,
+        R: Serialize + DeserializeOwned,
+    {
+        // the params are serialized once, since they may be sent to every provider
+        let params = serde_json::to_value(params)?;
+
+        let mut errors = Vec::new();
+        let mut timeouts = 0;
+        for idx in self.order() {
+            let request = self.providers[idx].request(method, &params);
+            match select(request, Delay::new(self.timeout)).await {
+                Either::Left((Ok(res), _)) => {
+                    self.record_success(idx);
+                    return Ok(res);
+                }
+                Either::Left((Err(err), _)) => {
+                    // the node is up, the request itself failed
+                    if as_error_response(&err).is_some() {
+                        self.record_success(idx);
+                        return Err(FallbackError::ClientError(err));
+                    }
+                    trace!("provider {} failed: {}", idx, err);
+                    errors.push(err);
+                }
+                Either::Right(_) => {
+                    trace!("provider {} timed out", idx);
+                    timeouts += 1;
+                }
+            }
+            self.record_failure(idx);
+
+            if NON_IDEMPOTENT_METHODS.contains(&method) {
+                break;
+            }
+        }
+
+        Err(FallbackError::AllProvidersFailed { errors, timeouts })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Middleware, MockProvider, Provider, Quorum, QuorumProvider, WeightedProvider};
+    use ethers_core::types::{Bytes, TxHash, U64};
+
+    #[tokio::test]
+    async fn fails_over() {
+        let primary = MockProvider::new();
+        let secondary = MockProvider::new();
+        let provider = Provider::new(FallbackProvider::new(vec![
+            primary.clone(),
+            secondary.clone(),
+        ]));
+
+        // the primary has no responses, so it fails
+        secondary.push(U64::from(12)).unwrap();
+        assert_eq!(provider.get_block_number().await.unwrap(), 12.into());
+        primary.assert_request("eth_blockNumber", ()).unwrap();
+        secondary.assert_request("eth_blockNumber", ()).unwrap();
+        assert!(!provider.as_ref().is_healthy(0));
+        assert!(provider.as_ref().is_healthy(1));
+
+        // the primary is skipped during its cooldown
+        secondary.push(U64::from(13)).unwrap();
+        assert_eq!(provider.get_block_number().await.unwrap(), 13.into());
+        primary.assert_request("eth_blockNumber", ()).unwrap_err();
+
+        // all providers fail
+        let err = provider
+            .as_ref()
+            .request::<_, U64>("eth_blockNumber", ())
+            .await
+            .unwrap_err();
+        match err {
+            FallbackError::AllProvidersFailed { errors, timeouts } => {
+                assert_eq!(errors.len(), 2);
+                assert_eq!(timeouts, 0);
+            }
+            err => panic!("unexpected error {:?}", err),
+        }
+    }
+
+    #[tokio::test]
+    async fn uses_recovered_providers() {
+        let primary = MockProvider::new();
+        let secondary = MockProvider::new();
+        let provider = Provider::new(
+            FallbackProvider::new(vec![primary.clone(), secondary.clone()])
+                .cooldown(Duration::from_millis(0)),
+        );
+
+        secondary.push(U64::from(12)).unwrap();
+        assert_eq!(provider.get_block_number().await.unwrap(), 12.into());
+
+        // the cooldown elapsed, so the primary is preferred again
+        primary.push(U64::from(13)).unwrap();
+        assert_eq!(provider.get_block_number().await.unwrap(), 13.into());
+        assert!(provider.as_ref().is_healthy(0));
+    }
+
+    #[tokio::test]
+    async fn does_not_resend_transactions() {
+        let primary = MockProvider::new();
+        let secondary = MockProvider::new();
+        let provider = Provider::new(FallbackProvider::new(vec![
+            primary.clone(),
+            secondary.clone(),
+        ]));
+
+        // the primary fails, but may have broadcast the transaction
+        secondary.push(TxHash::zero()).unwrap();
+        assert!(provider
+            .send_raw_transaction_bytes(Bytes::from(vec![1]))
+            .await
+            .is_err());
+        primary
+            .assert_request("eth_sendRawTransaction", [Bytes::from(vec![1])])
+            .unwrap();
+        secondary
+            .assert_request("eth_sendRawTransaction", [Bytes::from(vec![1])])
+            .unwrap_err();
+        assert!(!provider.as_ref().is_healthy(0));
+    }
+    #[tokio::test]
+    async fn returns_error_responses_of_nested_transports() {
+        let primary = MockProvider::new();
+        let secondary = MockProvider::new();
+        let quorum = |mock: &MockProvider| {
+            QuorumProvider::new(Quorum::All, vec![WeightedProvider::new(mock.clone())])
+        };
+        let provider = Provider::new(FallbackProvider::new(vec![
+            quorum(&primary),
+            quorum(&secondary),
+        ]));
+
+        // the revert agreed on by the quorum is not a failure of the provider
+        primary.push_error(
+            "eth_blockNumber",
+            JsonRpcError {
+                code: 3,
+                message: "execution reverted".to_owned(),
+                data: None,
+            },
+        );
+        let err = provider
+            .as_ref()
+            .request::<_, U64>("eth_blockNumber", ())
+            .await
+            .unwrap_err();
+        assert_eq!(err.as_error_response().unwrap().code, 3);
+        assert!(provider.as_ref().is_healthy(0));
+        secondary.assert_request("eth_blockNumber", ()).unwrap_err();
+    }
+}
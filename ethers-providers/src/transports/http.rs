@@ -2,16 +2,24 @@
 use crate::{provider::ProviderError, JsonRpcClient};
 
 use async_trait::async_trait;
-use reqwest::{Client, Error as ReqwestError};
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION},
+    Client, Error as ReqwestError, RequestBuilder,
+};
 use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
 use std::{
+    collections::HashMap,
     str::FromStr,
     sync::atomic::{AtomicU64, Ordering},
 };
 use thiserror::Error;
 use url::Url;
 
-use super::common::{JsonRpcError, Request, Response};
+use super::{
+    auth::Authorization,
+    common::{JsonRpcError, Request, Response, ResponseData},
+};
 
 /// A low-level JSON-RPC Client over HTTP.
 ///
@@ -27,11 +35,33 @@ use super::common::{JsonRpcError, Request, Response};
 /// # Ok(())
 /// # }
 /// ```
+///
+/// Endpoints which require authentication are supported by custom headers and the
+/// `Authorization` header:
+///
+/// ```no_run
+/// use ethers::providers::{Authorization, Http, JwtKey};
+/// use std::str::FromStr;
+///
+/// # fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// // a private node behind basic auth
+/// let provider = Http::from_str("https://node.example.com")?
+///     .auth(Authorization::basic("user", "password"));
+///
+/// // the authenticated endpoint of geth, with a fresh JWT for every request
+/// let secret = std::fs::read_to_string("/tmp/jwtsecret")?;
+/// let provider = Http::from_str("http://localhost:8551")?
+///     .auth(Authorization::jwt(JwtKey::from_hex(&secret)?));
+/// # Ok(())
+/// # }
+/// ```
 #[derive(Debug)]
 pub struct Provider {
     id: AtomicU64,
     client: Client,
     url: Url,
+    headers: HeaderMap,
+    auth: Option<Authorization>,
 }
 
 #[derive(Error, Debug)]
@@ -50,6 +80,10 @@ pub enum ClientError {
         err: serde_json::Error,
         text: String,
     },
+
+    /// Thrown if the node did not respond to a request of a batch
+    #[error("missing response to request {0} of the batch")]
+    MissingBatchResponse(u64),
 }
 
 impl From<ClientError> for ProviderError {
@@ -74,12 +108,7 @@ impl JsonRpcClient for Provider {
 
         let payload = Request::new(next_id, method, params);
 
-        let res = self
-            .client
-            .post(self.url.as_ref())
-            .json(&payload)
-            .send()
-            .await?;
+        let res = self.post(&payload).send().await?;
         let text = res.text().await?;
         let res: Response<R> =
             serde_json::from_str(&text).map_err(|err| ClientError::SerdeJson { err, text })?;
@@ -105,10 +134,91 @@ impl Provider {
             id: AtomicU64::new(0),
             client: Client::new(),
             url: url.into(),
+            headers: HeaderMap::new(),
+            auth: None,
+        }
+    }
+
+    /// Sets a header which is sent with every request, e.g. the API key of an RPC
+    /// vendor
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+
+    /// Sets the credentials sent in the `Authorization` header of every request
+    pub fn auth(mut self, auth: Authorization) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// Builds a POST request of the JSON payload with the headers of the client
+    fn post<T: Serialize>(&self, payload: &T) -> RequestBuilder {
+        let mut request = self
+            .client
+            .post(self.url.as_ref())
+            .headers(self.headers.clone())
+            .json(payload);
+        if let Some(ref auth) = self.auth {
+            request = request.header(AUTHORIZATION, auth.header_value());
         }
+        request
+    }
+}
+
+impl Provider {
+    /// Sends the `(method, params)` requests as a single JSON-RPC batch, returning the
+    /// result or error of each request in order
+    pub async fn request_batch(
+        &self,
+        requests: &[(String, Value)],
+    ) -> Result<Vec<Result<Value, JsonRpcError>>, ClientError> {
+        let first_id = self.id.fetch_add(requests.len() as u64, Ordering::SeqCst) + 1;
+        let ids = (first_id..).take(requests.len()).collect::<Vec<_>>();
+        let payload = ids
+            .iter()
+            .zip(requests)
+            .map(|(id, (method, params))| Request::new(*id, method, params))
+            .collect::<Vec<_>>();
+
+        let res = self.post(&payload).send().await?;
+        let text = res.text().await?;
+        let responses: Vec<Response<Value>> = match serde_json::from_str(&text) {
+            Ok(responses) => responses,
+            // nodes which do not support batches respond with a single error
+            Err(err) => {
+                return Err(match serde_json::from_str::<Response<Value>>(&text) {
+                    Ok(Response {
+                        data: ResponseData::Error { error },
+                        ..
+                    }) => ClientError::JsonRpcError(error),
+                    _ => ClientError::SerdeJson { err, text },
+                })
+            }
+        };
+
+        demux_batch(&ids, responses)
     }
 }
 
+/// Matches the responses of a batch with the ids of its requests, since they may be
+/// returned in any order
+fn demux_batch(
+    ids: &[u64],
+    responses: Vec<Response<Value>>,
+) -> Result<Vec<Result<Value, JsonRpcError>>, ClientError> {
+    let mut responses = responses
+        .into_iter()
+        .map(|res| (res.id, res.data))
+        .collect::<HashMap<_, _>>();
+    ids.iter()
+        .map(|id| match responses.remove(id) {
+            Some(data) => Ok(data.into_result()),
+            None => Err(ClientError::MissingBatchResponse(*id)),
+        })
+        .collect()
+}
+
 impl FromStr for Provider {
     type Err = url::ParseError;
 
@@ -124,6 +234,35 @@ impl Clone for Provider {
             id: AtomicU64::new(0),
             client: self.client.clone(),
             url: self.url.clone(),
+            headers: self.headers.clone(),
+            auth: self.auth.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn demultiplexes_batch_responses() {
+        let responses: Vec<Response<Value>> = serde_json::from_str(
+            r#"[
+                {"jsonrpc":"2.0","id":3,"result":"0x3"},
+                {"jsonrpc":"2.0","id":1,"result":"0x1"},
+                {"jsonrpc":"2.0","id":2,"error":{"code":-32000,"message":"failed"}}
+            ]"#,
+        )
+        .unwrap();
+
+        let results = demux_batch(&[1, 2, 3], responses.clone()).unwrap();
+        assert_eq!(results[0].as_ref().unwrap(), "0x1");
+        assert_eq!(results[1].as_ref().unwrap_err().code, -32000);
+        assert_eq!(results[2].as_ref().unwrap(), "0x3");
+
+        match demux_batch(&[1, 2, 3, 4], responses) {
+            Err(ClientError::MissingBatchResponse(4)) => {}
+            res => panic!("expected a missing response, got {:?}", res),
         }
     }
 }
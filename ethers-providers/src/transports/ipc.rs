@@ -0,0 +1,442 @@
+use crate::{
+    provider::ProviderError,
+    transports::common::{JsonRpcError, Notification, Request, Response, UNSUBSCRIBE_REQUEST_ID},
+    JsonRpcClient, PubsubClient,
+};
+use ethers_core::types::U256;
+
+use async_trait::async_trait;
+use futures_channel::{mpsc, oneshot};
+use futures_util::stream::{self, BoxStream, Fuse, StreamExt};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Debug},
+    path::Path,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+use thiserror::Error;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, WriteHalf};
+
+/// A JSON-RPC Client over Unix domain sockets, or named pipes on Windows, e.g. to
+/// the `geth.ipc` endpoint of a local node.
+///
+/// ```no_run
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// use ethers::providers::Ipc;
+///
+/// let ipc = Ipc::connect("/tmp/geth.ipc").await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Ipc {
+    id: Arc<AtomicU64>,
+    requests: mpsc::UnboundedSender<TransportMessage>,
+}
+
+type Pending = oneshot::Sender<Result<Value, JsonRpcError>>;
+type Subscription = mpsc::UnboundedSender<Value>;
+
+enum TransportMessage {
+    Request {
+        id: u64,
+        request: Vec<u8>,
+        sender: Pending,
+    },
+    Subscribe {
+        id: U256,
+        sink: Subscription,
+    },
+    Unsubscribe {
+        id: U256,
+    },
+}
+
+impl Debug for Ipc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IpcProvider").field("id", &self.id).finish()
+    }
+}
+
+impl Ipc {
+    /// Initializes a new IPC Client, given a connected socket or pipe
+    pub fn new<S>(stream: S) -> Self
+    where
+        S: AsyncRead + AsyncWrite + Send + 'static,
+    {
+        let (sink, requests) = mpsc::unbounded();
+
+        // Spawn the server
+        IpcServer::new(stream, requests).spawn();
+
+        Self {
+            id: Arc::new(AtomicU64::new(0)),
+            requests: sink,
+        }
+    }
+
+    /// Connects to the Unix domain socket at the provided path
+    #[cfg(unix)]
+    pub async fn connect(path: impl AsRef<Path>) -> Result<Self, IpcError> {
+        let stream = tokio::net::UnixStream::connect(path).await?;
+        Ok(Self::new(stream))
+    }
+
+    /// Connects to the named pipe at the provided path, e.g. `\\.\pipe\geth.ipc`
+    #[cfg(windows)]
+    pub async fn connect(path: impl AsRef<Path>) -> Result<Self, IpcError> {
+        let pipe = tokio::net::windows::named_pipe::ClientOptions::new().open(path.as_ref())?;
+        Ok(Self::new(pipe))
+    }
+
+    fn send(&self, msg: TransportMessage) -> Result<(), IpcError> {
+        self.requests.unbounded_send(msg).map_err(to_client_error)
+    }
+}
+
+#[async_trait]
+impl JsonRpcClient for Ipc {
+    type Error = IpcError;
+
+    async fn request<T: Serialize + Send + Sync, R: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: T,
+    ) -> Result<R, IpcError> {
+        let next_id = self.id.fetch_add(1, Ordering::SeqCst);
+
+        // send the message
+        let (sender, receiver) = oneshot::channel();
+        self.send(TransportMessage::Request {
+            id: next_id,
+            request: serde_json::to_vec(&Request::new(next_id, method, params))?,
+            sender,
+        })?;
+
+        // wait for the response and parse it
+        let res = receiver.await??;
+        Ok(serde_json::from_value(res)?)
+    }
+}
+
+impl PubsubClient for Ipc {
+    type NotificationStream = mpsc::UnboundedReceiver<Value>;
+
+    fn subscribe<T: Into<U256>>(&self, id: T) -> Result<Self::NotificationStream, IpcError> {
+        let (sink, stream) = mpsc::unbounded();
+        self.send(TransportMessage::Subscribe {
+            id: id.into(),
+            sink,
+        })?;
+        Ok(stream)
+    }
+
+    fn unsubscribe<T: Into<U256>>(&self, id: T) -> Result<(), IpcError> {
+        self.send(TransportMessage::Unsubscribe { id: id.into() })
+    }
+}
+
+struct IpcServer<S> {
+    writer: WriteHalf<S>,
+    reader: Fuse<BoxStream<'static, Vec<u8>>>,
+    requests: Fuse<mpsc::UnboundedReceiver<TransportMessage>>,
+
+    /// Splits the bytes read into JSON values
+    framer: Framer,
+    pending: BTreeMap<u64, Pending>,
+    subscriptions: BTreeMap<U256, Subscription>,
+}
+
+impl<S> IpcServer<S>
+where
+    S: AsyncRead + AsyncWrite + Send + 'static,
+{
+    /// Instantiates the IPC Server
+    fn new(stream: S, requests: mpsc::UnboundedReceiver<TransportMessage>) -> Self {
+        let (reader, writer) = tokio::io::split(stream);
+        // read the socket in chunks, until it is closed
+        let reader = stream::unfold(reader, |mut reader| async move {
+            let mut chunk = vec![0; 4096];
+            match reader.read(&mut chunk).await {
+                Ok(0) | Err(_) => None,
+                Ok(n) => {
+                    chunk.truncate(n);
+                    Some((chunk, reader))
+                }
+            }
+        });
+
+        Self {
+            writer,
+            reader: reader.boxed().fuse(),
+            requests: requests.fuse(),
+            framer: Framer::default(),
+            pending: BTreeMap::default(),
+            subscriptions: BTreeMap::default(),
+        }
+    }
+
+    /// Spawns the event loop, which runs until the connection is closed or all
+    /// clients are dropped
+    fn spawn(mut self) {
+        let f = async move {
+            loop {
+                futures_util::select! {
+                    // Handle requests
+                    msg = self.requests.next() => match msg {
+                        Some(msg) => self.handle_request(msg).await,
+                        None => break,
+                    },
+                    // Handle responses and notifications
+                    chunk = self.reader.next() => match chunk {
+                        Some(chunk) => self.handle_chunk(chunk),
+                        None => break,
+                    },
+                    complete => break,
+                }
+            }
+        };
+
+        tokio::spawn(f);
+    }
+
+    async fn handle_request(&mut self, msg: TransportMessage) {
+        match msg {
+            TransportMessage::Request {
+                id,
+                request,
+                sender,
+            } => {
+                if self.pending.insert(id, sender).is_some() {
+                    println!("Replacing a pending request with id {:?}", id);
+                }
+
+                if let Err(e) = self.writer.write_all(&request).await {
+                    println!("IPC connection error: {:?}", e);
+                    self.pending.remove(&id);
+                }
+            }
+            TransportMessage::Subscribe { id, sink } => {
+                if self.subscriptions.insert(id, sink).is_some() {
+                    println!("Replacing already-registered subscription with id {:?}", id);
+                }
+            }
+            TransportMessage::Unsubscribe { id } => {
+                if self.subscriptions.remove(&id).is_none() {
+                    println!(
+                        "Unsubscribing from non-existent subscription with id {:?}",
+                        id
+                    );
+                    return;
+                }
+
+                // cancel the subscription on the node, ignoring the response
+                let request = Request::new(UNSUBSCRIBE_REQUEST_ID, "eth_unsubscribe", [id]);
+                let request = serde_json::to_vec(&request).expect("could not serialize request");
+                if let Err(e) = self.writer.write_all(&request).await {
+                    println!("IPC connection error: {:?}", e);
+                }
+            }
+        }
+    }
+
+    /// Handles the JSON values completed by a chunk read from the socket
+    fn handle_chunk(&mut self, chunk: Vec<u8>) {
+        for value in self.framer.push(&chunk) {
+            self.handle_value(value);
+        }
+    }
+
+    fn handle_value(&mut self, value: Value) {
+        if let Ok(resp) = serde_json::from_value::<Response<Value>>(value.clone()) {
+            if let Some(request) = self.pending.remove(&resp.id) {
+                let _ = request.send(resp.data.into_result());
+            }
+        } else if let Ok(notification) = serde_json::from_value::<Notification<Value>>(value) {
+            let id = notification.params.subscription;
+            if let Some(stream) = self.subscriptions.get(&id) {
+                if stream.unbounded_send(notification.params.result).is_err() {
+                    self.subscriptions.remove(&id);
+                }
+            }
+        }
+    }
+}
+
+/// Splits the bytes read from the socket into top-level JSON values, scanning each
+/// byte once so that large responses arriving in many chunks are parsed only when
+/// they are complete
+#[derive(Debug, Default)]
+struct Framer {
+    /// The bytes of the incomplete value being read
+    buffer: Vec<u8>,
+    /// The number of buffered bytes which were already scanned
+    scanned: usize,
+    /// The nesting depth of the arrays and objects of the incomplete value
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+}
+
+impl Framer {
+    /// Buffers a chunk, returning the values it completed. Bytes which are not part of
+    /// an object or array, and values which are not valid JSON, are skipped so that the
+    /// values following them are still read.
+    fn push(&mut self, chunk: &[u8]) -> Vec<Value> {
+        self.buffer.extend_from_slice(chunk);
+
+        let mut values = Vec::new();
+        // the incomplete value, if any, always starts at the front of the buffer
+        let mut start = 0;
+        for i in self.scanned..self.buffer.len() {
+            let byte = self.buffer[i];
+            if self.depth == 0 {
+                if byte == b'{' || byte == b'[' {
+                    self.depth = 1;
+                    start = i;
+                } else {
+                    start = i + 1;
+                }
+            } else if self.in_string {
+                if self.escaped {
+                    self.escaped = false;
+                } else if byte == b'\\' {
+                    self.escaped = true;
+                } else if byte == b'"' {
+                    self.in_string = false;
+                } else if byte < 0x20 {
+                    // control characters cannot appear in strings, so the value is
+                    // malformed and the next one is looked for after this byte
+                    self.in_string = false;
+                    self.depth = 0;
+                    start = i + 1;
+                }
+            } else {
+                match byte {
+                    b'"' => self.in_string = true,
+                    b'{' | b'[' => self.depth += 1,
+                    b'}' | b']' => {
+                        self.depth -= 1;
+                        if self.depth == 0 {
+                            if let Ok(value) = serde_json::from_slice(&self.buffer[start..=i]) {
+                                values.push(value);
+                            }
+                            start = i + 1;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        self.buffer.drain(..start);
+        self.scanned = self.buffer.len();
+        values
+    }
+}
+
+// TrySendError is private :(
+fn to_client_error<T: ToString>(err: T) -> IpcError {
+    IpcError::ChannelError(err.to_string())
+}
+
+#[derive(Error, Debug)]
+/// Error thrown when sending an IPC message
+pub enum IpcError {
+    /// Thrown if deserialization failed
+    #[error(transparent)]
+    JsonError(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    /// Thrown if the response could not be parsed
+    JsonRpcError(#[from] JsonRpcError),
+
+    /// Thrown if there's an error over the IPC connection
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+
+    #[error("{0}")]
+    ChannelError(String),
+
+    #[error(transparent)]
+    Canceled(#[from] oneshot::Canceled),
+}
+
+impl From<IpcError> for ProviderError {
+    fn from(src: IpcError) -> Self {
+        ProviderError::JsonRpcClientError(Box::new(src))
+    }
+}
+
+#[cfg(test)]
+#[cfg(unix)]
+mod tests {
+    use super::*;
+    use ethers_core::types::U64;
+    use tokio::net::UnixListener;
+
+    #[tokio::test]
+    async fn request_and_subscription() {
+        let path = std::env::temp_dir().join(format!("ethers-ipc-{}.ipc", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0; 1024];
+            let _ = socket.read(&mut buf).await.unwrap();
+            // the response and notification are split across writes
+            let reply = br#"{"jsonrpc":"2.0","id":0,"result":"0x10"}{"jsonrpc":"2.0","method":"eth_subscription","params":{"subscription":"0x1","result":"0x20"}}"#;
+            socket.write_all(&reply[..20]).await.unwrap();
+            socket.flush().await.unwrap();
+            tokio::task::yield_now().await;
+            socket.write_all(&reply[20..]).await.unwrap();
+            // keep the connection open until the client is done
+            let _ = socket.read(&mut buf).await;
+        });
+
+        let ipc = Ipc::connect(&path).await.unwrap();
+        let mut stream = ipc.subscribe(1).unwrap();
+        let block: U64 = ipc.request("eth_blockNumber", ()).await.unwrap();
+        assert_eq!(block, 16.into());
+        assert_eq!(
+            stream.next().await.unwrap(),
+            Value::String("0x20".to_owned())
+        );
+
+        drop(ipc);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn frames_values_across_chunks() {
+        let mut framer = Framer::default();
+        let reply = br#"{"id":0,"result":"}{"}[{"id":1},{"id":2}]"#;
+        assert!(framer.push(&reply[..12]).is_empty());
+        assert!(framer.push(&reply[12..20]).is_empty());
+        assert_eq!(
+            framer.push(&reply[20..]),
+            vec![
+                serde_json::json!({"id": 0, "result": "}{"}),
+                serde_json::json!([{"id": 1}, {"id": 2}]),
+            ]
+        );
+        assert!(framer.buffer.is_empty());
+    }
+
+    #[test]
+    fn skips_malformed_data() {
+        let mut framer = Framer::default();
+        // garbage between values, an invalid value and a string broken by a newline
+        let reply = b"oops{\"id\":0}{\"id\":nope}{\"id\":\"1\n{\"id\":2}";
+        assert_eq!(
+            framer.push(reply),
+            vec![serde_json::json!({"id": 0}), serde_json::json!({"id": 2})]
+        );
+        assert!(framer.buffer.is_empty());
+    }
+}
@@ -0,0 +1,105 @@
+use crate::JsonRpcClient;
+
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{fmt::Debug, time::Instant};
+
+/// The counter of requests, labeled with their method and status
+const REQUESTS: &str = "ethers_rpc_requests_total";
+/// The histogram of request durations in seconds, labeled with their method and status
+const DURATION: &str = "ethers_rpc_request_duration_seconds";
+
+/// A transport wrapper which records metrics about the requests of the wrapped transport
+/// through the [`metrics`](https://docs.rs/metrics) facade, so that they are exported by
+/// the installed recorder (e.g. a Prometheus exporter).
+///
+/// The `ethers_rpc_requests_total` counter and the `ethers_rpc_request_duration_seconds`
+/// histogram are labeled with the `method` of the request and its `status` (`ok` or
+/// `error`), from which the error rate of each method is derived. They are also labeled
+/// with the `endpoint` passed to [`MetricsClient::new`], to tell multiple nodes apart.
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers::providers::{Http, MetricsClient, Provider};
+/// use std::str::FromStr;
+///
+/// # fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let url = "http://localhost:8545";
+/// let http = MetricsClient::new(Http::from_str(url)?, url);
+/// let provider = Provider::new(http);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct MetricsClient<T> {
+    inner: T,
+    endpoint: String,
+}
+
+impl<T> MetricsClient<T> {
+    /// Wraps the transport, labeling the metrics of its requests with `endpoint`
+    pub fn new(inner: T, endpoint: impl Into<String>) -> Self {
+        Self {
+            inner,
+            endpoint: endpoint.into(),
+        }
+    }
+
+    /// Returns the wrapped transport
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+}
+
+#[async_trait]
+impl<T: JsonRpcClient> JsonRpcClient for MetricsClient<T> {
+    type Error = T::Error;
+
+    async fn request<P, R>(&self, method: &str, params: P) -> Result<R, Self::Error>
+    where
+        P: Debug + Serialize + Send + Sync,
+        R: Serialize + DeserializeOwned,
+    {
+        let start = Instant::now();
+        let res = self.inner.request(method, params).await;
+        let elapsed = start.elapsed();
+
+        let status = if res.is_ok() { "ok" } else { "error" };
+        metrics::increment_counter!(
+            REQUESTS,
+            "method" => method.to_owned(),
+            "status" => status,
+            "endpoint" => self.endpoint.clone()
+        );
+        metrics::histogram!(
+            DURATION,
+            elapsed,
+            "method" => method.to_owned(),
+            "status" => status,
+            "endpoint" => self.endpoint.clone()
+        );
+
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Middleware, MockProvider, Provider};
+    use ethers_core::types::U64;
+
+    #[tokio::test]
+    async fn forwards_requests() {
+        let mock = MockProvider::new();
+        let provider = Provider::new(MetricsClient::new(mock.clone(), "mock"));
+
+        mock.push(U64::from(12)).unwrap();
+        assert_eq!(provider.get_block_number().await.unwrap(), 12.into());
+        mock.assert_request("eth_blockNumber", ()).unwrap();
+
+        // errors are passed through
+        provider.get_block_number().await.unwrap_err();
+    }
+}
@@ -1,3 +1,4 @@
+use super::common::JsonRpcError;
 use crate::{JsonRpcClient, ProviderError};
 
 use async_trait::async_trait;
@@ -5,16 +6,23 @@ use serde::{de::DeserializeOwned, Serialize};
 use serde_json::Value;
 use std::{
     borrow::Borrow,
-    collections::VecDeque,
+    collections::{HashMap, VecDeque},
     sync::{Arc, Mutex},
 };
 use thiserror::Error;
 
 #[derive(Clone, Debug)]
 /// Mock transport used in test environments.
+///
+/// Responses registered for a method with [`push_response`](MockProvider::push_response)
+/// or [`push_error`](MockProvider::push_error) are returned in order to the requests of
+/// that method. The other requests pop the responses pushed with
+/// [`push`](MockProvider::push), last in first out.
 pub struct MockProvider {
     requests: Arc<Mutex<VecDeque<(String, Value)>>>,
     responses: Arc<Mutex<VecDeque<Value>>>,
+    method_responses: Arc<Mutex<HashMap<String, VecDeque<Result<Value, JsonRpcError>>>>>,
+    history: Arc<Mutex<Vec<(String, Value)>>>,
 }
 
 impl Default for MockProvider {
@@ -34,12 +42,29 @@ impl JsonRpcClient for MockProvider {
         method: &str,
         input: T,
     ) -> Result<R, MockError> {
+        let input = serde_json::to_value(input)?;
         self.requests
             .lock()
             .unwrap()
-            .push_back((method.to_owned(), serde_json::to_value(input)?));
-        let mut data = self.responses.lock().unwrap();
-        let element = data.pop_back().ok_or(MockError::EmptyResponses)?;
+            .push_back((method.to_owned(), input.clone()));
+        self.history
+            .lock()
+            .unwrap()
+            .push((method.to_owned(), input));
+
+        let method_response = self
+            .method_responses
+            .lock()
+            .unwrap()
+            .get_mut(method)
+            .and_then(VecDeque::pop_front);
+        let element = match method_response {
+            Some(response) => response?,
+            None => {
+                let mut data = self.responses.lock().unwrap();
+                data.pop_back().ok_or(MockError::EmptyResponses)?
+            }
+        };
         let res: R = serde_json::from_value(element)?;
 
         Ok(res)
@@ -72,6 +97,8 @@ impl MockProvider {
         Self {
             requests: Arc::new(Mutex::new(VecDeque::new())),
             responses: Arc::new(Mutex::new(VecDeque::new())),
+            method_responses: Arc::new(Mutex::new(HashMap::new())),
+            history: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
@@ -81,6 +108,58 @@ impl MockProvider {
         self.responses.lock().unwrap().push_back(value);
         Ok(())
     }
+
+    /// Pushes a response to the requests of the method. The responses of a method are
+    /// returned in the order in which they were pushed.
+    pub fn push_response<T: Serialize + Send + Sync, K: Borrow<T>>(
+        &self,
+        method: &str,
+        data: K,
+    ) -> Result<(), MockError> {
+        let value = serde_json::to_value(data.borrow())?;
+        self.push_method_response(method, Ok(value));
+        Ok(())
+    }
+
+    /// Pushes an error response to the requests of the method, e.g. a revert
+    pub fn push_error(&self, method: &str, error: JsonRpcError) {
+        self.push_method_response(method, Err(error));
+    }
+
+    fn push_method_response(&self, method: &str, response: Result<Value, JsonRpcError>) {
+        self.method_responses
+            .lock()
+            .unwrap()
+            .entry(method.to_owned())
+            .or_default()
+            .push_back(response);
+    }
+
+    /// Returns the methods of all requests made so far, in order. Unlike
+    /// [`assert_request`](MockProvider::assert_request), this does not consume them.
+    pub fn calls(&self) -> Vec<String> {
+        self.history
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(method, _)| method.clone())
+            .collect()
+    }
+
+    /// Returns the number of requests of the method made so far
+    pub fn call_count(&self, method: &str) -> usize {
+        self.history
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(m, _)| m == method)
+            .count()
+    }
+
+    /// Checks that the requests made so far were of the provided methods, in order
+    pub fn assert_calls(&self, methods: &[&str]) {
+        assert_eq!(self.calls(), methods);
+    }
 }
 
 #[derive(Error, Debug)]
@@ -94,6 +173,10 @@ pub enum MockError {
 
     #[error("empty responses array, please push some responses")]
     EmptyResponses,
+
+    /// The error response pushed with [`MockProvider::push_error`]
+    #[error(transparent)]
+    JsonRpcError(#[from] JsonRpcError),
 }
 
 impl From<MockError> for ProviderError {
@@ -106,7 +189,7 @@ impl From<MockError> for ProviderError {
 mod tests {
     use super::*;
     use crate::Middleware;
-    use ethers_core::types::U64;
+    use ethers_core::types::{TransactionRequest, U64};
 
     #[tokio::test]
     async fn pushes_request_and_response() {
@@ -150,4 +233,46 @@ mod tests {
         let block = provider.get_block_number().await.unwrap();
         assert_eq!(block.as_u64(), 12);
     }
+
+    #[tokio::test]
+    async fn responds_by_method() {
+        let (provider, mock) = crate::Provider::mocked();
+
+        mock.push_response("eth_blockNumber", U64::from(12))
+            .unwrap();
+        mock.push_response("eth_blockNumber", U64::from(13))
+            .unwrap();
+        mock.push_response("eth_chainId", U64::from(1)).unwrap();
+        mock.push_error(
+            "eth_estimateGas",
+            JsonRpcError {
+                code: -32601,
+                message: "the method eth_estimateGas does not exist".to_owned(),
+                data: None,
+            },
+        );
+        // the requests of other methods fall back to the pushed responses
+        mock.push(U64::from(100)).unwrap();
+
+        assert_eq!(provider.get_chainid().await.unwrap(), 1.into());
+        assert_eq!(provider.get_block_number().await.unwrap(), 12.into());
+        assert_eq!(provider.get_block_number().await.unwrap(), 13.into());
+        let err = provider
+            .estimate_gas(&TransactionRequest::new().into())
+            .await
+            .unwrap_err();
+        assert!(err.is_method_not_found());
+        assert_eq!(provider.get_gas_price().await.unwrap(), 100.into());
+        provider.get_block_number().await.unwrap_err();
+
+        assert_eq!(mock.call_count("eth_blockNumber"), 3);
+        mock.assert_calls(&[
+            "eth_chainId",
+            "eth_blockNumber",
+            "eth_blockNumber",
+            "eth_estimateGas",
+            "eth_gasPrice",
+            "eth_blockNumber",
+        ]);
+    }
 }
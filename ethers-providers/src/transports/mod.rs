@@ -1,4 +1,8 @@
 mod common;
+pub use common::JsonRpcError;
+
+mod auth;
+pub use auth::{Authorization, JwtKey};
 
 mod http;
 pub use http::Provider as Http;
@@ -8,5 +12,117 @@ mod ws;
 #[cfg(feature = "ws")]
 pub use ws::Ws;
 
+#[cfg(feature = "ipc")]
+mod ipc;
+#[cfg(feature = "ipc")]
+pub use ipc::{Ipc, IpcError};
+
+#[cfg(feature = "opentelemetry")]
+mod telemetry;
+#[cfg(feature = "opentelemetry")]
+pub use telemetry::TelemetryClient;
+
+#[cfg(feature = "metrics")]
+mod metrics;
+#[cfg(feature = "metrics")]
+pub use self::metrics::MetricsClient;
+
+// the transport relies on JavaScript values never being shared across threads, which
+// only holds without the wasm threads proposal
+#[cfg(all(
+    feature = "browser",
+    target_arch = "wasm32",
+    not(target_feature = "atomics")
+))]
+mod eip1193;
+#[cfg(all(
+    feature = "browser",
+    target_arch = "wasm32",
+    not(target_feature = "atomics")
+))]
+pub use eip1193::{Eip1193, Eip1193Error};
+
 mod mock;
 pub use mock::{MockError, MockProvider};
+
+mod fallback;
+pub use fallback::{FallbackError, FallbackProvider};
+
+mod quorum;
+pub use quorum::{Quorum, QuorumError, QuorumProvider, WeightedProvider};
+
+mod rate_limit;
+pub use rate_limit::RateLimited;
+
+mod retry;
+pub use retry::{
+    RateLimitRetryPolicy, RetryClient, RetryClientError, RetryPolicy, NON_IDEMPOTENT_METHODS,
+};
+
+/// Returns the JSON-RPC error response of the node, if the error of the transport is one
+pub(crate) fn as_error_response<'a>(
+    err: &'a (dyn std::error::Error + Send + Sync + 'static),
+) -> Option<&'a JsonRpcError> {
+    if let Some(err) = err.downcast_ref::<JsonRpcError>() {
+        return Some(err);
+    }
+    if let Some(http::ClientError::JsonRpcError(err)) = err.downcast_ref() {
+        return Some(err);
+    }
+    #[cfg(feature = "ws")]
+    if let Some(ws::ClientError::JsonRpcError(err)) = err.downcast_ref() {
+        return Some(err);
+    }
+    #[cfg(feature = "ipc")]
+    if let Some(ipc::IpcError::JsonRpcError(err)) = err.downcast_ref() {
+        return Some(err);
+    }
+    if let Some(mock::MockError::JsonRpcError(err)) = err.downcast_ref() {
+        return Some(err);
+    }
+    #[cfg(all(
+        feature = "browser",
+        target_arch = "wasm32",
+        not(target_feature = "atomics")
+    ))]
+    if let Some(eip1193::Eip1193Error::JsonRpcError(err)) = err.downcast_ref() {
+        return Some(err);
+    }
+
+    // the errors of the wrapped transports are unwrapped through up to two retry,
+    // quorum or fallback layers, e.g. a `FallbackProvider<QuorumProvider<Http>>`
+    macro_rules! unwrap_layer {
+        ($ty:ty) => {
+            if let Some(err) = err.downcast_ref::<RetryClientError<$ty>>() {
+                return err.as_error_response();
+            }
+            if let Some(err) = err.downcast_ref::<QuorumError<$ty>>() {
+                return err.as_error_response();
+            }
+            if let Some(err) = err.downcast_ref::<FallbackError<$ty>>() {
+                return err.as_error_response();
+            }
+        };
+    }
+    macro_rules! unwrap_layers {
+        ($ty:ty) => {
+            unwrap_layer!($ty);
+            unwrap_layer!(RetryClientError<$ty>);
+            unwrap_layer!(QuorumError<$ty>);
+            unwrap_layer!(FallbackError<$ty>);
+        };
+    }
+    unwrap_layers!(http::ClientError);
+    #[cfg(feature = "ws")]
+    unwrap_layers!(ws::ClientError);
+    #[cfg(feature = "ipc")]
+    unwrap_layers!(ipc::IpcError);
+    #[cfg(all(
+        feature = "browser",
+        target_arch = "wasm32",
+        not(target_feature = "atomics")
+    ))]
+    unwrap_layers!(eip1193::Eip1193Error);
+    unwrap_layers!(mock::MockError);
+    None
+}
@@ -0,0 +1,381 @@
+use super::{as_error_response, common::JsonRpcError};
+use crate::{provider::ProviderError, JsonRpcClient};
+
+use async_trait::async_trait;
+use futures_util::{future::FutureExt, stream::FuturesUnordered, StreamExt};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::fmt::Debug;
+use thiserror::Error;
+
+/// The weight of the providers which must agree on a response for it to be returned
+/// by a [`QuorumProvider`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quorum {
+    /// All providers must agree
+    All,
+    /// More than half of the total weight must agree
+    Majority,
+    /// At least the percentage of the total weight must agree
+    Percentage(u8),
+    /// At least the absolute weight must agree
+    Weight(u64),
+}
+
+impl Default for Quorum {
+    fn default() -> Self {
+        Quorum::Majority
+    }
+}
+
+impl Quorum {
+    /// Returns the weight required to reach the quorum, given the total weight of the
+    /// providers
+    fn weight(self, total: u64) -> u64 {
+        match self {
+            Quorum::All => total,
+            Quorum::Majority => total / 2 + 1,
+            Quorum::Percentage(percentage) => {
+                let percentage = u64::from(percentage.min(100));
+                ((total * percentage + 99) / 100).max(1)
+            }
+            Quorum::Weight(weight) => weight.min(total),
+        }
+    }
+}
+
+/// A transport with the weight of its responses in a [`QuorumProvider`]
+#[derive(Debug, Clone)]
+pub struct WeightedProvider<T> {
+    inner: T,
+    weight: u64,
+}
+
+impl<T> WeightedProvider<T> {
+    /// Wraps the transport with a weight of 1
+    pub fn new(inner: T) -> Self {
+        Self::with_weight(inner, 1)
+    }
+
+    /// Wraps the transport with the provided weight
+    pub fn with_weight(inner: T, weight: u64) -> Self {
+        Self { inner, weight }
+    }
+
+    /// Returns the wrapped transport
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns the weight of the transport
+    pub fn weight(&self) -> u64 {
+        self.weight
+    }
+}
+
+impl<T> From<T> for WeightedProvider<T> {
+    fn from(inner: T) -> Self {
+        Self::new(inner)
+    }
+}
+
+#[derive(Error, Debug)]
+/// Error thrown by a [`QuorumProvider`]
+pub enum QuorumError<E: std::error::Error + 'static> {
+    /// Thrown if the providers did not agree on a response. Contains the distinct
+    /// responses and the errors of the providers which failed.
+    #[error("no quorum reached: the providers returned diverging responses")]
+    NoQuorumReached { values: Vec<Value>, errors: Vec<E> },
+
+    /// Thrown if a quorum of the providers returned the same JSON-RPC error, e.g. because
+    /// the call reverted
+    #[error(transparent)]
+    JsonRpcError(E),
+
+    /// Thrown if the params could not be serialized or the response deserialized
+    #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
+}
+
+impl<E> QuorumError<E>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    /// Returns the JSON-RPC error response of the node, if a quorum of the providers
+    /// failed with it
+    pub fn as_error_response(&self) -> Option<&JsonRpcError> {
+        match self {
+            QuorumError::JsonRpcError(err) => super::as_error_response(err),
+            _ => None,
+        }
+    }
+}
+
+impl<E> From<QuorumError<E>> for ProviderError
+where
+    E: std::error::Error + Into<ProviderError> + Send + Sync + 'static,
+{
+    fn from(src: QuorumError<E>) -> Self {
+        match src {
+            QuorumError::JsonRpcError(err) => err.into(),
+            src => ProviderError::JsonRpcClientError(Box::new(src)),
+        }
+    }
+}
+
+/// A transport which sends each request to multiple providers and returns a response
+/// only once a [`Quorum`] of them, by weight, returned the same value. This protects
+/// reads against a single faulty or malicious node.
+///
+/// The requests are sent concurrently and the response is returned as soon as the
+/// quorum is reached, without waiting for the remaining providers. Likewise, a JSON-RPC
+/// error returned by a quorum of the providers is returned as is.
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers::providers::{Http, Provider, Quorum, QuorumProvider, WeightedProvider};
+/// use std::str::FromStr;
+///
+/// # fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let quorum = QuorumProvider::new(
+///     Quorum::Majority,
+///     vec![
+///         WeightedProvider::with_weight(Http::from_str("http://localhost:8545")?, 2),
+///         WeightedProvider::new(Http::from_str("https://eth.llamarpc.com")?),
+///         WeightedProvider::new(Http::from_str("https://cloudflare-eth.com")?),
+///     ],
+/// );
+/// let provider = Provider::new(quorum);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct QuorumProvider<T> {
+    quorum: Quorum,
+    providers: Vec<WeightedProvider<T>>,
+}
+
+impl<T> QuorumProvider<T> {
+    /// Instantiates the quorum of the providers
+    pub fn new(quorum: Quorum, providers: impl IntoIterator<Item = WeightedProvider<T>>) -> Self {
+        Self {
+            quorum,
+            providers: providers.into_iter().collect(),
+        }
+    }
+
+    /// Adds a provider to the quorum
+    pub fn add_provider(&mut self, provider: WeightedProvider<T>) {
+        self.providers.push(provider);
+    }
+
+    /// Returns the quorum which must be reached
+    pub fn quorum(&self) -> Quorum {
+        self.quorum
+    }
+
+    /// Returns the providers of the quorum
+    pub fn providers(&self) -> &[WeightedProvider<T>] {
+        &self.providers
+    }
+
+    /// Returns the weight required to reach the quorum
+    fn quorum_weight(&self) -> u64 {
+        self.quorum
+            .weight(self.providers.iter().map(|p| p.weight).sum())
+    }
+}
+
+#[async_trait]
+impl<T> JsonRpcClient for QuorumProvider<T>
+where
+    T: JsonRpcClient,
+    T::Error: Send + Sync + 'static,
+{
+    type Error = QuorumError<T::Error>;
+
+    async fn request<A, R>(&self, method: &str, params: A) -> Result<R, Self::Error>
+    where
+        A: Debug + Serialize + Send + Sync,
+        R: Serialize + DeserializeOwned,
+    {
+        let params = serde_json::to_value(params)?;
+        let quorum_weight = self.quorum_weight();
+
+        let mut requests = self
+            .providers
+            .iter()
+            .map(|provider| {
+                provider
+                    .inner
+                    .request::<_, Value>(method, &params)
+                    .map(move |res| (provider.weight, res))
+            })
+            .collect::<FuturesUnordered<_>>();
+
+        // the distinct responses and JSON-RPC errors, with the total weight of the
+        // providers returning them
+        let mut values: Vec<(Value, u64)> = Vec::new();
+        let mut rpc_errors: Vec<(JsonRpcError, u64)> = Vec::new();
+        let mut errors = Vec::new();
+        while let Some((weight, res)) = requests.next().await {
+            let value = match res {
+                Ok(value) => value,
+                Err(err) => {
+                    if let Some(rpc_error) = as_error_response(&err) {
+                        let idx = match rpc_errors
+                            .iter()
+                            .position(|(e, _)| same_error(e, rpc_error))
+                        {
+                            Some(idx) => idx,
+                            None => {
+                                rpc_errors.push((rpc_error.clone(), 0));
+                                rpc_errors.len() - 1
+                            }
+                        };
+                        rpc_errors[idx].1 += weight;
+                        if rpc_errors[idx].1 >= quorum_weight {
+                            return Err(QuorumError::JsonRpcError(err));
+                        }
+                    }
+                    errors.push(err);
+                    continue;
+                }
+            };
+            let idx = match values.iter().position(|(v, _)| *v == value) {
+                Some(idx) => idx,
+                None => {
+                    values.push((value, 0));
+                    values.len() - 1
+                }
+            };
+            values[idx].1 += weight;
+            if values[idx].1 >= quorum_weight {
+                let (value, _) = values.swap_remove(idx);
+                return Ok(serde_json::from_value(value)?);
+            }
+        }
+
+        Err(QuorumError::NoQuorumReached {
+            values: values.into_iter().map(|(value, _)| value).collect(),
+            errors,
+        })
+    }
+}
+
+/// Returns whether the providers returned the same JSON-RPC error
+fn same_error(a: &JsonRpcError, b: &JsonRpcError) -> bool {
+    a.code == b.code && a.message == b.message && a.data == b.data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Middleware, MockProvider, Provider};
+    use ethers_core::types::{TransactionRequest, U64};
+
+    #[test]
+    fn computes_quorum_weight() {
+        assert_eq!(Quorum::All.weight(5), 5);
+        assert_eq!(Quorum::Majority.weight(4), 3);
+        assert_eq!(Quorum::Majority.weight(5), 3);
+        assert_eq!(Quorum::Percentage(50).weight(5), 3);
+        assert_eq!(Quorum::Percentage(0).weight(5), 1);
+        assert_eq!(Quorum::Weight(7).weight(5), 5);
+    }
+
+    fn quorum(mocks: &[MockProvider]) -> Provider<QuorumProvider<MockProvider>> {
+        Provider::new(QuorumProvider::new(
+            Quorum::Majority,
+            mocks.iter().cloned().map(WeightedProvider::new),
+        ))
+    }
+
+    #[tokio::test]
+    async fn requires_quorum() {
+        let mocks = vec![
+            MockProvider::new(),
+            MockProvider::new(),
+            MockProvider::new(),
+        ];
+        let provider = quorum(&mocks);
+
+        // 2 out of 3 providers agree
+        mocks[0].push(U64::from(12)).unwrap();
+        mocks[1].push(U64::from(13)).unwrap();
+        mocks[2].push(U64::from(12)).unwrap();
+        assert_eq!(provider.get_block_number().await.unwrap(), 12.into());
+        mocks[0].assert_request("eth_blockNumber", ()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn reports_divergence() {
+        let mocks = vec![
+            MockProvider::new(),
+            MockProvider::new(),
+            MockProvider::new(),
+        ];
+        let provider = quorum(&mocks);
+
+        // the providers diverge or fail
+        mocks[0].push(U64::from(12)).unwrap();
+        mocks[1].push(U64::from(13)).unwrap();
+        let res = provider
+            .as_ref()
+            .request::<_, U64>("eth_blockNumber", ())
+            .await;
+        match res.unwrap_err() {
+            QuorumError::NoQuorumReached { values, errors } => {
+                assert_eq!(values.len(), 2);
+                assert_eq!(errors.len(), 1);
+            }
+            err => panic!("unexpected error {:?}", err),
+        }
+    }
+
+    #[tokio::test]
+    async fn returns_agreed_errors() {
+        let mocks = vec![
+            MockProvider::new(),
+            MockProvider::new(),
+            MockProvider::new(),
+        ];
+        let provider = quorum(&mocks);
+
+        // 2 out of 3 providers return the same revert
+        let revert = |data: &str| JsonRpcError {
+            code: 3,
+            message: "execution reverted".to_owned(),
+            data: Some(Value::String(data.to_owned())),
+        };
+        mocks[0].push_error("eth_call", revert("0x01"));
+        mocks[1].push_error("eth_call", revert("0x02"));
+        mocks[2].push_error("eth_call", revert("0x01"));
+        let err = provider
+            .call(&TransactionRequest::new().into(), None)
+            .await
+            .unwrap_err();
+        let err = err.as_error_response().unwrap();
+        assert_eq!(err.code, 3);
+        assert_eq!(err.data, Some(Value::String("0x01".to_owned())));
+    }
+
+    #[tokio::test]
+    async fn weighs_providers() {
+        let heavy = MockProvider::new();
+        let light = MockProvider::new();
+        let quorum = QuorumProvider::new(
+            Quorum::Majority,
+            vec![
+                WeightedProvider::with_weight(heavy.clone(), 3),
+                WeightedProvider::new(light.clone()),
+            ],
+        );
+        let provider = Provider::new(quorum);
+
+        heavy.push(U64::from(12)).unwrap();
+        light.push(U64::from(13)).unwrap();
+        assert_eq!(provider.get_block_number().await.unwrap(), 12.into());
+    }
+}
@@ -0,0 +1,170 @@
+use crate::JsonRpcClient;
+
+use async_trait::async_trait;
+use futures_timer::Delay;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// The state of a token bucket. The tokens go negative when requests are queued, so
+/// that each request waits for the tokens reserved by the requests before it.
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+/// A transport wrapper which limits the rate of the requests with a token bucket,
+/// e.g. to stay within the compute unit quota of an RPC vendor instead of being rate
+/// limited by it.
+///
+/// Each request consumes the weight of its method from the bucket, which is refilled
+/// at a constant rate up to its burst capacity. When the bucket is exhausted, requests
+/// wait until their tokens are refilled, in the order in which they were made.
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers::providers::{Http, Provider, RateLimited};
+/// use std::str::FromStr;
+///
+/// # fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let http = Http::from_str("https://eth-mainnet.alchemyapi.io/v2/API_KEY")?;
+/// // 330 compute units per second, weighing the methods by their compute units
+/// let client = RateLimited::new(http, 330)
+///     .default_weight(26)
+///     .method_weight("eth_blockNumber", 10)
+///     .method_weight("eth_getLogs", 75);
+/// let provider = Provider::new(client);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct RateLimited<T> {
+    inner: T,
+    bucket: Mutex<Bucket>,
+    rate: u32,
+    capacity: u32,
+    default_weight: u32,
+    weights: HashMap<String, u32>,
+}
+
+impl<T> RateLimited<T> {
+    /// Wraps the transport, allowing `rate` tokens per second with a burst capacity of
+    /// one second worth of tokens. Each request has a weight of 1 by default.
+    pub fn new(inner: T, rate: u32) -> Self {
+        let rate = rate.max(1);
+        Self {
+            inner,
+            bucket: Mutex::new(Bucket {
+                tokens: f64::from(rate),
+                updated_at: Instant::now(),
+            }),
+            rate,
+            capacity: rate,
+            default_weight: 1,
+            weights: HashMap::new(),
+        }
+    }
+
+    /// Sets the maximum number of tokens which can be accumulated while idle
+    pub fn burst(mut self, capacity: u32) -> Self {
+        self.capacity = capacity;
+        let bucket = self.bucket.get_mut().unwrap();
+        bucket.tokens = bucket.tokens.min(f64::from(capacity));
+        self
+    }
+
+    /// Sets the weight of the methods without a weight of their own
+    pub fn default_weight(mut self, weight: u32) -> Self {
+        self.default_weight = weight;
+        self
+    }
+
+    /// Sets the weight of a method
+    pub fn method_weight(mut self, method: impl Into<String>, weight: u32) -> Self {
+        self.weights.insert(method.into(), weight);
+        self
+    }
+
+    /// Returns the wrapped transport
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns the weight of the method
+    pub fn weight(&self, method: &str) -> u32 {
+        self.weights
+            .get(method)
+            .copied()
+            .unwrap_or(self.default_weight)
+    }
+
+    /// Reserves `weight` tokens at `now`, returning how long to wait until they are
+    /// available
+    fn reserve(&self, weight: u32, now: Instant) -> Duration {
+        let mut bucket = self.bucket.lock().unwrap();
+        let elapsed = now.saturating_duration_since(bucket.updated_at);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * f64::from(self.rate))
+            .min(f64::from(self.capacity));
+        bucket.updated_at = now;
+
+        bucket.tokens -= f64::from(weight);
+        if bucket.tokens >= 0.0 {
+            Duration::from_secs(0)
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / f64::from(self.rate))
+        }
+    }
+}
+
+#[async_trait]
+impl<T: JsonRpcClient> JsonRpcClient for RateLimited<T> {
+    type Error = T::Error;
+
+    async fn request<A, R>(&self, method: &str, params: A) -> Result<R, Self::Error>
+    where
+        A: Debug + Serialize + Send + Sync,
+        R: Serialize + DeserializeOwned,
+    {
+        let wait = self.reserve(self.weight(method), Instant::now());
+        if wait > Duration::from_secs(0) {
+            Delay::new(wait).await;
+        }
+        self.inner.request(method, params).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MockProvider;
+
+    #[test]
+    fn reserves_tokens() {
+        let client = RateLimited::new(MockProvider::new(), 10)
+            .burst(5)
+            .method_weight("eth_getLogs", 4);
+        assert_eq!(client.weight("eth_getLogs"), 4);
+        assert_eq!(client.weight("eth_blockNumber"), 1);
+
+        let start = client.bucket.lock().unwrap().updated_at;
+        // the burst is available immediately
+        assert_eq!(client.reserve(4, start), Duration::from_secs(0));
+        assert_eq!(client.reserve(1, start), Duration::from_secs(0));
+        // the next requests are queued behind each other
+        assert_eq!(client.reserve(1, start), Duration::from_millis(100));
+        assert_eq!(client.reserve(4, start), Duration::from_millis(500));
+
+        // the bucket refills over time, up to its capacity
+        let later = start + Duration::from_secs(1);
+        assert_eq!(client.reserve(5, later), Duration::from_secs(0));
+        let much_later = later + Duration::from_secs(10);
+        assert_eq!(client.reserve(5, much_later), Duration::from_secs(0));
+        assert_eq!(client.reserve(1, much_later), Duration::from_millis(100));
+    }
+}
@@ -0,0 +1,495 @@
+use super::{common::JsonRpcError, http};
+use crate::{provider::ProviderError, JsonRpcClient, PubsubClient};
+
+use async_trait::async_trait;
+use ethers_core::types::U256;
+use futures_timer::Delay;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::{
+    collections::hash_map::RandomState,
+    fmt::Debug,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+use thiserror::Error;
+use tracing::trace;
+
+/// Classifies the errors of a transport, deciding which requests are retried by a
+/// [`RetryClient`]
+pub trait RetryPolicy<E>: Debug + Send + Sync {
+    /// Returns whether the request which failed with `error` should be retried
+    fn should_retry(&self, error: &E) -> bool;
+}
+
+/// The methods which are never retried by default, since retrying them after an error
+/// which is not definitive, e.g. a timeout, could e.g. broadcast a transaction twice
+pub const NON_IDEMPOTENT_METHODS: &[&str] = &["eth_sendTransaction", "eth_sendRawTransaction"];
+
+/// Retries the requests which were rate limited, or which failed because the node or
+/// the gateway in front of it was temporarily unavailable
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RateLimitRetryPolicy;
+
+/// Returns whether the node rejected the request because of rate limiting. Infura uses
+/// the -32005 code, while other providers mirror the HTTP 429 status.
+fn is_rate_limited(err: &JsonRpcError) -> bool {
+    err.code == 429 || err.code == -32005 || err.message.to_lowercase().contains("rate limit")
+}
+
+impl RetryPolicy<http::ClientError> for RateLimitRetryPolicy {
+    fn should_retry(&self, error: &http::ClientError) -> bool {
+        match error {
+            http::ClientError::ReqwestError(err) => {
+                err.is_timeout()
+                    || err.is_connect()
+                    || err.status().map_or(false, |status| {
+                        status.as_u16() == 429 || status.is_server_error()
+                    })
+            }
+            http::ClientError::JsonRpcError(err) => is_rate_limited(err),
+            // error pages of rate limiters and gateways are not JSON
+            http::ClientError::SerdeJson { text, .. } => {
+                let text = text.to_lowercase();
+                [
+                    "too many requests",
+                    "rate limit",
+                    "service unavailable",
+                    "bad gateway",
+                    "gateway timeout",
+                ]
+                .iter()
+                .any(|pattern| text.contains(pattern))
+            }
+            http::ClientError::MissingBatchResponse(_) => false,
+        }
+    }
+}
+
+#[cfg(feature = "ws")]
+impl RetryPolicy<super::ws::ClientError> for RateLimitRetryPolicy {
+    fn should_retry(&self, error: &super::ws::ClientError) -> bool {
+        match error {
+            super::ws::ClientError::JsonRpcError(err) => is_rate_limited(err),
+            _ => false,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+/// Error thrown by a [`RetryClient`]
+pub enum RetryClientError<E: std::error::Error + 'static> {
+    /// Thrown by the wrapped transport, after all retries were exhausted or if the
+    /// error was not retryable
+    #[error(transparent)]
+    ClientError(E),
+
+    /// Thrown if the params could not be serialized
+    #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
+}
+
+impl<E> RetryClientError<E>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    /// Returns the JSON-RPC error response of the node, if the wrapped transport failed
+    /// with one
+    pub fn as_error_response(&self) -> Option<&JsonRpcError> {
+        match self {
+            RetryClientError::ClientError(err) => super::as_error_response(err),
+            RetryClientError::SerdeJson(_) => None,
+        }
+    }
+}
+
+impl<E> From<RetryClientError<E>> for ProviderError
+where
+    E: std::error::Error + Into<ProviderError> + 'static,
+{
+    fn from(src: RetryClientError<E>) -> Self {
+        match src {
+            RetryClientError::ClientError(err) => err.into(),
+            RetryClientError::SerdeJson(err) => ProviderError::SerdeJson(err),
+        }
+    }
+}
+
+/// A transport wrapper which retries failed requests with exponential backoff, e.g.
+/// when an RPC vendor rate limits the requests. The [`RetryPolicy`] decides which
+/// errors are transient.
+///
+/// The delay before the n-th retry is `initial_backoff * 2^n`, capped at `max_backoff`.
+/// With jitter, a random delay of up to half of it is subtracted, so that concurrent
+/// clients do not retry in lockstep. The [`NON_IDEMPOTENT_METHODS`] are never retried,
+/// unless configured otherwise with [`RetryClient::excluded_methods`].
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers::providers::{Http, Provider, RateLimitRetryPolicy, RetryClient};
+/// use std::{str::FromStr, time::Duration};
+///
+/// # fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let http = Http::from_str("https://mainnet.infura.io/v3/c60b0bb42f8a4c6481ecd229eddaca27")?;
+/// let client = RetryClient::new(http, RateLimitRetryPolicy)
+///     .max_retries(10)
+///     .initial_backoff(Duration::from_millis(500));
+/// let provider = Provider::new(client);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct RetryClient<T, P = RateLimitRetryPolicy> {
+    inner: T,
+    policy: P,
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+    excluded_methods: Vec<String>,
+}
+
+impl<T, P> RetryClient<T, P> {
+    /// Wraps the transport, retrying the errors classified as transient by `policy` up
+    /// to 5 times, with an initial backoff of 250ms
+    pub fn new(inner: T, policy: P) -> Self {
+        Self {
+            inner,
+            policy,
+            max_retries: 5,
+            initial_backoff: Duration::from_millis(250),
+            max_backoff: Duration::from_secs(30),
+            jitter: true,
+            excluded_methods: NON_IDEMPOTENT_METHODS
+                .iter()
+                .map(|method| method.to_string())
+                .collect(),
+        }
+    }
+
+    /// Sets the maximum number of retries of a request
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Sets the delay before the first retry
+    pub fn initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Sets the maximum delay between retries
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Sets whether the delays are randomized
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Sets the methods which are never retried, replacing the [`NON_IDEMPOTENT_METHODS`]
+    pub fn excluded_methods<I, S>(mut self, methods: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.excluded_methods = methods.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Returns the wrapped transport
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns the delay before the retry following `attempt` failed attempts
+    fn backoff(&self, attempt: u32) -> Duration {
+        let backoff = self
+            .initial_backoff
+            .checked_mul(1 << attempt.min(31))
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff);
+        if !self.jitter {
+            return backoff;
+        }
+
+        // a random fraction of the delay, without depending on an RNG
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u32(attempt);
+        let fraction = (hasher.finish() % 1000) as f64 / 1000.0;
+        backoff.mul_f64(1.0 - fraction / 2.0)
+    }
+}
+
+#[async_trait]
+impl<T, P> JsonRpcClient for RetryClient<T, P>
+where
+    T: JsonRpcClient,
+    T::Error: 'static,
+    P: RetryPolicy<T::Error>,
+{
+    type Error = RetryClientError<T::Error>;
+
+    async fn request<A, R>(&self, method: &str, params: A) -> Result<R, Self::Error>
+    where
+        A: Debug + Serialize + Send + Sync,
+        R: Serialize + DeserializeOwned,
+    {
+        // the params are serialized once, since they are sent on every attempt
+        let params = serde_json::to_value(params)?;
+        let max_retries = if self
+            .excluded_methods
+            .iter()
+            .any(|excluded| excluded == method)
+        {
+            0
+        } else {
+            self.max_retries
+        };
+        let mut attempt = 0;
+        loop {
+            // the response is not held across the delay, since it need not be `Send`
+            let backoff = {
+                let res = match params {
+                    // parameterless requests are sent without params
+                    Value::Null => self.inner.request(method, ()).await,
+                    ref params => self.inner.request(method, params).await,
+                };
+                match res {
+                    Err(err) if attempt < max_retries && self.policy.should_retry(&err) => {
+                        let backoff = self.backoff(attempt);
+                        attempt += 1;
+                        trace!(method, attempt, ?backoff, "retrying request: {}", err);
+                        #[cfg(feature = "opentelemetry")]
+                        super::telemetry::record_retry(attempt);
+                        backoff
+                    }
+                    res => return res.map_err(RetryClientError::ClientError),
+                }
+            };
+            Delay::new(backoff).await;
+        }
+    }
+}
+
+/// Subscriptions are passed through to the wrapped transport, since their notifications
+/// are pushed by the node rather than requested
+impl<T, P> PubsubClient for RetryClient<T, P>
+where
+    T: PubsubClient,
+    T::Error: 'static,
+    P: RetryPolicy<T::Error>,
+{
+    type NotificationStream = T::NotificationStream;
+
+    fn subscribe<I: Into<U256>>(&self, id: I) -> Result<Self::NotificationStream, Self::Error> {
+        self.inner
+            .subscribe(id)
+            .map_err(RetryClientError::ClientError)
+    }
+
+    fn unsubscribe<I: Into<U256>>(&self, id: I) -> Result<(), Self::Error> {
+        self.inner
+            .unsubscribe(id)
+            .map_err(RetryClientError::ClientError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Middleware, MockError, MockProvider, Provider};
+    use ethers_core::types::U64;
+    use futures_util::{stream, StreamExt};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug)]
+    struct RetryAll;
+
+    impl RetryPolicy<MockError> for RetryAll {
+        fn should_retry(&self, _: &MockError) -> bool {
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn retries_requests() {
+        let mock = MockProvider::new();
+        let client = RetryClient::new(mock.clone(), RetryAll)
+            .max_retries(2)
+            .initial_backoff(Duration::from_millis(1));
+        let provider = Provider::new(client);
+
+        mock.push(U64::from(12)).unwrap();
+        assert_eq!(provider.get_block_number().await.unwrap(), 12.into());
+        mock.assert_request("eth_blockNumber", ()).unwrap();
+
+        // the mock has no more responses, so all 3 attempts fail
+        provider.get_block_number().await.unwrap_err();
+        for _ in 0..3 {
+            mock.assert_request("eth_blockNumber", ()).unwrap();
+        }
+        mock.assert_request("eth_blockNumber", ()).unwrap_err();
+    }
+
+    /// A pubsub transport answering requests with a mock, whose subscriptions stream
+    /// their id once
+    #[derive(Debug, Clone, Default)]
+    struct MockPubsub {
+        mock: MockProvider,
+        unsubscribed: Arc<Mutex<Vec<U256>>>,
+    }
+
+    #[async_trait]
+    impl JsonRpcClient for MockPubsub {
+        type Error = MockError;
+
+        async fn request<A, R>(&self, method: &str, params: A) -> Result<R, MockError>
+        where
+            A: Debug + Serialize + Send + Sync,
+            R: Serialize + DeserializeOwned,
+        {
+            self.mock.request(method, params).await
+        }
+    }
+
+    impl PubsubClient for MockPubsub {
+        type NotificationStream = stream::Iter<std::vec::IntoIter<Value>>;
+
+        fn subscribe<T: Into<U256>>(&self, id: T) -> Result<Self::NotificationStream, MockError> {
+            Ok(stream::iter(vec![serde_json::to_value(id.into())?]))
+        }
+
+        fn unsubscribe<T: Into<U256>>(&self, id: T) -> Result<(), MockError> {
+            self.unsubscribed.lock().unwrap().push(id.into());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn passes_subscriptions_through() {
+        let transport = MockPubsub::default();
+        let client = RetryClient::new(transport.clone(), RetryAll);
+
+        let notifications = client.subscribe(7u64).unwrap().collect::<Vec<_>>().await;
+        assert_eq!(
+            notifications,
+            vec![serde_json::to_value(U256::from(7)).unwrap()]
+        );
+
+        client.unsubscribe(7u64).unwrap();
+        assert_eq!(*transport.unsubscribed.lock().unwrap(), vec![U256::from(7)]);
+    }
+
+    /// A transport whose requests are all rejected by the node
+    #[derive(Debug)]
+    struct Rejecting(JsonRpcError);
+
+    #[async_trait]
+    impl JsonRpcClient for Rejecting {
+        type Error = http::ClientError;
+
+        async fn request<A, R>(&self, _: &str, _: A) -> Result<R, http::ClientError>
+        where
+            A: Debug + Serialize + Send + Sync,
+            R: Serialize + DeserializeOwned,
+        {
+            Err(http::ClientError::JsonRpcError(self.0.clone()))
+        }
+    }
+
+    #[tokio::test]
+    async fn surfaces_error_responses() {
+        let revert = JsonRpcError {
+            code: 3,
+            message: "execution reverted".to_owned(),
+            data: None,
+        };
+        let client = RetryClient::new(Rejecting(revert.clone()), RateLimitRetryPolicy);
+        let err = client.request::<_, U64>("eth_call", ()).await.unwrap_err();
+        assert_eq!(err.as_error_response().unwrap().code, revert.code);
+
+        // also through transports wrapping the retry layer
+        assert_eq!(
+            super::super::as_error_response(&err).unwrap().message,
+            revert.message
+        );
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_non_idempotent_methods() {
+        let mock = MockProvider::new();
+        let client = RetryClient::new(mock.clone(), RetryAll)
+            .max_retries(2)
+            .initial_backoff(Duration::from_millis(1));
+        let provider = Provider::new(client);
+
+        let rlp = ethers_core::types::Bytes::from(vec![1, 2, 3]);
+        assert!(provider
+            .send_raw_transaction_bytes(rlp.clone())
+            .await
+            .is_err());
+        mock.assert_request("eth_sendRawTransaction", [&rlp])
+            .unwrap();
+        mock.assert_request("eth_sendRawTransaction", [&rlp])
+            .unwrap_err();
+
+        // unless they are no longer excluded
+        let client = RetryClient::new(mock.clone(), RetryAll)
+            .max_retries(2)
+            .initial_backoff(Duration::from_millis(1))
+            .excluded_methods(Vec::<String>::new());
+        let provider = Provider::new(client);
+        assert!(provider
+            .send_raw_transaction_bytes(rlp.clone())
+            .await
+            .is_err());
+        for _ in 0..3 {
+            mock.assert_request("eth_sendRawTransaction", [&rlp])
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn classifies_http_errors() {
+        let policy = RateLimitRetryPolicy;
+        let rpc_error = |code, message: &str| {
+            http::ClientError::JsonRpcError(JsonRpcError {
+                code,
+                message: message.to_owned(),
+                data: None,
+            })
+        };
+        assert!(policy.should_retry(&rpc_error(-32005, "daily request count exceeded")));
+        assert!(policy.should_retry(&rpc_error(429, "exceeded its compute units")));
+        assert!(!policy.should_retry(&rpc_error(3, "execution reverted")));
+
+        let serde_error = |text: &str| http::ClientError::SerdeJson {
+            err: serde_json::from_str::<Value>(text).unwrap_err(),
+            text: text.to_owned(),
+        };
+        assert!(policy.should_retry(&serde_error("429 Too Many Requests")));
+        assert!(!policy.should_retry(&serde_error("not json")));
+    }
+
+    #[test]
+    fn backs_off_exponentially() {
+        let client = RetryClient::new(MockProvider::new(), RetryAll)
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_millis(500))
+            .jitter(false);
+        assert_eq!(client.backoff(0), Duration::from_millis(100));
+        assert_eq!(client.backoff(2), Duration::from_millis(400));
+        assert_eq!(client.backoff(3), Duration::from_millis(500));
+        assert_eq!(client.backoff(40), Duration::from_millis(500));
+
+        let client = client.jitter(true);
+        let backoff = client.backoff(1);
+        assert!(backoff <= Duration::from_millis(200) && backoff >= Duration::from_millis(100));
+    }
+}
@@ -0,0 +1,224 @@
+use crate::JsonRpcClient;
+
+use async_trait::async_trait;
+use opentelemetry::{
+    global,
+    metrics::{Counter, ValueRecorder},
+    trace::{get_active_span, FutureExt, SpanKind, StatusCode, TraceContextExt, Tracer},
+    Context, KeyValue,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    fmt::{self, Debug},
+    time::Instant,
+};
+
+/// The name of the instrumentation library reported with the spans and metrics
+const INSTRUMENTATION_NAME: &str = "ethers-providers";
+
+/// A transport wrapper which exports the requests of the wrapped transport to
+/// [OpenTelemetry](https://opentelemetry.io), using the globally installed tracer and
+/// meter providers (e.g. a Jaeger or OTLP pipeline).
+///
+/// Each request is a client span with the `rpc.method` and `net.peer.name` attributes,
+/// whose status is set on errors. Its `rpc.retries` attribute counts the retries of the
+/// request by the wrapped transport, if it retries failed requests. The span is the
+/// current context while the wrapped transport runs, so spans started further down the
+/// stack are nested under it. The `rpc.client.requests` counter and
+/// `rpc.client.duration` recorder (in seconds) are labeled with the method and the
+/// `rpc.status` (`ok` or `error`).
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers::providers::{Http, Provider, TelemetryClient};
+/// use std::str::FromStr;
+///
+/// # fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let url = "http://localhost:8545";
+/// let http = TelemetryClient::new(Http::from_str(url)?, url);
+/// let provider = Provider::new(http);
+/// # Ok(())
+/// # }
+/// ```
+pub struct TelemetryClient<T> {
+    inner: T,
+    endpoint: String,
+    requests: Counter<u64>,
+    duration: ValueRecorder<f64>,
+}
+
+impl<T> TelemetryClient<T> {
+    /// Wraps the transport, reporting `endpoint` as the peer of its requests
+    pub fn new(inner: T, endpoint: impl Into<String>) -> Self {
+        let meter = global::meter(INSTRUMENTATION_NAME);
+        Self {
+            inner,
+            endpoint: endpoint.into(),
+            requests: meter
+                .u64_counter("rpc.client.requests")
+                .with_description("The number of JSON-RPC requests")
+                .init(),
+            duration: meter
+                .f64_value_recorder("rpc.client.duration")
+                .with_description("The duration of JSON-RPC requests in seconds")
+                .init(),
+        }
+    }
+
+    /// Returns the wrapped transport
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<T: Debug> Debug for TelemetryClient<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TelemetryClient")
+            .field("inner", &self.inner)
+            .field("endpoint", &self.endpoint)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl<T: JsonRpcClient> JsonRpcClient for TelemetryClient<T> {
+    type Error = T::Error;
+
+    async fn request<P, R>(&self, method: &str, params: P) -> Result<R, Self::Error>
+    where
+        P: Debug + Serialize + Send + Sync,
+        R: Serialize + DeserializeOwned,
+    {
+        let tracer = global::tracer(INSTRUMENTATION_NAME);
+        let span = tracer
+            .span_builder(method)
+            .with_kind(SpanKind::Client)
+            .with_attributes(vec![
+                KeyValue::new("rpc.system", "jsonrpc"),
+                KeyValue::new("rpc.method", method.to_owned()),
+                KeyValue::new("net.peer.name", self.endpoint.clone()),
+                KeyValue::new("rpc.retries", 0i64),
+            ])
+            .start(&tracer);
+        let cx = Context::current_with_span(span);
+
+        let start = Instant::now();
+        let res = self
+            .inner
+            .request(method, params)
+            .with_context(cx.clone())
+            .await;
+        let elapsed = start.elapsed().as_secs_f64();
+
+        let status = match res {
+            Ok(_) => "ok",
+            Err(ref err) => {
+                cx.span().set_status(StatusCode::Error, err.to_string());
+                "error"
+            }
+        };
+        cx.span().end();
+
+        let labels = [
+            KeyValue::new("rpc.method", method.to_owned()),
+            KeyValue::new("rpc.status", status),
+        ];
+        self.requests.add(1, &labels);
+        self.duration.record(elapsed, &labels);
+
+        res
+    }
+}
+
+/// Records that the request of the current span is retried for the `retries`-th time, for
+/// transports retrying failed requests within a [`TelemetryClient`]
+pub(crate) fn record_retry(retries: u32) {
+    get_active_span(|span| span.set_attribute(KeyValue::new("rpc.retries", i64::from(retries))));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Middleware, MockError, MockProvider, Provider, RetryClient, RetryPolicy};
+    use ethers_core::types::U64;
+    use opentelemetry::{
+        sdk::{
+            export::trace::{ExportResult, SpanData, SpanExporter},
+            trace::TracerProvider,
+        },
+        Key, Value,
+    };
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    #[derive(Debug, Clone, Default)]
+    struct SpanCollector(Arc<Mutex<Vec<SpanData>>>);
+
+    #[async_trait]
+    impl SpanExporter for SpanCollector {
+        async fn export(&mut self, batch: Vec<SpanData>) -> ExportResult {
+            self.0.lock().unwrap().extend(batch);
+            Ok(())
+        }
+    }
+
+    #[derive(Debug)]
+    struct RetryAll;
+
+    impl RetryPolicy<MockError> for RetryAll {
+        fn should_retry(&self, _: &MockError) -> bool {
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn forwards_requests() {
+        let mock = MockProvider::new();
+        let provider = Provider::new(TelemetryClient::new(mock.clone(), "mock"));
+
+        mock.push(U64::from(12)).unwrap();
+        assert_eq!(provider.get_block_number().await.unwrap(), 12.into());
+        mock.assert_request("eth_blockNumber", ()).unwrap();
+
+        // errors are passed through
+        provider.get_block_number().await.unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn records_retries() {
+        let spans = SpanCollector::default();
+        global::set_tracer_provider(
+            TracerProvider::builder()
+                .with_simple_exporter(spans.clone())
+                .build(),
+        );
+
+        let mock = MockProvider::new();
+        let retry = RetryClient::new(mock.clone(), RetryAll)
+            .max_retries(1)
+            .initial_backoff(Duration::from_millis(1));
+        let client = TelemetryClient::new(retry, "mock");
+
+        // the mock has no responses, so both attempts fail
+        client
+            .request::<_, U64>("eth_chainId", ())
+            .await
+            .unwrap_err();
+        for _ in 0..2 {
+            mock.assert_request("eth_chainId", ()).unwrap();
+        }
+
+        let spans = spans.0.lock().unwrap();
+        let span = spans
+            .iter()
+            .find(|span| span.name == "eth_chainId")
+            .unwrap();
+        assert_eq!(
+            span.attributes.get(&Key::new("rpc.retries")),
+            Some(&Value::I64(1))
+        );
+    }
+}
@@ -1,6 +1,9 @@
 use crate::{
     provider::ProviderError,
-    transports::common::{JsonRpcError, Notification, Request, Response},
+    transports::{
+        auth::Authorization,
+        common::{JsonRpcError, Notification, Request, Response, UNSUBSCRIBE_REQUEST_ID},
+    },
     JsonRpcClient, PubsubClient,
 };
 use ethers_core::types::U256;
@@ -23,7 +26,11 @@ use std::{
 use thiserror::Error;
 use tokio_tungstenite::{
     connect_async,
-    tungstenite::{self, protocol::Message},
+    tungstenite::{
+        self,
+        http::header::{HeaderValue, AUTHORIZATION},
+        protocol::Message,
+    },
 };
 
 /// A JSON-RPC Client over Websockets.
@@ -98,6 +105,20 @@ impl Ws {
         Ok(Self::new(ws))
     }
 
+    /// Initializes a new WebSocket Client, sending the credentials in the
+    /// `Authorization` header of the handshake request. Additional headers can be sent
+    /// by passing an `http::Request` to [`Ws::connect`].
+    pub async fn connect_with_auth(
+        url: impl tungstenite::client::IntoClientRequest + Unpin,
+        auth: Authorization,
+    ) -> Result<Self, ClientError> {
+        let mut request = url.into_client_request()?;
+        let value = HeaderValue::from_str(&auth.header_value())
+            .map_err(|err| tungstenite::Error::HttpFormat(err.into()))?;
+        request.headers_mut().insert(AUTHORIZATION, value);
+        Self::connect(request).await
+    }
+
     fn send(&self, msg: TransportMessage) -> Result<(), ClientError> {
         self.requests.unbounded_send(msg).map_err(to_client_error)
     }
@@ -242,6 +263,14 @@ where
                         "Unsubscribing from non-existent subscription with id {:?}",
                         id
                     );
+                    return Ok(());
+                }
+
+                // cancel the subscription on the node, ignoring the response
+                let request = Request::new(UNSUBSCRIBE_REQUEST_ID, "eth_unsubscribe", [id]);
+                let request = serde_json::to_string(&request)?;
+                if let Err(e) = self.ws.send(Message::Text(request)).await {
+                    println!("WS connection error: {:?}", e);
                 }
             }
         };
@@ -0,0 +1,413 @@
+//! Signer backed by a secp256k1 key of Google Cloud KMS
+//! [Official Docs](https://cloud.google.com/kms/docs/create-validate-signatures)
+use crate::{to_eip155_v, Signer};
+
+use async_trait::async_trait;
+use ethers_core::{
+    types::{Address, Signature, TypedTransaction, H256, U256},
+    utils::{hash_message, keccak256},
+};
+use reqwest::{Client, RequestBuilder, StatusCode};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::json;
+use std::{str::FromStr, time::Duration};
+use thiserror::Error;
+
+const KMS_URL: &str = "https://cloudkms.googleapis.com/v1";
+
+/// The DER prefix of the SubjectPublicKeyInfo of secp256k1 keys, followed by the
+/// uncompressed public key
+const SPKI_PREFIX: [u8; 23] = [
+    0x30, 0x56, 0x30, 0x10, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x05, 0x2b,
+    0x81, 0x04, 0x00, 0x0a, 0x03, 0x42, 0x00,
+];
+
+/// The order of the secp256k1 curve
+const SECP256K1_N: &str = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141";
+
+/// A signer whose private key is held by Google Cloud KMS. The key must be an
+/// `EC_SIGN_SECP256K1_SHA256` key version. KMS signs the digests it is provided with,
+/// so messages and transactions are hashed locally and only their hash is sent.
+///
+/// Requests are authenticated with an OAuth2 access token with the
+/// `cloudkms.cryptoKeyVersions.useToSign` and `viewPublicKey` permissions, e.g. the
+/// output of `gcloud auth print-access-token`. Transient failures (rate limits, server
+/// errors and connection errors) are retried with an exponential backoff.
+///
+/// ```no_run
+/// use ethers_signers::{GcpKmsSigner, Signer};
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let key = "projects/my-project/locations/global/keyRings/my-ring/cryptoKeys/my-key/cryptoKeyVersions/1";
+/// let signer = GcpKmsSigner::new(key, "access-token", Some(1)).await?;
+///
+/// let signature = signer.sign_message("hello world").await?;
+/// signature.verify("hello world", signer.address())?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct GcpKmsSigner {
+    client: Client,
+    key_name: String,
+    access_token: String,
+    retries: usize,
+    /// The signer's chain id (for EIP-155), signs w/o replay protection if left unset
+    pub chain_id: Option<u64>,
+    /// The address of the KMS key, instantiated at runtime
+    pub address: Address,
+}
+
+#[derive(Error, Debug)]
+/// Error thrown by the Google Cloud KMS signer
+pub enum GcpKmsError {
+    /// Error propagated from the HTTP client
+    #[error(transparent)]
+    ReqwestError(#[from] reqwest::Error),
+    /// Error propagated from the base64 decoder
+    #[error(transparent)]
+    Base64Error(#[from] base64::DecodeError),
+    /// Error returned by the KMS API
+    #[error("KMS request failed with status {status}: {message}")]
+    KmsError {
+        /// The HTTP status of the response
+        status: u16,
+        /// The message of the error
+        message: String,
+    },
+    /// The public key of the KMS key is not a secp256k1 key
+    #[error("invalid public key, expected an EC_SIGN_SECP256K1_SHA256 key")]
+    InvalidPublicKey,
+    /// The signature returned by KMS is not a valid DER encoded ECDSA signature
+    #[error("invalid DER signature")]
+    InvalidSignature,
+    /// The signature returned by KMS does not recover to the address of the key
+    #[error("could not recover the address of the key from the signature")]
+    RecoveryError,
+}
+
+#[derive(Deserialize)]
+struct PublicKeyResponse {
+    pem: String,
+}
+
+#[derive(Deserialize)]
+struct SignResponse {
+    signature: String,
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    error: ErrorDetails,
+}
+
+#[derive(Deserialize)]
+struct ErrorDetails {
+    message: String,
+}
+
+impl GcpKmsSigner {
+    /// Instantiates the signer of the provided key version, e.g.
+    /// `projects/{project}/locations/{location}/keyRings/{ring}/cryptoKeys/{key}/cryptoKeyVersions/{version}`,
+    /// by retrieving its public key
+    pub async fn new<K, T>(
+        key_name: K,
+        access_token: T,
+        chain_id: Option<u64>,
+    ) -> Result<Self, GcpKmsError>
+    where
+        K: Into<String>,
+        T: Into<String>,
+    {
+        let mut signer = Self {
+            client: Client::new(),
+            key_name: key_name.into(),
+            access_token: access_token.into(),
+            retries: 3,
+            chain_id,
+            address: Address::zero(),
+        };
+        signer.address = signer.get_address().await?;
+        Ok(signer)
+    }
+
+    /// Sets the number of times the requests failing with a transient error are retried
+    /// (3 by default)
+    pub fn retries(mut self, retries: usize) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Sets the access token used to authenticate the requests, e.g. once it has been
+    /// refreshed
+    pub fn access_token<T: Into<String>>(mut self, access_token: T) -> Self {
+        self.access_token = access_token.into();
+        self
+    }
+
+    /// Retrieves the public key of the KMS key and returns its address
+    pub async fn get_address(&self) -> Result<Address, GcpKmsError> {
+        let url = format!("{}/{}/publicKey", KMS_URL, self.key_name);
+        let response: PublicKeyResponse = self.send(|| self.client.get(&url)).await?;
+        address_from_pem(&response.pem)
+    }
+
+    /// Signs the digest with the KMS key, and returns the signature with its `v` set as
+    /// per EIP-155 if a chain id is provided, or in 'Electrum' notation otherwise
+    pub async fn sign_digest(
+        &self,
+        digest: H256,
+        chain_id: Option<u64>,
+    ) -> Result<Signature, GcpKmsError> {
+        let url = format!("{}/{}:asymmetricSign", KMS_URL, self.key_name);
+        let body = json!({ "digest": { "sha256": base64::encode(digest) } });
+        let response: SignResponse = self.send(|| self.client.post(&url).json(&body)).await?;
+        let der = base64::decode(response.signature)?;
+
+        let mut signature = signature_from_der(&der, digest, self.address)?;
+        signature.v = to_eip155_v(signature.v as u8, chain_id);
+        Ok(signature)
+    }
+
+    /// Sends the request, retrying it on transient failures
+    async fn send<R, F>(&self, request: F) -> Result<R, GcpKmsError>
+    where
+        R: DeserializeOwned,
+        F: Fn() -> RequestBuilder,
+    {
+        let mut backoff = Duration::from_millis(100);
+        let mut attempt = 0;
+        loop {
+            let result = match request().bearer_auth(&self.access_token).send().await {
+                Ok(response) if response.status().is_success() => {
+                    return Ok(response.json().await?)
+                }
+                Ok(response) => {
+                    let status = response.status();
+                    let message = match response.json::<ErrorResponse>().await {
+                        Ok(error) => error.error.message,
+                        Err(_) => status.to_string(),
+                    };
+                    let transient =
+                        status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error();
+                    let err = GcpKmsError::KmsError {
+                        status: status.as_u16(),
+                        message,
+                    };
+                    (err, transient)
+                }
+                Err(err) => {
+                    let transient = err.is_timeout() || err.is_connect();
+                    (err.into(), transient)
+                }
+            };
+
+            match result {
+                (_, true) if attempt < self.retries => {
+                    attempt += 1;
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+                (err, _) => return Err(err),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Signer for GcpKmsSigner {
+    type Error = GcpKmsError;
+
+    /// Signs the hash of the provided message after prefixing it
+    async fn sign_message<S: Send + Sync + AsRef<[u8]>>(
+        &self,
+        message: S,
+    ) -> Result<Signature, Self::Error> {
+        self.sign_digest(hash_message(message), None).await
+    }
+
+    /// Signs the transaction
+    async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, Self::Error> {
+        let sighash = tx.sighash(self.chain_id);
+        match tx {
+            TypedTransaction::Legacy(_) => self.sign_digest(sighash, self.chain_id).await,
+            _ => {
+                let mut signature = self.sign_digest(sighash, None).await?;
+                // typed transactions are signed with the y-parity instead of the 'Electrum' `v`
+                signature.v -= 27;
+                Ok(signature)
+            }
+        }
+    }
+
+    /// Returns the signer's Ethereum Address
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Returns the chain id used for EIP-155 signing, if any
+    fn chain_id(&self) -> Option<u64> {
+        self.chain_id
+    }
+}
+
+/// Decodes the address of a PEM encoded secp256k1 public key
+fn address_from_pem(pem: &str) -> Result<Address, GcpKmsError> {
+    let encoded: String = pem
+        .lines()
+        .filter(|line| !line.starts_with("-----"))
+        .collect();
+    let der = base64::decode(encoded)?;
+    if der.len() != SPKI_PREFIX.len() + 65 || der[..SPKI_PREFIX.len()] != SPKI_PREFIX {
+        return Err(GcpKmsError::InvalidPublicKey);
+    }
+
+    // skip the 0x04 tag of the uncompressed public key
+    let hash = keccak256(&der[SPKI_PREFIX.len() + 1..]);
+    Ok(Address::from_slice(&hash[12..]))
+}
+
+/// Decodes a DER encoded ECDSA signature of the digest. Its `s` is normalized to the
+/// lower half of the curve order, as required by Ethereum, and its `v` is set to the
+/// recovery id which recovers the address, since KMS does not return it.
+fn signature_from_der(
+    der: &[u8],
+    digest: H256,
+    address: Address,
+) -> Result<Signature, GcpKmsError> {
+    let (r, s) = parse_der(der).ok_or(GcpKmsError::InvalidSignature)?;
+
+    let n = U256::from_str(SECP256K1_N).expect("valid curve order");
+    let s = if s > n / 2 { n - s } else { s };
+
+    let mut signature = Signature {
+        r: uint_to_h256(r),
+        s: uint_to_h256(s),
+        v: 0,
+    };
+    for recovery_id in 0..2 {
+        signature.v = recovery_id;
+        if signature.recover(digest).ok() == Some(address) {
+            return Ok(signature);
+        }
+    }
+    Err(GcpKmsError::RecoveryError)
+}
+
+/// Parses the `SEQUENCE { r INTEGER, s INTEGER }` of a DER encoded ECDSA signature
+fn parse_der(der: &[u8]) -> Option<(U256, U256)> {
+    if der.len() < 2 || der[0] != 0x30 || der[1] as usize != der.len() - 2 {
+        return None;
+    }
+    let (r, rest) = parse_der_integer(&der[2..])?;
+    let (s, rest) = parse_der_integer(rest)?;
+    if !rest.is_empty() {
+        return None;
+    }
+    Some((r, s))
+}
+
+fn parse_der_integer(der: &[u8]) -> Option<(U256, &[u8])> {
+    if der.len() < 2 || der[0] != 0x02 {
+        return None;
+    }
+    let len = der[1] as usize;
+    let bytes = der.get(2..2 + len)?;
+    // integers are prefixed with a zero byte if their high bit is set
+    let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+    if bytes.len() - start > 32 {
+        return None;
+    }
+    Some((U256::from_big_endian(&bytes[start..]), &der[2 + len..]))
+}
+
+fn uint_to_h256(value: U256) -> H256 {
+    let mut bytes = [0u8; 32];
+    value.to_big_endian(&mut bytes);
+    H256(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LocalWallet;
+
+    // the uncompressed public key of the private key 1, i.e. the generator point
+    const PUBLIC_KEY: &str = "0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8";
+
+    fn der_integer(value: H256) -> Vec<u8> {
+        let bytes = value.as_bytes();
+        let start = bytes.iter().position(|b| *b != 0).unwrap_or(31);
+        let mut integer = bytes[start..].to_vec();
+        if integer[0] & 0x80 != 0 {
+            integer.insert(0, 0);
+        }
+        let mut der = vec![0x02, integer.len() as u8];
+        der.extend(integer);
+        der
+    }
+
+    fn der_signature(r: H256, s: H256) -> Vec<u8> {
+        let mut body = der_integer(r);
+        body.extend(der_integer(s));
+        let mut der = vec![0x30, body.len() as u8];
+        der.extend(body);
+        der
+    }
+
+    #[test]
+    fn decodes_pem_address() {
+        let mut der = SPKI_PREFIX.to_vec();
+        der.extend(hex::decode(PUBLIC_KEY).unwrap());
+        let pem = format!(
+            "-----BEGIN PUBLIC KEY-----\n{}\n{}\n-----END PUBLIC KEY-----\n",
+            &base64::encode(&der)[..64],
+            &base64::encode(&der)[64..]
+        );
+        assert_eq!(
+            address_from_pem(&pem).unwrap(),
+            "7E5F4552091A69125d5DfCb7b8C2659029395Bdf".parse().unwrap()
+        );
+
+        // keys of other curves are rejected
+        der[SPKI_PREFIX.len() - 4] = 0x0b;
+        let pem = base64::encode(&der);
+        assert!(matches!(
+            address_from_pem(&pem),
+            Err(GcpKmsError::InvalidPublicKey)
+        ));
+    }
+
+    #[tokio::test]
+    async fn recovers_der_signature() {
+        let wallet: LocalWallet =
+            "0000000000000000000000000000000000000000000000000000000000000001"
+                .parse()
+                .unwrap();
+        let digest = hash_message("Some data");
+        let expected = wallet.sign_message("Some data").await.unwrap();
+
+        let der = der_signature(expected.r, expected.s);
+        let mut signature = signature_from_der(&der, digest, wallet.address()).unwrap();
+        signature.v = to_eip155_v(signature.v as u8, None);
+        assert_eq!(signature, expected);
+
+        // KMS may return signatures with a high `s`, which are normalized
+        let n = U256::from_str(SECP256K1_N).unwrap();
+        let high_s = uint_to_h256(n - U256::from_big_endian(expected.s.as_bytes()));
+        let der = der_signature(expected.r, high_s);
+        let mut signature = signature_from_der(&der, digest, wallet.address()).unwrap();
+        signature.v = to_eip155_v(signature.v as u8, None);
+        assert_eq!(signature, expected);
+
+        // signatures of other keys do not recover to the address
+        assert!(matches!(
+            signature_from_der(&der, digest, Address::zero()),
+            Err(GcpKmsError::RecoveryError)
+        ));
+        assert!(matches!(
+            signature_from_der(&der[1..], digest, wallet.address()),
+            Err(GcpKmsError::InvalidSignature)
+        ));
+    }
+}
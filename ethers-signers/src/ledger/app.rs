@@ -8,7 +8,8 @@ use futures_util::lock::Mutex;
 
 use ethers_core::{
     types::{
-        Address, NameOrAddress, Signature, Transaction, TransactionRequest, TxHash, H256, U256,
+        Address, NameOrAddress, Signature, Transaction, TransactionRequest, TxHash,
+        TypedTransaction, H256, U256,
     },
     utils::keccak256,
 };
@@ -75,6 +76,17 @@ impl LedgerEthereum {
         Self::get_address_with_path_transport(&transport, derivation).await
     }
 
+    /// Gets the account at the provided index, following the scheme of our derivation
+    /// path. Custom derivation paths fall back to the BIP-44 path `m/44'/60'/0'/0/{index}`.
+    pub async fn get_address_at_index(&self, index: usize) -> Result<Address, LedgerError> {
+        let derivation = match self.derivation {
+            DerivationType::LedgerLive(_) => DerivationType::LedgerLive(index),
+            DerivationType::Legacy(_) => DerivationType::Legacy(index),
+            DerivationType::Other(_) => DerivationType::Other(format!("m/44'/60'/0'/0/{}", index)),
+        };
+        self.get_address_with_path(&derivation).await
+    }
+
     async fn get_address_with_path_transport(
         transport: &Ledger,
         derivation: &DerivationType,
@@ -122,10 +134,12 @@ impl LedgerEthereum {
         Ok(format!("{}.{}.{}", result[1], result[2], result[3]))
     }
 
-    /// Signs an Ethereum transaction (requires confirmation on the ledger)
+    /// Signs an Ethereum transaction (requires confirmation on the ledger). Typed
+    /// transactions require version 1.9.0 of the Ethereum app and are signed with the
+    /// y-parity of the signature as `v`.
     pub async fn sign_tx(
         &self,
-        tx: &TransactionRequest,
+        tx: &TypedTransaction,
         chain_id: Option<u64>,
     ) -> Result<Signature, LedgerError> {
         let mut payload = Self::path_to_bytes(&self.derivation);
@@ -251,7 +265,7 @@ mod tests {
             .nonce(5)
             .data(data)
             .value(ethers_core::utils::parse_ether(100).unwrap());
-        let tx = ledger.sign_transaction(&tx_req).await.unwrap();
+        let tx = ledger.sign_transaction(&tx_req.into()).await.unwrap();
     }
 
     #[tokio::test]
@@ -1,10 +1,10 @@
 pub mod app;
 pub mod types;
 
-use crate::Signer;
+use crate::{DeriveAddress, Signer};
 use app::LedgerEthereum;
 use async_trait::async_trait;
-use ethers_core::types::{Address, Signature, TransactionRequest};
+use ethers_core::types::{Address, Signature, TypedTransaction};
 use types::LedgerError;
 
 #[async_trait]
@@ -20,10 +20,7 @@ impl Signer for LedgerEthereum {
     }
 
     /// Signs the transaction
-    async fn sign_transaction(
-        &self,
-        message: &TransactionRequest,
-    ) -> Result<Signature, Self::Error> {
+    async fn sign_transaction(&self, message: &TypedTransaction) -> Result<Signature, Self::Error> {
         self.sign_tx(message, self.chain_id).await
     }
 
@@ -31,4 +28,19 @@ impl Signer for LedgerEthereum {
     fn address(&self) -> Address {
         self.address
     }
+
+    /// Returns the chain id used for EIP-155 signing, if any
+    fn chain_id(&self) -> Option<u64> {
+        self.chain_id
+    }
+}
+
+#[async_trait]
+impl DeriveAddress for LedgerEthereum {
+    type Error = LedgerError;
+
+    /// Gets the account at the provided index, following the ledger's derivation scheme
+    async fn derive_address(&self, index: u32) -> Result<Address, Self::Error> {
+        self.get_address_at_index(index as usize).await
+    }
 }
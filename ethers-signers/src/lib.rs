@@ -10,6 +10,7 @@
 //! - [Private key](crate::LocalWallet)
 //! - [Ledger](crate::Ledger)
 //! - [YubiHSM2](crate::YubiWallet)
+//! - [Google Cloud KMS](crate::GcpKmsSigner)
 //!
 //! ```no_run
 //! # use ethers::{
@@ -27,7 +28,7 @@
 //!     .value(10000);
 //!
 //! // sign it
-//! let signature = wallet.sign_transaction(&tx).await?;
+//! let signature = wallet.sign_transaction(&tx.into()).await?;
 //!
 //! // can also sign a message
 //! let signature = wallet.sign_message("hello world").await?;
@@ -39,7 +40,7 @@
 //! [`Transaction`]: ethers_core::types::Transaction
 //! [`TransactionRequest`]: ethers_core::types::TransactionRequest
 mod wallet;
-pub use wallet::{MnemonicBuilder, Wallet, WalletError};
+pub use wallet::{KeystoreKdf, MnemonicBuilder, Wallet, WalletError};
 
 /// Re-export the BIP-32 crate so that wordlists can be accessed conveniently.
 pub use coins_bip39;
@@ -62,8 +63,13 @@ pub use ledger::{
 #[cfg(feature = "yubi")]
 pub use yubihsm;
 
+#[cfg(feature = "gcp")]
+mod gcp;
+#[cfg(feature = "gcp")]
+pub use gcp::{GcpKmsError, GcpKmsSigner};
+
 use async_trait::async_trait;
-use ethers_core::types::{Address, Signature, TransactionRequest};
+use ethers_core::types::{Address, Bytes, Signature, TypedTransaction};
 use std::error::Error;
 
 /// Applies [EIP155](https://github.com/ethereum/EIPs/blob/master/EIPS/eip-155.md)
@@ -90,12 +96,42 @@ pub trait Signer: std::fmt::Debug + Send + Sync {
         message: S,
     ) -> Result<Signature, Self::Error>;
 
-    /// Signs the transaction
-    async fn sign_transaction(
-        &self,
-        message: &TransactionRequest,
-    ) -> Result<Signature, Self::Error>;
+    /// Signs the transaction. Legacy transactions are signed with the chain id of the
+    /// signer applied to the `v` of the signature, if any, as per EIP-155. Typed
+    /// transactions contain their chain id, which should be set beforehand (local wallets
+    /// default it to their own), and are signed with the y-parity of the signature as `v`.
+    async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, Self::Error>;
+
+    /// Signs the transaction and returns it encoded, ready to be broadcast with
+    /// `eth_sendRawTransaction`. Since no node is involved, the nonce, gas and fees of
+    /// the transaction must be set beforehand.
+    async fn sign_raw_transaction(&self, tx: &TypedTransaction) -> Result<Bytes, Self::Error> {
+        // typed transactions without a chain id are signed for the signer's chain, which
+        // must then be part of their encoding too
+        let mut tx = tx.clone();
+        if let (None, Some(chain_id)) = (tx.chain_id(), self.chain_id()) {
+            tx.set_chain_id(chain_id);
+        }
+        let signature = self.sign_transaction(&tx).await?;
+        Ok(tx.rlp_signed(&signature))
+    }
 
     /// Returns the signer's Ethereum Address
     fn address(&self) -> Address;
+
+    /// Returns the chain id the signer applies to legacy transactions as per EIP-155,
+    /// if any. Defaults to `None`.
+    fn chain_id(&self) -> Option<u64> {
+        None
+    }
+}
+
+/// Trait for HD wallets which derive accounts by index, e.g. to discover the used accounts
+/// of a mnemonic or hardware wallet
+#[async_trait]
+pub trait DeriveAddress: std::fmt::Debug + Send + Sync {
+    type Error: Error + Send + Sync;
+
+    /// Returns the address of the account at the provided index
+    async fn derive_address(&self, index: u32) -> Result<Address, Self::Error>;
 }
@@ -0,0 +1,196 @@
+//! Encryption of private keys following the Web3 Secret Storage (V3 keystore) definition
+//! <https://github.com/ethereum/wiki/wiki/Web3-Secret-Storage-Definition>
+//!
+//! Keystores are decrypted with `eth-keystore`, which however only encrypts them with
+//! fixed scrypt parameters. Encrypting them with the configured KDF is done here instead.
+use crate::WalletError;
+
+use aes::Aes128;
+use ctr::{
+    cipher::stream::{NewStreamCipher, SyncStreamCipher},
+    Ctr128,
+};
+use ethers_core::{types::Address, utils::keccak256};
+use hmac::Hmac;
+use pbkdf2::pbkdf2;
+use rand::{CryptoRng, Rng};
+use scrypt::{scrypt, Params as ScryptParams};
+use serde::Serialize;
+use sha2::Sha256;
+
+const DERIVED_KEY_LEN: usize = 32;
+
+/// The key derivation function used to derive the encryption key of a keystore from its
+/// password. The higher its cost, the slower a brute-force of the password, but also the
+/// slower the decryption of the keystore.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeystoreKdf {
+    /// [scrypt](https://tools.ietf.org/html/rfc7914) with a CPU/memory cost of `2^log_n`,
+    /// a block size of `r` and a parallelization of `p`
+    Scrypt { log_n: u8, r: u32, p: u32 },
+    /// PBKDF2 with HMAC-SHA256 and `c` iterations
+    Pbkdf2 { c: u32 },
+}
+
+impl KeystoreKdf {
+    /// The scrypt parameters of geth's standard keystores (`n = 2^18`), as written by
+    /// `geth account new`
+    pub const GETH_STANDARD: Self = KeystoreKdf::Scrypt {
+        log_n: 18,
+        r: 8,
+        p: 1,
+    };
+
+    /// The scrypt parameters of geth's light keystores (`n = 2^12`), as written with
+    /// `--lightkdf`
+    pub const GETH_LIGHT: Self = KeystoreKdf::Scrypt {
+        log_n: 12,
+        r: 8,
+        p: 6,
+    };
+}
+
+impl Default for KeystoreKdf {
+    /// Scrypt with `n = 2^13`, `r = 8` and `p = 1`, the parameters of the keystores
+    /// created with [`Wallet::new_keystore`](crate::Wallet::new_keystore)
+    fn default() -> Self {
+        KeystoreKdf::Scrypt {
+            log_n: 13,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub(super) struct Keystore {
+    address: String,
+    crypto: CryptoJson,
+    id: String,
+    version: u8,
+}
+
+#[derive(Serialize)]
+struct CryptoJson {
+    cipher: &'static str,
+    cipherparams: CipherParams,
+    ciphertext: String,
+    kdf: &'static str,
+    kdfparams: KdfParams,
+    mac: String,
+}
+
+#[derive(Serialize)]
+struct CipherParams {
+    iv: String,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum KdfParams {
+    Scrypt {
+        dklen: usize,
+        n: u32,
+        p: u32,
+        r: u32,
+        salt: String,
+    },
+    Pbkdf2 {
+        c: u32,
+        dklen: usize,
+        prf: &'static str,
+        salt: String,
+    },
+}
+
+impl Keystore {
+    /// Encrypts the private key with the password, using the KDF to derive the encryption key
+    pub(super) fn encrypt<R: Rng + CryptoRng>(
+        rng: &mut R,
+        secret: &[u8],
+        address: Address,
+        password: &[u8],
+        kdf: KeystoreKdf,
+    ) -> Result<Self, WalletError> {
+        let salt: [u8; 32] = rng.gen();
+        let mut key = [0u8; DERIVED_KEY_LEN];
+        let (kdf, kdfparams) = match kdf {
+            KeystoreKdf::Scrypt { log_n, r, p } => {
+                // `n` is written as a 32 bit integer, which bounds `log_n` below 32
+                let n = 1u32
+                    .checked_shl(log_n.into())
+                    .ok_or(WalletError::InvalidKdfParams)?;
+                let params =
+                    ScryptParams::new(log_n, r, p).map_err(|_| WalletError::InvalidKdfParams)?;
+                scrypt(password, &salt, &params, &mut key)
+                    .expect("the derived key has a valid length");
+                let params = KdfParams::Scrypt {
+                    dklen: DERIVED_KEY_LEN,
+                    n,
+                    p,
+                    r,
+                    salt: hex::encode(salt),
+                };
+                ("scrypt", params)
+            }
+            KeystoreKdf::Pbkdf2 { c } => {
+                if c == 0 {
+                    return Err(WalletError::InvalidKdfParams);
+                }
+                pbkdf2::<Hmac<Sha256>>(password, &salt, c, &mut key);
+                let params = KdfParams::Pbkdf2 {
+                    c,
+                    dklen: DERIVED_KEY_LEN,
+                    prf: "hmac-sha256",
+                    salt: hex::encode(salt),
+                };
+                ("pbkdf2", params)
+            }
+        };
+
+        // the first half of the derived key encrypts the private key, the second one
+        // authenticates the ciphertext
+        let iv: [u8; 16] = rng.gen();
+        let mut ciphertext = secret.to_vec();
+        Ctr128::<Aes128>::new_var(&key[..16], &iv)
+            .expect("the key and iv have valid lengths")
+            .apply_keystream(&mut ciphertext);
+        let mac = keccak256([&key[16..], &ciphertext[..]].concat());
+
+        Ok(Self {
+            address: hex::encode(address),
+            crypto: CryptoJson {
+                cipher: "aes-128-ctr",
+                cipherparams: CipherParams {
+                    iv: hex::encode(iv),
+                },
+                ciphertext: hex::encode(ciphertext),
+                kdf,
+                kdfparams,
+                mac: hex::encode(mac),
+            },
+            id: uuid_v4(rng),
+            version: 3,
+        })
+    }
+
+    /// The id of the keystore, which is used as its file name
+    pub(super) fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+/// Generates a random (version 4) UUID
+fn uuid_v4<R: Rng + CryptoRng>(rng: &mut R) -> String {
+    let mut bytes: [u8; 16] = rng.gen();
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    format!(
+        "{}-{}-{}-{}-{}",
+        hex::encode(&bytes[..4]),
+        hex::encode(&bytes[4..6]),
+        hex::encode(&bytes[6..8]),
+        hex::encode(&bytes[8..10]),
+        hex::encode(&bytes[10..])
+    )
+}
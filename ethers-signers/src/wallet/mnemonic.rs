@@ -1,12 +1,14 @@
 //! Specific helper functions for creating/loading a mnemonic private key following BIP-39
 //! specifications
-use crate::{Wallet, WalletError};
+use crate::{DeriveAddress, Wallet, WalletError};
+
+use async_trait::async_trait;
 
 use coins_bip32::path::DerivationPath;
 use coins_bip39::{Mnemonic, Wordlist};
 use ethers_core::{
     k256::ecdsa::SigningKey,
-    types::PathOrString,
+    types::{Address, PathOrString},
     utils::{secret_key_to_address, to_checksum},
 };
 use rand::Rng;
@@ -27,6 +29,9 @@ pub struct MnemonicBuilder<W: Wordlist> {
     /// The derivation path at which the extended private key child will be derived at. By default
     /// the mnemonic builder uses the path: "m/44'/60'/0'/0/0".
     derivation_path: DerivationPath,
+    /// The derivation path without its last index, under which the accounts of the phrase are
+    /// built. By default this is the prefix "m/44'/60'/0'/0/".
+    derivation_path_prefix: String,
     /// Optional password for the mnemonic phrase.
     password: Option<String>,
     /// Optional field that if enabled, writes the mnemonic phrase to disk storage at the provided
@@ -57,6 +62,7 @@ impl<W: Wordlist> Default for MnemonicBuilder<W> {
                 DEFAULT_DERIVATION_PATH_PREFIX, 0
             ))
             .expect("should parse the default derivation path"),
+            derivation_path_prefix: DEFAULT_DERIVATION_PATH_PREFIX.to_string(),
             password: None,
             write_to: None,
             _wordlist: PhantomData,
@@ -117,12 +123,18 @@ impl<W: Wordlist> MnemonicBuilder<W> {
             DEFAULT_DERIVATION_PATH_PREFIX,
             index.into()
         ))?;
+        self.derivation_path_prefix = DEFAULT_DERIVATION_PATH_PREFIX.to_string();
         Ok(self)
     }
 
-    /// Sets the derivation path of the child key to be derived.
+    /// Sets the derivation path of the child key to be derived. Accounts are built under the
+    /// same path, with their index in place of its last one.
     pub fn derivation_path(mut self, path: &str) -> Result<Self, WalletError> {
         self.derivation_path = DerivationPath::from_str(path)?;
+        self.derivation_path_prefix = match path.rfind('/') {
+            Some(index) => path[..=index].to_string(),
+            None => String::new(),
+        };
         Ok(self)
     }
 
@@ -152,6 +164,42 @@ impl<W: Wordlist> MnemonicBuilder<W> {
         self.mnemonic_to_wallet(&mnemonic)
     }
 
+    /// Builds the wallets of the first `count` accounts of the phrase, i.e. the ones at
+    /// "m/44'/60'/0'/0/{index}" for indices `0..count`, as used by ganache and hardhat for
+    /// their test accounts. If a derivation path is set in the builder, the accounts are
+    /// built under it instead, with their index in place of its last one. This method
+    /// expects the phrase field to be set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ethers_signers::{MnemonicBuilder, coins_bip39::English};
+    /// # fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// let accounts = MnemonicBuilder::<English>::default()
+    ///     .phrase("test test test test test test test test test test test junk")
+    ///     .build_accounts(10)?;
+    /// assert_eq!(accounts.len(), 10);
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn build_accounts(&self, count: u32) -> Result<Vec<Wallet<SigningKey>>, WalletError> {
+        let phrase = self
+            .phrase
+            .as_ref()
+            .ok_or(MnemonicBuilderError::ExpectedPhraseNotFound)?
+            .read()?;
+        let mnemonic = Mnemonic::<W>::new_from_phrase(&phrase)?;
+        (0..count)
+            .map(|index| {
+                let path =
+                    DerivationPath::from_str(&format!("{}{}", self.derivation_path_prefix, index))?;
+                self.derive_wallet(&mnemonic, &path)
+            })
+            .collect()
+    }
+
     /// Builds a `LocalWallet` using the parameters set in the mnemonic builder and constructing
     /// the phrase using the provided random number generator.
     pub fn build_random<R: Rng>(&self, rng: &mut R) -> Result<Wallet<SigningKey>, WalletError> {
@@ -174,8 +222,15 @@ impl<W: Wordlist> MnemonicBuilder<W> {
         &self,
         mnemonic: &Mnemonic<W>,
     ) -> Result<Wallet<SigningKey>, WalletError> {
-        let derived_priv_key =
-            mnemonic.derive_key(&self.derivation_path, self.password.as_deref())?;
+        self.derive_wallet(mnemonic, &self.derivation_path)
+    }
+
+    fn derive_wallet(
+        &self,
+        mnemonic: &Mnemonic<W>,
+        path: &DerivationPath,
+    ) -> Result<Wallet<SigningKey>, WalletError> {
+        let derived_priv_key = mnemonic.derive_key(path, self.password.as_deref())?;
         let key: &SigningKey = derived_priv_key.as_ref();
         let signer = SigningKey::from_bytes(&key.to_bytes())?;
         let address = secret_key_to_address(&signer);
@@ -188,6 +243,24 @@ impl<W: Wordlist> MnemonicBuilder<W> {
     }
 }
 
+#[async_trait]
+impl<W: Wordlist + std::fmt::Debug + Send + Sync> DeriveAddress for MnemonicBuilder<W> {
+    type Error = WalletError;
+
+    /// Derives the address of the account at `index` from the builder's phrase, along
+    /// the same derivation path as [`build_accounts`](MnemonicBuilder::build_accounts)
+    async fn derive_address(&self, index: u32) -> Result<Address, WalletError> {
+        let phrase = self
+            .phrase
+            .as_ref()
+            .ok_or(MnemonicBuilderError::ExpectedPhraseNotFound)?
+            .read()?;
+        let mnemonic = Mnemonic::<W>::new_from_phrase(&phrase)?;
+        let path = DerivationPath::from_str(&format!("{}{}", self.derivation_path_prefix, index))?;
+        Ok(self.derive_wallet(&mnemonic, &path)?.address)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -248,6 +321,83 @@ mod tests {
             })
     }
 
+    #[test]
+    fn builds_accounts() {
+        // the default accounts of hardhat
+        let accounts = MnemonicBuilder::<English>::default()
+            .phrase("test test test test test test test test test test test junk")
+            .build_accounts(3)
+            .unwrap();
+        let addresses = accounts
+            .iter()
+            .map(|wallet| to_checksum(&wallet.address, None))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            addresses,
+            vec![
+                "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266",
+                "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+                "0x3C44CdDdB6a900fa2b585dd299e03d12FA4293BC",
+            ]
+        );
+
+        // the accounts match the ones built at their index
+        let wallet = MnemonicBuilder::<English>::default()
+            .phrase("test test test test test test test test test test test junk")
+            .index(2u32)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(wallet, accounts[2]);
+
+        assert!(MnemonicBuilder::<English>::default()
+            .build_accounts(1)
+            .is_err());
+    }
+
+    #[test]
+    fn builds_accounts_under_derivation_path() {
+        let phrase = "test test test test test test test test test test test junk";
+        let accounts = MnemonicBuilder::<English>::default()
+            .phrase(phrase)
+            .derivation_path(TEST_DERIVATION_PATH)
+            .unwrap()
+            .build_accounts(2)
+            .unwrap();
+
+        // the accounts are the ones at "m/44'/60'/0'/2/{index}"
+        for (index, account) in accounts.iter().enumerate() {
+            let wallet = MnemonicBuilder::<English>::default()
+                .phrase(phrase)
+                .derivation_path(&format!("m/44'/60'/0'/2/{}", index))
+                .unwrap()
+                .build()
+                .unwrap();
+            assert_eq!(&wallet, account);
+        }
+
+        let default_accounts = MnemonicBuilder::<English>::default()
+            .phrase(phrase)
+            .build_accounts(2)
+            .unwrap();
+        assert_ne!(accounts, default_accounts);
+    }
+
+    #[tokio::test]
+    async fn derives_addresses_under_derivation_path() {
+        let builder = MnemonicBuilder::<English>::default()
+            .phrase("test test test test test test test test test test test junk")
+            .derivation_path(TEST_DERIVATION_PATH)
+            .unwrap();
+        let accounts = builder.build_accounts(2).unwrap();
+
+        // the addresses scanned for are the ones of the built accounts
+        for (index, account) in accounts.iter().enumerate() {
+            let address = builder.derive_address(index as u32).await.unwrap();
+            assert_eq!(address, account.address);
+        }
+    }
+
     #[tokio::test]
     async fn mnemonic_write_read() {
         let dir = tempdir().unwrap();
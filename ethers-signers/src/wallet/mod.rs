@@ -1,12 +1,15 @@
 mod hash;
 
+mod keystore;
+pub use keystore::KeystoreKdf;
+
 mod mnemonic;
 pub use mnemonic::{MnemonicBuilder, MnemonicBuilderError};
 
 mod private_key;
 pub use private_key::WalletError;
 
-#[cfg(feature = "yubihsm")]
+#[cfg(feature = "yubi")]
 mod yubi;
 
 use crate::{to_eip155_v, Signer};
@@ -16,7 +19,7 @@ use ethers_core::{
         elliptic_curve::FieldBytes,
         Secp256k1,
     },
-    types::{Address, Signature, TransactionRequest, H256},
+    types::{Address, Signature, TypedTransaction, H256},
     utils::hash_message,
 };
 use hash::Sha256Proxy;
@@ -66,7 +69,7 @@ pub struct Wallet<D: DigestSigner<Sha256Proxy, RecoverableSignature>> {
 
 #[async_trait]
 impl<D: Sync + Send + DigestSigner<Sha256Proxy, RecoverableSignature>> Signer for Wallet<D> {
-    type Error = std::convert::Infallible;
+    type Error = WalletError;
 
     async fn sign_message<S: Send + Sync + AsRef<[u8]>>(
         &self,
@@ -78,14 +81,34 @@ impl<D: Sync + Send + DigestSigner<Sha256Proxy, RecoverableSignature>> Signer fo
         Ok(self.sign_hash_with_eip155(message_hash, None))
     }
 
-    async fn sign_transaction(&self, tx: &TransactionRequest) -> Result<Signature, Self::Error> {
-        let sighash = tx.sighash(self.chain_id);
-        Ok(self.sign_hash_with_eip155(sighash, self.chain_id))
+    async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, Self::Error> {
+        match tx {
+            TypedTransaction::Legacy(_) => {
+                let sighash = tx.sighash(self.chain_id);
+                Ok(self.sign_hash_with_eip155(sighash, self.chain_id))
+            }
+            _ => {
+                // typed transactions commit to their chain id, which defaults to the wallet's
+                let mut tx = tx.clone();
+                if tx.chain_id().is_none() {
+                    tx.set_chain_id(self.chain_id.ok_or(WalletError::MissingChainId)?);
+                }
+                let sighash = tx.sighash::<u64>(None);
+                let mut signature = self.sign_hash_with_eip155(sighash, None);
+                // typed transactions are signed with the y-parity instead of the 'Electrum' `v`
+                signature.v -= 27;
+                Ok(signature)
+            }
+        }
     }
 
     fn address(&self) -> Address {
         self.address
     }
+
+    fn chain_id(&self) -> Option<u64> {
+        self.chain_id
+    }
 }
 
 impl<D: DigestSigner<Sha256Proxy, RecoverableSignature>> Wallet<D> {
@@ -1,5 +1,8 @@
 //! Specific helper functions for loading an offline K256 Private Key stored on disk
-use super::Wallet;
+use super::{
+    keystore::{Keystore, KeystoreKdf},
+    Wallet,
+};
 
 use crate::wallet::mnemonic::MnemonicBuilderError;
 use coins_bip32::Bip32Error;
@@ -10,7 +13,7 @@ use ethers_core::{
     rand::{CryptoRng, Rng},
     utils::secret_key_to_address,
 };
-use std::{path::Path, str::FromStr};
+use std::{fs, path::Path, str::FromStr};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -37,6 +40,16 @@ pub enum WalletError {
     /// Error propagated from the mnemonic builder module.
     #[error(transparent)]
     MnemonicBuilderError(#[from] MnemonicBuilderError),
+    /// Error thrown when encrypting a keystore with invalid KDF parameters
+    #[error("invalid keystore KDF parameters")]
+    InvalidKdfParams,
+    /// Error thrown when signing a typed transaction without a chain id, if the wallet
+    /// has none either
+    #[error("typed transactions must be signed for a chain id")]
+    MissingChainId,
+    /// Error propagated from serde_json
+    #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
 }
 
 impl Clone for Wallet<SigningKey> {
@@ -69,7 +82,56 @@ impl Wallet<SigningKey> {
         })
     }
 
-    /// Decrypts an encrypted JSON from the provided path to construct a Wallet instance
+    /// Encrypts the private key of the wallet with the provided password following the
+    /// Web3 Secret Storage definition, using the provided KDF, and stores it as a JSON
+    /// keystore in the provided directory. Returns the name of the keystore file.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ethers_core::rand::thread_rng;
+    /// use ethers_signers::{KeystoreKdf, LocalWallet};
+    ///
+    /// # fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// let wallet = LocalWallet::new(&mut thread_rng());
+    /// let name = wallet.encrypt_keystore(
+    ///     "./keystore",
+    ///     &mut thread_rng(),
+    ///     "password",
+    ///     KeystoreKdf::GETH_STANDARD,
+    /// )?;
+    ///
+    /// let decrypted = LocalWallet::decrypt_keystore(format!("./keystore/{}", name), "password")?;
+    /// assert_eq!(decrypted.address(), wallet.address());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn encrypt_keystore<P, R, S>(
+        &self,
+        dir: P,
+        rng: &mut R,
+        password: S,
+        kdf: KeystoreKdf,
+    ) -> Result<String, WalletError>
+    where
+        P: AsRef<Path>,
+        R: Rng + CryptoRng,
+        S: AsRef<[u8]>,
+    {
+        let keystore = Keystore::encrypt(
+            rng,
+            &self.signer.to_bytes(),
+            self.address,
+            password.as_ref(),
+            kdf,
+        )?;
+        let contents = serde_json::to_string(&keystore)?;
+        fs::write(dir.as_ref().join(keystore.id()), contents)?;
+        Ok(keystore.id().to_owned())
+    }
+
+    /// Decrypts an encrypted JSON from the provided path to construct a Wallet instance.
+    /// Keystores encrypted with either scrypt or PBKDF2, e.g. the ones of geth, are supported.
     pub fn decrypt_keystore<P, S>(keypath: P, password: S) -> Result<Self, WalletError>
     where
         P: AsRef<Path>,
@@ -148,7 +210,6 @@ mod tests {
     use super::*;
     use crate::Signer;
     use ethers_core::types::Address;
-    use std::fs;
     use tempfile::tempdir;
 
     #[tokio::test]
@@ -174,6 +235,61 @@ mod tests {
         }
     }
 
+    #[test]
+    fn encrypts_keystore() {
+        let dir = tempdir().unwrap();
+        let mut rng = rand::thread_rng();
+        let wallet = Wallet::<SigningKey>::new(&mut rng);
+
+        for kdf in [
+            KeystoreKdf::Scrypt {
+                log_n: 10,
+                r: 8,
+                p: 1,
+            },
+            KeystoreKdf::Pbkdf2 { c: 1000 },
+        ]
+        .iter()
+        {
+            let name = wallet
+                .encrypt_keystore(&dir, &mut rng, "randpsswd", *kdf)
+                .unwrap();
+            let path = dir.path().join(&name);
+
+            let keystore: serde_json::Value =
+                serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+            assert_eq!(keystore["version"], 3);
+            assert_eq!(keystore["id"], name);
+            assert_eq!(keystore["address"], hex::encode(wallet.address));
+
+            let decrypted = Wallet::<SigningKey>::decrypt_keystore(&path, "randpsswd").unwrap();
+            assert_eq!(decrypted, wallet);
+            assert!(Wallet::<SigningKey>::decrypt_keystore(&path, "wrong").is_err());
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_kdf_params() {
+        let dir = tempdir().unwrap();
+        let mut rng = rand::thread_rng();
+        let wallet = Wallet::<SigningKey>::new(&mut rng);
+        let err = wallet
+            .encrypt_keystore(&dir, &mut rng, "randpsswd", KeystoreKdf::Pbkdf2 { c: 0 })
+            .unwrap_err();
+        assert!(matches!(err, WalletError::InvalidKdfParams));
+
+        // `n = 2^32` does not fit in the keystore
+        let kdf = KeystoreKdf::Scrypt {
+            log_n: 32,
+            r: 8,
+            p: 1,
+        };
+        let err = wallet
+            .encrypt_keystore(&dir, &mut rng, "randpsswd", kdf)
+            .unwrap_err();
+        assert!(matches!(err, WalletError::InvalidKdfParams));
+    }
+
     #[tokio::test]
     async fn signs_msg() {
         let message = "Some data";
@@ -225,11 +341,90 @@ mod tests {
                 .unwrap();
         let wallet = wallet.set_chain_id(chain_id);
 
-        let sig = wallet.sign_transaction(&tx).await.unwrap();
+        let sig = wallet.sign_transaction(&tx.clone().into()).await.unwrap();
         let sighash = tx.sighash(Some(chain_id));
         assert!(sig.verify(sighash, wallet.address).is_ok());
     }
 
+    #[tokio::test]
+    #[cfg(not(feature = "celo"))]
+    async fn signs_raw_tx() {
+        use ethers_core::types::TransactionRequest;
+        let tx = TransactionRequest::pay(Address::repeat_byte(0x35), 1_000_000_000u64)
+            .nonce(9)
+            .gas(21_000)
+            .gas_price(20_000_000_000u64);
+        let wallet: Wallet<SigningKey> =
+            "4646464646464646464646464646464646464646464646464646464646464646"
+                .parse()
+                .unwrap();
+        let wallet = wallet.set_chain_id(1u64);
+
+        let typed = tx.clone().into();
+        let rlp = wallet.sign_raw_transaction(&typed).await.unwrap();
+        let sig = wallet.sign_transaction(&typed).await.unwrap();
+        assert_eq!(rlp, tx.rlp_signed(&sig));
+        assert_eq!(sig.v, 37);
+        assert!(sig.verify(tx.sighash(Some(1u64)), wallet.address).is_ok());
+    }
+
+    #[tokio::test]
+    async fn signs_eip1559_tx() {
+        use ethers_core::types::{Eip1559TransactionRequest, EIP1559_TX_TYPE};
+        let tx = Eip1559TransactionRequest::pay(Address::repeat_byte(0x35), 1_000_000_000u64)
+            .chain_id(1u64)
+            .nonce(9)
+            .gas(21_000)
+            .max_priority_fee_per_gas(1_000_000_000u64)
+            .max_fee_per_gas(20_000_000_000u64);
+        let wallet: Wallet<SigningKey> =
+            "4646464646464646464646464646464646464646464646464646464646464646"
+                .parse()
+                .unwrap();
+
+        // the chain id of the wallet only applies to legacy transactions
+        let wallet = wallet.set_chain_id(5u64);
+        let typed = tx.clone().into();
+        let sig = wallet.sign_transaction(&typed).await.unwrap();
+        assert!(sig.v <= 1);
+        assert!(sig.verify(tx.sighash(), wallet.address).is_ok());
+
+        let rlp = wallet.sign_raw_transaction(&typed).await.unwrap();
+        assert_eq!(rlp, tx.rlp_signed(&sig));
+        assert_eq!(rlp.as_ref()[0], EIP1559_TX_TYPE);
+    }
+
+    #[tokio::test]
+    async fn signs_eip1559_tx_for_wallet_chain_id() {
+        use ethers_core::types::Eip1559TransactionRequest;
+        let tx = Eip1559TransactionRequest::pay(Address::repeat_byte(0x35), 1_000_000_000u64)
+            .nonce(9)
+            .gas(21_000)
+            .max_priority_fee_per_gas(1_000_000_000u64)
+            .max_fee_per_gas(20_000_000_000u64);
+        let wallet: Wallet<SigningKey> =
+            "4646464646464646464646464646464646464646464646464646464646464646"
+                .parse()
+                .unwrap();
+
+        // transactions without a chain id cannot be signed by wallets without one
+        let typed = tx.clone().into();
+        assert!(matches!(
+            wallet.sign_transaction(&typed).await.unwrap_err(),
+            WalletError::MissingChainId
+        ));
+
+        let wallet = wallet.set_chain_id(1u64);
+        let rlp = wallet.sign_raw_transaction(&typed).await.unwrap();
+        let expected = hex::decode("02f86f0109843b9aca008504a817c800825208943535353535353535353535353535353535353535843b9aca0080c001a03ac6d85cea2dfb141b45ab24983f35105c6bbff4b60ca65fde700421ae1e0b7ba054f9685d8a416197aa07e464dcfc57c52369440de1fa7c9b255c741cead1f571")
+            .unwrap();
+        assert_eq!(rlp.as_ref(), &expected[..]);
+
+        // which is the same as signing it for the chain explicitly
+        let sig = wallet.sign_transaction(&typed).await.unwrap();
+        assert_eq!(rlp, tx.chain_id(1u64).rlp_signed(&sig));
+    }
+
     #[test]
     fn key_to_address() {
         let wallet: Wallet<SigningKey> =
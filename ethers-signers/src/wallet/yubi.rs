@@ -1,4 +1,18 @@
-//! Helpers for creating wallets for YubiHSM2
+//! Helpers for creating wallets for YubiHSM2. The private key never leaves the device:
+//! it is either generated on it or uploaded to it, and all the messages and transactions
+//! are signed through the device's session.
+//!
+//! ```no_run
+//! use ethers_signers::{yubihsm::{Connector, Credentials}, Signer, YubiWallet};
+//!
+//! # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+//! let connector = Connector::usb(&Default::default());
+//! let wallet = YubiWallet::connect(connector, Credentials::default(), 0).set_chain_id(1u64);
+//! let signature = wallet.sign_message("hello world").await?;
+//! signature.verify("hello world", wallet.address())?;
+//! # Ok(())
+//! # }
+//! ```
 use super::Wallet;
 use ethers_core::{k256::Secp256k1, types::Address, utils::keccak256};
 use yubihsm::{
@@ -93,6 +107,30 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    #[cfg(not(feature = "celo"))]
+    async fn signs_tx() {
+        use ethers_core::types::TransactionRequest;
+
+        let connector = yubihsm::Connector::mockhsm();
+        let wallet = Wallet::<YubiSigner<Secp256k1>>::new(
+            connector,
+            Credentials::default(),
+            0,
+            Label::from_bytes(&[]).unwrap(),
+            Domain::at(1).unwrap(),
+        )
+        .set_chain_id(1u64);
+
+        let tx = TransactionRequest::pay(Address::repeat_byte(0x35), 1_000_000_000u64)
+            .nonce(9)
+            .gas(21_000)
+            .gas_price(20_000_000_000u64);
+        let sig = wallet.sign_transaction(&tx.clone().into()).await.unwrap();
+        assert!(sig.v == 37 || sig.v == 38);
+        assert!(sig.verify(tx.sighash(Some(1u64)), wallet.address()).is_ok());
+    }
+
     #[tokio::test]
     async fn new_key() {
         let connector = yubihsm::Connector::mockhsm();
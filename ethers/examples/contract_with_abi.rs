@@ -43,7 +43,7 @@ async fn main() -> Result<()> {
     );
 
     // 6. deploy it with the constructor arguments
-    let contract = factory.deploy("initial value".to_string())?.send().await?;
+    let (contract, _receipt) = factory.deploy("initial value".to_string())?.send().await?;
 
     // 7. get the contract's address
     let addr = contract.address();
@@ -0,0 +1,29 @@
+use anyhow::Result;
+use ethers::{prelude::*, utils::Ganache};
+use std::convert::TryFrom;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let ganache = Ganache::new().spawn();
+    let wallet: LocalWallet = ganache.keys()[0].clone().into();
+    // ganache uses chain id 1337
+    let wallet = wallet.set_chain_id(1337u64);
+
+    // craft the transaction, filling in the fields which would otherwise be
+    // queried from a node
+    let tx = TransactionRequest::pay(Address::repeat_byte(0x11), 10000)
+        .nonce(0)
+        .gas(21000)
+        .gas_price(20_000_000_000u64);
+
+    // sign it without any network access
+    let rlp = wallet.sign_raw_transaction(&tx.into()).await?;
+    println!("Signed tx: {}", serde_json::to_string(&rlp)?);
+
+    // the raw transaction can be broadcast later, from any machine
+    let provider = Provider::<Http>::try_from(ganache.endpoint())?;
+    let receipt = provider.send_raw_transaction_bytes(rlp).await?.await?;
+    println!("Tx receipt: {}", serde_json::to_string(&receipt)?);
+
+    Ok(())
+}
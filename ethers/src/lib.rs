@@ -77,6 +77,11 @@
 //! with a [`Signer`]. The middleware architecture allows users to either use one of the existing
 //! middleware, or they are free to write on of their own.
 //!
+//! ## `etherscan`
+//!
+//! A client for the [Etherscan](https://etherscan.io/apis) API and its deployments on other
+//! chains, e.g. to retrieve the ABI and the source code of verified contracts at runtime.
+//!
 //! [`Provider`]: providers::Provider
 //! [`Middleware`]: providers::Middleware
 //! [`Wallet`]: signers::Wallet
@@ -90,6 +95,7 @@
 //! [`types`]: core::types
 pub use ethers_contract as contract;
 pub use ethers_core as core;
+pub use ethers_etherscan as etherscan;
 pub use ethers_middleware as middleware;
 pub use ethers_providers as providers;
 pub use ethers_signers as signers;